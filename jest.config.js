@@ -15,6 +15,7 @@ module.exports = {
     'src/chains/avalanche/avalanche.ts',
     'src/chains/avalanche/pangolin/pangolin.ts',
     'src/chains/cosmos/cosmos.ts',
+    'src/chains/osmosis/osmosis.ts',
     'src/chains/near/near.ts',
     'src/chains/near/near.base.ts',
     'src/connectors/uniswap/uniswap.config.ts',
@@ -33,6 +34,8 @@ module.exports = {
     'test/*',
   ],
   modulePathIgnorePatterns: ['<rootDir>/dist/'],
+  // the forked-chain tests need a local node, see test/fork/README.md
+  testPathIgnorePatterns: ['/node_modules/', '<rootDir>/test/fork/'],
   setupFilesAfterEnv: ['<rootDir>/test/setupTests.js'],
   globalSetup: '<rootDir>/test/setup.ts',
   globalTeardown: '<rootDir>/test/teardown.ts',
@@ -0,0 +1,9 @@
+const config = require('./jest.config');
+
+// connector integration tests against a local hardhat node, see
+// test/fork/README.md
+module.exports = {
+  ...config,
+  testMatch: ['<rootDir>/test/fork/**/*.test.ts'],
+  testPathIgnorePatterns: ['/node_modules/'],
+};
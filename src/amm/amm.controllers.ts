@@ -1,4 +1,7 @@
+import Decimal from 'decimal.js-light';
 import {
+  ConnectorQuote,
+  ConnectorQuoteError,
   EstimateGasResponse,
   PerpAvailablePairsResponse,
   PerpCreateTakerRequest,
@@ -10,28 +13,63 @@ import {
   PerpPricesResponse,
   PriceRequest,
   PriceResponse,
+  QuoteRequest,
+  QuoteResponse,
   TradeRequest,
   TradeResponse,
   AddLiquidityRequest,
   AddLiquidityResponse,
   RemoveLiquidityRequest,
   RemoveLiquidityResponse,
+  RebalanceRequest,
+  RebalanceResponse,
   CollectEarnedFeesRequest,
   PositionRequest,
   PositionResponse,
+  PositionsRequest,
+  PositionsResponse,
   PoolPriceRequest,
   PoolPriceResponse,
+  PoolInfoRequest,
+  PoolInfoResponse,
+  PoolObservationsRequest,
+  PoolObservationsResponse,
+  V2AddLiquidityRequest,
+  V2AddLiquidityResponse,
+  V2RemoveLiquidityRequest,
+  V2RemoveLiquidityResponse,
+  V2PositionRequest,
+  V2PositionResponse,
   PerpBalanceRequest,
   PerpBalanceResponse,
+  PerpCreateMakerRequest,
+  PerpCreateMakerResponse,
+  PerpOrdersRequest,
+  PerpOrdersResponse,
+  PerpCancelOrderRequest,
+  PerpCancelOrderResponse,
+  PerpMarginRequest,
+  PerpMarginResponse,
+  PerpFundingResponse,
+  PerpMarketInfoResponse,
+  PerpPriceHistoryRequest,
+  PerpPriceHistoryResponse,
 } from './amm.requests';
 import {
   price as uniswapPrice,
   trade as uniswapTrade,
   addLiquidity as uniswapV3AddLiquidity,
   removeLiquidity as uniswapV3RemoveLiquidity,
+  rebalanceLiquidity as uniswapV3RebalanceLiquidity,
   collectEarnedFees as uniswapV3CollectEarnedFees,
   positionInfo as uniswapV3PositionInfo,
+  positionsOfWallet as uniswapV3PositionsOfWallet,
   poolPrice as uniswapV3PoolPrice,
+  poolInfo as uniswapV3PoolInfo,
+  increaseObservations as uniswapV3IncreaseObservations,
+  v2AddLiquidity as uniswapV2AddLiquidity,
+  v2RemoveLiquidity as uniswapV2RemoveLiquidity,
+  v2Position as uniswapV2Position,
   estimateGas as uniswapEstimateGas,
 } from '../connectors/uniswap/uniswap.controllers';
 import {
@@ -39,6 +77,11 @@ import {
   trade as refTrade,
   estimateGas as refEstimateGas,
 } from '../connectors/ref/ref.controllers';
+import {
+  price as osmosisPrice,
+  trade as osmosisTrade,
+  estimateGas as osmosisEstimateGas,
+} from '../connectors/osmosis/osmosis.controllers';
 import {
   getPriceData as perpPriceData,
   createTakerOrder,
@@ -47,9 +90,25 @@ import {
   getAvailablePairs,
   checkMarketStatus,
   getAccountValue,
+  createMakerOrder,
+  getOrders,
+  cancelMakerOrder,
+  changeMargin,
+  getFundingRates,
+  getMarketInfo,
+  getPriceHistory,
 } from '../connectors/perp/perp.controllers';
-import { getChain, getConnector } from '../services/connection-manager';
 import {
+  getChain,
+  getConnector,
+  getSwapConnectors,
+  getV2LPConnector,
+} from '../services/connection-manager';
+import { latency } from '../services/base';
+import { logger } from '../services/logger';
+import {
+  CosmosAMMish,
+  Cosmosish,
   Ethereumish,
   Nearish,
   NetworkSelectionRequest,
@@ -57,36 +116,169 @@ import {
   RefAMMish,
   Uniswapish,
   UniswapLPish,
+  UniswapV2LPish,
 } from '../services/common-interfaces';
 
 export async function price(req: PriceRequest): Promise<PriceResponse> {
-  const chain = await getChain<Ethereumish | Nearish>(req.chain, req.network);
-  const connector: Uniswapish | RefAMMish = await getConnector<
-    Uniswapish | RefAMMish
+  const chain = await getChain<Ethereumish | Nearish | Cosmosish>(
+    req.chain,
+    req.network
+  );
+  const connector: Uniswapish | RefAMMish | CosmosAMMish = await getConnector<
+    Uniswapish | RefAMMish | CosmosAMMish
   >(req.chain, req.network, req.connector);
 
-  // we currently use the presence of routerAbi to distinguish Uniswapish from RefAMMish
+  // we currently use the presence of routerAbi to distinguish Uniswapish from
+  // RefAMMish, and of swapMessage for CosmosAMMish
   if ('routerAbi' in connector) {
     return uniswapPrice(<Ethereumish>chain, connector, req);
+  } else if ('swapMessage' in connector) {
+    return osmosisPrice(<Cosmosish>chain, connector, req);
   } else {
     return refPrice(<Nearish>chain, connector, req);
   }
 }
 
 export async function trade(req: TradeRequest): Promise<TradeResponse> {
-  const chain = await getChain<Ethereumish | Nearish>(req.chain, req.network);
-  const connector: Uniswapish | RefAMMish = await getConnector<
-    Uniswapish | RefAMMish
+  const chain = await getChain<Ethereumish | Nearish | Cosmosish>(
+    req.chain,
+    req.network
+  );
+  const connector: Uniswapish | RefAMMish | CosmosAMMish = await getConnector<
+    Uniswapish | RefAMMish | CosmosAMMish
   >(req.chain, req.network, req.connector);
 
-  // we currently use the presence of routerAbi to distinguish Uniswapish from RefAMMish
+  // we currently use the presence of routerAbi to distinguish Uniswapish from
+  // RefAMMish, and of swapMessage for CosmosAMMish
   if ('routerAbi' in connector) {
     return uniswapTrade(<Ethereumish>chain, connector, req);
+  } else if ('swapMessage' in connector) {
+    return osmosisTrade(<Cosmosish>chain, connector, req);
   } else {
     return refTrade(<Nearish>chain, connector, req);
   }
 }
 
+// the price of the native token in the quote token, to compare the gas costs
+// with the amounts traded. The connectors trade the wrapped native token.
+async function nativePriceInQuote(
+  chain: Ethereumish | Nearish,
+  req: QuoteRequest,
+  priced: Array<[string, PriceResponse]>
+): Promise<Decimal | undefined> {
+  const native = chain.nativeTokenSymbol.toUpperCase();
+  const nativeSymbols = [native, 'W' + native];
+  if (nativeSymbols.includes(req.quote.toUpperCase())) return new Decimal(1);
+  if (nativeSymbols.includes(req.base.toUpperCase())) {
+    return new Decimal(priced[0][1].price);
+  }
+  for (const [connector] of priced) {
+    try {
+      const nativePrice = await price({
+        chain: req.chain,
+        network: req.network,
+        connector,
+        base: 'W' + chain.nativeTokenSymbol,
+        quote: req.quote,
+        amount: '1',
+        side: 'SELL',
+      });
+      return new Decimal(nativePrice.price);
+    } catch (e) {
+      logger.info(
+        `Could not price ${chain.nativeTokenSymbol} on ${connector}: ${e}`
+      );
+    }
+  }
+  return undefined;
+}
+
+export async function quote(req: QuoteRequest): Promise<QuoteResponse> {
+  const startTimestamp: number = Date.now();
+  const chain = await getChain<Ethereumish | Nearish>(req.chain, req.network);
+  const connectors = req.connectors
+    ? req.connectors
+    : getSwapConnectors(req.chain, req.network);
+
+  const results = await Promise.allSettled(
+    connectors.map((connector) =>
+      price({
+        chain: req.chain,
+        network: req.network,
+        connector,
+        base: req.base,
+        quote: req.quote,
+        amount: req.amount,
+        side: req.side,
+        allowedSlippage: req.allowedSlippage,
+      })
+    )
+  );
+  const priced: Array<[string, PriceResponse]> = [];
+  const errors: ConnectorQuoteError[] = [];
+  results.forEach((result, i) => {
+    if (result.status === 'fulfilled') {
+      priced.push([connectors[i], result.value]);
+    } else {
+      errors.push({
+        connector: connectors[i],
+        message: result.reason?.message || String(result.reason),
+      });
+    }
+  });
+
+  // the more received for a sell, and the less paid for a buy, the better
+  const better = (a: Decimal, b: Decimal): number =>
+    req.side === 'SELL' ? b.cmp(a) : a.cmp(b);
+
+  priced.sort(([, a], [, b]) =>
+    better(new Decimal(a.expectedAmount), new Decimal(b.expectedAmount))
+  );
+  const nativePrice =
+    priced.length > 0
+      ? await nativePriceInQuote(chain, req, priced)
+      : undefined;
+
+  const quotes: ConnectorQuote[] = priced.map(([connector, response]) => {
+    const expectedAmount = new Decimal(response.expectedAmount);
+    const gasCostInQuote = nativePrice
+      ? nativePrice.mul(response.gasCost)
+      : undefined;
+    let netAmount = expectedAmount;
+    if (gasCostInQuote) {
+      netAmount =
+        req.side === 'SELL'
+          ? expectedAmount.sub(gasCostInQuote)
+          : expectedAmount.add(gasCostInQuote);
+    }
+    return {
+      connector,
+      price: response.price,
+      expectedAmount: response.expectedAmount,
+      gasCost: response.gasCost,
+      gasCostInQuote: gasCostInQuote?.toString(),
+      netAmount: netAmount.toString(),
+    };
+  });
+  quotes.sort((a, b) =>
+    better(new Decimal(a.netAmount), new Decimal(b.netAmount))
+  );
+
+  return {
+    network: req.network,
+    timestamp: startTimestamp,
+    latency: latency(startTimestamp, Date.now()),
+    base: req.base,
+    quote: req.quote,
+    amount: req.amount,
+    side: req.side,
+    gasPrice: chain.gasPrice,
+    gasPriceToken: chain.nativeTokenSymbol,
+    quotes,
+    errors,
+  };
+}
+
 export async function addLiquidity(
   req: AddLiquidityRequest
 ): Promise<AddLiquidityResponse> {
@@ -137,6 +329,30 @@ export async function positionInfo(
   return uniswapV3PositionInfo(chain, connector, req);
 }
 
+export async function rebalanceLiquidity(
+  req: RebalanceRequest
+): Promise<RebalanceResponse> {
+  const chain = await getChain<Ethereumish>(req.chain, req.network);
+  const connector: UniswapLPish = await getConnector<UniswapLPish>(
+    req.chain,
+    req.network,
+    req.connector
+  );
+  return uniswapV3RebalanceLiquidity(chain, connector, req);
+}
+
+export async function positionsOfWallet(
+  req: PositionsRequest
+): Promise<PositionsResponse> {
+  const chain = await getChain<Ethereumish>(req.chain, req.network);
+  const connector: UniswapLPish = await getConnector<UniswapLPish>(
+    req.chain,
+    req.network,
+    req.connector
+  );
+  return uniswapV3PositionsOfWallet(chain, connector, req);
+}
+
 export async function poolPrice(
   req: PoolPriceRequest
 ): Promise<PoolPriceResponse> {
@@ -149,17 +365,83 @@ export async function poolPrice(
   return uniswapV3PoolPrice(chain, connector, req);
 }
 
+export async function poolInfo(
+  req: PoolInfoRequest
+): Promise<PoolInfoResponse> {
+  const chain = await getChain<Ethereumish>(req.chain, req.network);
+  const connector: UniswapLPish = await getConnector<UniswapLPish>(
+    req.chain,
+    req.network,
+    req.connector
+  );
+  return uniswapV3PoolInfo(chain, connector, req);
+}
+
+export async function increaseObservations(
+  req: PoolObservationsRequest
+): Promise<PoolObservationsResponse> {
+  const chain = await getChain<Ethereumish>(req.chain, req.network);
+  const connector: UniswapLPish = await getConnector<UniswapLPish>(
+    req.chain,
+    req.network,
+    req.connector
+  );
+  return uniswapV3IncreaseObservations(chain, connector, req);
+}
+
+export async function v2AddLiquidity(
+  req: V2AddLiquidityRequest
+): Promise<V2AddLiquidityResponse> {
+  const chain = await getChain<Ethereumish>(req.chain, req.network);
+  const connector: UniswapV2LPish = await getV2LPConnector(
+    req.chain,
+    req.network,
+    req.connector
+  );
+  return uniswapV2AddLiquidity(chain, connector, req);
+}
+
+export async function v2RemoveLiquidity(
+  req: V2RemoveLiquidityRequest
+): Promise<V2RemoveLiquidityResponse> {
+  const chain = await getChain<Ethereumish>(req.chain, req.network);
+  const connector: UniswapV2LPish = await getV2LPConnector(
+    req.chain,
+    req.network,
+    req.connector
+  );
+  return uniswapV2RemoveLiquidity(chain, connector, req);
+}
+
+export async function v2Position(
+  req: V2PositionRequest
+): Promise<V2PositionResponse> {
+  const chain = await getChain<Ethereumish>(req.chain, req.network);
+  const connector: UniswapV2LPish = await getV2LPConnector(
+    req.chain,
+    req.network,
+    req.connector
+  );
+  return uniswapV2Position(chain, connector, req);
+}
+
 export async function estimateGas(
   req: NetworkSelectionRequest
 ): Promise<EstimateGasResponse> {
-  const chain = await getChain<Ethereumish | Nearish>(req.chain, req.network);
-  const connector: Uniswapish | RefAMMish = await getConnector<
-    Uniswapish | RefAMMish
+  const chain = await getChain<Ethereumish | Nearish | Cosmosish>(
+    req.chain,
+    req.network
+  );
+  const connector: Uniswapish | RefAMMish | CosmosAMMish = await getConnector<
+    Uniswapish | RefAMMish | CosmosAMMish
   >(req.chain, req.network, req.connector);
 
-  // we currently use the presence of routerAbi to distinguish Uniswapish from RefAMMish
+  // we currently use the presence of routerAbi to distinguish Uniswapish from
+  // RefAMMish, and of swapMessage for CosmosAMMish
   if ('routerAbi' in connector) {
     return uniswapEstimateGas(<Ethereumish>chain, connector);
+  } else if ('swapMessage' in connector) {
+    return osmosisEstimateGas(<Cosmosish>chain, connector);
   } else {
     return refEstimateGas(<Nearish>chain, connector);
   }
@@ -192,6 +474,59 @@ export async function perpOrder(
   return createTakerOrder(chain, connector, req, isOpen);
 }
 
+export async function perpLimitOrder(
+  req: PerpCreateMakerRequest
+): Promise<PerpCreateMakerResponse> {
+  const chain = await getChain<Ethereumish>(req.chain, req.network);
+  const connector: Perpish = await getConnector<Perpish>(
+    req.chain,
+    req.network,
+    req.connector,
+    req.address
+  );
+  return createMakerOrder(chain, connector, req);
+}
+
+export async function perpOrders(
+  req: PerpOrdersRequest
+): Promise<PerpOrdersResponse> {
+  const chain = await getChain<Ethereumish>(req.chain, req.network);
+  const connector: Perpish = await getConnector<Perpish>(
+    req.chain,
+    req.network,
+    req.connector,
+    req.address
+  );
+  return getOrders(chain, connector, req);
+}
+
+export async function perpCancelOrder(
+  req: PerpCancelOrderRequest
+): Promise<PerpCancelOrderResponse> {
+  const chain = await getChain<Ethereumish>(req.chain, req.network);
+  const connector: Perpish = await getConnector<Perpish>(
+    req.chain,
+    req.network,
+    req.connector,
+    req.address
+  );
+  return cancelMakerOrder(chain, connector, req);
+}
+
+export async function perpMargin(
+  req: PerpMarginRequest,
+  isAdd: boolean
+): Promise<PerpMarginResponse> {
+  const chain = await getChain<Ethereumish>(req.chain, req.network);
+  const connector: Perpish = await getConnector<Perpish>(
+    req.chain,
+    req.network,
+    req.connector,
+    req.address
+  );
+  return changeMargin(chain, connector, req, isAdd);
+}
+
 export async function perpPosition(
   req: PerpPositionRequest
 ): Promise<PerpPositionResponse> {
@@ -239,6 +574,42 @@ export async function getMarketStatus(
   return checkMarketStatus(chain, connector, req);
 }
 
+export async function perpFunding(
+  req: PerpMarketRequest
+): Promise<PerpFundingResponse> {
+  const chain = await getChain<Ethereumish>(req.chain, req.network);
+  const connector: Perpish = await getConnector<Perpish>(
+    req.chain,
+    req.network,
+    req.connector
+  );
+  return getFundingRates(chain, connector, req);
+}
+
+export async function perpMarketInfo(
+  req: PerpMarketRequest
+): Promise<PerpMarketInfoResponse> {
+  const chain = await getChain<Ethereumish>(req.chain, req.network);
+  const connector: Perpish = await getConnector<Perpish>(
+    req.chain,
+    req.network,
+    req.connector
+  );
+  return getMarketInfo(chain, connector, req);
+}
+
+export async function perpPriceHistory(
+  req: PerpPriceHistoryRequest
+): Promise<PerpPriceHistoryResponse> {
+  const chain = await getChain<Ethereumish>(req.chain, req.network);
+  const connector: Perpish = await getConnector<Perpish>(
+    req.chain,
+    req.network,
+    req.connector
+  );
+  return getPriceHistory(chain, connector, req);
+}
+
 export async function estimatePerpGas(
   req: NetworkSelectionRequest
 ): Promise<EstimateGasResponse> {
@@ -1,7 +1,17 @@
-import { PerpPosition } from '../connectors/perp/perp';
+import {
+  PerpFundingRate,
+  PerpLimitOrder,
+  PerpMarketInfo,
+  PerpPosition,
+} from '../connectors/perp/perp';
+import { PerpPriceSample } from '../connectors/perp/perp.history';
+import { PoolInfo } from '../connectors/uniswap/uniswap.lp.analytics';
+import { SimulationResult } from '../evm/evm.simulation';
 import {
   NetworkSelectionRequest,
   PositionInfo as LPPositionInfo,
+  TradeRoute,
+  V2PositionInfo,
 } from '../services/common-interfaces';
 export type Side = 'BUY' | 'SELL';
 export type PerpSide = 'LONG' | 'SHORT';
@@ -30,6 +40,45 @@ export interface PriceResponse {
   gasCost: string;
 }
 
+export interface QuoteRequest extends NetworkSelectionRequest {
+  quote: string;
+  base: string;
+  amount: string;
+  side: Side;
+  allowedSlippage?: string;
+  connectors?: string[]; // defaults to every connector on the network
+}
+
+export interface ConnectorQuote {
+  connector: string;
+  price: string;
+  expectedAmount: string; // received for a SELL, paid for a BUY
+  gasCost: string; // in the native token
+  gasCostInQuote?: string; // unset if the native token couldn't be priced
+  // expectedAmount net of the gas cost, in the quote token. The quotes are
+  // ranked by it.
+  netAmount: string;
+}
+
+export interface ConnectorQuoteError {
+  connector: string;
+  message: string;
+}
+
+export interface QuoteResponse {
+  network: string;
+  timestamp: number;
+  latency: number;
+  base: string;
+  quote: string;
+  amount: string;
+  side: Side;
+  gasPrice: number;
+  gasPriceToken: string;
+  quotes: ConnectorQuote[]; // best first
+  errors: ConnectorQuoteError[];
+}
+
 export interface PoolPriceRequest extends NetworkSelectionRequest {
   token0: string;
   token1: string;
@@ -38,6 +87,48 @@ export interface PoolPriceRequest extends NetworkSelectionRequest {
   interval: number;
 }
 
+export interface PoolInfoRequest extends NetworkSelectionRequest {
+  token0: string;
+  token1: string;
+  fee: string;
+  rangePercent?: number; // of the price to report the liquidity within
+  depthPercents?: number[];
+  twapWindow?: number; // in seconds
+}
+
+export interface PoolInfoResponse extends PoolInfo {
+  network: string;
+  timestamp: number;
+  latency: number;
+}
+
+export interface PoolObservationsRequest extends NetworkSelectionRequest {
+  address: string;
+  token0: string;
+  token1: string;
+  fee: string;
+  cardinality: number;
+  nonce?: number;
+  maxFeePerGas?: string;
+  maxPriorityFeePerGas?: string;
+}
+
+export interface PoolObservationsResponse {
+  network: string;
+  timestamp: number;
+  latency: number;
+  pool: string | undefined;
+  cardinality: number;
+  gasPrice: number;
+  gasPriceToken: string;
+  gasLimit: number;
+  gasCost: string;
+  nonce: number;
+  txHash: string | undefined;
+  maxFeePerGas?: string; // in wei, set for EIP-1559 transactions
+  maxPriorityFeePerGas?: string;
+}
+
 export interface PoolPriceResponse {
   token0: string;
   token1: string;
@@ -61,6 +152,13 @@ export interface TradeRequest extends NetworkSelectionRequest {
   maxFeePerGas?: string;
   maxPriorityFeePerGas?: string;
   allowedSlippage?: string;
+  simulate?: boolean; // dry-run the transaction instead of sending it
+  simulationBlock?: number; // defaults to the latest block
+  path?: string[]; // token symbols or addresses, from the token paid
+  fees?: string[]; // V3 fee tier of each hop of the path, e.g. 'LOW'
+  intermediateTokens?: string[]; // the only tokens the route may go through
+  excludedPools?: string[]; // pool addresses the route must avoid
+  autoWrap?: boolean; // pay or receive the native coin, not its wrapped token
 }
 
 export interface TradeResponse {
@@ -80,6 +178,10 @@ export interface TradeResponse {
   gasCost: string;
   nonce?: number;
   txHash: string | any | undefined;
+  maxFeePerGas?: string; // in wei, set for EIP-1559 transactions
+  maxPriorityFeePerGas?: string;
+  simulation?: SimulationResult; // set instead of txHash when simulating
+  route?: TradeRoute[]; // the pools the trade swaps through
 }
 
 export interface AddLiquidityRequest extends NetworkSelectionRequest {
@@ -95,6 +197,8 @@ export interface AddLiquidityRequest extends NetworkSelectionRequest {
   nonce?: number;
   maxFeePerGas?: string;
   maxPriorityFeePerGas?: string;
+  simulate?: boolean;
+  simulationBlock?: number;
 }
 
 export interface AddLiquidityResponse {
@@ -111,6 +215,9 @@ export interface AddLiquidityResponse {
   gasCost: string;
   nonce: number;
   txHash: string | undefined;
+  maxFeePerGas?: string; // in wei, set for EIP-1559 transactions
+  maxPriorityFeePerGas?: string;
+  simulation?: SimulationResult; // set instead of txHash when simulating
 }
 
 export interface CollectEarnedFeesRequest extends NetworkSelectionRequest {
@@ -119,6 +226,8 @@ export interface CollectEarnedFeesRequest extends NetworkSelectionRequest {
   nonce?: number;
   maxFeePerGas?: string;
   maxPriorityFeePerGas?: string;
+  simulate?: boolean;
+  simulationBlock?: number;
 }
 
 export interface RemoveLiquidityRequest extends CollectEarnedFeesRequest {
@@ -136,6 +245,36 @@ export interface RemoveLiquidityResponse {
   gasCost: string;
   nonce: number;
   txHash: string | undefined;
+  maxFeePerGas?: string; // in wei, set for EIP-1559 transactions
+  maxPriorityFeePerGas?: string;
+  simulation?: SimulationResult; // set instead of txHash when simulating
+}
+
+export interface RebalanceRequest extends CollectEarnedFeesRequest {
+  lowerPrice: string; // of token0 in token1, the new range
+  upperPrice: string;
+}
+
+export interface RebalanceResponse {
+  network: string;
+  timestamp: number;
+  latency: number;
+  tokenId: number; // the position removed
+  newTokenId?: number; // the position minted, from a dry run
+  token0: string;
+  token1: string;
+  amount0: string; // put into the new position
+  amount1: string;
+  swapRequired: boolean;
+  gasPrice: number;
+  gasPriceToken: string;
+  gasLimit: number;
+  gasCost: string;
+  nonce: number;
+  txHash: string | undefined;
+  maxFeePerGas?: string; // in wei, set for EIP-1559 transactions
+  maxPriorityFeePerGas?: string;
+  simulation?: SimulationResult; // set instead of txHash when simulating
 }
 
 export interface PositionRequest extends NetworkSelectionRequest {
@@ -148,6 +287,96 @@ export interface PositionResponse extends LPPositionInfo {
   latency: number;
 }
 
+export interface PositionsRequest extends NetworkSelectionRequest {
+  address: string;
+}
+
+export interface PositionsResponse {
+  network: string;
+  timestamp: number;
+  latency: number;
+  address: string;
+  positions: LPPositionInfo[];
+}
+
+// Uniswap V2 style pairs, token0 and token1 may be given in either order
+export interface V2AddLiquidityRequest extends NetworkSelectionRequest {
+  address: string;
+  token0: string;
+  token1: string;
+  amount0: string;
+  amount1: string;
+  allowedSlippage?: string; // e.g. '1/100', the connector's when not set
+  nonce?: number;
+  maxFeePerGas?: string;
+  maxPriorityFeePerGas?: string;
+  simulate?: boolean;
+  simulationBlock?: number;
+}
+
+export interface V2AddLiquidityResponse {
+  network: string;
+  timestamp: number;
+  latency: number;
+  token0: string;
+  token1: string;
+  amount0: string;
+  amount1: string;
+  gasPrice: number;
+  gasPriceToken: string;
+  gasLimit: number;
+  gasCost: string;
+  nonce: number;
+  txHash: string | undefined;
+  maxFeePerGas?: string; // in wei, set for EIP-1559 transactions
+  maxPriorityFeePerGas?: string;
+  simulation?: SimulationResult; // set instead of txHash when simulating
+}
+
+export interface V2RemoveLiquidityRequest extends NetworkSelectionRequest {
+  address: string;
+  token0: string;
+  token1: string;
+  decreasePercent?: number; // of the LP tokens held, 100 when not set
+  allowedSlippage?: string;
+  nonce?: number;
+  maxFeePerGas?: string;
+  maxPriorityFeePerGas?: string;
+  simulate?: boolean;
+  simulationBlock?: number;
+}
+
+export interface V2RemoveLiquidityResponse {
+  network: string;
+  timestamp: number;
+  latency: number;
+  token0: string;
+  token1: string;
+  liquidity?: string; // the LP tokens redeemed
+  approvalTxHash?: string; // set when the router had to be approved first
+  gasPrice: number;
+  gasPriceToken: string;
+  gasLimit: number;
+  gasCost: string;
+  nonce: number;
+  txHash: string | undefined;
+  maxFeePerGas?: string; // in wei, set for EIP-1559 transactions
+  maxPriorityFeePerGas?: string;
+  simulation?: SimulationResult; // set instead of txHash when simulating
+}
+
+export interface V2PositionRequest extends NetworkSelectionRequest {
+  address: string;
+  token0: string;
+  token1: string;
+}
+
+export interface V2PositionResponse extends V2PositionInfo {
+  network: string;
+  timestamp: number;
+  latency: number;
+}
+
 export interface EstimateGasResponse {
   network: string;
   timestamp: number;
@@ -182,6 +411,35 @@ export interface PerpMarketResponse {
   isActive: boolean;
 }
 
+export interface PerpFundingResponse extends PerpFundingRate {
+  network: string;
+  timestamp: number;
+  latency: number;
+  base: string;
+  quote: string;
+}
+
+export interface PerpMarketInfoResponse extends PerpMarketInfo {
+  network: string;
+  timestamp: number;
+  latency: number;
+  base: string;
+  quote: string;
+}
+
+export interface PerpPriceHistoryRequest extends PerpMarketRequest {
+  from?: number; // ms
+}
+
+export interface PerpPriceHistoryResponse {
+  network: string;
+  timestamp: number;
+  latency: number;
+  base: string;
+  quote: string;
+  samples: PerpPriceSample[];
+}
+
 export interface PerpBalanceRequest extends NetworkSelectionRequest {
   address: string;
 }
@@ -216,7 +474,7 @@ export interface PerpCreateTakerRequest extends NetworkSelectionRequest {
   quote: string;
   base: string;
   address: string;
-  amount?: string;
+  amount?: string; // on close, only reduces the position by this much base
   side?: PerpSide;
   allowedSlippage?: string;
   nonce?: number;
@@ -236,3 +494,84 @@ export interface PerpCreateTakerResponse {
   nonce: number;
   txHash: string | undefined;
 }
+
+export interface PerpCreateMakerRequest extends NetworkSelectionRequest {
+  quote: string;
+  base: string;
+  address: string;
+  amount: string;
+  side: PerpSide;
+  price: string;
+  nonce?: number;
+}
+
+export interface PerpCreateMakerResponse {
+  network: string;
+  timestamp: number;
+  latency: number;
+  base: string;
+  quote: string;
+  amount: string;
+  side: PerpSide;
+  price: string;
+  orderId: string;
+  gasPrice: number;
+  gasPriceToken: string;
+  gasLimit: number;
+  gasCost: string;
+  nonce: number;
+  txHash: string | undefined;
+}
+
+export interface PerpOrdersRequest extends PerpMarketRequest {
+  address: string;
+}
+
+export interface PerpOrdersResponse {
+  network: string;
+  timestamp: number;
+  latency: number;
+  base: string;
+  quote: string;
+  orders: PerpLimitOrder[];
+}
+
+export interface PerpCancelOrderRequest extends PerpMarketRequest {
+  address: string;
+  orderId: string;
+  nonce?: number;
+}
+
+export interface PerpCancelOrderResponse {
+  network: string;
+  timestamp: number;
+  latency: number;
+  base: string;
+  quote: string;
+  orderId: string;
+  gasPrice: number;
+  gasPriceToken: string;
+  gasLimit: number;
+  gasCost: string;
+  nonce: number;
+  txHash: string | undefined;
+}
+
+export interface PerpMarginRequest extends NetworkSelectionRequest {
+  address: string;
+  amount: string;
+  nonce?: number;
+}
+
+export interface PerpMarginResponse {
+  network: string;
+  timestamp: number;
+  latency: number;
+  amount: string;
+  gasPrice: number;
+  gasPriceToken: string;
+  gasLimit: number;
+  gasCost: string;
+  nonce: number;
+  txHash: string | undefined;
+}
@@ -4,6 +4,7 @@ import { Router, Request, Response } from 'express';
 import { asyncHandler } from '../services/error-handler';
 import {
   price,
+  quote,
   trade,
   estimatePerpGas,
   perpMarketPrices,
@@ -12,12 +13,26 @@ import {
   perpPosition,
   perpPairs,
   positionInfo,
+  positionsOfWallet,
   addLiquidity,
   reduceLiquidity,
+  rebalanceLiquidity,
   collectFees,
   poolPrice,
+  poolInfo,
+  increaseObservations,
+  v2AddLiquidity,
+  v2RemoveLiquidity,
+  v2Position,
   estimateGas,
   perpBalance,
+  perpLimitOrder,
+  perpOrders,
+  perpCancelOrder,
+  perpMargin,
+  perpFunding,
+  perpMarketInfo,
+  perpPriceHistory,
 } from './amm.controllers';
 import {
   EstimateGasResponse,
@@ -31,19 +46,47 @@ import {
   PerpPricesResponse,
   PriceRequest,
   PriceResponse,
+  QuoteRequest,
+  QuoteResponse,
   TradeRequest,
   TradeResponse,
   AddLiquidityRequest,
   AddLiquidityResponse,
   RemoveLiquidityRequest,
   RemoveLiquidityResponse,
+  RebalanceRequest,
+  RebalanceResponse,
   CollectEarnedFeesRequest,
   PositionRequest,
   PositionResponse,
+  PositionsRequest,
+  PositionsResponse,
   PoolPriceRequest,
   PoolPriceResponse,
+  PoolInfoRequest,
+  PoolInfoResponse,
+  PoolObservationsRequest,
+  PoolObservationsResponse,
+  V2AddLiquidityRequest,
+  V2AddLiquidityResponse,
+  V2RemoveLiquidityRequest,
+  V2RemoveLiquidityResponse,
+  V2PositionRequest,
+  V2PositionResponse,
   PerpBalanceRequest,
   PerpBalanceResponse,
+  PerpCreateMakerRequest,
+  PerpCreateMakerResponse,
+  PerpOrdersRequest,
+  PerpOrdersResponse,
+  PerpCancelOrderRequest,
+  PerpCancelOrderResponse,
+  PerpMarginRequest,
+  PerpMarginResponse,
+  PerpFundingResponse,
+  PerpMarketInfoResponse,
+  PerpPriceHistoryRequest,
+  PerpPriceHistoryResponse,
 } from './amm.requests';
 import {
   validateEstimateGasRequest,
@@ -53,13 +96,25 @@ import {
   validatePerpPairsRequest,
   validatePerpPositionRequest,
   validatePriceRequest,
+  validateQuoteRequest,
   validateTradeRequest,
   validateAddLiquidityRequest,
   validateRemoveLiquidityRequest,
+  validateRebalanceRequest,
   validateCollectFeeRequest,
   validatePositionRequest,
+  validatePositionsRequest,
   validatePoolPriceRequest,
+  validatePoolInfoRequest,
+  validatePoolObservationsRequest,
+  validateV2AddLiquidityRequest,
+  validateV2RemoveLiquidityRequest,
+  validateV2PositionRequest,
   validatePerpBalanceRequest,
+  validatePerpLimitOrderRequest,
+  validatePerpCancelOrderRequest,
+  validatePerpMarginRequest,
+  validatePerpPriceHistoryRequest,
 } from './amm.validators';
 import { NetworkSelectionRequest } from '../services/common-interfaces';
 
@@ -79,6 +134,19 @@ export namespace AmmRoutes {
     )
   );
 
+  router.post(
+    '/quote',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, QuoteRequest>,
+        res: Response<QuoteResponse | string, {}>
+      ) => {
+        validateQuoteRequest(req.body);
+        res.status(200).json(await quote(req.body));
+      }
+    )
+  );
+
   router.post(
     '/trade',
     asyncHandler(
@@ -122,6 +190,19 @@ export namespace AmmLiquidityRoutes {
     )
   );
 
+  router.post(
+    '/positions',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, PositionsRequest>,
+        res: Response<PositionsResponse | string, {}>
+      ) => {
+        validatePositionsRequest(req.body);
+        res.status(200).json(await positionsOfWallet(req.body));
+      }
+    )
+  );
+
   router.post(
     '/add',
     asyncHandler(
@@ -148,6 +229,19 @@ export namespace AmmLiquidityRoutes {
     )
   );
 
+  router.post(
+    '/rebalance',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, RebalanceRequest>,
+        res: Response<RebalanceResponse | string, {}>
+      ) => {
+        validateRebalanceRequest(req.body);
+        res.status(200).json(await rebalanceLiquidity(req.body));
+      }
+    )
+  );
+
   router.post(
     '/collect_fees',
     asyncHandler(
@@ -173,6 +267,71 @@ export namespace AmmLiquidityRoutes {
       }
     )
   );
+
+  router.post(
+    '/pool',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, PoolInfoRequest>,
+        res: Response<PoolInfoResponse | string, {}>
+      ) => {
+        validatePoolInfoRequest(req.body);
+        res.status(200).json(await poolInfo(req.body));
+      }
+    )
+  );
+
+  router.post(
+    '/pool/observations',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, PoolObservationsRequest>,
+        res: Response<PoolObservationsResponse | string, {}>
+      ) => {
+        validatePoolObservationsRequest(req.body);
+        res.status(200).json(await increaseObservations(req.body));
+      }
+    )
+  );
+
+  router.post(
+    '/v2/add',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, V2AddLiquidityRequest>,
+        res: Response<V2AddLiquidityResponse | string, {}>
+      ) => {
+        validateV2AddLiquidityRequest(req.body);
+        res.status(200).json(await v2AddLiquidity(req.body));
+      }
+    )
+  );
+
+  router.post(
+    '/v2/remove',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, V2RemoveLiquidityRequest>,
+        res: Response<V2RemoveLiquidityResponse | string, {}>
+      ) => {
+        validateV2RemoveLiquidityRequest(req.body);
+        res.status(200).json(await v2RemoveLiquidity(req.body));
+      }
+    )
+  );
+
+  router.post(
+    '/v2/position',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, V2PositionRequest>,
+        res: Response<V2PositionResponse | string, {}>
+      ) => {
+        validateV2PositionRequest(req.body);
+        res.status(200).json(await v2Position(req.body));
+      }
+    )
+  );
 }
 
 export namespace PerpAmmRoutes {
@@ -204,6 +363,45 @@ export namespace PerpAmmRoutes {
     )
   );
 
+  router.post(
+    '/funding',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, PerpMarketRequest>,
+        res: Response<PerpFundingResponse | string, {}>
+      ) => {
+        validatePerpMarketStatusRequest(req.body);
+        res.status(200).json(await perpFunding(req.body));
+      }
+    )
+  );
+
+  router.post(
+    '/market-info',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, PerpMarketRequest>,
+        res: Response<PerpMarketInfoResponse | string, {}>
+      ) => {
+        validatePerpMarketStatusRequest(req.body);
+        res.status(200).json(await perpMarketInfo(req.body));
+      }
+    )
+  );
+
+  router.post(
+    '/price-history',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, PerpPriceHistoryRequest>,
+        res: Response<PerpPriceHistoryResponse | string, {}>
+      ) => {
+        validatePerpPriceHistoryRequest(req.body);
+        res.status(200).json(await perpPriceHistory(req.body));
+      }
+    )
+  );
+
   router.post(
     '/pairs',
     asyncHandler(
@@ -269,6 +467,71 @@ export namespace PerpAmmRoutes {
     )
   );
 
+  router.post(
+    '/order',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, PerpCreateMakerRequest>,
+        res: Response<PerpCreateMakerResponse | string, {}>
+      ) => {
+        validatePerpLimitOrderRequest(req.body);
+        res.status(200).json(await perpLimitOrder(req.body));
+      }
+    )
+  );
+
+  router.post(
+    '/orders',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, PerpOrdersRequest>,
+        res: Response<PerpOrdersResponse | string, {}>
+      ) => {
+        validatePerpPositionRequest(req.body);
+        res.status(200).json(await perpOrders(req.body));
+      }
+    )
+  );
+
+  router.post(
+    '/order/cancel',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, PerpCancelOrderRequest>,
+        res: Response<PerpCancelOrderResponse | string, {}>
+      ) => {
+        validatePerpCancelOrderRequest(req.body);
+        res.status(200).json(await perpCancelOrder(req.body));
+      }
+    )
+  );
+
+  router.post(
+    '/margin/add',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, PerpMarginRequest>,
+        res: Response<PerpMarginResponse | string, {}>
+      ) => {
+        validatePerpMarginRequest(req.body);
+        res.status(200).json(await perpMargin(req.body, true));
+      }
+    )
+  );
+
+  router.post(
+    '/margin/remove',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, PerpMarginRequest>,
+        res: Response<PerpMarginResponse | string, {}>
+      ) => {
+        validatePerpMarginRequest(req.body);
+        res.status(200).json(await perpMargin(req.body, false));
+      }
+    )
+  );
+
   router.post(
     '/estimateGas',
     asyncHandler(
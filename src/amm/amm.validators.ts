@@ -14,6 +14,8 @@ import {
   validateAddress,
   validateMaxFeePerGas,
   validateMaxPriorityFeePerGas,
+  validateSimulate,
+  validateSimulationBlock,
 } from '../chains/ethereum/ethereum.validators';
 
 import { FeeAmount } from '@uniswap/v3-sdk';
@@ -37,6 +39,18 @@ export const invalidSideError: string =
 export const invalidPerpSideError: string =
   'The side param must be a string of "LONG" or "SHORT".';
 
+export const invalidPriceError: string =
+  'The price param must be a string of a positive float or integer number.';
+
+export const invalidOrderIdError: string =
+  'The orderId param must be a string of a 32 byte hex.';
+
+export const invalidFromError: string =
+  'If from is included it must be a POSIX timestamp in milliseconds.';
+
+export const invalidConnectorsError: string =
+  'If connectors is included it must be a list of connector names.';
+
 export const invalidFeeTier: string = 'Incorrect fee tier';
 
 export const invalidLimitPriceError: string =
@@ -51,12 +65,39 @@ export const invalidTokenIdError: string =
 export const invalidTimeError: string =
   'Period or interval has to be a non-negative integer.';
 
+export const invalidRangePercentError: string =
+  'If rangePercent is included it must be a number between 0 and 100.';
+
+export const invalidDepthPercentsError: string =
+  'If depthPercents is included it must list numbers between 0 and 100.';
+
+export const invalidTwapWindowError: string =
+  'If twapWindow is included it must be a non-negative integer of seconds.';
+
+export const invalidCardinalityError: string =
+  'The cardinality param must be an integer between 1 and 65535.';
+
 export const invalidDecreasePercentError: string =
   'If decreasePercent is included it must be a non-negative integer.';
 
 export const invalidAllowedSlippageError: string =
   'The allowedSlippage param may be null or a string of a fraction.';
 
+export const invalidPathError: string =
+  'If path is included it must list at least two tokens.';
+
+export const invalidFeesError: string =
+  'If fees is included it must list fee tiers, e.g. LOW or MEDIUM.';
+
+export const invalidIntermediateTokensError: string =
+  'If intermediateTokens is included it must list tokens.';
+
+export const invalidExcludedPoolsError: string =
+  'If excludedPools is included it must list pool addresses.';
+
+export const invalidAutoWrapError: string =
+  'If autoWrap is included it must be a boolean.';
+
 export const validateConnector: Validator = mkValidator(
   'connector',
   invalidConnectorError,
@@ -93,6 +134,13 @@ export const validateAmount: Validator = mkValidator(
   (val) => typeof val === 'string' && isFloatString(val)
 );
 
+export const validateOptionalAmount: Validator = mkValidator(
+  'amount',
+  invalidAmountError,
+  (val) => typeof val === 'string' && isFloatString(val),
+  true
+);
+
 export const validateAmount0: Validator = mkValidator(
   'amount0',
   invalidAmountError,
@@ -117,6 +165,25 @@ export const validatePerpSide: Validator = mkValidator(
   (val) => typeof val === 'string' && (val === 'LONG' || val === 'SHORT')
 );
 
+export const validatePrice: Validator = mkValidator(
+  'price',
+  invalidPriceError,
+  (val) => typeof val === 'string' && isFloatString(val) && Number(val) > 0
+);
+
+export const validateOrderId: Validator = mkValidator(
+  'orderId',
+  invalidOrderIdError,
+  (val) => typeof val === 'string' && /^0x[0-9a-fA-F]{64}$/.test(val)
+);
+
+export const validateFrom: Validator = mkValidator(
+  'from',
+  invalidFromError,
+  (val) => typeof val === 'number' && val >= 0 && Number.isInteger(val),
+  true
+);
+
 export const validateFee: Validator = mkValidator(
   'fee',
   invalidFeeTier,
@@ -139,6 +206,19 @@ export const validateUpperPrice: Validator = mkValidator(
   true
 );
 
+// the range a rebalance moves to can't be left out
+export const validateRequiredLowerPrice: Validator = mkValidator(
+  'lowerPrice',
+  invalidLPPriceError,
+  (val) => typeof val === 'string' && isFloatString(val)
+);
+
+export const validateRequiredUpperPrice: Validator = mkValidator(
+  'upperPrice',
+  invalidLPPriceError,
+  (val) => typeof val === 'string' && isFloatString(val)
+);
+
 export const validateLimitPrice: Validator = mkValidator(
   'limitPrice',
   invalidLimitPriceError,
@@ -169,6 +249,37 @@ export const validateInterval: Validator = mkValidator(
   true
 );
 
+const isPercent = (val: any): boolean =>
+  typeof val === 'number' && val > 0 && val < 100;
+
+export const validateRangePercent: Validator = mkValidator(
+  'rangePercent',
+  invalidRangePercentError,
+  isPercent,
+  true
+);
+
+export const validateDepthPercents: Validator = mkValidator(
+  'depthPercents',
+  invalidDepthPercentsError,
+  (val) => Array.isArray(val) && val.every(isPercent),
+  true
+);
+
+export const validateTwapWindow: Validator = mkValidator(
+  'twapWindow',
+  invalidTwapWindowError,
+  (val) => typeof val === 'number' && val >= 0 && Number.isInteger(val),
+  true
+);
+
+export const validateCardinality: Validator = mkValidator(
+  'cardinality',
+  invalidCardinalityError,
+  (val) =>
+    typeof val === 'number' && Number.isInteger(val) && val > 0 && val < 65536
+);
+
 export const validateDecreasePercent: Validator = mkValidator(
   'decreasePercent',
   invalidDecreasePercentError,
@@ -185,6 +296,59 @@ export const validateAllowedSlippage: Validator = mkValidator(
   true
 );
 
+const isStringList = (val: any): boolean =>
+  Array.isArray(val) && val.every((item) => typeof item === 'string');
+
+export const validatePath: Validator = mkValidator(
+  'path',
+  invalidPathError,
+  (val) => isStringList(val) && val.length >= 2,
+  true
+);
+
+export const validateFees: Validator = mkValidator(
+  'fees',
+  invalidFeesError,
+  (val) =>
+    isStringList(val) &&
+    val.every((fee: string) =>
+      Object.keys(FeeAmount).includes(fee.toUpperCase())
+    ),
+  true
+);
+
+export const validateIntermediateTokens: Validator = mkValidator(
+  'intermediateTokens',
+  invalidIntermediateTokensError,
+  isStringList,
+  true
+);
+
+export const validateExcludedPools: Validator = mkValidator(
+  'excludedPools',
+  invalidExcludedPoolsError,
+  (val) =>
+    isStringList(val) &&
+    val.every((pool: string) => /^0x[0-9a-fA-F]{40}$/.test(pool)),
+  true
+);
+
+export const validateAutoWrap: Validator = mkValidator(
+  'autoWrap',
+  invalidAutoWrapError,
+  (val) => typeof val === 'boolean',
+  true
+);
+
+export const validateConnectors: Validator = mkValidator(
+  'connectors',
+  invalidConnectorsError,
+  (val) =>
+    Array.isArray(val) &&
+    val.every((connector) => typeof connector === 'string'),
+  true
+);
+
 export const validatePriceRequest: RequestValidator = mkRequestValidator([
   validateConnector,
   validateChain,
@@ -196,6 +360,17 @@ export const validatePriceRequest: RequestValidator = mkRequestValidator([
   validateAllowedSlippage,
 ]);
 
+export const validateQuoteRequest: RequestValidator = mkRequestValidator([
+  validateConnectors,
+  validateChain,
+  validateNetwork,
+  validateQuote,
+  validateBase,
+  validateAmount,
+  validateSide,
+  validateAllowedSlippage,
+]);
+
 export const validateTradeRequest: RequestValidator = mkRequestValidator([
   validateConnector,
   validateChain,
@@ -209,6 +384,13 @@ export const validateTradeRequest: RequestValidator = mkRequestValidator([
   validateMaxFeePerGas,
   validateMaxPriorityFeePerGas,
   validateAllowedSlippage,
+  validateSimulate,
+  validateSimulationBlock,
+  validatePath,
+  validateFees,
+  validateIntermediateTokens,
+  validateExcludedPools,
+  validateAutoWrap,
 ]);
 
 export const validatePerpPositionRequest: RequestValidator = mkRequestValidator(
@@ -238,6 +420,16 @@ export const validatePerpMarketStatusRequest: RequestValidator =
     validateBase,
   ]);
 
+export const validatePerpPriceHistoryRequest: RequestValidator =
+  mkRequestValidator([
+    validateConnector,
+    validateChain,
+    validateNetwork,
+    validateQuote,
+    validateBase,
+    validateFrom,
+  ]);
+
 export const validatePerpPairsRequest: RequestValidator = mkRequestValidator([
   validateConnector,
   validateChain,
@@ -266,10 +458,46 @@ export const validatePerpCloseTradeRequest: RequestValidator =
     validateQuote,
     validateBase,
     validateAddress,
+    validateOptionalAmount,
     validateNonce,
     validateAllowedSlippage,
   ]);
 
+export const validatePerpLimitOrderRequest: RequestValidator =
+  mkRequestValidator([
+    validateConnector,
+    validateChain,
+    validateNetwork,
+    validateQuote,
+    validateBase,
+    validateAmount,
+    validatePrice,
+    validateAddress,
+    validatePerpSide,
+    validateNonce,
+  ]);
+
+export const validatePerpCancelOrderRequest: RequestValidator =
+  mkRequestValidator([
+    validateConnector,
+    validateChain,
+    validateNetwork,
+    validateQuote,
+    validateBase,
+    validateAddress,
+    validateOrderId,
+    validateNonce,
+  ]);
+
+export const validatePerpMarginRequest: RequestValidator = mkRequestValidator([
+  validateConnector,
+  validateChain,
+  validateNetwork,
+  validateAddress,
+  validateAmount,
+  validateNonce,
+]);
+
 export const validateEstimateGasRequest: RequestValidator = mkRequestValidator([
   validateConnector,
   validateChain,
@@ -293,6 +521,8 @@ export const validateAddLiquidityRequest: RequestValidator = mkRequestValidator(
     validateNonce,
     validateMaxFeePerGas,
     validateMaxPriorityFeePerGas,
+    validateSimulate,
+    validateSimulationBlock,
   ]
 );
 
@@ -307,8 +537,25 @@ export const validateRemoveLiquidityRequest: RequestValidator =
     validateNonce,
     validateMaxFeePerGas,
     validateMaxPriorityFeePerGas,
+    validateSimulate,
+    validateSimulationBlock,
   ]);
 
+export const validateRebalanceRequest: RequestValidator = mkRequestValidator([
+  validateConnector,
+  validateChain,
+  validateNetwork,
+  validateAddress,
+  validateTokenId,
+  validateRequiredLowerPrice,
+  validateRequiredUpperPrice,
+  validateNonce,
+  validateMaxFeePerGas,
+  validateMaxPriorityFeePerGas,
+  validateSimulate,
+  validateSimulationBlock,
+]);
+
 export const validateCollectFeeRequest: RequestValidator = mkRequestValidator([
   validateConnector,
   validateChain,
@@ -318,6 +565,8 @@ export const validateCollectFeeRequest: RequestValidator = mkRequestValidator([
   validateNonce,
   validateMaxFeePerGas,
   validateMaxPriorityFeePerGas,
+  validateSimulate,
+  validateSimulationBlock,
 ]);
 
 export const validatePositionRequest: RequestValidator = mkRequestValidator([
@@ -327,6 +576,13 @@ export const validatePositionRequest: RequestValidator = mkRequestValidator([
   validateTokenId,
 ]);
 
+export const validatePositionsRequest: RequestValidator = mkRequestValidator([
+  validateConnector,
+  validateChain,
+  validateNetwork,
+  validateAddress,
+]);
+
 export const validatePoolPriceRequest: RequestValidator = mkRequestValidator([
   validateConnector,
   validateChain,
@@ -337,3 +593,74 @@ export const validatePoolPriceRequest: RequestValidator = mkRequestValidator([
   validateInterval,
   validatePeriod,
 ]);
+
+export const validatePoolInfoRequest: RequestValidator = mkRequestValidator([
+  validateConnector,
+  validateChain,
+  validateNetwork,
+  validateToken0,
+  validateToken1,
+  validateFee,
+  validateRangePercent,
+  validateDepthPercents,
+  validateTwapWindow,
+]);
+
+export const validatePoolObservationsRequest: RequestValidator =
+  mkRequestValidator([
+    validateConnector,
+    validateChain,
+    validateNetwork,
+    validateAddress,
+    validateToken0,
+    validateToken1,
+    validateFee,
+    validateCardinality,
+    validateNonce,
+    validateMaxFeePerGas,
+    validateMaxPriorityFeePerGas,
+  ]);
+
+export const validateV2AddLiquidityRequest: RequestValidator =
+  mkRequestValidator([
+    validateConnector,
+    validateChain,
+    validateNetwork,
+    validateAddress,
+    validateToken0,
+    validateToken1,
+    validateAmount0,
+    validateAmount1,
+    validateAllowedSlippage,
+    validateNonce,
+    validateMaxFeePerGas,
+    validateMaxPriorityFeePerGas,
+    validateSimulate,
+    validateSimulationBlock,
+  ]);
+
+export const validateV2RemoveLiquidityRequest: RequestValidator =
+  mkRequestValidator([
+    validateConnector,
+    validateChain,
+    validateNetwork,
+    validateAddress,
+    validateToken0,
+    validateToken1,
+    validateDecreasePercent,
+    validateAllowedSlippage,
+    validateNonce,
+    validateMaxFeePerGas,
+    validateMaxPriorityFeePerGas,
+    validateSimulate,
+    validateSimulationBlock,
+  ]);
+
+export const validateV2PositionRequest: RequestValidator = mkRequestValidator([
+  validateConnector,
+  validateChain,
+  validateNetwork,
+  validateAddress,
+  validateToken0,
+  validateToken1,
+]);
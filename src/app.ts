@@ -4,7 +4,9 @@ import { Request, Response, NextFunction } from 'express';
 import { ConfigRoutes } from './services/config/config.routes';
 import { CosmosRoutes } from './chains/cosmos/cosmos.routes';
 import { WalletRoutes } from './services/wallet/wallet.routes';
+import { HistoryRoutes } from './services/history/history.routes';
 import { logger } from './services/logger';
+import { authMiddleware, keepRawBody } from './services/auth';
 import { addHttps } from './https';
 import {
   asyncHandler,
@@ -13,6 +15,7 @@ import {
   gatewayErrorMiddleware,
 } from './services/error-handler';
 import { ConfigManagerV2 } from './services/config-manager-v2';
+import { registerConfigReloaders } from './services/config-reload';
 import { SwaggerManager } from './services/swagger-manager';
 import { NetworkRoutes } from './network/network.routes';
 import { ConnectorsRoutes } from './connectors/connectors.routes';
@@ -33,14 +36,21 @@ import { PancakeSwapConfig } from './connectors/pancakeswap/pancakeswap.config';
 
 import swaggerUi from 'swagger-ui-express';
 import { NearRoutes } from './chains/near/near.routes';
+import { EventStreamManager } from './network/network.events';
+import { Server as HttpServer } from 'http';
+import { Server as HttpsServer } from 'https';
+import { GatewayLifecycle, GatewayServer } from './lifecycle';
 
 export const gatewayApp = express();
 
+// running requests are waited for on a restart or shutdown
+gatewayApp.use(GatewayLifecycle.getInstance().trackRequests);
+
 // parse body for application/json
-gatewayApp.use(express.json());
+gatewayApp.use(express.json({ verify: keepRawBody }));
 
 // parse url for application/x-www-form-urlencoded
-gatewayApp.use(express.urlencoded({ extended: true }));
+gatewayApp.use(express.urlencoded({ extended: true, verify: keepRawBody }));
 
 // logging middleware
 // skip logging path '/' or `/network/status`
@@ -54,7 +64,11 @@ gatewayApp.use(
   })
 );
 
+// ip whitelist and api keys
+gatewayApp.use(authMiddleware);
+
 // mount sub routers
+registerConfigReloaders();
 gatewayApp.use('/config', ConfigRoutes.router);
 gatewayApp.use('/network', NetworkRoutes.router);
 gatewayApp.use('/evm', EVMRoutes.router);
@@ -64,6 +78,7 @@ gatewayApp.use('/amm', AmmRoutes.router);
 gatewayApp.use('/amm/perp', PerpAmmRoutes.router);
 gatewayApp.use('/amm/liquidity', AmmLiquidityRoutes.router);
 gatewayApp.use('/wallet', WalletRoutes.router);
+gatewayApp.use('/history', HistoryRoutes.router);
 gatewayApp.use('/cosmos', CosmosRoutes.router);
 gatewayApp.use('/near', NearRoutes.router);
 
@@ -97,10 +112,17 @@ gatewayApp.get(
 gatewayApp.post(
   '/restart',
   asyncHandler(async (_req, res) => {
-    // kill the current process and trigger the exit event
-    process.exit(1);
-    // this is only to satisfy the compiler, it will never be called.
-    res.status(200).json();
+    // the restart waits for the running requests, so it starts once this
+    // response is sent
+    res.on('finish', () => {
+      GatewayLifecycle.getInstance()
+        .restart()
+        .catch((e) => {
+          logger.error(`Failed to restart the gateway: ${e}`);
+          process.exit(1);
+        });
+    });
+    res.status(200).json({ message: 'The gateway is restarting' });
   })
 );
 
@@ -125,6 +147,7 @@ export const swaggerDocument = SwaggerManager.generateSwaggerJson(
     './docs/swagger/main-routes.yml',
     './docs/swagger/connectors-routes.yml',
     './docs/swagger/wallet-routes.yml',
+    './docs/swagger/history-routes.yml',
     './docs/swagger/amm-routes.yml',
     './docs/swagger/amm-liquidity-routes.yml',
     './docs/swagger/evm-routes.yml',
@@ -134,7 +157,7 @@ export const swaggerDocument = SwaggerManager.generateSwaggerJson(
   ]
 );
 
-export const startSwagger = async () => {
+export const startSwagger = async (): Promise<HttpServer> => {
   const swaggerApp = express();
   const swaggerPort = 8080;
 
@@ -144,10 +167,10 @@ export const startSwagger = async () => {
 
   swaggerApp.use('/', swaggerUi.serve, swaggerUi.setup(swaggerDocument));
 
-  await swaggerApp.listen(swaggerPort);
+  return await swaggerApp.listen(swaggerPort);
 };
 
-export const startGateway = async () => {
+export const startGateway = async (): Promise<GatewayServer[]> => {
   const port = ConfigManagerV2.getInstance().get('server.port');
   if (!ConfigManagerV2.getInstance().get('server.id')) {
     ConfigManagerV2.getInstance().set(
@@ -156,12 +179,13 @@ export const startGateway = async () => {
     );
   }
   logger.info(`⚡️ Starting Gateway API on port ${port}...`);
+  let server: HttpServer | HttpsServer;
   if (ConfigManagerV2.getInstance().get('server.unsafeDevModeWithHTTP')) {
     logger.info('Running in UNSAFE HTTP! This could expose private keys.');
-    await gatewayApp.listen(port);
+    server = await gatewayApp.listen(port);
   } else {
     try {
-      await addHttps(gatewayApp).listen(port);
+      server = await addHttps(gatewayApp).listen(port);
       logger.info('The gateway server is secured behind HTTPS.');
     } catch (e) {
       logger.error(
@@ -170,6 +194,7 @@ export const startGateway = async () => {
       process.exit();
     }
   }
+  EventStreamManager.getInstance().attach(server);
 
-  await startSwagger();
+  return [server, await startSwagger()];
 };
@@ -5,9 +5,33 @@ import NodeCache from 'node-cache';
 import fse from 'fs-extra';
 import { ConfigManagerCertPassphrase } from '../../services/config-manager-cert-passphrase';
 import { BigNumber } from 'ethers';
-import { AccountData, DirectSignResponse } from '@cosmjs/proto-signing';
-
-import { IndexedTx, setupIbcExtension } from '@cosmjs/stargate';
+import {
+  AccountData,
+  DirectSignResponse,
+  EncodeObject,
+  GeneratedType,
+  OfflineDirectSigner,
+  Registry,
+} from '@cosmjs/proto-signing';
+
+import {
+  calculateFee,
+  defaultRegistryTypes,
+  DeliverTxResponse,
+  GasPrice,
+  IndexedTx,
+  setupIbcExtension,
+  SigningStargateClient,
+  StdFee,
+} from '@cosmjs/stargate';
+import {
+  RpcEndpointConfig,
+  RpcEndpointHealth,
+  RpcEndpointPool,
+} from '../../services/rpc-endpoint-pool';
+import { resolveDBPath } from '../../paths';
+import { ReferenceCountingCloseable } from '../../services/refcounting-closeable';
+import { TransactionJournal } from '../../services/transaction-journal';
 
 //Cosmos
 const { DirectSecp256k1Wallet } = require('@cosmjs/proto-signing');
@@ -52,8 +76,32 @@ export type NewBlockHandler = (bn: number) => void;
 
 export type NewDebugMsgHandler = (msg: any) => void;
 
+// StargateClient has no push subscription for new blocks, so the height is
+// polled at this interval (in milliseconds) while handlers are registered.
+export const BLOCK_POLLING_INTERVAL = 5000;
+
+// the codes of the axios errors, under the cosmjs RPC client, of an endpoint
+// that can't be reached or doesn't answer in time
+const TRANSPORT_ERROR_CODES = [
+  'ECONNREFUSED',
+  'ECONNRESET',
+  'ECONNABORTED',
+  'ETIMEDOUT',
+  'ENOTFOUND',
+  'EAI_AGAIN',
+];
+
+// transport failures and rate limits are worth another endpoint, ABCI query
+// errors such as an unknown pool are the same on every node
+export function isRetryableCosmosError(error: any): boolean {
+  if (TRANSPORT_ERROR_CODES.includes(error.code)) return true;
+  const status = error.response?.status;
+  if (typeof status === 'number' && status >= 429) return true;
+  return /rate limit|too many requests/i.test(String(error.message));
+}
+
 export class CosmosBase {
-  private _provider;
+  private _pool: RpcEndpointPool<Promise<any>>;
   protected tokenList: Token[] = [];
   private _tokenMap: Record<string, Token> = {};
 
@@ -67,29 +115,90 @@ export class CosmosBase {
   public tokenListSource: string;
   public tokenListType: TokenListType;
   public cache: NodeCache;
+  // the messages the gateway can sign, connectors register their own
+  private _registry: Registry = new Registry(defaultRegistryTypes);
+  private _blockHandlers: NewBlockHandler[] = [];
+  private _blockPollingTimer: ReturnType<typeof setInterval> | null = null;
+  private _lastBlockNumber: number = 0;
+  private readonly _refCountingHandle: string;
+  private readonly _journal: TransactionJournal;
 
   constructor(
     chainName: string,
     rpcUrl: string,
     tokenListSource: string,
     tokenListType: TokenListType,
-    gasPriceConstant: number
+    gasPriceConstant: number,
+    transactionDbPath: string,
+    rpcURLs: RpcEndpointConfig[] = [],
+    rpcQuorum: number = 1
   ) {
-    this._provider = StargateClient.connect(rpcUrl);
+    // one StargateClient per endpoint, connected on first use
+    this._pool = new RpcEndpointPool(
+      rpcURLs.length > 0 ? rpcURLs : [{ url: rpcUrl }],
+      (url: string) => StargateClient.connect(url),
+      { quorum: rpcQuorum, isRetryable: isRetryableCosmosError }
+    );
     this.chainName = chainName;
     this.rpcUrl = rpcUrl;
     this.gasPriceConstant = gasPriceConstant;
     this.tokenListSource = tokenListSource;
     this.tokenListType = tokenListType;
     this.cache = new NodeCache({ stdTTL: 3600 }); // set default cache ttl to 1hr
+    this._refCountingHandle = ReferenceCountingCloseable.createHandle();
+    this._journal = TransactionJournal.getInstance(
+      resolveDBPath(transactionDbPath),
+      this._refCountingHandle
+    );
   }
 
   ready(): boolean {
     return this._ready;
   }
 
+  // the client of the best endpoint at the moment
   public get provider() {
-    return this._provider;
+    return this._pool.ordered()[0].client;
+  }
+
+  public rpcHealth(): RpcEndpointHealth[] {
+    return this._pool.health();
+  }
+
+  public get journal(): TransactionJournal {
+    return this._journal;
+  }
+
+  public onNewBlock(func: NewBlockHandler) {
+    this._blockHandlers.push(func);
+    if (this._blockPollingTimer === null) {
+      this._blockPollingTimer = setInterval(
+        this.pollNewBlock.bind(this),
+        BLOCK_POLLING_INTERVAL
+      );
+    }
+  }
+
+  public offNewBlock(func: NewBlockHandler) {
+    this._blockHandlers = this._blockHandlers.filter(
+      (handler) => handler !== func
+    );
+    if (this._blockHandlers.length === 0 && this._blockPollingTimer !== null) {
+      clearInterval(this._blockPollingTimer);
+      this._blockPollingTimer = null;
+    }
+  }
+
+  async pollNewBlock(): Promise<void> {
+    try {
+      const blockNumber = await this.getCurrentBlockNumber();
+      if (blockNumber > this._lastBlockNumber) {
+        this._lastBlockNumber = blockNumber;
+        this._blockHandlers.forEach((handler) => handler(blockNumber));
+      }
+    } catch (_e) {
+      // the node is unreachable, try again on the next interval
+    }
   }
 
   async init(): Promise<void> {
@@ -297,13 +406,13 @@ export class CosmosBase {
   async getBalances(wallet: CosmosWallet): Promise<Record<string, TokenValue>> {
     const balances: Record<string, TokenValue> = {};
 
-    const provider = await this._provider;
-
     const accounts = await wallet.getAccounts();
 
     const { address } = accounts[0];
 
-    const allTokens = await provider.getAllBalances(address);
+    const allTokens = await this._pool.executeQuorum(async (provider) =>
+      (await provider).getAllBalances(address)
+    );
 
     await Promise.all(
       allTokens.map(async (t: { denom: string; amount: string }) => {
@@ -314,9 +423,12 @@ export class CosmosBase {
 
           // Get base denom by IBC hash
           if (ibcHash) {
-            const { denomTrace } = await setupIbcExtension(
-              await provider.queryClient
-            ).ibc.transfer.denomTrace(ibcHash);
+            const { denomTrace } = await this._pool.execute(
+              async (provider) =>
+                setupIbcExtension(
+                  (await provider).queryClient
+                ).ibc.transfer.denomTrace(ibcHash)
+            );
 
             if (denomTrace) {
               const { baseDenom } = denomTrace;
@@ -337,10 +449,115 @@ export class CosmosBase {
     return balances;
   }
 
+  // a bank send of an amount of a denom, in its smallest unit
+  msgSend(
+    from: string,
+    to: string,
+    amount: string,
+    denom: string
+  ): EncodeObject {
+    return {
+      typeUrl: '/cosmos.bank.v1beta1.MsgSend',
+      value: { fromAddress: from, toAddress: to, amount: [{ denom, amount }] },
+    };
+  }
+
+  /**
+   * An ICS-20 transfer of an amount of a denom over an IBC channel. If it is
+   * not relayed to the destination chain within timeoutSeconds it times out
+   * and the amount is refunded.
+   */
+  msgIbcTransfer(
+    from: string,
+    to: string,
+    amount: string,
+    denom: string,
+    channel: string,
+    timeoutSeconds: number
+  ): EncodeObject {
+    const timeout = Math.floor(Date.now() / 1000) + timeoutSeconds;
+    return {
+      typeUrl: '/ibc.applications.transfer.v1.MsgTransfer',
+      value: {
+        sourcePort: 'transfer',
+        sourceChannel: channel,
+        token: { denom, amount },
+        sender: from,
+        receiver: to,
+        timeoutTimestamp: `${timeout}000000000`, // in nanoseconds
+      },
+    };
+  }
+
+  /**
+   * The fee of messages signed by a wallet: their simulated gas times
+   * gasAdjustment is the gas limit, paid at gasPrice.
+   */
+  async estimateFee(
+    wallet: CosmosWallet,
+    messages: EncodeObject[],
+    gasPrice: GasPrice,
+    gasAdjustment: number,
+    memo: string = ''
+  ): Promise<StdFee> {
+    return this.withSigningClient(wallet, async (client, sender) => {
+      const gas = await client.simulate(sender, messages, memo);
+      return calculateFee(Math.ceil(gas * gasAdjustment), gasPrice);
+    });
+  }
+
+  /**
+   * Signs messages with a wallet and broadcasts them. Resolves once the
+   * transaction is in a block, the code of the response tells if it failed
+   * there.
+   */
+  async signAndBroadcast(
+    wallet: CosmosWallet,
+    messages: EncodeObject[],
+    fee: StdFee,
+    memo: string = ''
+  ): Promise<DeliverTxResponse> {
+    return this.withSigningClient(wallet, (client, sender) =>
+      client.signAndBroadcast(sender, messages, fee, memo)
+    );
+  }
+
+  public registerMessageType(typeUrl: string, type: GeneratedType): void {
+    this._registry.register(typeUrl, type);
+  }
+
+  /**
+   * Queries a gRPC method of the node through ABCI, like
+   * /osmosis.poolmanager.v1beta1.Query/AllPools, with an encoded request.
+   */
+  async queryAbci(path: string, request: Uint8Array): Promise<Uint8Array> {
+    return await this._pool.execute(async (provider) =>
+      (await provider).queryClient.queryUnverified(path, request)
+    );
+  }
+
+  private async withSigningClient<T>(
+    wallet: CosmosWallet,
+    func: (client: SigningStargateClient, sender: string) => Promise<T>
+  ): Promise<T> {
+    const [account] = await wallet.getAccounts();
+    const client = await SigningStargateClient.connectWithSigner(
+      this.rpcUrl,
+      wallet as unknown as OfflineDirectSigner,
+      { registry: this._registry }
+    );
+    try {
+      return await func(client, account.address);
+    } finally {
+      client.disconnect();
+    }
+  }
+
   // returns a cosmos tx for a txHash
   async getTransaction(id: string): Promise<IndexedTx> {
-    const provider = await this._provider;
-    const transaction = await provider.getTx(id);
+    const transaction = await this._pool.executeQuorum(async (provider) =>
+      (await provider).getTx(id)
+    );
 
     if (!transaction) {
       throw new Error('Transaction not found');
@@ -360,8 +577,12 @@ export class CosmosBase {
   }
 
   async getCurrentBlockNumber(): Promise<number> {
-    const provider = await this._provider;
+    return await this._pool.execute(async (provider) =>
+      (await provider).getHeight()
+    );
+  }
 
-    return await provider.getHeight();
+  async close() {
+    await this._journal.close(this._refCountingHandle);
   }
 }
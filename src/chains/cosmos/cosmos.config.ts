@@ -1,39 +1,60 @@
 import { TokenListType } from '../../services/base';
 import { ConfigManagerV2 } from '../../services/config-manager-v2';
+import { RpcEndpointConfig } from '../../services/rpc-endpoint-pool';
 export interface NetworkConfig {
   name: string;
   rpcURL: string;
+  rpcURLs: RpcEndpointConfig[] | undefined;
+  rpcQuorum: number | undefined;
   tokenListType: TokenListType;
   tokenListSource: string;
+  ibcChannels: Record<string, string> | undefined;
 }
 
 export interface Config {
   network: NetworkConfig;
   nativeCurrencySymbol: string;
   manualGasPrice: number;
+  minimumGasPrice: number;
+  gasAdjustment: number;
+  ibcTimeout: number;
 }
 
 export namespace CosmosConfig {
-  export const config: Config = getCosmosConfig('cosmos');
+  export const config: Config = getCosmosConfig(
+    'cosmos',
+    ConfigManagerV2.getInstance().get('cosmos.network')
+  );
 }
 
-export function getCosmosConfig(chainName: string): Config {
+export function getCosmosConfig(chainName: string, network: string): Config {
   const configManager = ConfigManagerV2.getInstance();
-  const network = configManager.get(chainName + '.network');
   return {
     network: {
       name: network,
       rpcURL: configManager.get(chainName + '.networks.' + network + '.rpcURL'),
+      rpcURLs: configManager.get(
+        chainName + '.networks.' + network + '.rpcURLs'
+      ),
+      rpcQuorum: configManager.get(
+        chainName + '.networks.' + network + '.rpcQuorum'
+      ),
       tokenListType: configManager.get(
         chainName + '.networks.' + network + '.tokenListType'
       ),
       tokenListSource: configManager.get(
         chainName + '.networks.' + network + '.tokenListSource'
       ),
+      ibcChannels: configManager.get(
+        chainName + '.networks.' + network + '.ibcChannels'
+      ),
     },
     nativeCurrencySymbol: configManager.get(
       chainName + '.nativeCurrencySymbol'
     ),
     manualGasPrice: configManager.get(chainName + '.manualGasPrice'),
+    minimumGasPrice: configManager.get(chainName + '.minimumGasPrice'),
+    gasAdjustment: configManager.get(chainName + '.gasAdjustment'),
+    ibcTimeout: configManager.get(chainName + '.ibcTimeout'),
   };
 }
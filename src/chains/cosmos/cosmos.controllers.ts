@@ -2,15 +2,34 @@ import { Cosmos } from './cosmos';
 import {
   CosmosBalanceRequest,
   CosmosBalanceResponse,
+  CosmosIbcTransferRequest,
+  CosmosIbcTransferResponse,
   CosmosPollRequest,
   CosmosPollResponse,
+  CosmosTransferRequest,
+  CosmosTransferResponse,
+  CosmosTxRequest,
+  CosmosTxResponse,
+  TransactionResponseStatusCode,
 } from './cosmos.requests';
 import { latency, TokenValue, tokenValueToString } from '../../services/base';
 import {
+  GAS_PRICE_TOO_LOW_ERROR_MESSAGE,
   HttpException,
+  IBC_CHANNEL_NOT_FOUND_ERROR_CODE,
+  IBC_CHANNEL_NOT_FOUND_ERROR_MESSAGE,
+  LOAD_WALLET_ERROR_CODE,
+  LOAD_WALLET_ERROR_MESSAGE,
   TOKEN_NOT_SUPPORTED_ERROR_CODE,
   TOKEN_NOT_SUPPORTED_ERROR_MESSAGE,
+  TRANSACTION_GAS_PRICE_TOO_LOW,
 } from '../../services/error-handler';
+import { Cosmosish } from '../../services/common-interfaces';
+import { checkRisk } from '../../services/risk-manager';
+import { EncodeObject } from '@cosmjs/proto-signing';
+import { GasPrice, logs } from '@cosmjs/stargate';
+import { utils } from 'ethers';
+import { CosmosWallet, Token } from './cosmos-base';
 
 const { decodeTxRaw } = require('@cosmjs/proto-signing');
 
@@ -66,6 +85,192 @@ export const toCosmosBalances = (
   return walletBalances;
 };
 
+export async function transfer(
+  cosmos: Cosmos,
+  req: CosmosTransferRequest
+): Promise<CosmosTransferResponse> {
+  const initTime = Date.now();
+  const { wallet, token } = await prepareTransfer(cosmos, req);
+
+  const message = cosmos.msgSend(
+    req.address,
+    req.to,
+    utils.parseUnits(req.amount, token.decimals).toString(),
+    token.base
+  );
+  const tx = await signAndBroadcast(cosmos, wallet, [message], req);
+  await journalTransfer(cosmos, req, tx);
+
+  return {
+    network: cosmos.chain,
+    timestamp: initTime,
+    latency: latency(initTime, Date.now()),
+    token: req.token,
+    denom: token.base,
+    to: req.to,
+    amount: req.amount,
+    ...tx,
+  };
+}
+
+export async function ibcTransfer(
+  cosmos: Cosmos,
+  req: CosmosIbcTransferRequest
+): Promise<CosmosIbcTransferResponse> {
+  const initTime = Date.now();
+  const channel = cosmos.ibcChannels[req.destination];
+  if (!channel) {
+    throw new HttpException(
+      500,
+      IBC_CHANNEL_NOT_FOUND_ERROR_MESSAGE(req.destination),
+      IBC_CHANNEL_NOT_FOUND_ERROR_CODE
+    );
+  }
+  const { wallet, token } = await prepareTransfer(cosmos, req);
+
+  const message = cosmos.msgIbcTransfer(
+    req.address,
+    req.to,
+    utils.parseUnits(req.amount, token.decimals).toString(),
+    token.base,
+    channel,
+    cosmos.ibcTimeout
+  );
+  const tx = await signAndBroadcast(cosmos, wallet, [message], req);
+  await journalTransfer(cosmos, req, tx);
+
+  return {
+    network: cosmos.chain,
+    timestamp: initTime,
+    latency: latency(initTime, Date.now()),
+    token: req.token,
+    denom: token.base,
+    to: req.to,
+    amount: req.amount,
+    destination: req.destination,
+    channel,
+    ...tx,
+  };
+}
+
+// loads the wallet and the token of a transfer and checks its risk
+async function prepareTransfer(
+  cosmos: Cosmos,
+  req: CosmosTransferRequest
+): Promise<{ wallet: CosmosWallet; token: Token }> {
+  let wallet: CosmosWallet;
+  try {
+    wallet = await cosmos.getWallet(req.address, 'cosmos');
+  } catch (err) {
+    throw new HttpException(
+      500,
+      LOAD_WALLET_ERROR_MESSAGE + err,
+      LOAD_WALLET_ERROR_CODE
+    );
+  }
+
+  const token = cosmos.getTokenBySymbol(req.token);
+  if (!token) {
+    throw new HttpException(
+      500,
+      TOKEN_NOT_SUPPORTED_ERROR_MESSAGE + req.token,
+      TOKEN_NOT_SUPPORTED_ERROR_CODE
+    );
+  }
+  await checkRisk({
+    wallet: req.address,
+    tokens: [req.token],
+    amounts: [req.amount],
+  });
+
+  return { wallet, token };
+}
+
+// a broadcast returns once the transaction is in a block, so its entry is
+// settled right away
+async function journalTransfer(
+  cosmos: Cosmos,
+  req: CosmosTransferRequest,
+  tx: CosmosTxResponse
+): Promise<void> {
+  if (tx.txHash === undefined) return;
+  await cosmos.journal.record({
+    txHash: tx.txHash,
+    chain: cosmos.chainName,
+    network: cosmos.chain,
+    type: 'transfer',
+    wallet: req.address,
+    tokens: [req.token],
+    amounts: [req.amount],
+    gasPrice: tx.gasPrice,
+    gasLimit: tx.gasLimit,
+  });
+  await cosmos.journal.updateStatus(
+    tx.txHash,
+    tx.code ? 'FAILED' : 'CONFIRMED'
+  );
+}
+
+/**
+ * Estimates the fee of messages at the gas price of the request, at least
+ * the minimum gas price, and unless the request is a simulation signs and
+ * broadcasts them.
+ */
+export async function signAndBroadcast(
+  cosmos: Cosmosish,
+  wallet: CosmosWallet,
+  messages: EncodeObject[],
+  req: CosmosTxRequest
+): Promise<CosmosTxResponse> {
+  const feeToken = cosmos.getTokenBySymbol(cosmos.nativeTokenSymbol);
+  if (!feeToken) {
+    throw new HttpException(
+      500,
+      TOKEN_NOT_SUPPORTED_ERROR_MESSAGE + cosmos.nativeTokenSymbol,
+      TOKEN_NOT_SUPPORTED_ERROR_CODE
+    );
+  }
+  const gasPrice =
+    req.gasPrice !== undefined ? req.gasPrice : cosmos.minimumGasPrice;
+  if (gasPrice < cosmos.minimumGasPrice) {
+    throw new HttpException(
+      500,
+      GAS_PRICE_TOO_LOW_ERROR_MESSAGE(gasPrice, cosmos.minimumGasPrice),
+      TRANSACTION_GAS_PRICE_TOO_LOW
+    );
+  }
+
+  const fee = await cosmos.estimateFee(
+    wallet,
+    messages,
+    GasPrice.fromString(`${gasPrice}${feeToken.base}`),
+    req.gasAdjustment || cosmos.gasAdjustment,
+    req.memo
+  );
+  const estimate = {
+    gasPrice,
+    gasLimit: parseInt(fee.gas, 10),
+    fee: utils.formatUnits(fee.amount[0].amount, feeToken.decimals),
+  };
+  if (req.simulate) return estimate;
+
+  const result = await cosmos.signAndBroadcast(
+    wallet,
+    messages,
+    fee,
+    req.memo
+  );
+  return {
+    ...estimate,
+    txHash: result.transactionHash,
+    txBlock: result.height,
+    code: result.code,
+    rawLog: result.rawLog,
+    gasUsed: result.gasUsed,
+    gasWanted: result.gasWanted,
+  };
+}
+
 export async function poll(
   cosmos: Cosmos,
   req: CosmosPollRequest
@@ -73,6 +278,7 @@ export async function poll(
   const initTime = Date.now();
   const transaction = await cosmos.getTransaction(req.txHash);
   const currentBlock = await cosmos.getCurrentBlockNumber();
+  const succeeded = transaction.code === 0;
 
   return {
     network: cosmos.chain,
@@ -80,6 +286,16 @@ export async function poll(
     txHash: req.txHash,
     currentBlock,
     txBlock: transaction.height,
+    txStatus: succeeded
+      ? TransactionResponseStatusCode.CONFIRMED
+      : TransactionResponseStatusCode.FAILED,
+    code: transaction.code,
+    rawLog: transaction.rawLog,
+    // a failed transaction has its error message in place of the logs
+    logs:
+      succeeded && transaction.rawLog
+        ? logs.parseRawLog(transaction.rawLog)
+        : [],
     gasUsed: transaction.gasUsed,
     gasWanted: transaction.gasWanted,
     txData: decodeTxRaw(transaction.tx),
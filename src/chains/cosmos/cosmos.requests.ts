@@ -1,4 +1,5 @@
 import { DecodedTxRaw } from '@cosmjs/proto-signing';
+import { logs } from '@cosmjs/stargate';
 export interface CosmosBalanceRequest {
   address: string; // the user's Cosmos address as Bech32
   tokenSymbols: string[]; // a list of token symbol
@@ -16,6 +17,53 @@ export interface CosmosTokenRequest {
   token: string;
 }
 
+// options of requests that sign and broadcast a transaction
+export interface CosmosTxRequest {
+  gasPrice?: number; // in the smallest unit of the native currency, uatom
+  gasAdjustment?: number; // the simulated gas times this is the gas limit
+  memo?: string;
+  simulate?: boolean; // only estimate the fee, do not broadcast
+}
+
+export interface CosmosTxResponse {
+  gasPrice: number;
+  gasLimit: number;
+  fee: string; // in units of the native currency
+  // the rest is undefined for a simulated request
+  txHash?: string;
+  txBlock?: number;
+  code?: number; // 0 when the transaction succeeded
+  rawLog?: string;
+  gasUsed?: number;
+  gasWanted?: number;
+}
+
+export interface CosmosTransferRequest extends CosmosTxRequest {
+  address: string; // the sender's Cosmos address as Bech32
+  to: string; // the receiving address as Bech32
+  token: string; // a token symbol, or the native currency symbol
+  amount: string; // in units of the token, e.g. '1.5'
+}
+
+export interface CosmosTransferResponse extends CosmosTxResponse {
+  network: string;
+  timestamp: number;
+  latency: number;
+  token: string;
+  denom: string;
+  to: string;
+  amount: string;
+}
+
+export interface CosmosIbcTransferRequest extends CosmosTransferRequest {
+  destination: string; // a chain with an IBC channel in ibcChannels
+}
+
+export interface CosmosIbcTransferResponse extends CosmosTransferResponse {
+  destination: string;
+  channel: string;
+}
+
 export interface CosmosPollRequest {
   txHash: string;
 }
@@ -31,6 +79,10 @@ export interface CosmosPollResponse {
   txHash: string;
   currentBlock: number;
   txBlock: number;
+  txStatus: TransactionResponseStatusCode;
+  code: number; // 0 when the transaction succeeded
+  rawLog: string; // the error message of a failed transaction
+  logs: readonly logs.Log[]; // the events of each message, when succeeded
   gasUsed: number;
   gasWanted: number;
   txData: DecodedTxRaw | null;
@@ -3,16 +3,27 @@ import { NextFunction, Router, Request, Response } from 'express';
 import { verifyCosmosIsAvailable } from './cosmos-middlewares';
 import { asyncHandler } from '../../services/error-handler';
 import { Cosmos } from './cosmos';
-import { balances, poll } from './cosmos.controllers';
+import {
+  balances,
+  ibcTransfer,
+  poll,
+  transfer,
+} from './cosmos.controllers';
 import {
   CosmosBalanceResponse,
   CosmosBalanceRequest,
+  CosmosIbcTransferRequest,
+  CosmosIbcTransferResponse,
   CosmosPollRequest,
   CosmosPollResponse,
+  CosmosTransferRequest,
+  CosmosTransferResponse,
 } from './cosmos.requests';
 import {
   validateCosmosBalanceRequest,
+  validateCosmosIbcTransferRequest,
   validateCosmosPollRequest,
+  validateCosmosTransferRequest,
 } from './cosmos.validators';
 
 export namespace CosmosRoutes {
@@ -29,11 +40,12 @@ export namespace CosmosRoutes {
   router.get(
     '/',
     asyncHandler(async (_req: Request, res: Response) => {
-      const { rpcUrl, chain } = await getCosmos(_req);
+      const cosmos = await getCosmos(_req);
 
       res.status(200).json({
-        network: chain,
-        rpcUrl: rpcUrl,
+        network: cosmos.chain,
+        rpcUrl: cosmos.rpcUrl,
+        rpcEndpoints: cosmos.rpcHealth(),
         connection: true,
         timestamp: Date.now(),
       });
@@ -56,6 +68,38 @@ export namespace CosmosRoutes {
     )
   );
 
+  // Sends a token from a wallet to an address
+  router.post(
+    '/transfer',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, CosmosTransferRequest>,
+        res: Response<CosmosTransferResponse, {}>
+      ) => {
+        const cosmos = await getCosmos(req);
+
+        validateCosmosTransferRequest(req.body);
+        res.status(200).json(await transfer(cosmos, req.body));
+      }
+    )
+  );
+
+  // Sends a token from a wallet to an address on another chain over IBC
+  router.post(
+    '/ibcTransfer',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, CosmosIbcTransferRequest>,
+        res: Response<CosmosIbcTransferResponse, {}>
+      ) => {
+        const cosmos = await getCosmos(req);
+
+        validateCosmosIbcTransferRequest(req.body);
+        res.status(200).json(await ibcTransfer(cosmos, req.body));
+      }
+    )
+  );
+
   // Gets status information about given transaction hash
   router.post(
     '/poll',
@@ -2,43 +2,86 @@ import { Cosmosish } from '../../services/common-interfaces';
 import { CosmosBase } from './cosmos-base';
 import { getCosmosConfig } from './cosmos.config';
 import { logger } from '../../services/logger';
+import { ConfigManagerV2 } from '../../services/config-manager-v2';
 
+// the human readable part of the addresses of each chain of the Cosmos family
+const BECH32_PREFIXES: Record<string, string> = {
+  cosmos: 'cosmos',
+  osmosis: 'osmo',
+};
+
+/**
+ * A chain of the Cosmos family, configured in the namespace of its name.
+ * Instances are kept by network followed by chain name.
+ */
 export class Cosmos extends CosmosBase implements Cosmosish {
   private static _instances: { [name: string]: Cosmos };
+  private _bech32Prefix: string;
   private _gasPrice: number;
+  private _minimumGasPrice: number;
+  private _gasAdjustment: number;
+  private _ibcChannels: Record<string, string>;
+  private _ibcTimeout: number;
   private _nativeTokenSymbol: string;
   private _chain: string;
   private _requestCount: number;
   private _metricsLogInterval: number;
+  private _metricsTimer: ReturnType<typeof setInterval>;
 
-  private constructor(network: string) {
-    const config = getCosmosConfig('cosmos');
+  private constructor(
+    network: string,
+    chainName: string,
+    bech32Prefix: string
+  ) {
+    const config = getCosmosConfig(chainName, network);
     super(
-      'cosmos',
+      chainName,
       config.network.rpcURL,
       config.network.tokenListSource,
       config.network.tokenListType,
-      config.manualGasPrice
+      config.manualGasPrice,
+      ConfigManagerV2.getInstance().get('server.transactionDbPath'),
+      config.network.rpcURLs,
+      config.network.rpcQuorum
     );
     this._chain = network;
+    this._bech32Prefix = bech32Prefix;
     this._nativeTokenSymbol = config.nativeCurrencySymbol;
 
     this._gasPrice = config.manualGasPrice;
+    this._minimumGasPrice = config.minimumGasPrice;
+    this._gasAdjustment = config.gasAdjustment;
+    this._ibcChannels = config.network.ibcChannels || {};
+    this._ibcTimeout = config.ibcTimeout;
 
     this._requestCount = 0;
     this._metricsLogInterval = 300000; // 5 minutes
 
-    setInterval(this.metricLogger.bind(this), this.metricsLogInterval);
+    this._metricsTimer = setInterval(
+      this.metricLogger.bind(this),
+      this.metricsLogInterval
+    );
   }
 
-  public static getInstance(network: string): Cosmos {
+  public static getInstance(
+    network: string,
+    chainName: string = 'cosmos'
+  ): Cosmos {
+    if (!(chainName in BECH32_PREFIXES)) {
+      throw new Error(`${chainName} is not a chain of the Cosmos family.`);
+    }
     if (Cosmos._instances === undefined) {
       Cosmos._instances = {};
     }
-    if (!(network in Cosmos._instances)) {
-      Cosmos._instances[network] = new Cosmos(network);
+    const key = network + chainName;
+    if (!(key in Cosmos._instances)) {
+      Cosmos._instances[key] = new Cosmos(
+        network,
+        chainName,
+        BECH32_PREFIXES[chainName]
+      );
     }
-    return Cosmos._instances[network];
+    return Cosmos._instances[key];
   }
 
   public static getConnectedInstances(): { [name: string]: Cosmos } {
@@ -63,6 +106,27 @@ export class Cosmos extends CosmosBase implements Cosmosish {
     return this._gasPrice;
   }
 
+  public get minimumGasPrice(): number {
+    return this._minimumGasPrice;
+  }
+
+  public get gasAdjustment(): number {
+    return this._gasAdjustment;
+  }
+
+  // the IBC transfer channel to each destination chain
+  public get ibcChannels(): Record<string, string> {
+    return this._ibcChannels;
+  }
+
+  public get ibcTimeout(): number {
+    return this._ibcTimeout;
+  }
+
+  public get bech32Prefix(): string {
+    return this._bech32Prefix;
+  }
+
   public get chain(): string {
     return this._chain;
   }
@@ -80,8 +144,11 @@ export class Cosmos extends CosmosBase implements Cosmosish {
   }
 
   async close() {
-    if (this._chain in Cosmos._instances) {
-      delete Cosmos._instances[this._chain];
+    clearInterval(this._metricsTimer);
+    await super.close();
+    const key = this._chain + this.chainName;
+    if (key in Cosmos._instances) {
+      delete Cosmos._instances[key];
     }
   }
 }
@@ -5,7 +5,10 @@ import {
   RequestValidator,
   Validator,
   validateTxHash,
+  validateToken,
+  validateTransferAmount,
 } from '../../services/validators';
+import { validateSimulate } from '../ethereum/ethereum.validators';
 import { normalizeBech32 } from '@cosmjs/encoding';
 
 export const invalidCosmosAddressError: string =
@@ -28,9 +31,82 @@ export const validatePublicKey: Validator = mkValidator(
   (val) => typeof val === 'string' && isValidCosmosAddress(val)
 );
 
+export const invalidCosmosToError: string =
+  'The to param is not a valid Cosmos address. (Bech32 format)';
+
+export const validateTo: Validator = mkValidator(
+  'to',
+  invalidCosmosToError,
+  (val) => typeof val === 'string' && isValidCosmosAddress(val)
+);
+
+export const invalidGasPriceError: string =
+  'If gasPrice is included it must be a positive number.';
+
+export const validateGasPrice: Validator = mkValidator(
+  'gasPrice',
+  invalidGasPriceError,
+  (val) => typeof val === 'number' && val > 0,
+  true
+);
+
+export const invalidGasAdjustmentError: string =
+  'If gasAdjustment is included it must be a number of at least 1.';
+
+export const validateGasAdjustment: Validator = mkValidator(
+  'gasAdjustment',
+  invalidGasAdjustmentError,
+  (val) => typeof val === 'number' && val >= 1,
+  true
+);
+
+export const invalidMemoError: string =
+  'If memo is included it must be a string.';
+
+export const validateMemo: Validator = mkValidator(
+  'memo',
+  invalidMemoError,
+  (val) => typeof val === 'string',
+  true
+);
+
+export const invalidDestinationError: string =
+  'The destination param must be the name of a chain.';
+
+export const validateDestination: Validator = mkValidator(
+  'destination',
+  invalidDestinationError,
+  (val) => typeof val === 'string'
+);
+
 export const validateCosmosBalanceRequest: RequestValidator =
   mkRequestValidator([validatePublicKey, validateTokenSymbols]);
 
 export const validateCosmosPollRequest: RequestValidator = mkRequestValidator([
   validateTxHash,
 ]);
+
+export const validateCosmosTransferRequest: RequestValidator =
+  mkRequestValidator([
+    validatePublicKey,
+    validateTo,
+    validateToken,
+    validateTransferAmount,
+    validateGasPrice,
+    validateGasAdjustment,
+    validateMemo,
+    validateSimulate,
+  ]);
+
+export const validateCosmosIbcTransferRequest: RequestValidator =
+  mkRequestValidator([
+    validatePublicKey,
+    validateTo,
+    validateToken,
+    validateTransferAmount,
+    validateDestination,
+    validateGasPrice,
+    validateGasAdjustment,
+    validateMemo,
+    validateSimulate,
+  ]);
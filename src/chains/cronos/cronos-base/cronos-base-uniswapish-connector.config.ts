@@ -20,13 +20,19 @@ export namespace CronosBaseUniswapishConnectorConfig {
     );
     const networks: Array<string> = Object.keys(contractAddresses);
     return {
-      allowedSlippage: ConfigManagerV2.getInstance().get(
-        `${connector}.allowedSlippage`
-      ),
-      gasLimitEstimate: ConfigManagerV2.getInstance().get(
-        `${connector}.gasLimitEstimate`
-      ),
-      ttl: ConfigManagerV2.getInstance().get(`${connector}.ttl`),
+      get allowedSlippage() {
+        return ConfigManagerV2.getInstance().get(
+          `${connector}.allowedSlippage`
+        );
+      },
+      get gasLimitEstimate() {
+        return ConfigManagerV2.getInstance().get(
+          `${connector}.gasLimitEstimate`
+        );
+      },
+      get ttl() {
+        return ConfigManagerV2.getInstance().get(`${connector}.ttl`);
+      },
       routerAddress: (network: string) =>
         ConfigManagerV2.getInstance().get(
           `${connector}.contractAddresses.` + network + '.routerAddress'
@@ -1,5 +1,6 @@
 import {
   ExpectedTrade,
+  NativeSwap,
   Pairish,
   Percentish,
   TokenAmountish,
@@ -10,6 +11,7 @@ import {
   UniswapishSwapParameters,
   UniswapishTrade,
 } from '../../../services/common-interfaces';
+import { toNativeSwap } from '../../../connectors/uniswap/uniswap.native-swap';
 import {
   BigNumber,
   Contract,
@@ -55,6 +57,7 @@ export abstract class CronosBaseUniswapishConnector implements Uniswapish {
   public async init() {
     if (this._chain == 'cronos' && !this._cronos.ready())
       throw new Error('Cronos is not available');
+    this._tokenList = {};
     for (const token of this._cronos.storedTokenList) {
       this._tokenList[token.address] = this._sdkProvider.buildToken(
         this._chainId,
@@ -234,13 +237,18 @@ export abstract class CronosBaseUniswapishConnector implements Uniswapish {
     nonce?: number,
     maxFeePerGas?: BigNumber,
     maxPriorityFeePerGas?: BigNumber,
-    allowedSlippage?: string
+    allowedSlippage?: string,
+    nativeSwap?: NativeSwap
   ): Promise<Transaction> {
-    const result = this._sdkProvider.swapCallParameters(trade, {
-      ttl,
-      recipient: wallet.address,
-      allowedSlippage: this.getAllowedSlippage(allowedSlippage),
-    });
+    const result = toNativeSwap(
+      this._sdkProvider.swapCallParameters(trade, {
+        ttl,
+        recipient: wallet.address,
+        allowedSlippage: this.getAllowedSlippage(allowedSlippage),
+      }),
+      abi,
+      nativeSwap
+    );
 
     const contract = new Contract(
       CronosBaseUniswapishConnectorRoute,
@@ -315,6 +323,12 @@ export abstract class CronosBaseUniswapishConnector implements Uniswapish {
 
     return CronosBaseUniswapishConnector._instances[instanceName];
   }
+
+  public static getConnectedInstances(): {
+    [name: string]: CronosBaseUniswapishConnector;
+  } {
+    return CronosBaseUniswapishConnector._instances;
+  }
 }
 
 export interface CronosBaseUniswapishSDKProvider {
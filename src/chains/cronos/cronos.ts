@@ -27,11 +27,15 @@ export class Cronos extends EthereumBase implements Ethereumish {
       config.manualGasPrice,
       config.gasLimitTransaction,
       ConfigManagerV2.getInstance().get('server.nonceDbPath'),
-      ConfigManagerV2.getInstance().get('server.transactionDbPath')
+      ConfigManagerV2.getInstance().get('server.transactionDbPath'),
+      config.network.nodeURLs,
+      config.network.nodeQuorum
     );
     this._chain = config.network.name;
     this._nativeTokenSymbol = config.nativeCurrencySymbol;
     this._gasPrice = config.manualGasPrice;
+    this.feeStrategy = config.network.feeStrategy;
+    this.wrappedNativeAddress = config.network.wrappedNativeAddress;
 
     this._gasPriceRefreshInterval =
       config.network.gasPriceRefreshInterval !== undefined
@@ -60,7 +64,7 @@ export class Cronos extends EthereumBase implements Ethereumish {
    * Automatically update the prevailing gas price on the network from the connected RPC node.
    */
   async updateGasPrice(): Promise<void> {
-    if (this._gasPriceRefreshInterval === null) {
+    if (this._gasPriceRefreshInterval === null || this.closed) {
       return;
     }
 
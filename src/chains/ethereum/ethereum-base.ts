@@ -8,16 +8,30 @@ import {
 } from 'ethers';
 import axios from 'axios';
 import { promises as fs } from 'fs';
-import path from 'path';
-import { rootPath } from '../../paths';
+import { resolveDBPath } from '../../paths';
 import { TokenListType, TokenValue, walletPath } from '../../services/base';
 import { EVMNonceManager } from '../../evm/evm.nonce';
 import NodeCache from 'node-cache';
 import { EvmTxStorage } from '../../evm/evm.tx-storage';
+import { TransactionJournal } from '../../services/transaction-journal';
 import fse from 'fs-extra';
 import { ConfigManagerCertPassphrase } from '../../services/config-manager-cert-passphrase';
 import { logger } from '../../services/logger';
 import { ReferenceCountingCloseable } from '../../services/refcounting-closeable';
+import abi from './ethereum.abi.json';
+import {
+  RpcEndpointConfig,
+  RpcEndpointHealth,
+} from '../../services/rpc-endpoint-pool';
+import { EvmProviderPool } from '../../evm/evm.provider-pool';
+import {
+  mergeTokenLists,
+  readTokenOverlay,
+  TokenOverlay,
+  tokenOverlayFile,
+  writeTokenOverlay,
+} from '../../evm/evm.token-list';
+import { FeeStrategy, getGasPriceBumpPercent } from './ethereum.config';
 
 // information about an Ethereum token
 export interface TokenInfo {
@@ -28,17 +42,37 @@ export interface TokenInfo {
   decimals: number;
 }
 
+// type-2 transaction fees, in wei
+export interface EIP1559Fees {
+  baseFeePerGas: BigNumber;
+  maxFeePerGas: BigNumber;
+  maxPriorityFeePerGas: BigNumber;
+}
+
+// fees are estimated at most once per block or so
+const EIP1559_FEES_CACHE_KEY = 'eip1559Fees';
+const EIP1559_FEES_CACHE_TTL = 12;
+
+// the calls a token added by address is described with
+const ERC20_METADATA_ABI = [
+  'function name() view returns (string)',
+  'function symbol() view returns (string)',
+  'function decimals() view returns (uint8)',
+];
+
 export type NewBlockHandler = (bn: number) => void;
 
 export type NewDebugMsgHandler = (msg: any) => void;
 
 export class EthereumBase {
-  private _provider;
+  private _provider: EvmProviderPool;
   protected tokenList: TokenInfo[] = [];
   private _tokenMap: Record<string, TokenInfo> = {};
+  private _tokenListRevision: number = 0;
   // there are async values set in the constructor
   private _ready: boolean = false;
   private _initializing: boolean = false;
+  private _closed: boolean = false;
   public chainName;
   public chainId;
   public rpcUrl;
@@ -46,10 +80,13 @@ export class EthereumBase {
   private _gasLimitTransaction;
   public tokenListSource: string;
   public tokenListType: TokenListType;
+  public feeStrategy: FeeStrategy | undefined;
+  public wrappedNativeAddress: string | undefined;
   public cache: NodeCache;
   private readonly _refCountingHandle: string;
   private readonly _nonceManager: EVMNonceManager;
   private readonly _txStorage: EvmTxStorage;
+  private readonly _journal: TransactionJournal;
 
   constructor(
     chainName: string,
@@ -60,9 +97,16 @@ export class EthereumBase {
     gasPriceConstant: number,
     gasLimitTransaction: number,
     nonceDbPath: string,
-    transactionDbPath: string
+    transactionDbPath: string,
+    nodeURLs: RpcEndpointConfig[] = [],
+    nodeQuorum: number = 1
   ) {
-    this._provider = new providers.StaticJsonRpcProvider(rpcUrl);
+    // nodeURL alone is a pool of one
+    this._provider = new EvmProviderPool(
+      nodeURLs.length > 0 ? nodeURLs : [{ url: rpcUrl }],
+      chainId,
+      nodeQuorum
+    );
     this.chainName = chainName;
     this.chainId = chainId;
     this.rpcUrl = rpcUrl;
@@ -84,6 +128,10 @@ export class EthereumBase {
       this._refCountingHandle
     );
     this._txStorage.declareOwnership(this._refCountingHandle);
+    this._journal = TransactionJournal.getInstance(
+      this.resolveDBPath(transactionDbPath),
+      this._refCountingHandle
+    );
   }
 
   ready(): boolean {
@@ -98,11 +146,12 @@ export class EthereumBase {
     return this._gasLimitTransaction;
   }
 
+  public rpcHealth(): RpcEndpointHealth[] {
+    return this._provider.health();
+  }
+
   public resolveDBPath(oldPath: string): string {
-    if (oldPath.charAt(0) === '/') return oldPath;
-    const dbDir: string = path.join(rootPath(), 'db/');
-    fse.mkdirSync(dbDir, { recursive: true });
-    return path.join(dbDir, oldPath);
+    return resolveDBPath(oldPath);
   }
 
   public events() {
@@ -115,6 +164,10 @@ export class EthereumBase {
     this._provider.on('block', func);
   }
 
+  public offNewBlock(func: NewBlockHandler) {
+    this._provider.off('block', func);
+  }
+
   public onDebugMessage(func: NewDebugMsgHandler) {
     this._provider.on('debug', func);
   }
@@ -134,18 +187,143 @@ export class EthereumBase {
     tokenListSource: string,
     tokenListType: TokenListType
   ): Promise<void> {
-    this.tokenList = await this.getTokenList(tokenListSource, tokenListType);
+    const overlay = await readTokenOverlay(this.tokenOverlayFile);
+    const lists: TokenInfo[][] = [
+      await this.getTokenList(tokenListSource, tokenListType),
+    ];
+    for (const { type, source } of overlay.sources) {
+      try {
+        lists.push(await this.getTokenList(source, type));
+      } catch (e) {
+        logger.error(
+          `Could not load the token list ${source}: ${(e as Error).message}`
+        );
+      }
+    }
+    lists.push(overlay.tokens);
+
     // Only keep tokens in the same chain
-    this.tokenList = this.tokenList.filter(
-      (token: TokenInfo) => token.chainId === this.chainId
+    const removed = overlay.removed.map((address) => address.toLowerCase());
+    this.tokenList = mergeTokenLists(
+      lists.map((list) =>
+        (list || []).filter(
+          (token: TokenInfo) => token.chainId === this.chainId
+        )
+      )
+    ).filter(
+      (token: TokenInfo) => !removed.includes(token.address.toLowerCase())
     );
-    if (this.tokenList) {
-      this.tokenList.forEach(
-        (token: TokenInfo) => (this._tokenMap[token.symbol] = token)
+    this._tokenMap = {};
+    this.tokenList.forEach(
+      (token: TokenInfo) => (this._tokenMap[token.symbol] = token)
+    );
+    this._tokenListRevision += 1;
+  }
+
+  // bumped on every load, so connectors know when to reload their tokens
+  public get tokenListRevision(): number {
+    return this._tokenListRevision;
+  }
+
+  public get tokenOverlayFile(): string {
+    return tokenOverlayFile(this.chainName, this.chainId);
+  }
+
+  private async updateTokenOverlay(
+    update: (overlay: TokenOverlay) => TokenOverlay
+  ): Promise<void> {
+    const file = this.tokenOverlayFile;
+    await writeTokenOverlay(file, update(await readTokenOverlay(file)));
+    await this.loadTokens(this.tokenListSource, this.tokenListType);
+  }
+
+  // the token at an address, as its contract describes it
+  async getTokenMetadata(address: string): Promise<TokenInfo> {
+    const contract = new Contract(address, ERC20_METADATA_ABI, this._provider);
+    try {
+      const [name, symbol, decimals] = await Promise.all([
+        contract.name(),
+        contract.symbol(),
+        contract.decimals(),
+      ]);
+      return {
+        chainId: this.chainId,
+        address: utils.getAddress(address),
+        name,
+        symbol,
+        decimals,
+      };
+    } catch (e) {
+      throw new Error(
+        `${address} is not an ERC-20 token contract on chain ${this.chainId}`
       );
     }
   }
 
+  /**
+   * Adds the ERC-20 token at an address to the user tokens, described by the
+   * name, symbol and decimals its contract reports, then reloads the tokens.
+   */
+  async addToken(address: string): Promise<TokenInfo> {
+    const token = await this.getTokenMetadata(address);
+    const other = (a: string) => a.toLowerCase() !== address.toLowerCase();
+    await this.updateTokenOverlay((overlay) => ({
+      ...overlay,
+      tokens: overlay.tokens.filter((t) => other(t.address)).concat(token),
+      removed: overlay.removed.filter(other),
+    }));
+    logger.info(`Added token ${token.symbol} at ${token.address}.`);
+    return token;
+  }
+
+  /**
+   * Hides a token, given by symbol or address, from every token list until
+   * it is added again.
+   */
+  async removeToken(symbolOrAddress: string): Promise<TokenInfo> {
+    const token = utils.isAddress(symbolOrAddress)
+      ? this.tokenList.find(
+          (t) => t.address.toLowerCase() === symbolOrAddress.toLowerCase()
+        )
+      : this.getTokenBySymbol(symbolOrAddress);
+    if (!token) {
+      throw new Error(`${symbolOrAddress} is not in the token list`);
+    }
+    const other = (a: string) =>
+      a.toLowerCase() !== token.address.toLowerCase();
+    await this.updateTokenOverlay((overlay) => ({
+      ...overlay,
+      tokens: overlay.tokens.filter((t) => other(t.address)),
+      removed: overlay.removed.filter(other).concat(token.address),
+    }));
+    logger.info(`Removed token ${token.symbol} at ${token.address}.`);
+    return token;
+  }
+
+  /**
+   * Adds a token list to the lists tokens are loaded from. Its tokens take
+   * over the symbols and addresses of the lists added before it.
+   */
+  async importTokenList(
+    source: string,
+    type: TokenListType
+  ): Promise<TokenInfo[]> {
+    const tokens = ((await this.getTokenList(source, type)) || []).filter(
+      (token: TokenInfo) => token.chainId === this.chainId
+    );
+    if (tokens.length === 0) {
+      throw new Error(`${source} has no tokens for chain ${this.chainId}`);
+    }
+    await this.updateTokenOverlay((overlay) => ({
+      ...overlay,
+      sources: overlay.sources
+        .filter((s) => s.source !== source)
+        .concat({ type, source }),
+    }));
+    logger.info(`Imported ${tokens.length} tokens from ${source}.`);
+    return tokens;
+  }
+
   // returns a Tokens for a given list source and list type
   async getTokenList(
     tokenListSource: string,
@@ -170,6 +348,10 @@ export class EthereumBase {
     return this._txStorage;
   }
 
+  public get journal(): TransactionJournal {
+    return this._journal;
+  }
+
   // ethereum token lists are large. instead of reloading each time with
   // getTokenList, we can read the stored tokenList value from when the
   // object was initiated.
@@ -315,21 +497,138 @@ export class EthereumBase {
       nonce,
       wallet.address,
       async (nextNonce) => {
-        const params: any = {
-          gasLimit: this._gasLimitTransaction,
-          nonce: nextNonce,
-        };
-        if (maxFeePerGas || maxPriorityFeePerGas) {
-          params.maxFeePerGas = maxFeePerGas;
-          params.maxPriorityFeePerGas = maxPriorityFeePerGas;
-        } else if (gasPrice) {
-          params.gasPrice = (gasPrice * 1e9).toFixed(0);
-        }
+        const params = this.txParams(
+          nextNonce,
+          maxFeePerGas,
+          maxPriorityFeePerGas,
+          gasPrice
+        );
         return contract.approve(spender, amount, params);
       }
     );
   }
 
+  /**
+   * Sends an amount of a token to an address, or of the native currency when
+   * no token contract is given.
+   */
+  async transfer(
+    wallet: Wallet,
+    to: string,
+    amount: BigNumber,
+    contract?: Contract,
+    nonce?: number,
+    maxFeePerGas?: BigNumber,
+    maxPriorityFeePerGas?: BigNumber,
+    gasPrice?: number
+  ): Promise<Transaction> {
+    logger.info(
+      `Transferring ${amount.toString()} of ` +
+        `${contract ? contract.address : 'the native currency'} from ` +
+        `${wallet.address} to ${to}.`
+    );
+    return this.nonceManager.provideNonce(
+      nonce,
+      wallet.address,
+      async (nextNonce) => {
+        const params = this.txParams(
+          nextNonce,
+          maxFeePerGas,
+          maxPriorityFeePerGas,
+          gasPrice
+        );
+        if (contract) return contract.transfer(to, amount, params);
+        return wallet.sendTransaction({ ...params, to, value: amount });
+      }
+    );
+  }
+
+  /**
+   * Deposits an amount of the native currency into the wrapped native token
+   * of the network, e.g. ETH into WETH.
+   */
+  async wrapNative(
+    wallet: Wallet,
+    amount: BigNumber,
+    nonce?: number,
+    maxFeePerGas?: BigNumber,
+    maxPriorityFeePerGas?: BigNumber,
+    gasPrice?: number
+  ): Promise<Transaction> {
+    const contract = this.getWrappedNativeContract(wallet);
+    logger.info(`Wrapping ${amount.toString()} for ${wallet.address}.`);
+    return this.nonceManager.provideNonce(
+      nonce,
+      wallet.address,
+      async (nextNonce) =>
+        contract.deposit({
+          ...this.txParams(
+            nextNonce,
+            maxFeePerGas,
+            maxPriorityFeePerGas,
+            gasPrice
+          ),
+          value: amount,
+        })
+    );
+  }
+
+  /**
+   * Withdraws an amount of the wrapped native token back into the native
+   * currency, e.g. WETH into ETH.
+   */
+  async unwrapNative(
+    wallet: Wallet,
+    amount: BigNumber,
+    nonce?: number,
+    maxFeePerGas?: BigNumber,
+    maxPriorityFeePerGas?: BigNumber,
+    gasPrice?: number
+  ): Promise<Transaction> {
+    const contract = this.getWrappedNativeContract(wallet);
+    logger.info(`Unwrapping ${amount.toString()} for ${wallet.address}.`);
+    return this.nonceManager.provideNonce(
+      nonce,
+      wallet.address,
+      async (nextNonce) =>
+        contract.withdraw(
+          amount,
+          this.txParams(
+            nextNonce,
+            maxFeePerGas,
+            maxPriorityFeePerGas,
+            gasPrice
+          )
+        )
+    );
+  }
+
+  private getWrappedNativeContract(wallet: Wallet): Contract {
+    if (!this.wrappedNativeAddress) {
+      throw new Error(
+        `No wrappedNativeAddress is configured for ${this.chainName} ${this.chainId}.`
+      );
+    }
+    return new Contract(this.wrappedNativeAddress, abi.WETHAbi, wallet);
+  }
+
+  // type-2 fees when given, the legacy gas price in gwei otherwise
+  private txParams(
+    nonce: number,
+    maxFeePerGas?: BigNumber,
+    maxPriorityFeePerGas?: BigNumber,
+    gasPrice?: number
+  ): any {
+    const params: any = { gasLimit: this._gasLimitTransaction, nonce };
+    if (maxFeePerGas || maxPriorityFeePerGas) {
+      params.maxFeePerGas = maxFeePerGas;
+      params.maxPriorityFeePerGas = maxPriorityFeePerGas;
+    } else if (gasPrice) {
+      params.gasPrice = (gasPrice * 1e9).toFixed(0);
+    }
+    return params;
+  }
+
   public getTokenBySymbol(tokenSymbol: string): TokenInfo | undefined {
     return this.tokenList.find(
       (token: TokenInfo) =>
@@ -368,26 +667,155 @@ export class EthereumBase {
     );
   }
 
+  public get gasPriceBumpPercent(): number {
+    return getGasPriceBumpPercent(this.chainName);
+  }
+
+  // rebroadcast a pending transaction with the same nonce and payload but a
+  // higher fee, so that it replaces the original in the mempool
+  async speedUpTx(
+    wallet: Wallet,
+    original: providers.TransactionResponse
+  ): Promise<Transaction> {
+    logger.info(
+      `Speeding up transaction ${original.hash} with nonce ${original.nonce}.`
+    );
+    const bumpPercent = this.gasPriceBumpPercent;
+    const bump = (fee: BigNumber): BigNumber =>
+      fee.mul(Math.round((100 + bumpPercent) * 100)).div(10000);
+
+    const tx: providers.TransactionRequest = {
+      to: original.to,
+      data: original.data,
+      value: original.value,
+      nonce: original.nonce,
+      gasLimit: original.gasLimit,
+      chainId: original.chainId,
+    };
+    if (original.maxFeePerGas && original.maxPriorityFeePerGas) {
+      tx.type = 2;
+      tx.maxFeePerGas = bump(original.maxFeePerGas);
+      tx.maxPriorityFeePerGas = bump(original.maxPriorityFeePerGas);
+    } else if (original.gasPrice) {
+      tx.gasPrice = bump(original.gasPrice);
+    }
+
+    const response = await wallet.sendTransaction(tx);
+    logger.info(
+      `Transaction ${original.hash} replaced by ${response.hash} with nonce ${original.nonce}.`
+    );
+
+    // the nonce is normally committed already, since the original went
+    // through gateway. Only move the leading nonce if it was sent elsewhere.
+    const leadingNonce = await this.nonceManager.getNonceFromMemory(
+      wallet.address
+    );
+    if (leadingNonce === null || original.nonce > leadingNonce) {
+      await this.nonceManager.commitNonce(wallet.address, original.nonce);
+    }
+
+    return response;
+  }
+
   /**
-   * Get the base gas fee and the current max priority fee from the EVM
-   * node, and add them together.
+   * Get the prevailing gas price in gwei. On networks with a fee strategy,
+   * this is the next block's base fee plus the tip we would pay. Otherwise
+   * it's the node's legacy gas price, which already includes a tip.
    */
   async getGasPrice(): Promise<number | null> {
     if (!this.ready) {
       await this.init();
     }
-    const feeData: providers.FeeData = await this._provider.getFeeData();
-    if (feeData.gasPrice !== null && feeData.maxPriorityFeePerGas !== null) {
+    const fees = await this.getEIP1559Fees();
+    if (fees !== null) {
       return (
-        feeData.gasPrice.add(feeData.maxPriorityFeePerGas).toNumber() * 1e-9
+        fees.baseFeePerGas.add(fees.maxPriorityFeePerGas).toNumber() * 1e-9
       );
+    }
+    const feeData: providers.FeeData = await this._provider.getFeeData();
+    if (feeData.gasPrice !== null) {
+      return feeData.gasPrice.toNumber() * 1e-9;
     } else {
       return null;
     }
   }
 
+  /**
+   * Type-2 fees for the next transaction, or null if the network has no fee
+   * strategy or the node can't provide a fee history. Callers should then
+   * fall back to a legacy gas price.
+   */
+  async getEIP1559Fees(): Promise<EIP1559Fees | null> {
+    if (!this.feeStrategy) return null;
+    try {
+      return await this.estimateEIP1559Fees(this.feeStrategy);
+    } catch (e) {
+      logger.warn(`Could not estimate EIP-1559 fees: ${e}`);
+      return null;
+    }
+  }
+
+  async estimateEIP1559Fees(strategy: FeeStrategy): Promise<EIP1559Fees> {
+    const cached = this.cache.get<EIP1559Fees>(EIP1559_FEES_CACHE_KEY);
+    if (cached) return cached;
+
+    const history = await this._provider.send('eth_feeHistory', [
+      utils.hexValue(strategy.blockHistory),
+      'latest',
+      [strategy.rewardPercentile],
+    ]);
+
+    // the last base fee in the history is the one of the next block
+    const baseFeePerGas = BigNumber.from(
+      history.baseFeePerGas[history.baseFeePerGas.length - 1]
+    );
+    // the median of the sampled blocks, so a single block full of MEV
+    // bundles doesn't move the tip
+    const rewards: BigNumber[] = history.reward
+      .map((reward: string[]) => BigNumber.from(reward[0]))
+      .sort((a: BigNumber, b: BigNumber) => (a.lt(b) ? -1 : a.gt(b) ? 1 : 0));
+    let maxPriorityFeePerGas =
+      rewards.length > 0
+        ? rewards[Math.floor(rewards.length / 2)]
+        : BigNumber.from(0);
+    if (strategy.maxPriorityFeePerGasCap !== undefined) {
+      const cap = utils.parseUnits(
+        String(strategy.maxPriorityFeePerGasCap),
+        'gwei'
+      );
+      if (maxPriorityFeePerGas.gt(cap)) maxPriorityFeePerGas = cap;
+    }
+
+    let maxFeePerGas = baseFeePerGas
+      .mul(Math.round(strategy.baseFeeMultiplier * 100))
+      .div(100)
+      .add(maxPriorityFeePerGas);
+    if (strategy.maxFeePerGasCap !== undefined) {
+      const cap = utils.parseUnits(String(strategy.maxFeePerGasCap), 'gwei');
+      if (maxFeePerGas.gt(cap)) maxFeePerGas = cap;
+    }
+    if (maxPriorityFeePerGas.gt(maxFeePerGas)) {
+      maxPriorityFeePerGas = maxFeePerGas;
+    }
+
+    const fees: EIP1559Fees = {
+      baseFeePerGas,
+      maxFeePerGas,
+      maxPriorityFeePerGas,
+    };
+    this.cache.set(EIP1559_FEES_CACHE_KEY, fees, EIP1559_FEES_CACHE_TTL);
+    return fees;
+  }
+
+  // a closed instance was replaced, its background refreshes should stop
+  public get closed(): boolean {
+    return this._closed;
+  }
+
   async close() {
+    this._closed = true;
     await this._nonceManager.close(this._refCountingHandle);
     await this._txStorage.close(this._refCountingHandle);
+    await this._journal.close(this._refCountingHandle);
   }
 }
@@ -1,12 +1,29 @@
 import { TokenListType } from '../../services/base';
 import { ConfigManagerV2 } from '../../services/config-manager-v2';
+import { logger } from '../../services/logger';
+import { RpcEndpointConfig } from '../../services/rpc-endpoint-pool';
+// how type-2 (EIP-1559) fees are derived from eth_feeHistory. Networks
+// without a fee strategy keep sending legacy gasPrice transactions.
+export interface FeeStrategy {
+  rewardPercentile: number; // percentile of the priority fees paid per block
+  blockHistory: number; // number of recent blocks to sample
+  baseFeeMultiplier: number; // headroom over the next block's base fee
+  maxFeePerGasCap?: number; // in gwei
+  maxPriorityFeePerGasCap?: number; // in gwei
+}
+
 export interface NetworkConfig {
   name: string;
   chainID: number;
   nodeURL: string;
+  nodeURLs: RpcEndpointConfig[] | undefined; // takes precedence over nodeURL
+  nodeQuorum: number | undefined; // endpoints that must agree on a balance
   tokenListType: TokenListType;
   tokenListSource: string;
   gasPriceRefreshInterval: number | undefined;
+  feeStrategy: FeeStrategy | undefined;
+  // the canonical wrapped native token (WETH, WAVAX, ...) of the network
+  wrappedNativeAddress: string | undefined;
 }
 
 export interface EthereumGasStationConfig {
@@ -23,6 +40,30 @@ export interface Config {
   gasLimitTransaction: number;
 }
 
+// geth and most other clients require a replacement transaction to pay at
+// least 10% more than the one it replaces
+export const MIN_GAS_PRICE_BUMP_PERCENT = 10;
+
+// the percent a sped up transaction pays more than the one it replaces, in
+// the namespace of any EVM chain. A lower bump would be rejected as
+// underpriced, so the minimum is used instead.
+export function getGasPriceBumpPercent(chainName: string): number {
+  const bumpPercent = ConfigManagerV2.getInstance().get(
+    chainName + '.gasPriceBumpPercent'
+  );
+  if (bumpPercent === undefined || bumpPercent === null) {
+    return MIN_GAS_PRICE_BUMP_PERCENT;
+  }
+  if (!(bumpPercent >= MIN_GAS_PRICE_BUMP_PERCENT)) {
+    logger.warn(
+      `${chainName}.gasPriceBumpPercent ${bumpPercent} is below the minimum ` +
+        `replacement bump, using ${MIN_GAS_PRICE_BUMP_PERCENT}.`
+    );
+    return MIN_GAS_PRICE_BUMP_PERCENT;
+  }
+  return bumpPercent;
+}
+
 export function getEthereumConfig(
   chainName: string,
   networkName: string
@@ -37,6 +78,12 @@ export function getEthereumConfig(
       nodeURL: ConfigManagerV2.getInstance().get(
         chainName + '.networks.' + network + '.nodeURL'
       ),
+      nodeURLs: ConfigManagerV2.getInstance().get(
+        chainName + '.networks.' + network + '.nodeURLs'
+      ),
+      nodeQuorum: ConfigManagerV2.getInstance().get(
+        chainName + '.networks.' + network + '.nodeQuorum'
+      ),
       tokenListType: ConfigManagerV2.getInstance().get(
         chainName + '.networks.' + network + '.tokenListType'
       ),
@@ -46,6 +93,12 @@ export function getEthereumConfig(
       gasPriceRefreshInterval: ConfigManagerV2.getInstance().get(
         chainName + '.networks.' + network + '.gasPriceRefreshInterval'
       ),
+      feeStrategy: ConfigManagerV2.getInstance().get(
+        chainName + '.networks.' + network + '.feeStrategy'
+      ),
+      wrappedNativeAddress: ConfigManagerV2.getInstance().get(
+        chainName + '.networks.' + network + '.wrappedNativeAddress'
+      ),
     },
     nativeCurrencySymbol: ConfigManagerV2.getInstance().get(
       chainName + '.networks.' + network + '.nativeCurrencySymbol'
@@ -14,6 +14,10 @@ import {
   LOAD_WALLET_ERROR_MESSAGE,
   TOKEN_NOT_SUPPORTED_ERROR_CODE,
   TOKEN_NOT_SUPPORTED_ERROR_MESSAGE,
+  TRANSACTION_NOT_FOUND_ERROR_CODE,
+  TRANSACTION_NOT_FOUND_ERROR_MESSAGE,
+  TRANSACTION_NOT_REPLACEABLE_ERROR_CODE,
+  TRANSACTION_NOT_REPLACEABLE_ERROR_MESSAGE,
 } from '../../services/error-handler';
 import { tokenValueToString } from '../../services/base';
 import { TokenInfo } from './ethereum-base';
@@ -36,8 +40,14 @@ import {
   AllowancesResponse,
   ApproveRequest,
   ApproveResponse,
+  TransferRequest,
+  TransferResponse,
+  WrapRequest,
+  WrapResponse,
   CancelRequest,
   CancelResponse,
+  SpeedUpRequest,
+  SpeedUpResponse,
 } from '../../evm/evm.requests';
 import {
   PollRequest,
@@ -46,6 +56,8 @@ import {
   BalanceResponse,
 } from '../../network/network.requests';
 import { logger } from '../../services/logger';
+import { simulateTransaction } from '../../evm/evm.simulation';
+import { checkRisk } from '../../services/risk-manager';
 
 export async function nonce(
   ethereum: Ethereumish,
@@ -226,6 +238,8 @@ export async function approve(
       TOKEN_NOT_SUPPORTED_ERROR_CODE
     );
   }
+  await checkRisk({ wallet: wallet.address, tokens: [token] });
+
   const amountBigNumber = amount
     ? utils.parseUnits(amount, fullToken.decimals)
     : constants.MaxUint256;
@@ -238,6 +252,43 @@ export async function approve(
   if (maxPriorityFeePerGas) {
     maxPriorityFeePerGasBigNumber = BigNumber.from(maxPriorityFeePerGas);
   }
+  if (!maxFeePerGasBigNumber && !maxPriorityFeePerGasBigNumber) {
+    const fees = await ethereumish.getEIP1559Fees();
+    if (fees) {
+      maxFeePerGasBigNumber = fees.maxFeePerGas;
+      maxPriorityFeePerGasBigNumber = fees.maxPriorityFeePerGas;
+    }
+  }
+  if (req.simulate) {
+    const simulation = await simulateTransaction(
+      ethereumish.provider,
+      wallet,
+      (signer: Wallet, signerNonce: number) =>
+        ethereumish.approveERC20(
+          ethereumish.getContract(fullToken.address, signer),
+          signer,
+          spender,
+          amountBigNumber,
+          signerNonce,
+          maxFeePerGasBigNumber,
+          maxPriorityFeePerGasBigNumber,
+          ethereumish.gasPrice
+        ),
+      nonce,
+      req.simulationBlock
+    );
+    return {
+      network: ethereumish.chain,
+      timestamp: initTime,
+      latency: latency(initTime, Date.now()),
+      tokenAddress: fullToken.address,
+      spender: spender,
+      amount: bigNumberWithDecimalToStr(amountBigNumber, fullToken.decimals),
+      nonce: simulation.nonce,
+      simulation,
+    };
+  }
+
   // instantiate a contract and pass in wallet, which act on behalf of that signer
   const contract = ethereumish.getContract(fullToken.address, wallet);
 
@@ -262,6 +313,20 @@ export async function approve(
       new Date(),
       ethereumish.gasPrice
     );
+    await ethereumish.journal.record({
+      txHash: approval.hash,
+      chain: ethereumish.chainName,
+      network: ethereumish.chain,
+      type: 'approve',
+      wallet: wallet.address,
+      nonce: approval.nonce,
+      tokens: [fullToken.symbol],
+      amounts: [
+        bigNumberWithDecimalToStr(amountBigNumber, fullToken.decimals),
+      ],
+      gasPrice: ethereumish.gasPrice,
+      gasLimit: ethereumish.gasLimitTransaction,
+    });
   }
 
   return {
@@ -276,10 +341,270 @@ export async function approve(
   };
 }
 
+export async function transfer(
+  ethereumish: Ethereumish,
+  req: TransferRequest
+): Promise<TransferResponse> {
+  const { amount, nonce, address, to, token } = req;
+
+  const initTime = Date.now();
+  let wallet: Wallet;
+  try {
+    wallet = await ethereumish.getWallet(address);
+  } catch (err) {
+    throw new HttpException(
+      500,
+      LOAD_WALLET_ERROR_MESSAGE + err,
+      LOAD_WALLET_ERROR_CODE
+    );
+  }
+
+  // the native currency has no contract and 18 decimals on every evm chain
+  const native = token === ethereumish.nativeTokenSymbol;
+  const fullToken = native ? undefined : ethereumish.getTokenBySymbol(token);
+  if (!native && !fullToken) {
+    throw new HttpException(
+      500,
+      TOKEN_NOT_SUPPORTED_ERROR_MESSAGE + token,
+      TOKEN_NOT_SUPPORTED_ERROR_CODE
+    );
+  }
+  const decimals = fullToken ? fullToken.decimals : 18;
+  const amountBigNumber = utils.parseUnits(amount, decimals);
+  await checkRisk({
+    wallet: wallet.address,
+    tokens: [token],
+    amounts: [amount],
+  });
+
+  let maxFeePerGasBigNumber;
+  if (req.maxFeePerGas) {
+    maxFeePerGasBigNumber = BigNumber.from(req.maxFeePerGas);
+  }
+  let maxPriorityFeePerGasBigNumber;
+  if (req.maxPriorityFeePerGas) {
+    maxPriorityFeePerGasBigNumber = BigNumber.from(req.maxPriorityFeePerGas);
+  }
+  if (!maxFeePerGasBigNumber && !maxPriorityFeePerGasBigNumber) {
+    const fees = await ethereumish.getEIP1559Fees();
+    if (fees) {
+      maxFeePerGasBigNumber = fees.maxFeePerGas;
+      maxPriorityFeePerGasBigNumber = fees.maxPriorityFeePerGas;
+    }
+  }
+  const send = (signer: Wallet, signerNonce?: number) =>
+    ethereumish.transfer(
+      signer,
+      to,
+      amountBigNumber,
+      fullToken && ethereumish.getContract(fullToken.address, signer),
+      signerNonce,
+      maxFeePerGasBigNumber,
+      maxPriorityFeePerGasBigNumber,
+      ethereumish.gasPrice
+    );
+
+  const response = {
+    network: ethereumish.chain,
+    timestamp: initTime,
+    token,
+    tokenAddress: fullToken?.address,
+    to,
+    amount: bigNumberWithDecimalToStr(amountBigNumber, decimals),
+  };
+
+  if (req.simulate) {
+    const simulation = await simulateTransaction(
+      ethereumish.provider,
+      wallet,
+      send,
+      nonce,
+      req.simulationBlock
+    );
+    return {
+      ...response,
+      latency: latency(initTime, Date.now()),
+      nonce: simulation.nonce,
+      simulation,
+    };
+  }
+
+  const tx = await send(wallet, nonce);
+  if (tx.hash) {
+    await ethereumish.txStorage.saveTx(
+      ethereumish.chain,
+      ethereumish.chainId,
+      tx.hash,
+      new Date(),
+      ethereumish.gasPrice
+    );
+    await ethereumish.journal.record({
+      txHash: tx.hash,
+      chain: ethereumish.chainName,
+      network: ethereumish.chain,
+      type: 'transfer',
+      wallet: wallet.address,
+      nonce: tx.nonce,
+      tokens: [token],
+      amounts: [response.amount],
+      gasPrice: ethereumish.gasPrice,
+      gasLimit: ethereumish.gasLimitTransaction,
+    });
+  }
+
+  return {
+    ...response,
+    latency: latency(initTime, Date.now()),
+    nonce: tx.nonce,
+    txHash: tx.hash,
+    transfer: toEthereumTransaction(tx),
+  };
+}
+
+export async function wrap(
+  ethereumish: Ethereumish,
+  req: WrapRequest
+): Promise<WrapResponse> {
+  return wrapOrUnwrap(ethereumish, req, 'wrap');
+}
+
+export async function unwrap(
+  ethereumish: Ethereumish,
+  req: WrapRequest
+): Promise<WrapResponse> {
+  return wrapOrUnwrap(ethereumish, req, 'unwrap');
+}
+
+async function wrapOrUnwrap(
+  ethereumish: Ethereumish,
+  req: WrapRequest,
+  type: 'wrap' | 'unwrap'
+): Promise<WrapResponse> {
+  const initTime = Date.now();
+  const wrappedAddress = ethereumish.wrappedNativeAddress;
+  if (!wrappedAddress) {
+    throw new HttpException(
+      500,
+      TOKEN_NOT_SUPPORTED_ERROR_MESSAGE +
+        `wrapped ${ethereumish.nativeTokenSymbol}`,
+      TOKEN_NOT_SUPPORTED_ERROR_CODE
+    );
+  }
+  // the wrapped token may be missing from the token list, it is W + native
+  const wrapped = ethereumish.storedTokenList.find(
+    (token) => token.address.toLowerCase() === wrappedAddress.toLowerCase()
+  );
+  const wrappedSymbol = wrapped
+    ? wrapped.symbol
+    : 'W' + ethereumish.nativeTokenSymbol;
+
+  let wallet: Wallet;
+  try {
+    wallet = await ethereumish.getWallet(req.address);
+  } catch (err) {
+    throw new HttpException(
+      500,
+      LOAD_WALLET_ERROR_MESSAGE + err,
+      LOAD_WALLET_ERROR_CODE
+    );
+  }
+  // wrapped native tokens have the 18 decimals of the native currency
+  const amountBigNumber = utils.parseUnits(req.amount, 18);
+  await checkRisk({
+    wallet: wallet.address,
+    tokens: [type === 'wrap' ? ethereumish.nativeTokenSymbol : wrappedSymbol],
+    amounts: [req.amount],
+  });
+
+  let maxFeePerGasBigNumber;
+  if (req.maxFeePerGas) {
+    maxFeePerGasBigNumber = BigNumber.from(req.maxFeePerGas);
+  }
+  let maxPriorityFeePerGasBigNumber;
+  if (req.maxPriorityFeePerGas) {
+    maxPriorityFeePerGasBigNumber = BigNumber.from(req.maxPriorityFeePerGas);
+  }
+  if (!maxFeePerGasBigNumber && !maxPriorityFeePerGasBigNumber) {
+    const fees = await ethereumish.getEIP1559Fees();
+    if (fees) {
+      maxFeePerGasBigNumber = fees.maxFeePerGas;
+      maxPriorityFeePerGasBigNumber = fees.maxPriorityFeePerGas;
+    }
+  }
+  const send = (signer: Wallet, signerNonce?: number) => {
+    const args = [
+      signer,
+      amountBigNumber,
+      signerNonce,
+      maxFeePerGasBigNumber,
+      maxPriorityFeePerGasBigNumber,
+      ethereumish.gasPrice,
+    ] as const;
+    return type === 'wrap'
+      ? ethereumish.wrapNative(...args)
+      : ethereumish.unwrapNative(...args);
+  };
+
+  const response = {
+    network: ethereumish.chain,
+    timestamp: initTime,
+    token: wrappedSymbol,
+    tokenAddress: wrappedAddress,
+    amount: bigNumberWithDecimalToStr(amountBigNumber, 18),
+  };
+
+  if (req.simulate) {
+    const simulation = await simulateTransaction(
+      ethereumish.provider,
+      wallet,
+      send,
+      req.nonce,
+      req.simulationBlock
+    );
+    return {
+      ...response,
+      latency: latency(initTime, Date.now()),
+      nonce: simulation.nonce,
+      simulation,
+    };
+  }
+
+  const tx = await send(wallet, req.nonce);
+  if (tx.hash) {
+    await ethereumish.txStorage.saveTx(
+      ethereumish.chain,
+      ethereumish.chainId,
+      tx.hash,
+      new Date(),
+      ethereumish.gasPrice
+    );
+    await ethereumish.journal.record({
+      txHash: tx.hash,
+      chain: ethereumish.chainName,
+      network: ethereumish.chain,
+      type,
+      wallet: wallet.address,
+      nonce: tx.nonce,
+      tokens: [ethereumish.nativeTokenSymbol, wrappedSymbol],
+      amounts: [response.amount, response.amount],
+      gasPrice: ethereumish.gasPrice,
+      gasLimit: ethereumish.gasLimitTransaction,
+    });
+  }
+
+  return {
+    ...response,
+    latency: latency(initTime, Date.now()),
+    nonce: tx.nonce,
+    txHash: tx.hash,
+    transaction: toEthereumTransaction(tx),
+  };
+}
+
 // TransactionReceipt from ethers uses BigNumber which is not easy to interpret directly from JSON.
 // Transform those BigNumbers to string and pass the rest of the data without changes.
 
-const toEthereumTransactionReceipt = (
+export const toEthereumTransactionReceipt = (
   receipt: ethers.providers.TransactionReceipt | null
 ): CustomTransactionReceipt | null => {
   if (receipt) {
@@ -377,6 +702,11 @@ export async function poll(
       // tx has been processed
       txBlock = txReceipt.blockNumber;
       txStatus = typeof txReceipt.status === 'number' ? 1 : -1;
+      await ethereumish.journal.updateStatus(
+        req.txHash,
+        txReceipt.status === 1 ? 'CONFIRMED' : 'FAILED',
+        toEthereumTransactionReceipt(txReceipt)
+      );
       if (txReceipt.status === 0) {
         const gasUsed = BigNumber.from(txReceipt.gasUsed).toNumber();
         const gasLimit = BigNumber.from(txData.gasLimit).toNumber();
@@ -402,6 +732,12 @@ export async function poll(
     }
   }
 
+  const replacementTxHash = await ethereumish.txStorage.getReplacement(
+    ethereumish.chain,
+    ethereumish.chainId,
+    req.txHash
+  );
+
   logger.info(
     `Poll ${ethereumish.chain}, txHash ${req.txHash}, status ${txStatus}.`
   );
@@ -414,6 +750,7 @@ export async function poll(
     txStatus,
     txData: toEthereumTransactionResponse(txData),
     txReceipt: toEthereumTransactionReceipt(txReceipt),
+    replacementTxHash,
   };
 }
 
@@ -440,6 +777,21 @@ export async function cancel(
     `Cancelled transaction at nonce ${req.nonce}, cancel txHash ${cancelTx.hash}.`
   );
 
+  if (cancelTx.hash) {
+    await ethereumish.journal.record({
+      txHash: cancelTx.hash,
+      chain: ethereumish.chainName,
+      network: ethereumish.chain,
+      type: 'cancel',
+      wallet: wallet.address,
+      nonce: req.nonce,
+      tokens: [],
+      amounts: [],
+      gasPrice: ethereumish.gasPrice,
+      gasLimit: ethereumish.gasLimitTransaction,
+    });
+  }
+
   return {
     network: ethereumish.chain,
     timestamp: initTime,
@@ -447,3 +799,93 @@ export async function cancel(
     txHash: cancelTx.hash,
   };
 }
+
+export async function speedUp(
+  ethereumish: Ethereumish,
+  req: SpeedUpRequest
+): Promise<SpeedUpResponse> {
+  const initTime = Date.now();
+  let wallet: Wallet;
+  try {
+    wallet = await ethereumish.getWallet(req.address);
+  } catch (err) {
+    throw new HttpException(
+      500,
+      LOAD_WALLET_ERROR_MESSAGE + err,
+      LOAD_WALLET_ERROR_CODE
+    );
+  }
+
+  const original = await ethereumish.getTransaction(req.txHash);
+  if (!original) {
+    throw new HttpException(
+      500,
+      TRANSACTION_NOT_FOUND_ERROR_MESSAGE(req.txHash),
+      TRANSACTION_NOT_FOUND_ERROR_CODE
+    );
+  }
+  if (original.blockNumber) {
+    throw new HttpException(
+      500,
+      TRANSACTION_NOT_REPLACEABLE_ERROR_MESSAGE(
+        req.txHash,
+        `it was already mined in block ${original.blockNumber}.`
+      ),
+      TRANSACTION_NOT_REPLACEABLE_ERROR_CODE
+    );
+  }
+  if (original.from.toLowerCase() !== wallet.address.toLowerCase()) {
+    throw new HttpException(
+      500,
+      TRANSACTION_NOT_REPLACEABLE_ERROR_MESSAGE(
+        req.txHash,
+        `it was not sent by ${wallet.address}.`
+      ),
+      TRANSACTION_NOT_REPLACEABLE_ERROR_CODE
+    );
+  }
+
+  const replacement = await ethereumish.speedUpTx(wallet, original);
+
+  if (replacement.hash) {
+    const fee = replacement.maxFeePerGas || replacement.gasPrice;
+    await ethereumish.txStorage.saveReplacement(
+      ethereumish.chain,
+      ethereumish.chainId,
+      req.txHash,
+      replacement.hash
+    );
+    await ethereumish.txStorage.saveTx(
+      ethereumish.chain,
+      ethereumish.chainId,
+      replacement.hash,
+      new Date(),
+      fee ? fee.toNumber() * 1e-9 : ethereumish.gasPrice
+    );
+    await ethereumish.journal.recordReplacement(
+      req.txHash,
+      replacement.hash,
+      fee ? fee.toNumber() * 1e-9 : undefined
+    );
+  }
+
+  logger.info(
+    `Sped up transaction ${req.txHash}, replacement txHash ${replacement.hash}.`
+  );
+
+  return {
+    network: ethereumish.chain,
+    timestamp: initTime,
+    latency: latency(initTime, Date.now()),
+    originalTxHash: req.txHash,
+    txHash: replacement.hash,
+    nonce: replacement.nonce,
+    gasPrice: replacement.gasPrice ? replacement.gasPrice.toString() : null,
+    maxFeePerGas: replacement.maxFeePerGas
+      ? replacement.maxFeePerGas.toString()
+      : null,
+    maxPriorityFeePerGas: replacement.maxPriorityFeePerGas
+      ? replacement.maxPriorityFeePerGas.toString()
+      : null,
+  };
+}
@@ -1,6 +1,6 @@
 import abi from '../ethereum/ethereum.abi.json';
 import { logger } from '../../services/logger';
-import { BigNumber, Contract, Transaction, Wallet } from 'ethers';
+import { Contract, Transaction, Wallet } from 'ethers';
 import { EthereumBase } from './ethereum-base';
 import { getEthereumConfig } from './ethereum.config';
 import { Provider } from '@ethersproject/abstract-provider';
@@ -22,6 +22,7 @@ export class Ethereum extends EthereumBase implements Ethereumish {
   private _chain: string;
   private _requestCount: number;
   private _metricsLogInterval: number;
+  private _metricsTimer: ReturnType<typeof setInterval>;
 
   private constructor(network: string) {
     const config = getEthereumConfig('ethereum', network);
@@ -34,11 +35,15 @@ export class Ethereum extends EthereumBase implements Ethereumish {
       config.manualGasPrice,
       config.gasLimitTransaction,
       ConfigManagerV2.getInstance().get('server.nonceDbPath'),
-      ConfigManagerV2.getInstance().get('server.transactionDbPath')
+      ConfigManagerV2.getInstance().get('server.transactionDbPath'),
+      config.network.nodeURLs,
+      config.network.nodeQuorum
     );
     this._chain = network;
     this._nativeTokenSymbol = config.nativeCurrencySymbol;
     this._gasPrice = config.manualGasPrice;
+    this.feeStrategy = config.network.feeStrategy;
+    this.wrappedNativeAddress = config.network.wrappedNativeAddress;
     this._gasPriceRefreshInterval =
       config.network.gasPriceRefreshInterval !== undefined
         ? config.network.gasPriceRefreshInterval
@@ -50,7 +55,10 @@ export class Ethereum extends EthereumBase implements Ethereumish {
     this._metricsLogInterval = 300000; // 5 minutes
 
     this.onDebugMessage(this.requestCounter.bind(this));
-    setInterval(this.metricLogger.bind(this), this.metricsLogInterval);
+    this._metricsTimer = setInterval(
+      this.metricLogger.bind(this),
+      this.metricsLogInterval
+    );
   }
 
   public static getInstance(network: string): Ethereum {
@@ -104,17 +112,15 @@ export class Ethereum extends EthereumBase implements Ethereumish {
   }
 
   /**
-   * Automatically update the prevailing gas price on the network.
-   *
-   * Otherwise, it'll obtain the prevailing gas price from the connected
-   * ETH node.
+   * Automatically update the prevailing gas price on the network from the
+   * connected ETH node.
    */
   async updateGasPrice(): Promise<void> {
-    if (this._gasPriceRefreshInterval === null) {
+    if (this._gasPriceRefreshInterval === null || this.closed) {
       return;
     }
 
-    const gasPrice = await this.getGasPriceFromEthereumNode();
+    const gasPrice = await this.getGasPrice();
     if (gasPrice !== null) {
       this._gasPrice = gasPrice;
     } else {
@@ -127,21 +133,6 @@ export class Ethereum extends EthereumBase implements Ethereumish {
     );
   }
 
-  /**
-   * Get the base gas fee and the current max priority fee from the Ethereum
-   * node, and add them together.
-   */
-  async getGasPriceFromEthereumNode(): Promise<number> {
-    const baseFee: BigNumber = await this.provider.getGasPrice();
-    let priorityFee: BigNumber = BigNumber.from('0');
-    if (this._chain === 'mainnet') {
-      priorityFee = BigNumber.from(
-        await this.provider.send('eth_maxPriorityFeePerGas', [])
-      );
-    }
-    return baseFee.add(priorityFee).toNumber() * 1e-9;
-  }
-
   getContract(
     tokenAddress: string,
     signerOrProvider?: Wallet | Provider
@@ -190,6 +181,7 @@ export class Ethereum extends EthereumBase implements Ethereumish {
   }
 
   async close() {
+    clearInterval(this._metricsTimer);
     await super.close();
     if (this._chain in Ethereum._instances) {
       delete Ethereum._instances[this._chain];
@@ -7,6 +7,8 @@ import {
   Validator,
   validateToken,
   validateAmount,
+  validateTransferAmount,
+  validateTxHash,
 } from '../../services/validators';
 
 // invalid parameter errors
@@ -17,6 +19,9 @@ export const invalidAddressError: string =
 export const invalidSpenderError: string =
   'The spender param is not a valid Ethereum address (0x followed by 40 hexidecimal characters).';
 
+export const invalidToError: string =
+  'The to param is not a valid Ethereum address (0x followed by 40 hexidecimal characters).';
+
 export const invalidNonceError: string =
   'If nonce is included it must be a non-negative integer.';
 
@@ -26,6 +31,12 @@ export const invalidMaxFeePerGasError: string =
 export const invalidMaxPriorityFeePerGasError: string =
   'If maxPriorityFeePerGas is included it must be a string of a non-negative integer.';
 
+export const invalidSimulateError: string =
+  'If simulate is included it must be a boolean.';
+
+export const invalidSimulationBlockError: string =
+  'If simulationBlock is included it must be a non-negative integer.';
+
 export const invalidChainError: string = 'The chain param is not a string.';
 
 export const invalidNetworkError: string = 'The network param is not a string.';
@@ -65,6 +76,12 @@ export const validateSpender: Validator = mkValidator(
       isAddress(val))
 );
 
+export const validateTo: Validator = mkValidator(
+  'to',
+  invalidToError,
+  (val) => typeof val === 'string' && isAddress(val)
+);
+
 export const validateNonce: Validator = mkValidator(
   'nonce',
   invalidNonceError,
@@ -88,6 +105,20 @@ export const validateMaxPriorityFeePerGas: Validator = mkValidator(
   true
 );
 
+export const validateSimulate: Validator = mkValidator(
+  'simulate',
+  invalidSimulateError,
+  (val) => typeof val === 'boolean',
+  true
+);
+
+export const validateSimulationBlock: Validator = mkValidator(
+  'simulationBlock',
+  invalidSimulationBlockError,
+  (val) => typeof val === 'number' && val >= 0 && Number.isInteger(val),
+  true
+);
+
 export const validateChain: Validator = mkValidator(
   'chain',
   invalidChainError,
@@ -125,9 +156,38 @@ export const validateApproveRequest: RequestValidator = mkRequestValidator([
   validateNonce,
   validateMaxFeePerGas,
   validateMaxPriorityFeePerGas,
+  validateSimulate,
+  validateSimulationBlock,
+]);
+
+export const validateTransferRequest: RequestValidator = mkRequestValidator([
+  validateAddress,
+  validateTo,
+  validateToken,
+  validateTransferAmount,
+  validateNonce,
+  validateMaxFeePerGas,
+  validateMaxPriorityFeePerGas,
+  validateSimulate,
+  validateSimulationBlock,
+]);
+
+export const validateWrapRequest: RequestValidator = mkRequestValidator([
+  validateAddress,
+  validateTransferAmount,
+  validateNonce,
+  validateMaxFeePerGas,
+  validateMaxPriorityFeePerGas,
+  validateSimulate,
+  validateSimulationBlock,
 ]);
 
 export const validateCancelRequest: RequestValidator = mkRequestValidator([
   validateNonce,
   validateAddress,
 ]);
+
+export const validateSpeedUpRequest: RequestValidator = mkRequestValidator([
+  validateTxHash,
+  validateAddress,
+]);
@@ -1,11 +1,15 @@
 import { TokenListType } from '../../services/base';
 import { ConfigManagerV2 } from '../../services/config-manager-v2';
+import { RpcEndpointConfig } from '../../services/rpc-endpoint-pool';
 interface NetworkConfig {
   name: string;
   chainID: number;
   nodeURL: string;
+  nodeURLs: RpcEndpointConfig[] | undefined;
+  nodeQuorum: number | undefined;
   tokenListType: TokenListType;
   tokenListSource: string;
+  wrappedNativeAddress: string | undefined;
 }
 
 interface Config {
@@ -31,12 +35,21 @@ export function getHarmonyConfig(
       nodeURL: ConfigManagerV2.getInstance().get(
         chainName + '.networks.' + network + '.nodeURL'
       ),
+      nodeURLs: ConfigManagerV2.getInstance().get(
+        chainName + '.networks.' + network + '.nodeURLs'
+      ),
+      nodeQuorum: ConfigManagerV2.getInstance().get(
+        chainName + '.networks.' + network + '.nodeQuorum'
+      ),
       tokenListType: ConfigManagerV2.getInstance().get(
         chainName + '.networks.' + network + '.tokenListType'
       ),
       tokenListSource: ConfigManagerV2.getInstance().get(
         chainName + '.networks.' + network + '.tokenListSource'
       ),
+      wrappedNativeAddress: ConfigManagerV2.getInstance().get(
+        chainName + '.networks.' + network + '.wrappedNativeAddress'
+      ),
     },
     nativeCurrencySymbol: ConfigManagerV2.getInstance().get(
       chainName + '.networks.' + network + '.nativeCurrencySymbol'
@@ -1,5 +1,4 @@
 import abi from '../ethereum/ethereum.abi.json';
-import axios, { AxiosRequestConfig } from 'axios';
 import { logger } from '../../services/logger';
 import { Contract, Transaction, Wallet } from 'ethers';
 import { EthereumBase } from '../ethereum/ethereum-base';
@@ -17,6 +16,7 @@ export class Harmony extends EthereumBase implements Ethereumish {
   private _chain: string;
   private _requestCount: number;
   private _metricsLogInterval: number;
+  private _metricsTimer: ReturnType<typeof setInterval>;
 
   private constructor(network: string) {
     const config = getHarmonyConfig('harmony', network);
@@ -29,11 +29,14 @@ export class Harmony extends EthereumBase implements Ethereumish {
       config.manualGasPrice,
       config.gasLimitTransaction,
       ConfigManagerV2.getInstance().get('server.nonceDbPath'),
-      ConfigManagerV2.getInstance().get('server.transactionDbPath')
+      ConfigManagerV2.getInstance().get('server.transactionDbPath'),
+      config.network.nodeURLs,
+      config.network.nodeQuorum
     );
     this._chain = network;
     this._nativeTokenSymbol = config.nativeCurrencySymbol;
     this._gasPrice = config.manualGasPrice;
+    this.wrappedNativeAddress = config.network.wrappedNativeAddress;
     this._gasPriceLastUpdated = null;
 
     this.updateGasPrice();
@@ -42,7 +45,10 @@ export class Harmony extends EthereumBase implements Ethereumish {
     this._metricsLogInterval = 300000; // 5 minutes
 
     this.onDebugMessage(this.requestCounter.bind(this));
-    setInterval(this.metricLogger.bind(this), this.metricsLogInterval);
+    this._metricsTimer = setInterval(
+      this.metricLogger.bind(this),
+      this.metricsLogInterval
+    );
   }
 
   public static getInstance(network: string): Harmony {
@@ -102,27 +108,12 @@ export class Harmony extends EthereumBase implements Ethereumish {
   async updateGasPrice(): Promise<void> {
     const harmonyConfig = getHarmonyConfig('harmony', this._chain);
 
-    if (harmonyConfig.autoGasPrice) {
-      const jsonData = JSON.stringify({
-        jsonrpc: '2.0',
-        id: 1,
-        method: 'hmyv2_gasPrice',
-        params: [],
-      });
-
-      const config: AxiosRequestConfig = {
-        method: 'post',
-        url: harmonyConfig.network.nodeURL,
-        headers: {
-          'Content-Type': 'application/json',
-        },
-        data: jsonData,
-      };
-
-      const { data } = await axios(config);
+    if (harmonyConfig.autoGasPrice && !this.closed) {
+      // through the provider, so it fails over like every other RPC call
+      const gasPrice = await this.provider.send('hmyv2_gasPrice', []);
 
       // divide by 1e9 to convert it to Gwei
-      this._gasPrice = data['result'] / 1e9;
+      this._gasPrice = gasPrice / 1e9;
       this._gasPriceLastUpdated = new Date();
 
       setTimeout(
@@ -164,6 +155,7 @@ export class Harmony extends EthereumBase implements Ethereumish {
   }
 
   async close() {
+    clearInterval(this._metricsTimer);
     await super.close();
     if (this._chain in Harmony._instances) {
       delete Harmony._instances[this._chain];
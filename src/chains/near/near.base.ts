@@ -13,15 +13,24 @@ import { promises as fs } from 'fs';
 import { TokenListType, TokenValue, walletPath } from '../../services/base';
 import NodeCache from 'node-cache';
 import { EvmTxStorage } from '../../evm/evm.tx-storage';
+import { TransactionJournal } from '../../services/transaction-journal';
 import fse from 'fs-extra';
 import { ConfigManagerCertPassphrase } from '../../services/config-manager-cert-passphrase';
 import { logger } from '../../services/logger';
 import { ReferenceCountingCloseable } from '../../services/refcounting-closeable';
-import path from 'path';
-import { rootPath } from '../../paths';
+import { resolveDBPath } from '../../paths';
+import {
+  RpcEndpointConfig,
+  RpcEndpointHealth,
+} from '../../services/rpc-endpoint-pool';
+import { NearProviderPool } from './near.provider-pool';
 import { Account } from 'near-api-js/lib/account';
 import { BigNumber } from 'ethers';
-import { NodeStatusResult, GasPrice } from 'near-api-js/lib/providers/provider';
+import {
+  AccessKeyView,
+  NodeStatusResult,
+  GasPrice,
+} from 'near-api-js/lib/providers/provider';
 import BN from 'bn.js';
 import { baseDecode } from 'borsh';
 
@@ -34,15 +43,40 @@ export interface TokenInfo {
   decimals: number;
 }
 
+// the NEAR an account has deposited for its storage on a NEP-145 contract,
+// in yoctoNEAR
+export interface StorageBalance {
+  total: string;
+  available: string;
+}
+
+export interface StorageBalanceBounds {
+  min: string;
+  max: string | null;
+}
+
+// a storage_deposit that registers an account on a contract
+export interface StorageDeposit {
+  accountId: string;
+  amount: string; // in yoctoNEAR
+}
+
+// the gas attached to storage_deposit calls
+export const STORAGE_DEPOSIT_GAS = '30000000000000';
+
+// the times a transaction is signed again after its nonce was used
+const NONCE_ATTEMPTS = 3;
+
 export type NewDebugMsgHandler = (msg: any) => void;
 
 export class NearBase {
-  private _provider: providers.JsonRpcProvider;
+  private _provider: NearProviderPool;
   protected tokenList: any;
   private _tokenMap: Record<string, TokenInfo> = {};
   // there are async values set in the constructor
   private _ready: boolean = false;
   private _initializing: boolean = false;
+  private _closed: boolean = false;
   private _initPromise: Promise<void> = Promise.resolve();
   private _keyStore: keyStores.InMemoryKeyStore;
   private _connection: Near | undefined;
@@ -57,6 +91,7 @@ export class NearBase {
   public rpcUrl: string;
   private readonly _refCountingHandle: string;
   private readonly _txStorage: EvmTxStorage;
+  private readonly _journal: TransactionJournal;
 
   constructor(
     chainName: string,
@@ -66,9 +101,14 @@ export class NearBase {
     tokenListType: TokenListType,
     gasPriceConstant: number,
     gasLimitTransaction: number,
-    transactionDbPath: string
+    transactionDbPath: string,
+    nodeURLs: RpcEndpointConfig[] = [],
+    nodeQuorum: number = 1
   ) {
-    this._provider = new providers.JsonRpcProvider({ url: rpcUrl });
+    this._provider = new NearProviderPool(
+      nodeURLs.length > 0 ? nodeURLs : [{ url: rpcUrl }],
+      nodeQuorum
+    );
     this.rpcUrl = rpcUrl;
     this.chainName = chainName;
     this.network = network;
@@ -84,6 +124,10 @@ export class NearBase {
       this._refCountingHandle
     );
     this._txStorage.declareOwnership(this._refCountingHandle);
+    this._journal = TransactionJournal.getInstance(
+      this.resolveDBPath(transactionDbPath),
+      this._refCountingHandle
+    );
     this._keyStore = new keyStores.InMemoryKeyStore();
   }
 
@@ -99,11 +143,12 @@ export class NearBase {
     return this._gasLimitTransaction;
   }
 
+  public rpcHealth(): RpcEndpointHealth[] {
+    return this._provider.health();
+  }
+
   public resolveDBPath(oldPath: string): string {
-    if (oldPath.charAt(0) === '/') return oldPath;
-    const dbDir: string = path.join(rootPath(), 'db/');
-    fse.mkdirSync(dbDir, { recursive: true });
-    return path.join(dbDir, oldPath);
+    return resolveDBPath(oldPath);
   }
 
   async init(): Promise<void> {
@@ -122,11 +167,15 @@ export class NearBase {
   }
 
   async connectProvider(): Promise<Near> {
-    return await connect({
+    const near = await connect({
       networkId: this.network,
       keyStore: this._keyStore,
       nodeUrl: this.rpcUrl,
     });
+    // connect always builds a single endpoint provider, have the accounts
+    // use the pool instead
+    Object.assign(near.connection, { provider: this._provider });
+    return near;
   }
 
   async loadTokens(
@@ -163,6 +212,10 @@ export class NearBase {
     return this._txStorage;
   }
 
+  public get journal(): TransactionJournal {
+    return this._journal;
+  }
+
   public get storedTokenList(): TokenInfo[] {
     return this.tokenList;
   }
@@ -282,14 +335,140 @@ export class NearBase {
     return await this._provider.txStatus(txHash, accountId);
   }
 
-  // adds allowance by spender to transfer the given amount of Token
+  /**
+   * NEP-141 tokens have no allowances, but the wallet and the spender must be
+   * registered on the token contract to receive it. Registers them in one
+   * transaction if they aren't, and returns nothing if both already are.
+   */
   async approveFungibleToken(
-    _contract: Contract,
+    contract: Contract | any,
     _wallet: keyStores.InMemoryKeyStore,
-    _spender: string,
+    spender: string,
     _amount: BigNumber
-  ): Promise<any> {
-    return;
+  ): Promise<providers.FinalExecutionOutcome | undefined> {
+    const account: Account = contract.account;
+    const deposits = await this.getStorageDeposits(contract, [
+      account.accountId,
+      spender,
+    ]);
+    if (deposits.length === 0) return;
+    return await this.signAndSendActions(
+      account,
+      contract.contractId,
+      deposits.map((deposit) => this.storageDepositAction(deposit, true))
+    );
+  }
+
+  // the storage of an account on a token contract, null if it isn't registered
+  async getStorageBalance(
+    contract: Contract | any,
+    accountId: string
+  ): Promise<StorageBalance | null> {
+    return await contract.storage_balance_of({ account_id: accountId });
+  }
+
+  async getStorageBalanceBounds(
+    contract: Contract | any
+  ): Promise<StorageBalanceBounds> {
+    return await contract.storage_balance_bounds();
+  }
+
+  /**
+   * The deposits that register the accounts missing on a token contract,
+   * each of the minimum storage balance of the contract.
+   */
+  async getStorageDeposits(
+    contract: Contract | any,
+    accountIds: string[]
+  ): Promise<StorageDeposit[]> {
+    const missing: string[] = [];
+    for (const accountId of new Set(accountIds)) {
+      if ((await this.getStorageBalance(contract, accountId)) === null) {
+        missing.push(accountId);
+      }
+    }
+    if (missing.length === 0) return [];
+
+    const { min } = await this.getStorageBalanceBounds(contract);
+    logger.info(
+      `Registering ${missing.join(', ')} on ${contract.contractId} ` +
+        `for ${min} yoctoNEAR each.`
+    );
+    return missing.map((accountId) => ({ accountId, amount: min }));
+  }
+
+  storageDepositAction(
+    deposit: StorageDeposit,
+    registrationOnly: boolean
+  ): transactions.Action {
+    return transactions.functionCall(
+      'storage_deposit',
+      {
+        account_id: deposit.accountId,
+        registration_only: registrationOnly,
+      },
+      new BN(STORAGE_DEPOSIT_GAS),
+      new BN(deposit.amount)
+    );
+  }
+
+  /**
+   * Deposits an amount of yoctoNEAR for the storage of an account on a token
+   * contract, registering it if it isn't.
+   */
+  async storageDeposit(
+    account: Account,
+    contractId: string,
+    deposit: StorageDeposit,
+    registrationOnly: boolean = false
+  ): Promise<providers.FinalExecutionOutcome> {
+    return await this.signAndSendActions(account, contractId, [
+      this.storageDepositAction(deposit, registrationOnly),
+    ]);
+  }
+
+  /**
+   * Signs the actions as one transaction with the next nonce of the key. The
+   * nonce is read from the latest block, since transactions sent moments ago
+   * aren't final yet, and read again if the node rejects it as used.
+   */
+  private async signAndSendActions(
+    account: Account,
+    receiverId: string,
+    actions: transactions.Action[]
+  ): Promise<providers.FinalExecutionOutcome> {
+    const { provider, signer, networkId } = account.connection;
+    const publicKey = await signer.getPublicKey(account.accountId, networkId);
+    for (let attempt = 1; ; attempt++) {
+      const accessKey = await provider.query<AccessKeyView>({
+        request_type: 'view_access_key',
+        finality: 'optimistic',
+        account_id: account.accountId,
+        public_key: publicKey.toString(),
+      });
+      const block = await provider.block({ finality: 'final' });
+
+      const [, signedTx] = await transactions.signTransaction(
+        receiverId,
+        accessKey.nonce + 1,
+        actions,
+        baseDecode(block.header.hash),
+        signer,
+        account.accountId,
+        networkId
+      );
+      try {
+        return await provider.sendTransaction(signedTx);
+      } catch (err: any) {
+        if (err?.type !== 'InvalidNonce' || attempt >= NONCE_ATTEMPTS) {
+          throw err;
+        }
+        logger.info(
+          `Nonce ${accessKey.nonce + 1} of ${account.accountId} was used, ` +
+            'retrying with the next one.'
+        );
+      }
+    }
   }
 
   public getTokenBySymbol(tokenSymbol: string): TokenInfo | undefined {
@@ -302,6 +481,35 @@ export class NearBase {
     return status.sync_info.latest_block_height;
   }
 
+  /**
+   * Sends an amount, in the smallest unit, of a NEP-141 token to an account,
+   * or of NEAR when no token contract is given. A receiver that isn't
+   * registered on the token contract is registered in the same transaction.
+   */
+  async transfer(
+    account: Account,
+    to: string,
+    amount: string,
+    contract?: Contract | any
+  ): Promise<providers.FinalExecutionOutcome> {
+    logger.info(
+      `Transferring ${amount} of ${contract?.contractId ?? 'NEAR'} from ` +
+        `${account.accountId} to ${to}.`
+    );
+    if (!contract) return account.sendMoney(to, new BN(amount));
+    const deposits = await this.getStorageDeposits(contract, [to]);
+    return await this.signAndSendActions(account, contract.contractId, [
+      ...deposits.map((deposit) => this.storageDepositAction(deposit, true)),
+      // ft_transfer requires exactly one yoctoNEAR attached
+      transactions.functionCall(
+        'ft_transfer',
+        { receiver_id: to, amount },
+        new BN(this._gasLimitTransaction),
+        new BN(1)
+      ),
+    ]);
+  }
+
   // cancel transaction
   async cancelTx(account: Account, nonce: number): Promise<string> {
     const block = await account.connection.provider.block({
@@ -338,7 +546,14 @@ export class NearBase {
     }
   }
 
+  // a closed instance was replaced, its background refreshes should stop
+  public get closed(): boolean {
+    return this._closed;
+  }
+
   async close() {
+    this._closed = true;
     await this._txStorage.close(this._refCountingHandle);
+    await this._journal.close(this._refCountingHandle);
   }
 }
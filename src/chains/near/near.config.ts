@@ -1,9 +1,12 @@
 import { TokenListType } from '../../services/base';
 import { ConfigManagerV2 } from '../../services/config-manager-v2';
+import { RpcEndpointConfig } from '../../services/rpc-endpoint-pool';
 
 export interface NetworkConfig {
   name: string;
   nodeURL: string;
+  nodeURLs: RpcEndpointConfig[] | undefined;
+  nodeQuorum: number | undefined;
   tokenListType: TokenListType;
   tokenListSource: string;
   gasPriceRefreshInterval: number | undefined;
@@ -24,6 +27,12 @@ export function getNearConfig(chainName: string, networkName: string): Config {
       nodeURL: ConfigManagerV2.getInstance().get(
         chainName + '.networks.' + network + '.nodeURL'
       ),
+      nodeURLs: ConfigManagerV2.getInstance().get(
+        chainName + '.networks.' + network + '.nodeURLs'
+      ),
+      nodeQuorum: ConfigManagerV2.getInstance().get(
+        chainName + '.networks.' + network + '.nodeQuorum'
+      ),
       tokenListType: ConfigManagerV2.getInstance().get(
         chainName + '.networks.' + network + '.tokenListType'
       ),
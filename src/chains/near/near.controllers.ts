@@ -16,11 +16,18 @@ import {
   CancelRequest,
   CancelResponse,
   PollResponse,
+  TransferRequest,
+  TransferResponse,
   BalanceRequest,
   BalanceResponse,
+  RegisterRequest,
+  RegisterResponse,
+  StorageRequest,
+  StorageResponse,
 } from './near.requests';
 import { logger } from '../../services/logger';
 import { Nearish } from '../../services/common-interfaces';
+import { checkRisk } from '../../services/risk-manager';
 
 export const getTokenSymbolsToTokens = (
   near: Nearish,
@@ -126,6 +133,12 @@ export async function poll(
     );
   }
 
+  await nearish.journal.updateStatus(
+    txHash,
+    txStatus === 1 ? 'CONFIRMED' : 'FAILED',
+    txReceipt.status
+  );
+
   logger.info(`Poll ${nearish.chain}, txHash ${txHash}, status ${txStatus}.`);
   return {
     network: nearish.chain,
@@ -137,6 +150,245 @@ export async function poll(
   };
 }
 
+export async function transfer(
+  nearish: Nearish,
+  req: TransferRequest
+): Promise<TransferResponse> {
+  const initTime = Date.now();
+  let account: Account;
+  try {
+    account = await nearish.getWallet(req.address);
+  } catch (err) {
+    throw new HttpException(
+      500,
+      LOAD_WALLET_ERROR_MESSAGE + err,
+      LOAD_WALLET_ERROR_CODE
+    );
+  }
+
+  const native = req.token === nearish.nativeTokenSymbol;
+  const token = native ? undefined : nearish.getTokenBySymbol(req.token);
+  if (!native && !token) {
+    throw new HttpException(
+      500,
+      TOKEN_NOT_SUPPORTED_ERROR_MESSAGE + req.token,
+      TOKEN_NOT_SUPPORTED_ERROR_CODE
+    );
+  }
+  await checkRisk({
+    wallet: account.accountId,
+    tokens: [req.token],
+    amounts: [req.amount],
+  });
+
+  const amount = token
+    ? ethersUtils.parseUnits(req.amount, token.decimals).toString()
+    : utils.format.parseNearAmount(req.amount);
+  if (!amount) {
+    throw new HttpException(
+      500,
+      TOKEN_NOT_SUPPORTED_ERROR_MESSAGE + req.token,
+      TOKEN_NOT_SUPPORTED_ERROR_CODE
+    );
+  }
+  const tx = await nearish.transfer(
+    account,
+    req.to,
+    amount,
+    token ? nearish.getContract(token.address, account) : undefined
+  );
+  const txHash = tx.transaction_outcome.id;
+
+  await nearish.journal.record({
+    txHash,
+    chain: nearish.chainName,
+    network: nearish.chain,
+    type: 'transfer',
+    wallet: account.accountId,
+    nonce: tx.transaction.nonce,
+    tokens: [req.token],
+    amounts: [req.amount],
+    gasLimit: nearish.gasLimitTransaction,
+  });
+  logger.info(`Transferred ${req.amount} ${req.token}, txHash ${txHash}.`);
+
+  return {
+    network: nearish.chain,
+    timestamp: initTime,
+    latency: latency(initTime, Date.now()),
+    token: req.token,
+    tokenAddress: token?.address,
+    to: req.to,
+    amount: req.amount,
+    txHash,
+  };
+}
+
+// the token of a storage request, NEAR itself has no storage to register for
+function getStorageToken(nearish: Nearish, symbol: string): TokenInfo {
+  const token =
+    symbol === nearish.nativeTokenSymbol
+      ? undefined
+      : nearish.getTokenBySymbol(symbol);
+  if (!token) {
+    throw new HttpException(
+      500,
+      TOKEN_NOT_SUPPORTED_ERROR_MESSAGE + symbol,
+      TOKEN_NOT_SUPPORTED_ERROR_CODE
+    );
+  }
+  return token;
+}
+
+/**
+ * Reports the storage the account holds on a token contract, after topping
+ * it up when the request has an amount of NEAR to deposit.
+ */
+export async function storage(
+  nearish: Nearish,
+  req: StorageRequest
+): Promise<StorageResponse> {
+  const initTime = Date.now();
+  let account: Account;
+  try {
+    account = await nearish.getWallet(req.address);
+  } catch (err) {
+    throw new HttpException(
+      500,
+      LOAD_WALLET_ERROR_MESSAGE + err,
+      LOAD_WALLET_ERROR_CODE
+    );
+  }
+  const token = getStorageToken(nearish, req.token);
+  const contract = nearish.getContract(token.address, account);
+
+  let txHash: string | undefined;
+  if (req.amount) {
+    await checkRisk({
+      wallet: account.accountId,
+      tokens: [nearish.nativeTokenSymbol],
+      amounts: [req.amount],
+    });
+    const amount = utils.format.parseNearAmount(req.amount) as string;
+    const tx = await nearish.storageDeposit(account, token.address, {
+      accountId: account.accountId,
+      amount,
+    });
+    txHash = tx.transaction_outcome.id;
+    await nearish.journal.record({
+      txHash,
+      chain: nearish.chainName,
+      network: nearish.chain,
+      type: 'storageDeposit',
+      wallet: account.accountId,
+      nonce: tx.transaction.nonce,
+      tokens: [nearish.nativeTokenSymbol],
+      amounts: [req.amount],
+      gasLimit: nearish.gasLimitTransaction,
+    });
+    logger.info(
+      `Deposited ${req.amount} NEAR for storage on ${token.address}, ` +
+        `txHash ${txHash}.`
+    );
+  }
+
+  const balance = await nearish.getStorageBalance(
+    contract,
+    account.accountId
+  );
+  const bounds = await nearish.getStorageBalanceBounds(contract);
+  return {
+    network: nearish.chain,
+    timestamp: initTime,
+    latency: latency(initTime, Date.now()),
+    token: req.token,
+    tokenAddress: token.address,
+    registered: balance !== null,
+    total: utils.format.formatNearAmount(balance ? balance.total : '0'),
+    available: utils.format.formatNearAmount(
+      balance ? balance.available : '0'
+    ),
+    minimum: utils.format.formatNearAmount(bounds.min),
+    txHash,
+  };
+}
+
+/**
+ * Registers the account on the contract of each token it isn't registered
+ * on yet, one transaction per token.
+ */
+export async function register(
+  nearish: Nearish,
+  req: RegisterRequest
+): Promise<RegisterResponse> {
+  const initTime = Date.now();
+  let account: Account;
+  try {
+    account = await nearish.getWallet(req.address);
+  } catch (err) {
+    throw new HttpException(
+      500,
+      LOAD_WALLET_ERROR_MESSAGE + err,
+      LOAD_WALLET_ERROR_CODE
+    );
+  }
+  const tokens = req.tokenSymbols.map((symbol) =>
+    getStorageToken(nearish, symbol)
+  );
+
+  const registered: Record<string, string> = {};
+  const alreadyRegistered: string[] = [];
+  // one at a time, each transaction takes the next nonce of the key
+  for (const [i, token] of tokens.entries()) {
+    const symbol = req.tokenSymbols[i];
+    const contract = nearish.getContract(token.address, account);
+    const [deposit] = await nearish.getStorageDeposits(contract, [
+      account.accountId,
+    ]);
+    if (!deposit) {
+      alreadyRegistered.push(symbol);
+      continue;
+    }
+
+    const amount = utils.format.formatNearAmount(deposit.amount);
+    await checkRisk({
+      wallet: account.accountId,
+      tokens: [nearish.nativeTokenSymbol],
+      amounts: [amount],
+    });
+    const tx = await nearish.storageDeposit(
+      account,
+      token.address,
+      deposit,
+      true
+    );
+    registered[symbol] = tx.transaction_outcome.id;
+    await nearish.journal.record({
+      txHash: registered[symbol],
+      chain: nearish.chainName,
+      network: nearish.chain,
+      type: 'storageDeposit',
+      wallet: account.accountId,
+      nonce: tx.transaction.nonce,
+      tokens: [nearish.nativeTokenSymbol],
+      amounts: [amount],
+      gasLimit: nearish.gasLimitTransaction,
+    });
+    logger.info(
+      `Registered ${account.accountId} on ${token.address}, ` +
+        `txHash ${registered[symbol]}.`
+    );
+  }
+
+  return {
+    network: nearish.chain,
+    timestamp: initTime,
+    latency: latency(initTime, Date.now()),
+    registered,
+    alreadyRegistered,
+  };
+}
+
 export async function cancel(
   nearish: Nearish,
   req: CancelRequest
@@ -0,0 +1,67 @@
+import { providers } from 'near-api-js';
+import {
+  RpcEndpointConfig,
+  RpcEndpointHealth,
+  RpcEndpointPool,
+} from '../../services/rpc-endpoint-pool';
+
+const QUORUM_METHODS = ['tx', 'EXPERIMENTAL_tx_status'];
+
+// a signed transaction sent again through another endpoint after a timeout
+// may already have landed, see EvmProviderPool
+const SEND_METHODS = ['broadcast_tx_commit', 'broadcast_tx_async'];
+
+// account balances and transaction outcomes need a quorum, see
+// EvmProviderPool
+export function isNearQuorumRead(method: string, params: any): boolean {
+  if (QUORUM_METHODS.includes(method)) return true;
+  return (
+    method === 'query' &&
+    params !== null &&
+    typeof params === 'object' &&
+    (params.request_type === 'view_account' ||
+      (params.request_type === 'call_function' &&
+        params.method_name === 'ft_balance_of'))
+  );
+}
+
+// near-api-js gives up on an endpoint with RetriesExceeded (unreachable or
+// overloaded) or TimeoutError, and answers other HTTP failures with an error
+// carrying the status code. Errors typed after an RPC error, such as
+// AccountDoesNotExist, are the same on every node.
+export function isRetryableNearError(error: any): boolean {
+  if (['RetriesExceeded', 'TimeoutError'].includes(error.type)) return true;
+  return typeof error.status === 'number' && error.status >= 429;
+}
+
+/**
+ * A NEAR JSON-RPC provider backed by several endpoints, the counterpart of
+ * EvmProviderPool. All the provider methods, and the accounts of the
+ * connection, go through `sendJsonRpc`.
+ */
+export class NearProviderPool extends providers.JsonRpcProvider {
+  readonly pool: RpcEndpointPool<providers.JsonRpcProvider>;
+
+  constructor(endpoints: RpcEndpointConfig[], quorum = 1) {
+    super({ url: endpoints[0].url });
+    this.pool = new RpcEndpointPool(
+      endpoints,
+      (url: string) => new providers.JsonRpcProvider({ url }),
+      { quorum, isRetryable: isRetryableNearError }
+    );
+  }
+
+  public health(): RpcEndpointHealth[] {
+    return this.pool.health();
+  }
+
+  async sendJsonRpc<T>(method: string, params: object): Promise<T> {
+    const call = (provider: providers.JsonRpcProvider) =>
+      provider.sendJsonRpc<T>(method, params);
+    return SEND_METHODS.includes(method)
+      ? await this.pool.executeOnce(call)
+      : isNearQuorumRead(method, params)
+      ? await this.pool.executeQuorum(call)
+      : await this.pool.execute(call);
+  }
+}
@@ -41,6 +41,56 @@ export interface PollResponse {
   txReceipt: providers.FinalExecutionOutcome | null;
 }
 
+export interface TransferRequest extends NetworkSelectionRequest {
+  address: string; // the sender's Near account Id
+  to: string; // the receiving account Id
+  token: string; // a token symbol, or NEAR
+  amount: string; // in units of the token, e.g. '1.5'
+}
+
+export interface TransferResponse {
+  network: string;
+  timestamp: number;
+  latency: number;
+  token: string;
+  tokenAddress?: string; // not set for NEAR
+  to: string;
+  amount: string;
+  txHash: string;
+}
+
+export interface StorageRequest extends NetworkSelectionRequest {
+  address: string; // the user's Near account Id
+  token: string; // a token symbol
+  amount?: string; // NEAR to deposit for the storage, e.g. '0.00125'
+}
+
+export interface StorageResponse {
+  network: string;
+  timestamp: number;
+  latency: number;
+  token: string;
+  tokenAddress: string;
+  registered: boolean;
+  total: string; // in NEAR, '0' if the account isn't registered
+  available: string; // in NEAR
+  minimum: string; // the deposit that registers an account, in NEAR
+  txHash?: string; // set when NEAR was deposited
+}
+
+export interface RegisterRequest extends NetworkSelectionRequest {
+  address: string; // the user's Near account Id
+  tokenSymbols: string[]; // the tokens to register the account on
+}
+
+export interface RegisterResponse {
+  network: string;
+  timestamp: number;
+  latency: number;
+  registered: Record<string, string>; // the txHash of each registration
+  alreadyRegistered: string[];
+}
+
 export interface CancelRequest extends NetworkSelectionRequest {
   nonce: number; // the nonce of the transaction to be canceled
   address: string; // the user's Near account Id
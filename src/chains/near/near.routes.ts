@@ -4,8 +4,23 @@ import { Nearish } from '../../services/common-interfaces';
 import { asyncHandler } from '../../services/error-handler';
 
 import { getChain } from '../../services/connection-manager';
-import { BalanceResponse, PollRequest, PollResponse } from './near.requests';
-import { validateBalanceRequest } from './near.validators';
+import {
+  BalanceResponse,
+  PollRequest,
+  PollResponse,
+  RegisterRequest,
+  RegisterResponse,
+  StorageRequest,
+  StorageResponse,
+  TransferRequest,
+  TransferResponse,
+} from './near.requests';
+import {
+  validateBalanceRequest,
+  validateRegisterRequest,
+  validateStorageRequest,
+  validateTransferRequest,
+} from './near.validators';
 import * as nearControllers from './near.controllers';
 import { getTokens } from '../../network/network.controllers';
 import {
@@ -66,6 +81,48 @@ export namespace NearRoutes {
     )
   );
 
+  router.post(
+    '/transfer',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, TransferRequest>,
+        res: Response<TransferResponse, {}>
+      ) => {
+        validateTransferRequest(req.body);
+        const chain = await getChain<Nearish>('near', req.body.network);
+        res.status(200).json(await nearControllers.transfer(chain, req.body));
+      }
+    )
+  );
+
+  router.post(
+    '/storage',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, StorageRequest>,
+        res: Response<StorageResponse, {}>
+      ) => {
+        validateStorageRequest(req.body);
+        const chain = await getChain<Nearish>('near', req.body.network);
+        res.status(200).json(await nearControllers.storage(chain, req.body));
+      }
+    )
+  );
+
+  router.post(
+    '/register',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, RegisterRequest>,
+        res: Response<RegisterResponse, {}>
+      ) => {
+        validateRegisterRequest(req.body);
+        const chain = await getChain<Nearish>('near', req.body.network);
+        res.status(200).json(await nearControllers.register(chain, req.body));
+      }
+    )
+  );
+
   router.get(
     '/tokens',
     asyncHandler(
@@ -23,7 +23,9 @@ export class Near extends NearBase {
       config.network.tokenListType,
       config.manualGasPrice,
       config.gasLimitTransaction,
-      ConfigManagerV2.getInstance().get('server.transactionDbPath')
+      ConfigManagerV2.getInstance().get('server.transactionDbPath'),
+      config.network.nodeURLs,
+      config.network.nodeQuorum
     );
     this._chain = config.network.name;
     this._nativeTokenSymbol = config.nativeCurrencySymbol;
@@ -75,7 +77,7 @@ export class Near extends NearBase {
    * Automatically update the prevailing gas price on the network.
    */
   async updateGasPrice(): Promise<void> {
-    if (this._gasPriceRefreshInterval === null) {
+    if (this._gasPriceRefreshInterval === null || this.closed) {
       return;
     }
 
@@ -1,5 +1,8 @@
 import {
+  isFloatString,
+  validateToken,
   validateTokenSymbols,
+  validateTransferAmount,
   mkValidator,
   mkRequestValidator,
   RequestValidator,
@@ -14,9 +17,15 @@ export const invalidAddressError: string =
 export const invalidSpenderError: string =
   'The spender param is not a valid Near address.';
 
+export const invalidToError: string =
+  'The to param is not a valid Near account Id.';
+
 export const invalidNonceError: string =
   'If nonce is included it must be a non-negative integer.';
 
+export const invalidStorageAmountError: string =
+  'If amount is included it must be a string of a positive number of NEAR.';
+
 export const invalidChainError: string = 'The chain param is not a string.';
 
 export const invalidNetworkError: string = 'The network param is not a string.';
@@ -35,6 +44,12 @@ export const validateSpender: Validator = mkValidator(
   (val) => typeof val === 'string'
 );
 
+export const validateTo: Validator = mkValidator(
+  'to',
+  invalidToError,
+  (val) => typeof val === 'string' && val.length > 0
+);
+
 export const validateNonce: Validator = mkValidator(
   'nonce',
   invalidNonceError,
@@ -44,6 +59,13 @@ export const validateNonce: Validator = mkValidator(
   true
 );
 
+export const validateStorageAmount: Validator = mkValidator(
+  'amount',
+  invalidStorageAmountError,
+  (val) => typeof val === 'string' && isFloatString(val) && parseFloat(val) > 0,
+  true
+);
+
 export const validateChain: Validator = mkValidator(
   'chain',
   invalidChainError,
@@ -62,3 +84,21 @@ export const validateBalanceRequest: RequestValidator = mkRequestValidator([
   validateAddress,
   validateTokenSymbols,
 ]);
+
+export const validateTransferRequest: RequestValidator = mkRequestValidator([
+  validateAddress,
+  validateTo,
+  validateToken,
+  validateTransferAmount,
+]);
+
+export const validateStorageRequest: RequestValidator = mkRequestValidator([
+  validateAddress,
+  validateToken,
+  validateStorageAmount,
+]);
+
+export const validateRegisterRequest: RequestValidator = mkRequestValidator([
+  validateAddress,
+  validateTokenSymbols,
+]);
@@ -13,6 +13,7 @@ import { UniswapConfig } from './uniswap/uniswap.config';
 import { VVSConfig } from './vvs/vvs.config';
 import { RefConfig } from './ref/ref.config';
 import { PancakeSwapConfig } from './pancakeswap/pancakeswap.config';
+import { OsmosisConfig } from './osmosis/osmosis.config';
 
 export namespace ConnectorsRoutes {
   export const router = Router();
@@ -95,6 +96,11 @@ export namespace ConnectorsRoutes {
             trading_type: PancakeSwapConfig.config.tradingTypes,
             available_networks: PancakeSwapConfig.config.availableNetworks,
           },
+          {
+            name: 'osmosis',
+            trading_type: OsmosisConfig.config.tradingTypes,
+            available_networks: OsmosisConfig.config.availableNetworks,
+          },
         ],
       });
     })
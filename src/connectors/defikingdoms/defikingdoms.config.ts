@@ -11,11 +11,15 @@ export namespace DefikingdomsConfig {
   }
 
   export const config: NetworkConfig = {
-    allowedSlippage: ConfigManagerV2.getInstance().get(
-      `defikingdoms.allowedSlippage`
-    ),
-    gasLimit: ConfigManagerV2.getInstance().get(`defikingdoms.gasLimit`),
-    ttl: ConfigManagerV2.getInstance().get(`defikingdoms.ttl`),
+    get allowedSlippage() {
+      return ConfigManagerV2.getInstance().get(`defikingdoms.allowedSlippage`);
+    },
+    get gasLimit() {
+      return ConfigManagerV2.getInstance().get(`defikingdoms.gasLimit`);
+    },
+    get ttl() {
+      return ConfigManagerV2.getInstance().get(`defikingdoms.ttl`);
+    },
     routerAddress: (network: string) =>
       ConfigManagerV2.getInstance().get(
         `defikingdoms.contractAddresses.${network}.routerAddress`
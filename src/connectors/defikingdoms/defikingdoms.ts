@@ -22,7 +22,12 @@ import { BigNumber, Transaction, Wallet } from 'ethers';
 import { logger } from '../../services/logger';
 import { percentRegexp } from '../../services/config-manager-v2';
 // import { Ethereum } from '../../chains/ethereum/ethereum';
-import { ExpectedTrade, Uniswapish } from '../../services/common-interfaces';
+import {
+  ExpectedTrade,
+  NativeSwap,
+  Uniswapish,
+} from '../../services/common-interfaces';
+import { toNativeSwap } from '../uniswap/uniswap.native-swap';
 import { Harmony } from '../../chains/harmony/harmony';
 
 export class Defikingdoms implements Uniswapish {
@@ -58,6 +63,10 @@ export class Defikingdoms implements Uniswapish {
     return Defikingdoms._instances[chain + network];
   }
 
+  public static getConnectedInstances(): { [name: string]: Defikingdoms } {
+    return Defikingdoms._instances;
+  }
+
   /**
    * Given a token's address, return the connector's native representation of
    * the token.
@@ -72,6 +81,7 @@ export class Defikingdoms implements Uniswapish {
     if (!this.harmony.ready()) {
       await this.harmony.init();
     }
+    this.tokenList = {};
     for (const token of this.harmony.storedTokenList) {
       this.tokenList[token.address] = new Token(
         this.chainId,
@@ -256,13 +266,18 @@ export class Defikingdoms implements Uniswapish {
     nonce?: number,
     _1?: BigNumber,
     _2?: BigNumber,
-    allowedSlippage?: string
+    allowedSlippage?: string,
+    nativeSwap?: NativeSwap
   ): Promise<Transaction> {
-    const result: SwapParameters = Router.swapCallParameters(trade, {
-      ttl,
-      recipient: wallet.address,
-      allowedSlippage: this.getAllowedSlippage(allowedSlippage),
-    });
+    const result: SwapParameters = toNativeSwap(
+      Router.swapCallParameters(trade, {
+        ttl,
+        recipient: wallet.address,
+        allowedSlippage: this.getAllowedSlippage(allowedSlippage),
+      }),
+      abi,
+      nativeSwap
+    );
 
     const contract: Contract = new Contract(defikingdomsRouter, abi, wallet);
     return this.harmony.nonceManager.provideNonce(
@@ -20,7 +20,12 @@ import { BigNumber, Transaction, Wallet } from 'ethers';
 import { logger } from '../../services/logger';
 import { percentRegexp } from '../../services/config-manager-v2';
 import { Harmony } from '../../chains/harmony/harmony';
-import { ExpectedTrade, Uniswapish } from '../../services/common-interfaces';
+import {
+  ExpectedTrade,
+  NativeSwap,
+  Uniswapish,
+} from '../../services/common-interfaces';
+import { toNativeSwap } from '../uniswap/uniswap.native-swap';
 
 export class Defira implements Uniswapish {
   private static _instances: { [name: string]: Defira };
@@ -58,6 +63,10 @@ export class Defira implements Uniswapish {
     return Defira._instances[chain + network];
   }
 
+  public static getConnectedInstances(): { [name: string]: Defira } {
+    return Defira._instances;
+  }
+
   /**
    * Given a token's address, return the connector's native representation of
    * the token.
@@ -72,6 +81,7 @@ export class Defira implements Uniswapish {
     if (!this.harmony.ready()) {
       await this.harmony.init();
     }
+    this.tokenList = {};
     for (const token of this.harmony.storedTokenList) {
       this.tokenList[token.address] = new Token(
         token.chainId || this.chainId,
@@ -291,13 +301,18 @@ export class Defira implements Uniswapish {
     nonce?: number,
     _1?: BigNumber,
     _2?: BigNumber,
-    allowedSlippage?: string
+    allowedSlippage?: string,
+    nativeSwap?: NativeSwap
   ): Promise<Transaction> {
-    const result: SwapParameters = DefiraRouter.swapCallParameters(trade, {
-      ttl,
-      recipient: wallet.address,
-      allowedSlippage: this.getAllowedSlippage(allowedSlippage),
-    });
+    const result: SwapParameters = toNativeSwap(
+      DefiraRouter.swapCallParameters(trade, {
+        ttl,
+        recipient: wallet.address,
+        allowedSlippage: this.getAllowedSlippage(allowedSlippage),
+      }),
+      abi,
+      nativeSwap
+    );
 
     const contract: Contract = new Contract(defiraRouter, abi, wallet);
     return this.harmony.nonceManager.provideNonce(
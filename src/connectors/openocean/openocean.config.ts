@@ -12,13 +12,15 @@ export namespace OpenoceanConfig {
   }
 
   export const config: NetworkConfig = {
-    allowedSlippage: ConfigManagerV2.getInstance().get(
-      'openocean.allowedSlippage'
-    ),
-    gasLimitEstimate: ConfigManagerV2.getInstance().get(
-      `openocean.gasLimitEstimate`
-    ),
-    ttl: ConfigManagerV2.getInstance().get('openocean.ttl'),
+    get allowedSlippage() {
+      return ConfigManagerV2.getInstance().get('openocean.allowedSlippage');
+    },
+    get gasLimitEstimate() {
+      return ConfigManagerV2.getInstance().get(`openocean.gasLimitEstimate`);
+    },
+    get ttl() {
+      return ConfigManagerV2.getInstance().get('openocean.ttl');
+    },
     routerAddress: (chain: string, network: string) =>
       ConfigManagerV2.getInstance().get(
         'openocean.contractAddresses.' +
@@ -81,6 +81,10 @@ export class Openocean implements Uniswapish {
     return Openocean._instances[chain + network];
   }
 
+  public static getConnectedInstances(): { [name: string]: Openocean } {
+    return Openocean._instances;
+  }
+
   public getChainInstance(network: string) {
     if (this._chain === 'ethereum') {
       return Ethereum.getInstance(network);
@@ -109,6 +113,7 @@ export class Openocean implements Uniswapish {
     if (!this.chainInstance.ready()) {
       await this.chainInstance.init();
     }
+    this.tokenList = {};
     for (const token of this.chainInstance.storedTokenList) {
       this.tokenList[token.address] = new Token(
         this.chainId,
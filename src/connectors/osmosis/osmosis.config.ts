@@ -0,0 +1,30 @@
+import { ConfigManagerV2 } from '../../services/config-manager-v2';
+import { AvailableNetworks } from '../../services/config-manager-types';
+
+// the connector is configured in the osmosis namespace of its chain
+export namespace OsmosisConfig {
+  export interface NetworkConfig {
+    allowedSlippage: string;
+    gasLimitEstimate: number;
+    tradingTypes: Array<string>;
+    availableNetworks: Array<AvailableNetworks>;
+  }
+
+  export const config: NetworkConfig = {
+    get allowedSlippage() {
+      return ConfigManagerV2.getInstance().get('osmosis.allowedSlippage');
+    },
+    get gasLimitEstimate() {
+      return ConfigManagerV2.getInstance().get('osmosis.gasLimitEstimate');
+    },
+    tradingTypes: ['COSMOS_AMM'],
+    availableNetworks: [
+      {
+        chain: 'osmosis',
+        networks: Object.keys(
+          ConfigManagerV2.getInstance().get('osmosis.networks')
+        ),
+      },
+    ],
+  };
+}
@@ -0,0 +1,334 @@
+import Decimal from 'decimal.js-light';
+import { utils } from 'ethers';
+import {
+  HttpException,
+  LOAD_WALLET_ERROR_CODE,
+  LOAD_WALLET_ERROR_MESSAGE,
+  TOKEN_NOT_SUPPORTED_ERROR_CODE,
+  TOKEN_NOT_SUPPORTED_ERROR_MESSAGE,
+  PRICE_FAILED_ERROR_CODE,
+  PRICE_FAILED_ERROR_MESSAGE,
+  TRADE_FAILED_ERROR_CODE,
+  TRADE_FAILED_ERROR_MESSAGE,
+  SWAP_PRICE_EXCEEDS_LIMIT_PRICE_ERROR_CODE,
+  SWAP_PRICE_EXCEEDS_LIMIT_PRICE_ERROR_MESSAGE,
+  SWAP_PRICE_LOWER_THAN_LIMIT_PRICE_ERROR_CODE,
+  SWAP_PRICE_LOWER_THAN_LIMIT_PRICE_ERROR_MESSAGE,
+  UNKNOWN_ERROR_ERROR_CODE,
+  UNKNOWN_ERROR_MESSAGE,
+} from '../../services/error-handler';
+import { latency } from '../../services/base';
+import { CosmosAMMish, Cosmosish } from '../../services/common-interfaces';
+import { logger } from '../../services/logger';
+import { checkRisk } from '../../services/risk-manager';
+import {
+  EstimateGasResponse,
+  PriceRequest,
+  PriceResponse,
+  TradeRequest,
+  TradeResponse,
+} from '../../amm/amm.requests';
+import { CosmosWallet, Token } from '../../chains/cosmos/cosmos-base';
+import { signAndBroadcast } from '../../chains/cosmos/cosmos.controllers';
+import { OsmosisTrade } from './osmosis';
+
+export interface TradeInfo {
+  baseToken: Token;
+  quoteToken: Token;
+  requestAmount: string;
+  expectedTrade: OsmosisTrade;
+  expectedAmount: string; // of the quote token, received or paid
+  estimatedPrice: string;
+}
+
+export async function getTradeInfo(
+  cosmosish: Cosmosish,
+  cosmosAMMish: CosmosAMMish,
+  baseAsset: string,
+  quoteAsset: string,
+  amount: string,
+  tradeSide: string
+): Promise<TradeInfo> {
+  const baseToken = getFullTokenFromSymbol(cosmosish, baseAsset);
+  const quoteToken = getFullTokenFromSymbol(cosmosish, quoteAsset);
+
+  let expectedTrade: OsmosisTrade;
+  let quoteAmount: string;
+  if (tradeSide === 'BUY') {
+    expectedTrade = await cosmosAMMish.estimateBuyTrade(
+      quoteToken,
+      baseToken,
+      amount
+    );
+    quoteAmount = expectedTrade.amountIn;
+  } else {
+    expectedTrade = await cosmosAMMish.estimateSellTrade(
+      baseToken,
+      quoteToken,
+      amount
+    );
+    quoteAmount = expectedTrade.amountOut;
+  }
+  const expectedAmount = utils.formatUnits(quoteAmount, quoteToken.decimals);
+
+  return {
+    baseToken,
+    quoteToken,
+    requestAmount: amount,
+    expectedTrade,
+    expectedAmount,
+    estimatedPrice: new Decimal(expectedAmount).div(amount).toString(),
+  };
+}
+
+// the cost of the estimated gas of a swap at the minimum gas price
+function gasCost(cosmosish: Cosmosish, cosmosAMMish: CosmosAMMish): string {
+  const nativeToken = getFullTokenFromSymbol(
+    cosmosish,
+    cosmosish.nativeTokenSymbol
+  );
+  return new Decimal(cosmosish.minimumGasPrice)
+    .mul(cosmosAMMish.gasLimitEstimate)
+    .div(new Decimal(10).pow(nativeToken.decimals))
+    .toString();
+}
+
+export async function price(
+  cosmosish: Cosmosish,
+  cosmosAMMish: CosmosAMMish,
+  req: PriceRequest
+): Promise<PriceResponse> {
+  const startTimestamp: number = Date.now();
+  let tradeInfo: TradeInfo;
+  try {
+    tradeInfo = await getTradeInfo(
+      cosmosish,
+      cosmosAMMish,
+      req.base,
+      req.quote,
+      req.amount,
+      req.side
+    );
+  } catch (e) {
+    if (e instanceof Error) {
+      throw new HttpException(
+        500,
+        PRICE_FAILED_ERROR_MESSAGE + e.message,
+        PRICE_FAILED_ERROR_CODE
+      );
+    } else {
+      throw new HttpException(
+        500,
+        UNKNOWN_ERROR_MESSAGE,
+        UNKNOWN_ERROR_ERROR_CODE
+      );
+    }
+  }
+
+  return {
+    network: cosmosish.chain,
+    timestamp: startTimestamp,
+    latency: latency(startTimestamp, Date.now()),
+    base: tradeInfo.baseToken.base,
+    quote: tradeInfo.quoteToken.base,
+    amount: new Decimal(req.amount).toFixed(tradeInfo.baseToken.decimals),
+    rawAmount: tradeInfo.requestAmount,
+    expectedAmount: tradeInfo.expectedAmount,
+    price: tradeInfo.estimatedPrice,
+    gasPrice: cosmosish.minimumGasPrice,
+    gasPriceToken: cosmosish.nativeTokenSymbol,
+    gasLimit: cosmosAMMish.gasLimitEstimate,
+    gasCost: gasCost(cosmosish, cosmosAMMish),
+  };
+}
+
+export async function trade(
+  cosmosish: Cosmosish,
+  cosmosAMMish: CosmosAMMish,
+  req: TradeRequest
+): Promise<TradeResponse> {
+  const startTimestamp: number = Date.now();
+
+  const limitPrice = req.limitPrice;
+  let wallet: CosmosWallet;
+  try {
+    wallet = await cosmosish.getWallet(req.address, cosmosish.bech32Prefix);
+  } catch (err) {
+    throw new HttpException(
+      500,
+      LOAD_WALLET_ERROR_MESSAGE + err,
+      LOAD_WALLET_ERROR_CODE
+    );
+  }
+
+  let tradeInfo: TradeInfo;
+  try {
+    tradeInfo = await getTradeInfo(
+      cosmosish,
+      cosmosAMMish,
+      req.base,
+      req.quote,
+      req.amount,
+      req.side
+    );
+  } catch (e) {
+    if (e instanceof Error) {
+      logger.error(`Could not get trade info. ${e.message}`);
+      throw new HttpException(
+        500,
+        TRADE_FAILED_ERROR_MESSAGE + e.message,
+        TRADE_FAILED_ERROR_CODE
+      );
+    } else {
+      logger.error('Unknown error trying to get trade info.');
+      throw new HttpException(
+        500,
+        UNKNOWN_ERROR_MESSAGE,
+        UNKNOWN_ERROR_ERROR_CODE
+      );
+    }
+  }
+
+  const { estimatedPrice, expectedAmount } = tradeInfo;
+  logger.info(
+    `Expected execution price is ${estimatedPrice}, ` +
+      `limit price is ${limitPrice}.`
+  );
+
+  await checkRisk({
+    wallet: req.address,
+    connector: req.connector,
+    tokens: [req.base, req.quote],
+    amounts: [req.amount, expectedAmount],
+    allowedSlippage: req.allowedSlippage,
+    journal: cosmosish.journal,
+  });
+
+  if (
+    req.side === 'BUY' &&
+    limitPrice &&
+    new Decimal(estimatedPrice).gt(new Decimal(limitPrice))
+  ) {
+    logger.error('Swap price exceeded limit price.');
+    throw new HttpException(
+      500,
+      SWAP_PRICE_EXCEEDS_LIMIT_PRICE_ERROR_MESSAGE(estimatedPrice, limitPrice),
+      SWAP_PRICE_EXCEEDS_LIMIT_PRICE_ERROR_CODE
+    );
+  }
+  if (
+    req.side === 'SELL' &&
+    limitPrice &&
+    new Decimal(estimatedPrice).lt(new Decimal(limitPrice))
+  ) {
+    logger.error('Swap price lower than limit price.');
+    throw new HttpException(
+      500,
+      SWAP_PRICE_LOWER_THAN_LIMIT_PRICE_ERROR_MESSAGE(
+        estimatedPrice,
+        limitPrice
+      ),
+      SWAP_PRICE_LOWER_THAN_LIMIT_PRICE_ERROR_CODE
+    );
+  }
+
+  const message = cosmosAMMish.swapMessage(
+    req.address,
+    tradeInfo.expectedTrade,
+    req.allowedSlippage
+  );
+  const tx = await signAndBroadcast(cosmosish, wallet, [message], {
+    simulate: req.simulate,
+  });
+  // a broadcast returns once the transaction is in a block, so its entry is
+  // settled right away
+  if (tx.txHash !== undefined) {
+    await cosmosish.journal.record({
+      txHash: tx.txHash,
+      chain: cosmosish.chainName,
+      network: cosmosish.chain,
+      type: 'trade',
+      connector: req.connector,
+      wallet: req.address,
+      tokens: [req.base, req.quote],
+      amounts: [req.amount, expectedAmount],
+      side: req.side,
+      price: estimatedPrice,
+      gasPrice: tx.gasPrice,
+      gasLimit: tx.gasLimit,
+    });
+    await cosmosish.journal.updateStatus(
+      tx.txHash,
+      tx.code ? 'FAILED' : 'CONFIRMED'
+    );
+  }
+  if (tx.code) {
+    logger.error(`Osmosis swap failed. ${tx.rawLog}`);
+    throw new HttpException(
+      500,
+      TRADE_FAILED_ERROR_MESSAGE + tx.rawLog,
+      TRADE_FAILED_ERROR_CODE
+    );
+  }
+  logger.info(`${req.side} Osmosis swap has been executed.`);
+
+  const { poolId, tokenIn, tokenOut } = tradeInfo.expectedTrade;
+  return {
+    network: cosmosish.chain,
+    timestamp: startTimestamp,
+    latency: latency(startTimestamp, Date.now()),
+    base: tradeInfo.baseToken.base,
+    quote: tradeInfo.quoteToken.base,
+    amount: new Decimal(req.amount).toFixed(tradeInfo.baseToken.decimals),
+    rawAmount: tradeInfo.requestAmount,
+    ...(req.side === 'BUY'
+      ? { expectedIn: expectedAmount }
+      : { expectedOut: expectedAmount }),
+    price: estimatedPrice,
+    gasPrice: tx.gasPrice,
+    gasPriceToken: cosmosish.nativeTokenSymbol,
+    gasLimit: tx.gasLimit,
+    gasCost: tx.fee,
+    txHash: tx.txHash,
+    route: [
+      {
+        percent: 100,
+        hops: [
+          {
+            tokenIn: tokenIn.base,
+            tokenOut: tokenOut.base,
+            pool: String(poolId),
+          },
+        ],
+      },
+    ],
+  };
+}
+
+export function getFullTokenFromSymbol(
+  cosmosish: Cosmosish,
+  tokenSymbol: string
+): Token {
+  const token = cosmosish.getTokenBySymbol(tokenSymbol);
+  if (!token)
+    throw new HttpException(
+      500,
+      TOKEN_NOT_SUPPORTED_ERROR_MESSAGE + tokenSymbol,
+      TOKEN_NOT_SUPPORTED_ERROR_CODE
+    );
+  return token;
+}
+
+export async function estimateGas(
+  cosmosish: Cosmosish,
+  cosmosAMMish: CosmosAMMish
+): Promise<EstimateGasResponse> {
+  return {
+    network: cosmosish.chain,
+    timestamp: Date.now(),
+    gasPrice: cosmosish.minimumGasPrice,
+    gasPriceToken: cosmosish.nativeTokenSymbol,
+    gasLimit: cosmosAMMish.gasLimitEstimate,
+    gasCost: gasCost(cosmosish, cosmosAMMish),
+  };
+}
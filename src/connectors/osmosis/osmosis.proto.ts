@@ -0,0 +1,249 @@
+import { GeneratedType } from '@cosmjs/proto-signing';
+import { fromUtf8 } from '@cosmjs/encoding';
+import { Reader, Writer } from 'protobufjs/minimal';
+
+// The few Osmosis poolmanager messages and queries the connector needs,
+// encoded field by field after osmosis/poolmanager/v1beta1/{tx,query}.proto
+// and the pool protos, instead of depending on generated Osmosis types.
+
+export const MSG_SWAP_EXACT_AMOUNT_IN =
+  '/osmosis.poolmanager.v1beta1.MsgSwapExactAmountIn';
+export const MSG_SWAP_EXACT_AMOUNT_OUT =
+  '/osmosis.poolmanager.v1beta1.MsgSwapExactAmountOut';
+
+export const ALL_POOLS_QUERY = '/osmosis.poolmanager.v1beta1.Query/AllPools';
+export const ESTIMATE_SWAP_EXACT_AMOUNT_IN_QUERY =
+  '/osmosis.poolmanager.v1beta1.Query/EstimateSwapExactAmountIn';
+export const ESTIMATE_SWAP_EXACT_AMOUNT_OUT_QUERY =
+  '/osmosis.poolmanager.v1beta1.Query/EstimateSwapExactAmountOut';
+
+const BALANCER_POOL = '/osmosis.gamm.v1beta1.Pool';
+const STABLESWAP_POOL = '/osmosis.gamm.poolmodels.stableswap.v1beta1.Pool';
+const CONCENTRATED_POOL = '/osmosis.concentratedliquidity.v1beta1.Pool';
+
+export interface Coin {
+  denom: string;
+  amount: string;
+}
+
+export interface SwapAmountInRoute {
+  poolId: number;
+  tokenOutDenom: string;
+}
+
+export interface SwapAmountOutRoute {
+  poolId: number;
+  tokenInDenom: string;
+}
+
+export interface MsgSwapExactAmountIn {
+  sender: string;
+  routes: SwapAmountInRoute[];
+  tokenIn: Coin;
+  tokenOutMinAmount: string;
+}
+
+export interface MsgSwapExactAmountOut {
+  sender: string;
+  routes: SwapAmountOutRoute[];
+  tokenInMaxAmount: string;
+  tokenOut: Coin;
+}
+
+// a GAMM or concentrated liquidity pool, with the denoms it holds
+export interface OsmosisPool {
+  id: number;
+  typeUrl: string;
+  denoms: string[];
+}
+
+// wire types
+const VARINT = 0;
+const LENGTH_DELIMITED = 2;
+
+const tag = (field: number, wireType: number): number =>
+  (field << 3) | wireType;
+
+function writeCoin(writer: Writer, field: number, coin: Coin): void {
+  writer.uint32(tag(field, LENGTH_DELIMITED)).fork();
+  writer.uint32(tag(1, LENGTH_DELIMITED)).string(coin.denom);
+  writer.uint32(tag(2, LENGTH_DELIMITED)).string(coin.amount);
+  writer.ldelim();
+}
+
+// both routes are a pool id and a denom
+function writeRoute(
+  writer: Writer,
+  field: number,
+  poolId: number,
+  denom: string
+): void {
+  writer.uint32(tag(field, LENGTH_DELIMITED)).fork();
+  writer.uint32(tag(1, VARINT)).uint64(poolId);
+  writer.uint32(tag(2, LENGTH_DELIMITED)).string(denom);
+  writer.ldelim();
+}
+
+type Fields = Map<number, Array<number | Uint8Array>>;
+
+// the fields of a message by number, length delimited ones left as bytes
+function readFields(input: Uint8Array | Reader): Fields {
+  const reader = input instanceof Reader ? input : Reader.create(input);
+  const fields: Fields = new Map();
+  while (reader.pos < reader.len) {
+    const key = reader.uint32();
+    let value: number | Uint8Array;
+    if ((key & 7) === VARINT) {
+      value = Number(reader.uint64().toString());
+    } else if ((key & 7) === LENGTH_DELIMITED) {
+      value = reader.bytes();
+    } else {
+      reader.skipType(key & 7);
+      continue;
+    }
+    const values = fields.get(key >>> 3) || [];
+    values.push(value);
+    fields.set(key >>> 3, values);
+  }
+  return fields;
+}
+
+const repeatedField = (fields: Fields, field: number): Uint8Array[] =>
+  (fields.get(field) || []) as Uint8Array[];
+
+const bytesField = (fields: Fields, field: number): Uint8Array =>
+  repeatedField(fields, field)[0] || new Uint8Array();
+
+const stringField = (fields: Fields, field: number): string =>
+  fromUtf8(bytesField(fields, field));
+
+const numberField = (fields: Fields, field: number): number =>
+  ((fields.get(field) || [0])[0] as number) || 0;
+
+const readCoin = (bytes: Uint8Array): Coin => {
+  const fields = readFields(bytes);
+  return { denom: stringField(fields, 1), amount: stringField(fields, 2) };
+};
+
+export const MsgSwapExactAmountInType: GeneratedType = {
+  encode(msg: MsgSwapExactAmountIn, writer: Writer = Writer.create()) {
+    writer.uint32(tag(1, LENGTH_DELIMITED)).string(msg.sender);
+    for (const route of msg.routes) {
+      writeRoute(writer, 2, route.poolId, route.tokenOutDenom);
+    }
+    writeCoin(writer, 3, msg.tokenIn);
+    writer.uint32(tag(4, LENGTH_DELIMITED)).string(msg.tokenOutMinAmount);
+    return writer;
+  },
+  decode(input: Uint8Array | Reader): MsgSwapExactAmountIn {
+    const fields = readFields(input);
+    return {
+      sender: stringField(fields, 1),
+      routes: repeatedField(fields, 2).map((bytes) => {
+        const route = readFields(bytes);
+        return {
+          poolId: numberField(route, 1),
+          tokenOutDenom: stringField(route, 2),
+        };
+      }),
+      tokenIn: readCoin(bytesField(fields, 3)),
+      tokenOutMinAmount: stringField(fields, 4),
+    };
+  },
+  fromPartial(object: MsgSwapExactAmountIn): MsgSwapExactAmountIn {
+    return object;
+  },
+};
+
+export const MsgSwapExactAmountOutType: GeneratedType = {
+  encode(msg: MsgSwapExactAmountOut, writer: Writer = Writer.create()) {
+    writer.uint32(tag(1, LENGTH_DELIMITED)).string(msg.sender);
+    for (const route of msg.routes) {
+      writeRoute(writer, 2, route.poolId, route.tokenInDenom);
+    }
+    writer.uint32(tag(3, LENGTH_DELIMITED)).string(msg.tokenInMaxAmount);
+    writeCoin(writer, 4, msg.tokenOut);
+    return writer;
+  },
+  decode(input: Uint8Array | Reader): MsgSwapExactAmountOut {
+    const fields = readFields(input);
+    return {
+      sender: stringField(fields, 1),
+      routes: repeatedField(fields, 2).map((bytes) => {
+        const route = readFields(bytes);
+        return {
+          poolId: numberField(route, 1),
+          tokenInDenom: stringField(route, 2),
+        };
+      }),
+      tokenInMaxAmount: stringField(fields, 3),
+      tokenOut: readCoin(bytesField(fields, 4)),
+    };
+  },
+  fromPartial(object: MsgSwapExactAmountOut): MsgSwapExactAmountOut {
+    return object;
+  },
+};
+
+/**
+ * The pools of an AllPoolsResponse. CosmWasm pools, which keep their assets
+ * in a contract, are left out.
+ */
+export function decodeAllPoolsResponse(bytes: Uint8Array): OsmosisPool[] {
+  const pools: OsmosisPool[] = [];
+  // each pool is packed in an Any of its type
+  for (const packed of repeatedField(readFields(bytes), 1)) {
+    const anyFields = readFields(packed);
+    const typeUrl = stringField(anyFields, 1);
+    const pool = readFields(bytesField(anyFields, 2));
+    if (typeUrl === BALANCER_POOL) {
+      // each pool asset is a coin and a weight
+      const denoms = repeatedField(pool, 6).map(
+        (asset) => readCoin(bytesField(readFields(asset), 1)).denom
+      );
+      pools.push({ id: numberField(pool, 2), typeUrl, denoms });
+    } else if (typeUrl === STABLESWAP_POOL) {
+      const denoms = repeatedField(pool, 6).map((coin) => readCoin(coin).denom);
+      pools.push({ id: numberField(pool, 2), typeUrl, denoms });
+    } else if (typeUrl === CONCENTRATED_POOL) {
+      const denoms = [stringField(pool, 6), stringField(pool, 7)];
+      pools.push({ id: numberField(pool, 4), typeUrl, denoms });
+    }
+  }
+  return pools;
+}
+
+export function encodeEstimateSwapExactAmountIn(
+  tokenIn: Coin,
+  routes: SwapAmountInRoute[]
+): Uint8Array {
+  const writer = Writer.create();
+  writer.uint32(tag(2, VARINT)).uint64(routes[0].poolId);
+  writer
+    .uint32(tag(3, LENGTH_DELIMITED))
+    .string(`${tokenIn.amount}${tokenIn.denom}`);
+  for (const route of routes) {
+    writeRoute(writer, 4, route.poolId, route.tokenOutDenom);
+  }
+  return writer.finish();
+}
+
+export function encodeEstimateSwapExactAmountOut(
+  tokenOut: Coin,
+  routes: SwapAmountOutRoute[]
+): Uint8Array {
+  const writer = Writer.create();
+  writer.uint32(tag(2, VARINT)).uint64(routes[0].poolId);
+  for (const route of routes) {
+    writeRoute(writer, 3, route.poolId, route.tokenInDenom);
+  }
+  writer
+    .uint32(tag(4, LENGTH_DELIMITED))
+    .string(`${tokenOut.amount}${tokenOut.denom}`);
+  return writer.finish();
+}
+
+// both estimates respond with the other amount of the swap in field 1
+export function decodeEstimateSwapResponse(bytes: Uint8Array): string {
+  return stringField(readFields(bytes), 1);
+}
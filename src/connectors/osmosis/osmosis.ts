@@ -0,0 +1,304 @@
+import Decimal from 'decimal.js-light';
+import { EncodeObject } from '@cosmjs/proto-signing';
+import { utils } from 'ethers';
+import { UniswapishPriceError as AMMishPriceError } from '../../services/error-handler';
+import { isFractionString } from '../../services/validators';
+import { percentRegexp } from '../../services/config-manager-v2';
+import { CosmosAMMish } from '../../services/common-interfaces';
+import { logger } from '../../services/logger';
+import { Cosmos } from '../../chains/cosmos/cosmos';
+import { Token } from '../../chains/cosmos/cosmos-base';
+import { OsmosisConfig } from './osmosis.config';
+import {
+  ALL_POOLS_QUERY,
+  decodeAllPoolsResponse,
+  decodeEstimateSwapResponse,
+  encodeEstimateSwapExactAmountIn,
+  encodeEstimateSwapExactAmountOut,
+  ESTIMATE_SWAP_EXACT_AMOUNT_IN_QUERY,
+  ESTIMATE_SWAP_EXACT_AMOUNT_OUT_QUERY,
+  MSG_SWAP_EXACT_AMOUNT_IN,
+  MSG_SWAP_EXACT_AMOUNT_OUT,
+  MsgSwapExactAmountIn,
+  MsgSwapExactAmountInType,
+  MsgSwapExactAmountOut,
+  MsgSwapExactAmountOutType,
+  OsmosisPool,
+} from './osmosis.proto';
+
+// a swap through a single pool, with its amounts in the smallest units
+export interface OsmosisTrade {
+  poolId: number;
+  tokenIn: Token;
+  tokenOut: Token;
+  amountIn: string;
+  amountOut: string;
+  exactIn: boolean;
+}
+
+// pools are created rarely, so the list of all of them is reused this long
+const POOLS_TTL = 60 * 1000; // ms
+
+export class OsmosisConnector implements CosmosAMMish {
+  private static _instances: { [name: string]: OsmosisConnector };
+  private osmosis: Cosmos;
+  private _gasLimitEstimate: number;
+  private _ready: boolean = false;
+  private _pools: Promise<OsmosisPool[]> | undefined;
+  private _poolsExpiry: number = 0;
+
+  private constructor(network: string) {
+    this.osmosis = Cosmos.getInstance(network, 'osmosis');
+    this._gasLimitEstimate = OsmosisConfig.config.gasLimitEstimate;
+  }
+
+  public static getInstance(chain: string, network: string): OsmosisConnector {
+    if (OsmosisConnector._instances === undefined) {
+      OsmosisConnector._instances = {};
+    }
+    if (!(chain + network in OsmosisConnector._instances)) {
+      OsmosisConnector._instances[chain + network] = new OsmosisConnector(
+        network
+      );
+    }
+
+    return OsmosisConnector._instances[chain + network];
+  }
+
+  public static getConnectedInstances(): {
+    [name: string]: OsmosisConnector;
+  } {
+    return OsmosisConnector._instances;
+  }
+
+  public async init() {
+    this.osmosis.registerMessageType(
+      MSG_SWAP_EXACT_AMOUNT_IN,
+      MsgSwapExactAmountInType
+    );
+    this.osmosis.registerMessageType(
+      MSG_SWAP_EXACT_AMOUNT_OUT,
+      MsgSwapExactAmountOutType
+    );
+    if (!this.osmosis.ready()) {
+      await this.osmosis.init();
+    }
+    this._ready = true;
+  }
+
+  public ready(): boolean {
+    return this._ready;
+  }
+
+  /**
+   * Default gas limit for swap transactions.
+   */
+  public get gasLimitEstimate(): number {
+    return this._gasLimitEstimate;
+  }
+
+  /**
+   * Gets the allowed slippage percent from the optional parameter or the value
+   * in the configuration.
+   *
+   * @param allowedSlippageStr (Optional) should be of the form '1/10'.
+   */
+  public getAllowedSlippage(allowedSlippageStr?: string): number {
+    if (allowedSlippageStr != null && isFractionString(allowedSlippageStr)) {
+      const fractionSplit = allowedSlippageStr.split('/');
+      return Number(fractionSplit[0]) / Number(fractionSplit[1]);
+    }
+
+    const allowedSlippage = OsmosisConfig.config.allowedSlippage;
+    const nd = allowedSlippage.match(percentRegexp);
+    if (nd) return Number(nd[1]) / Number(nd[2]);
+    throw new Error(
+      'Encountered a malformed percent string in the config for ALLOWED_SLIPPAGE.'
+    );
+  }
+
+  /**
+   * The GAMM and concentrated liquidity pools holding both tokens.
+   */
+  async fetchPools(tokenA: Token, tokenB: Token): Promise<OsmosisPool[]> {
+    const pools = await this.allPools();
+    return pools.filter(
+      (pool) =>
+        pool.denoms.includes(tokenA.base) && pool.denoms.includes(tokenB.base)
+    );
+  }
+
+  // all the pools of the chain, queried again once they are POOLS_TTL old
+  private allPools(): Promise<OsmosisPool[]> {
+    const now = Date.now();
+    if (this._pools === undefined || now >= this._poolsExpiry) {
+      const pools = this.osmosis
+        .queryAbci(ALL_POOLS_QUERY, new Uint8Array())
+        .then(decodeAllPoolsResponse);
+      // a failed query isn't reused
+      pools.catch(() => {
+        if (this._pools === pools) this._pools = undefined;
+      });
+      this._pools = pools;
+      this._poolsExpiry = now + POOLS_TTL;
+    }
+    return this._pools;
+  }
+
+  /**
+   * Given the amount of `baseToken` to put into a transaction, calculate the
+   * amount of `quoteToken` that can be expected from the transaction.
+   *
+   * This is typically used for calculating token sell prices.
+   *
+   * @param baseToken Token input for the transaction
+   * @param quoteToken Output from the transaction
+   * @param amount Amount of `baseToken` to put into the transaction
+   */
+  async estimateSellTrade(
+    baseToken: Token,
+    quoteToken: Token,
+    amount: string
+  ): Promise<OsmosisTrade> {
+    logger.info(`Fetching pools for ${baseToken.base}-${quoteToken.base}.`);
+    const amountIn = utils.parseUnits(amount, baseToken.decimals).toString();
+    const pools = await this.fetchPools(baseToken, quoteToken);
+
+    // pools without the liquidity for the swap fail their estimate
+    const estimates = await Promise.allSettled(
+      pools.map(async (pool) =>
+        decodeEstimateSwapResponse(
+          await this.osmosis.queryAbci(
+            ESTIMATE_SWAP_EXACT_AMOUNT_IN_QUERY,
+            encodeEstimateSwapExactAmountIn(
+              { denom: baseToken.base, amount: amountIn },
+              [{ poolId: pool.id, tokenOutDenom: quoteToken.base }]
+            )
+          )
+        )
+      )
+    );
+    let trade: OsmosisTrade | undefined;
+    estimates.forEach((estimate, i) => {
+      if (
+        estimate.status === 'fulfilled' &&
+        (!trade || new Decimal(estimate.value).gt(trade.amountOut))
+      ) {
+        trade = {
+          poolId: pools[i].id,
+          tokenIn: baseToken,
+          tokenOut: quoteToken,
+          amountIn,
+          amountOut: estimate.value,
+          exactIn: true,
+        };
+      }
+    });
+    if (!trade) {
+      throw new AMMishPriceError(
+        `priceSwapIn: no trade pair found for ${baseToken.base} to ${quoteToken.base}.`
+      );
+    }
+    logger.info(
+      `Best trade for ${baseToken.base}-${quoteToken.base}: ` +
+        `pool ${trade.poolId}.`
+    );
+    return trade;
+  }
+
+  /**
+   * Given the amount of `baseToken` desired to acquire from a transaction,
+   * calculate the amount of `quoteToken` needed for the transaction.
+   *
+   * This is typically used for calculating token buy prices.
+   *
+   * @param quoteToken Token input for the transaction
+   * @param baseToken Token output from the transaction
+   * @param amount Amount of `baseToken` desired from the transaction
+   */
+  async estimateBuyTrade(
+    quoteToken: Token,
+    baseToken: Token,
+    amount: string
+  ): Promise<OsmosisTrade> {
+    logger.info(`Fetching pools for ${quoteToken.base}-${baseToken.base}.`);
+    const amountOut = utils.parseUnits(amount, baseToken.decimals).toString();
+    const pools = await this.fetchPools(quoteToken, baseToken);
+
+    const estimates = await Promise.allSettled(
+      pools.map(async (pool) =>
+        decodeEstimateSwapResponse(
+          await this.osmosis.queryAbci(
+            ESTIMATE_SWAP_EXACT_AMOUNT_OUT_QUERY,
+            encodeEstimateSwapExactAmountOut(
+              { denom: baseToken.base, amount: amountOut },
+              [{ poolId: pool.id, tokenInDenom: quoteToken.base }]
+            )
+          )
+        )
+      )
+    );
+    let trade: OsmosisTrade | undefined;
+    estimates.forEach((estimate, i) => {
+      if (
+        estimate.status === 'fulfilled' &&
+        (!trade || new Decimal(estimate.value).lt(trade.amountIn))
+      ) {
+        trade = {
+          poolId: pools[i].id,
+          tokenIn: quoteToken,
+          tokenOut: baseToken,
+          amountIn: estimate.value,
+          amountOut,
+          exactIn: false,
+        };
+      }
+    });
+    if (!trade) {
+      throw new AMMishPriceError(
+        `priceSwapOut: no trade pair found for ${quoteToken.base} to ${baseToken.base}.`
+      );
+    }
+    logger.info(
+      `Best trade for ${quoteToken.base}-${baseToken.base}: ` +
+        `pool ${trade.poolId}.`
+    );
+    return trade;
+  }
+
+  /**
+   * The swap message of a trade, which receives at least or pays at most its
+   * estimate less the allowed slippage.
+   *
+   * @param sender Address of the wallet swapping
+   * @param trade Expected trade
+   * @param allowedSlippage (Optional) Maximum allowable slippage
+   */
+  swapMessage(
+    sender: string,
+    trade: OsmosisTrade,
+    allowedSlippage?: string
+  ): EncodeObject {
+    const slippage = this.getAllowedSlippage(allowedSlippage);
+    if (trade.exactIn) {
+      const value: MsgSwapExactAmountIn = {
+        sender,
+        routes: [{ poolId: trade.poolId, tokenOutDenom: trade.tokenOut.base }],
+        tokenIn: { denom: trade.tokenIn.base, amount: trade.amountIn },
+        tokenOutMinAmount: new Decimal(trade.amountOut)
+          .mul(1 - slippage)
+          .toFixed(0, Decimal.ROUND_DOWN),
+      };
+      return { typeUrl: MSG_SWAP_EXACT_AMOUNT_IN, value };
+    }
+    const value: MsgSwapExactAmountOut = {
+      sender,
+      routes: [{ poolId: trade.poolId, tokenInDenom: trade.tokenIn.base }],
+      tokenInMaxAmount: new Decimal(trade.amountIn)
+        .mul(1 + slippage)
+        .toFixed(0, Decimal.ROUND_UP),
+      tokenOut: { denom: trade.tokenOut.base, amount: trade.amountOut },
+    };
+    return { typeUrl: MSG_SWAP_EXACT_AMOUNT_OUT, value };
+  }
+}
@@ -12,13 +12,15 @@ export namespace PancakeSwapConfig {
   }
 
   export const config: ExchangeConfig = {
-    allowedSlippage: ConfigManagerV2.getInstance().get(
-      'pancakeswap.allowedSlippage'
-    ),
-    gasLimitEstimate: ConfigManagerV2.getInstance().get(
-      `pancakeswap.gasLimitEstimate`
-    ),
-    ttl: ConfigManagerV2.getInstance().get('pancakeswap.ttl'),
+    get allowedSlippage() {
+      return ConfigManagerV2.getInstance().get('pancakeswap.allowedSlippage');
+    },
+    get gasLimitEstimate() {
+      return ConfigManagerV2.getInstance().get(`pancakeswap.gasLimitEstimate`);
+    },
+    get ttl() {
+      return ConfigManagerV2.getInstance().get('pancakeswap.ttl');
+    },
     routerAddress: (network: string) =>
       ConfigManagerV2.getInstance().get(
         'pancakeswap.contractAddresses.' + network + '.routerAddress'
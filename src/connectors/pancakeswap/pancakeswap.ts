@@ -17,7 +17,12 @@ import {
   Wallet,
 } from 'ethers';
 import { BinanceSmartChain } from '../../chains/binance-smart-chain/binance-smart-chain';
-import { ExpectedTrade, Uniswapish } from '../../services/common-interfaces';
+import {
+  ExpectedTrade,
+  NativeSwap,
+  Uniswapish,
+} from '../../services/common-interfaces';
+import { toNativeSwap } from '../uniswap/uniswap.native-swap';
 import { percentRegexp } from '../../services/config-manager-v2';
 import {
   InitializationError,
@@ -66,12 +71,17 @@ export class PancakeSwap implements Uniswapish {
     return PancakeSwap._instances[chain + network];
   }
 
+  public static getConnectedInstances(): { [name: string]: PancakeSwap } {
+    return PancakeSwap._instances;
+  }
+
   public async init() {
     if (this._chain == 'binance-smart-chain' && !this.bsc.ready())
       throw new InitializationError(
         SERVICE_UNITIALIZED_ERROR_MESSAGE('BinanceSmartChain'),
         SERVICE_UNITIALIZED_ERROR_CODE
       );
+    this.tokenList = {};
     for (const token of this.bsc.storedTokenList) {
       this.tokenList[token.address] = new Token(
         this.chainId,
@@ -279,13 +289,18 @@ export class PancakeSwap implements Uniswapish {
     nonce?: number,
     maxFeePerGas?: BigNumber,
     maxPriorityFeePerGas?: BigNumber,
-    allowedSlippage?: string
+    allowedSlippage?: string,
+    nativeSwap?: NativeSwap
   ): Promise<Transaction> {
-    const result: SwapParameters = Router.swapCallParameters(trade, {
-      ttl,
-      recipient: wallet.address,
-      allowedSlippage: this.getAllowedSlippage(allowedSlippage),
-    });
+    const result: SwapParameters = toNativeSwap(
+      Router.swapCallParameters(trade, {
+        ttl,
+        recipient: wallet.address,
+        allowedSlippage: this.getAllowedSlippage(allowedSlippage),
+      }),
+      abi,
+      nativeSwap
+    );
 
     const contract: Contract = new Contract(pancakeswapRouter, abi, wallet);
     if (nonce === undefined) {
@@ -12,13 +12,15 @@ export namespace PangolinConfig {
   }
 
   export const config: NetworkConfig = {
-    allowedSlippage: ConfigManagerV2.getInstance().get(
-      'pangolin.allowedSlippage'
-    ),
-    gasLimitEstimate: ConfigManagerV2.getInstance().get(
-      `pangolin.gasLimitEstimate`
-    ),
-    ttl: ConfigManagerV2.getInstance().get('pangolin.ttl'),
+    get allowedSlippage() {
+      return ConfigManagerV2.getInstance().get('pangolin.allowedSlippage');
+    },
+    get gasLimitEstimate() {
+      return ConfigManagerV2.getInstance().get(`pangolin.gasLimitEstimate`);
+    },
+    get ttl() {
+      return ConfigManagerV2.getInstance().get('pangolin.ttl');
+    },
     routerAddress: (network: string) =>
       ConfigManagerV2.getInstance().get(
         'pangolin.contractAddresses.' + network + '.routerAddress'
@@ -22,7 +22,12 @@ import {
 } from '@pangolindex/sdk';
 import { logger } from '../../services/logger';
 import { Avalanche } from '../../chains/avalanche/avalanche';
-import { ExpectedTrade, Uniswapish } from '../../services/common-interfaces';
+import {
+  ExpectedTrade,
+  NativeSwap,
+  Uniswapish,
+} from '../../services/common-interfaces';
+import { toNativeSwap } from '../uniswap/uniswap.native-swap';
 
 export class Pangolin implements Uniswapish {
   private static _instances: { [name: string]: Pangolin };
@@ -56,6 +61,10 @@ export class Pangolin implements Uniswapish {
     return Pangolin._instances[chain + network];
   }
 
+  public static getConnectedInstances(): { [name: string]: Pangolin } {
+    return Pangolin._instances;
+  }
+
   /**
    * Given a token's address, return the connector's native representation of
    * the token.
@@ -70,6 +79,7 @@ export class Pangolin implements Uniswapish {
     if (!this.avalanche.ready()) {
       await this.avalanche.init();
     }
+    this.tokenList = {};
     for (const token of this.avalanche.storedTokenList) {
       this.tokenList[token.address] = new Token(
         this.chainId,
@@ -256,13 +266,18 @@ export class Pangolin implements Uniswapish {
     nonce?: number,
     maxFeePerGas?: BigNumber,
     maxPriorityFeePerGas?: BigNumber,
-    allowedSlippage?: string
+    allowedSlippage?: string,
+    nativeSwap?: NativeSwap
   ): Promise<Transaction> {
-    const result = Router.swapCallParameters(trade, {
-      ttl,
-      recipient: wallet.address,
-      allowedSlippage: this.getAllowedSlippage(allowedSlippage),
-    });
+    const result = toNativeSwap(
+      Router.swapCallParameters(trade, {
+        ttl,
+        recipient: wallet.address,
+        allowedSlippage: this.getAllowedSlippage(allowedSlippage),
+      }),
+      abi,
+      nativeSwap
+    );
 
     const contract = new Contract(pangolinRouter, abi, wallet);
     return this.avalanche.nonceManager.provideNonce(
@@ -4,13 +4,35 @@ export namespace PerpConfig {
   export interface NetworkConfig {
     allowedSlippage: string;
     ttl: number;
+    priceHistorySize: number;
+    priceHistoryInterval: number;
+    openInterestStartBlock: number;
     tradingTypes: (type: string) => Array<string>;
     availableNetworks: Array<AvailableNetworks>;
   }
 
   export const config: NetworkConfig = {
-    allowedSlippage: ConfigManagerV2.getInstance().get(`perp.allowedSlippage`),
-    ttl: ConfigManagerV2.getInstance().get(`perp.versions.ttl`),
+    get allowedSlippage() {
+      return ConfigManagerV2.getInstance().get(`perp.allowedSlippage`);
+    },
+    get ttl() {
+      return ConfigManagerV2.getInstance().get(`perp.ttl`);
+    },
+    get priceHistorySize() {
+      return ConfigManagerV2.getInstance().get(`perp.priceHistorySize`) ?? 720;
+    },
+    get priceHistoryInterval() {
+      return (
+        ConfigManagerV2.getInstance().get(`perp.priceHistoryInterval`) ?? 60
+      );
+    },
+    // the block the clearing house was deployed at on optimism
+    get openInterestStartBlock() {
+      return (
+        ConfigManagerV2.getInstance().get(`perp.openInterestStartBlock`) ??
+        513
+      );
+    },
     tradingTypes: (type: string) =>
       type === 'perp' ? ['EVM_Perpetual'] : ['EVM_AMM_LP'],
     availableNetworks: [{ chain: 'ethereum', networks: ['optimism'] }],
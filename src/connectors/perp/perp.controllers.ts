@@ -16,6 +16,8 @@ import {
   Perpish,
 } from '../../services/common-interfaces';
 import { logger } from '../../services/logger';
+import { checkRisk } from '../../services/risk-manager';
+import { NewJournalEntry } from '../../services/transaction-journal';
 import {
   EstimateGasResponse,
   PriceRequest,
@@ -28,6 +30,18 @@ import {
   PerpMarketRequest,
   PerpMarketResponse,
   PerpBalanceResponse,
+  PerpCreateMakerRequest,
+  PerpCreateMakerResponse,
+  PerpOrdersRequest,
+  PerpOrdersResponse,
+  PerpCancelOrderRequest,
+  PerpCancelOrderResponse,
+  PerpMarginRequest,
+  PerpMarginResponse,
+  PerpFundingResponse,
+  PerpMarketInfoResponse,
+  PerpPriceHistoryRequest,
+  PerpPriceHistoryResponse,
 } from '../../amm/amm.requests';
 import { PerpPosition } from './perp';
 
@@ -90,6 +104,57 @@ export async function checkMarketStatus(
   };
 }
 
+export async function getFundingRates(
+  ethereumish: Ethereumish,
+  perpish: Perpish,
+  req: PerpMarketRequest
+): Promise<PerpFundingResponse> {
+  const startTimestamp: number = Date.now();
+  const rates = await perpish.fundingRates(`${req.base}${req.quote}`);
+  return {
+    network: ethereumish.chain,
+    timestamp: startTimestamp,
+    latency: latency(startTimestamp, Date.now()),
+    base: req.base,
+    quote: req.quote,
+    ...rates,
+  };
+}
+
+export async function getMarketInfo(
+  ethereumish: Ethereumish,
+  perpish: Perpish,
+  req: PerpMarketRequest
+): Promise<PerpMarketInfoResponse> {
+  const startTimestamp: number = Date.now();
+  const info = await perpish.marketInfo(`${req.base}${req.quote}`);
+  return {
+    network: ethereumish.chain,
+    timestamp: startTimestamp,
+    latency: latency(startTimestamp, Date.now()),
+    base: req.base,
+    quote: req.quote,
+    ...info,
+  };
+}
+
+export async function getPriceHistory(
+  ethereumish: Ethereumish,
+  perpish: Perpish,
+  req: PerpPriceHistoryRequest
+): Promise<PerpPriceHistoryResponse> {
+  const startTimestamp: number = Date.now();
+  const samples = perpish.priceHistory(`${req.base}${req.quote}`, req.from);
+  return {
+    network: ethereumish.chain,
+    timestamp: startTimestamp,
+    latency: latency(startTimestamp, Date.now()),
+    base: req.base,
+    quote: req.quote,
+    samples,
+  };
+}
+
 export async function getPosition(
   ethereumish: Ethereumish,
   perpish: Perpish,
@@ -127,12 +192,29 @@ export async function createTakerOrder(
       );
     }
 
+    // closing a position only ever reduces the exposure, so it is not
+    // limited. The quote is the market's settlement currency, not a token.
+    await checkRisk({
+      wallet: req.address,
+      connector: req.connector,
+      tokens: [req.base],
+      amounts: req.amount ? [req.amount] : [],
+      allowedSlippage: req.allowedSlippage,
+      journal: ethereumish.journal,
+    });
+
     tx = await perpish.openPosition(
       req.side === 'LONG' ? true : false,
       `${req.base}${req.quote}`,
       req.amount as string,
       req.allowedSlippage
     );
+  } else if (req.amount) {
+    tx = await perpish.reducePosition(
+      `${req.base}${req.quote}`,
+      req.amount,
+      req.allowedSlippage
+    );
   } else {
     tx = await perpish.closePosition(
       `${req.base}${req.quote}`,
@@ -140,17 +222,14 @@ export async function createTakerOrder(
     );
   }
 
-  await ethereumish.txStorage.saveTx(
-    ethereumish.chain,
-    ethereumish.chainId,
-    tx.hash as string,
-    new Date(),
-    ethereumish.gasPrice
-  );
-
-  logger.info(
-    `Order has been sent, txHash is ${tx.hash}, nonce is ${tx.nonce}, gasPrice is ${gasPrice}.`
-  );
+  await recordTx(ethereumish, perpish, tx, {
+    type: isOpen ? 'perpOpen' : 'perpClose',
+    connector: req.connector,
+    wallet: req.address,
+    tokens: [req.base, req.quote],
+    amounts: req.amount ? [req.amount] : [],
+    side: req.side,
+  });
 
   return {
     network: ethereumish.chain,
@@ -168,6 +247,209 @@ export async function createTakerOrder(
   };
 }
 
+export async function createMakerOrder(
+  ethereumish: Ethereumish,
+  perpish: Perpish,
+  req: PerpCreateMakerRequest
+): Promise<PerpCreateMakerResponse> {
+  const startTimestamp: number = Date.now();
+  const gasPrice: number = ethereumish.gasPrice;
+
+  await checkRisk({
+    wallet: req.address,
+    connector: req.connector,
+    tokens: [req.base],
+    amounts: [req.amount],
+    journal: ethereumish.journal,
+  });
+
+  let order: { orderId: string; transaction: Transaction };
+  try {
+    order = await perpish.createLimitOrder(
+      req.side === 'LONG',
+      `${req.base}${req.quote}`,
+      req.amount,
+      req.price
+    );
+  } catch (e) {
+    throw new HttpException(
+      500,
+      UNKNOWN_ERROR_MESSAGE + ' ' + (e as Error).message,
+      UNKNOWN_ERROR_ERROR_CODE
+    );
+  }
+  const tx = order.transaction;
+
+  await recordTx(ethereumish, perpish, tx, {
+    type: 'perpLimitOrder',
+    connector: req.connector,
+    wallet: req.address,
+    tokens: [req.base, req.quote],
+    amounts: [req.amount],
+    side: req.side,
+    price: req.price,
+  });
+
+  return {
+    network: ethereumish.chain,
+    timestamp: startTimestamp,
+    latency: latency(startTimestamp, Date.now()),
+    base: req.base,
+    quote: req.quote,
+    amount: req.amount,
+    side: req.side,
+    price: req.price,
+    orderId: order.orderId,
+    gasPrice: gasPrice,
+    gasPriceToken: ethereumish.nativeTokenSymbol,
+    gasLimit: perpish.gasLimit,
+    gasCost: gasCostInEthString(gasPrice, perpish.gasLimit),
+    nonce: tx.nonce,
+    txHash: tx.hash,
+  };
+}
+
+export async function getOrders(
+  ethereumish: Ethereumish,
+  perpish: Perpish,
+  req: PerpOrdersRequest
+): Promise<PerpOrdersResponse> {
+  const startTimestamp: number = Date.now();
+  const orders = await perpish.getLimitOrders(`${req.base}${req.quote}`);
+  return {
+    network: ethereumish.chain,
+    timestamp: startTimestamp,
+    latency: latency(startTimestamp, Date.now()),
+    base: req.base,
+    quote: req.quote,
+    orders,
+  };
+}
+
+export async function cancelMakerOrder(
+  ethereumish: Ethereumish,
+  perpish: Perpish,
+  req: PerpCancelOrderRequest
+): Promise<PerpCancelOrderResponse> {
+  const startTimestamp: number = Date.now();
+  const gasPrice: number = ethereumish.gasPrice;
+
+  let tx: Transaction;
+  try {
+    tx = await perpish.cancelLimitOrder(
+      `${req.base}${req.quote}`,
+      req.orderId
+    );
+  } catch (e) {
+    throw new HttpException(
+      500,
+      UNKNOWN_ERROR_MESSAGE + ' ' + (e as Error).message,
+      UNKNOWN_ERROR_ERROR_CODE
+    );
+  }
+
+  await recordTx(ethereumish, perpish, tx, {
+    type: 'perpCancelOrder',
+    connector: req.connector,
+    wallet: req.address,
+    tokens: [req.base, req.quote],
+    amounts: [],
+  });
+
+  return {
+    network: ethereumish.chain,
+    timestamp: startTimestamp,
+    latency: latency(startTimestamp, Date.now()),
+    base: req.base,
+    quote: req.quote,
+    orderId: req.orderId,
+    gasPrice: gasPrice,
+    gasPriceToken: ethereumish.nativeTokenSymbol,
+    gasLimit: perpish.gasLimit,
+    gasCost: gasCostInEthString(gasPrice, perpish.gasLimit),
+    nonce: tx.nonce,
+    txHash: tx.hash,
+  };
+}
+
+export async function changeMargin(
+  ethereumish: Ethereumish,
+  perpish: Perpish,
+  req: PerpMarginRequest,
+  isAdd: boolean
+): Promise<PerpMarginResponse> {
+  const startTimestamp: number = Date.now();
+  const gasPrice: number = ethereumish.gasPrice;
+
+  let tx: Transaction;
+  try {
+    tx = isAdd
+      ? await perpish.addMargin(req.amount)
+      : await perpish.removeMargin(req.amount);
+  } catch (e) {
+    throw new HttpException(
+      500,
+      UNKNOWN_ERROR_MESSAGE + ' ' + (e as Error).message,
+      UNKNOWN_ERROR_ERROR_CODE
+    );
+  }
+
+  await recordTx(ethereumish, perpish, tx, {
+    type: isAdd ? 'perpAddMargin' : 'perpRemoveMargin',
+    connector: req.connector,
+    wallet: req.address,
+    tokens: [],
+    amounts: [req.amount],
+  });
+
+  return {
+    network: ethereumish.chain,
+    timestamp: startTimestamp,
+    latency: latency(startTimestamp, Date.now()),
+    amount: req.amount,
+    gasPrice: gasPrice,
+    gasPriceToken: ethereumish.nativeTokenSymbol,
+    gasLimit: perpish.gasLimit,
+    gasCost: gasCostInEthString(gasPrice, perpish.gasLimit),
+    nonce: tx.nonce,
+    txHash: tx.hash,
+  };
+}
+
+// saves a sent transaction for polling and journals it
+async function recordTx(
+  ethereumish: Ethereumish,
+  perpish: Perpish,
+  tx: Transaction,
+  entry: Omit<
+    NewJournalEntry,
+    'txHash' | 'chain' | 'network' | 'nonce' | 'gasPrice' | 'gasLimit'
+  >
+): Promise<void> {
+  const gasPrice: number = ethereumish.gasPrice;
+  await ethereumish.txStorage.saveTx(
+    ethereumish.chain,
+    ethereumish.chainId,
+    tx.hash as string,
+    new Date(),
+    gasPrice
+  );
+
+  await ethereumish.journal.record({
+    ...entry,
+    txHash: tx.hash as string,
+    chain: ethereumish.chainName,
+    network: ethereumish.chain,
+    nonce: tx.nonce,
+    gasPrice,
+    gasLimit: perpish.gasLimit,
+  });
+
+  logger.info(
+    `Order has been sent, txHash is ${tx.hash}, nonce is ${tx.nonce}, gasPrice is ${gasPrice}.`
+  );
+}
+
 export function getFullTokenFromSymbol(
   ethereumish: Ethereumish,
   perpish: Perpish,
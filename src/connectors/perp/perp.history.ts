@@ -0,0 +1,129 @@
+import { Big } from 'big.js';
+import { NewBlockHandler } from '../../chains/ethereum/ethereum-base';
+import { logger } from '../../services/logger';
+import { PerpConfig } from './perp.config';
+
+export interface PerpPriceSample {
+  blockNumber: number;
+  timestamp: number;
+  markPrice: string;
+  indexPrice: string;
+}
+
+// what the history needs from the connector and the chain it runs on
+export interface PerpPriceSource {
+  availablePairs(): string[];
+  prices(tickerSymbol: string): Promise<{ markPrice: Big; indexPrice: Big }>;
+}
+
+export interface PerpBlockSource {
+  onNewBlock(func: NewBlockHandler): void;
+  offNewBlock(func: NewBlockHandler): void;
+}
+
+/**
+ * A rolling in-memory history of the mark and index prices of every market
+ * on a network, sampled on new blocks. It only holds what was sampled since
+ * the gateway started.
+ */
+export class PerpPriceHistory {
+  private static _instances: { [network: string]: PerpPriceHistory };
+  private _samples: Record<string, PerpPriceSample[]> = {};
+  private _lastSampleTime: number = 0;
+  private _sampling: boolean = false;
+  private _source?: PerpPriceSource;
+  private _blocks?: PerpBlockSource;
+  private readonly _handler: NewBlockHandler = (blockNumber: number) => {
+    this.onNewBlock(blockNumber).catch((e) =>
+      logger.error(`Perp price history sample failed: ${e}`)
+    );
+  };
+
+  public static getInstance(network: string): PerpPriceHistory {
+    if (PerpPriceHistory._instances === undefined) {
+      PerpPriceHistory._instances = {};
+    }
+    if (!(network in PerpPriceHistory._instances)) {
+      PerpPriceHistory._instances[network] = new PerpPriceHistory();
+    }
+    return PerpPriceHistory._instances[network];
+  }
+
+  // the chains sampled from are being replaced, sampling starts again with
+  // the next Perp instance
+  public static stopAll(): void {
+    for (const history of Object.values(PerpPriceHistory._instances || {})) {
+      history.stop();
+    }
+  }
+
+  public get started(): boolean {
+    return this._blocks !== undefined;
+  }
+
+  /**
+   * Starts sampling, once per network whatever the number of connected
+   * wallets.
+   */
+  public start(source: PerpPriceSource, blocks: PerpBlockSource): void {
+    if (this.started || PerpConfig.config.priceHistorySize === 0) return;
+    this._source = source;
+    this._blocks = blocks;
+    blocks.onNewBlock(this._handler);
+  }
+
+  public stop(): void {
+    if (this._blocks) this._blocks.offNewBlock(this._handler);
+    this._blocks = undefined;
+  }
+
+  async onNewBlock(
+    blockNumber: number,
+    now: number = Date.now()
+  ): Promise<void> {
+    const interval = PerpConfig.config.priceHistoryInterval * 1000;
+    if (
+      !this._source ||
+      this._sampling ||
+      now - this._lastSampleTime < interval
+    ) {
+      return;
+    }
+    this._sampling = true;
+    this._lastSampleTime = now;
+    try {
+      const source = this._source;
+      await Promise.all(
+        source.availablePairs().map(async (tickerSymbol) => {
+          const { markPrice, indexPrice } = await source.prices(tickerSymbol);
+          this.record(tickerSymbol, {
+            blockNumber,
+            timestamp: now,
+            markPrice: markPrice.toString(),
+            indexPrice: indexPrice.toString(),
+          });
+        })
+      );
+    } finally {
+      this._sampling = false;
+    }
+  }
+
+  public record(tickerSymbol: string, sample: PerpPriceSample): void {
+    const samples = this._samples[tickerSymbol] || [];
+    samples.push(sample);
+    const size = PerpConfig.config.priceHistorySize;
+    if (samples.length > size) samples.splice(0, samples.length - size);
+    this._samples[tickerSymbol] = samples;
+  }
+
+  /**
+   * @returns the samples of a market, oldest first.
+   * @param from Only the samples taken at or after this time, in ms.
+   */
+  public samples(tickerSymbol: string, from: number = 0): PerpPriceSample[] {
+    return (this._samples[tickerSymbol] || []).filter(
+      (sample) => sample.timestamp >= from
+    );
+  }
+}
@@ -0,0 +1,155 @@
+import { Big } from 'big.js';
+import { BigNumber, providers, utils } from 'ethers';
+import { logger } from '../../services/logger';
+import { PerpConfig } from './perp.config';
+
+// every change of a taker position is logged by the clearing house as a
+// PositionChanged, and the settlement of a position in a closed market as a
+// PositionClosed
+export const clearingHouseEvents = new utils.Interface([
+  'event PositionChanged(address indexed trader, address indexed baseToken, int256 exchangedPositionSize, int256 exchangedPositionNotional, uint256 fee, int256 openNotional, int256 realizedPnl, uint256 sqrtPriceAfterX96)',
+  'event PositionClosed(address indexed trader, address indexed baseToken, int256 closedPositionSize, int256 closedPositionNotional, int256 openNotional, int256 realizedPnl, uint256 closedPrice)',
+]);
+
+// the most blocks asked for in one eth_getLogs, halved when the node refuses
+const MAX_BLOCK_RANGE = 10000;
+
+// the base of the taker positions of a market, the virtual base token has 18
+// decimals
+export interface PerpOpenInterestSides {
+  long: Big;
+  short: Big;
+}
+
+/**
+ * The open interest of every market on a network. Perp v2 only keeps the
+ * position of each account on chain, so the positions are rebuilt from the
+ * clearing house's logs, from openInterestStartBlock on the first update and
+ * from where the last one stopped afterwards.
+ */
+export class PerpOpenInterest {
+  private static _instances: { [network: string]: PerpOpenInterest };
+  // the taker position size of every trader, by lowercase base token address
+  private _positions: Record<string, Record<string, BigNumber>> = {};
+  private _nextBlock?: number;
+  private _scanning?: Promise<void>;
+  // set once a scan has reached the latest block, until then the positions
+  // are those of a backfill still running
+  private _ready: boolean = false;
+
+  public static getInstance(network: string): PerpOpenInterest {
+    if (PerpOpenInterest._instances === undefined) {
+      PerpOpenInterest._instances = {};
+    }
+    if (!(network in PerpOpenInterest._instances)) {
+      PerpOpenInterest._instances[network] = new PerpOpenInterest();
+    }
+    return PerpOpenInterest._instances[network];
+  }
+
+  /**
+   * Applies the position changes logged up to the latest block. Concurrent
+   * updates wait for the same scan.
+   */
+  async update(
+    provider: providers.Provider,
+    clearingHouse: string
+  ): Promise<void> {
+    if (!this._scanning) {
+      this._scanning = this.scan(provider, clearingHouse).finally(() => {
+        this._scanning = undefined;
+      });
+    }
+    await this._scanning;
+  }
+
+  public get ready(): boolean {
+    return this._ready;
+  }
+
+  /**
+   * @returns the base of all long and of all short taker positions of a
+   * market, up to the latest block. Undefined while the first scan of the
+   * logs is running, which is left to run on rather than waited for.
+   */
+  async openInterest(
+    provider: providers.Provider,
+    clearingHouse: string,
+    baseToken: string
+  ): Promise<PerpOpenInterestSides | undefined> {
+    const update = this.update(provider, clearingHouse);
+    if (!this._ready) {
+      update.catch((e) =>
+        logger.error(`Perp open interest update failed: ${e}`)
+      );
+      return undefined;
+    }
+    await update;
+
+    let long = BigNumber.from(0);
+    let short = BigNumber.from(0);
+    const positions = this._positions[baseToken.toLowerCase()] || {};
+    for (const size of Object.values(positions)) {
+      if (size.gt(0)) long = long.add(size);
+      else short = short.sub(size);
+    }
+    return {
+      long: new Big(utils.formatEther(long)),
+      short: new Big(utils.formatEther(short)),
+    };
+  }
+
+  private async scan(
+    provider: providers.Provider,
+    clearingHouse: string
+  ): Promise<void> {
+    const latest = await provider.getBlockNumber();
+    let fromBlock = this._nextBlock ?? PerpConfig.config.openInterestStartBlock;
+    let range = MAX_BLOCK_RANGE;
+    while (fromBlock <= latest) {
+      const toBlock = Math.min(fromBlock + range - 1, latest);
+      let logs: providers.Log[];
+      try {
+        logs = await provider.getLogs({
+          address: clearingHouse,
+          topics: [
+            [
+              clearingHouseEvents.getEventTopic('PositionChanged'),
+              clearingHouseEvents.getEventTopic('PositionClosed'),
+            ],
+          ],
+          fromBlock,
+          toBlock,
+        });
+      } catch (e) {
+        if (range === 1) throw e;
+        range = Math.ceil(range / 2);
+        logger.debug(
+          `Perp position logs of blocks ${fromBlock} to ${toBlock} failed, ` +
+            `asking for ${range} blocks at a time.`
+        );
+        continue;
+      }
+      logs.forEach((log) => this.apply(log));
+      fromBlock = toBlock + 1;
+      this._nextBlock = fromBlock;
+    }
+    this._ready = true;
+  }
+
+  public apply(log: providers.Log): void {
+    const event = clearingHouseEvents.parseLog(log);
+    const trader: string = event.args.trader;
+    const baseToken: string = event.args.baseToken.toLowerCase();
+    const positions = this._positions[baseToken] || {};
+    const size =
+      event.name === 'PositionChanged'
+        ? (positions[trader] || BigNumber.from(0)).add(
+            event.args.exchangedPositionSize
+          )
+        : BigNumber.from(0);
+    if (size.isZero()) delete positions[trader];
+    else positions[trader] = size;
+    this._positions[baseToken] = positions;
+  }
+}
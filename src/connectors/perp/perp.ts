@@ -18,11 +18,13 @@ import {
 } from '@perp/sdk-curie';
 import { Token } from '@uniswap/sdk';
 import { Big } from 'big.js';
-import { Transaction, Wallet } from 'ethers';
+import { BigNumber, Contract, Transaction, utils, Wallet } from 'ethers';
 import { logger } from '../../services/logger';
 import { percentRegexp } from '../../services/config-manager-v2';
 import { Ethereum } from '../../chains/ethereum/ethereum';
 import { Perpish } from '../../services/common-interfaces';
+import { PerpPriceHistory, PerpPriceSample } from './perp.history';
+import { PerpOpenInterest } from './perp.open-interest';
 
 export interface PerpPosition {
   positionAmt: string;
@@ -32,8 +34,171 @@ export interface PerpPosition {
   entryPrice: string;
   tickerSymbol: string;
   pendingFundingPayment: string;
+  marginRatio: string;
+  liquidationPrice: string;
 }
 
+// Perp settles funding continuously, the rates are per 24 hours as on the
+// Perp app.
+export interface PerpFundingRate {
+  tickerSymbol: string;
+  // the rate paid now, from the mark and index TWAPs
+  fundingRate: string;
+  // where the rate is heading, from the current mark and index prices
+  predictedFundingRate: string;
+  markTwapPrice: string;
+  indexTwapPrice: string;
+  twapInterval: number; // seconds
+}
+
+export interface PerpMarketInfo {
+  tickerSymbol: string;
+  baseToken: string;
+  pool: string;
+  maxLeverage: string;
+  initialMarginRatio: string;
+  maintenanceMarginRatio: string;
+  tickSpacing: number;
+  // the price step of tickSpacing at the mark price
+  tickSize: string;
+  exchangeFeeRatio: string;
+  insuranceFundFeeRatio: string;
+  maxPriceSpreadRatio: string;
+  // the base of all long and of all short taker positions, and their
+  // notional at the mark price, null until the position logs are scanned
+  openInterestLong: string | null;
+  openInterestShort: string | null;
+  openInterest: string | null;
+}
+
+// A maker order, placed as liquidity on a single tick range that only holds
+// base (a SHORT above the market) or quote (a LONG below it).
+export interface PerpLimitOrder {
+  orderId: string;
+  side: string;
+  lowerTick: number;
+  upperTick: number;
+  lowerPrice: string;
+  upperPrice: string;
+  liquidity: string;
+  baseAmount: string;
+  quoteAmount: string;
+}
+
+// the virtual base and quote tokens of every market have 18 decimals
+const VIRTUAL_TOKEN_DECIMALS = 18;
+
+// margin ratios are stored in the clearing house config in units of 1e-6
+const RATIO_DECIMALS = 6;
+
+const poolAbi = ['function tickSpacing() view returns (int24)'];
+
+const erc20Abi = ['function decimals() view returns (uint8)'];
+
+const baseTokenAbi = [
+  'function getIndexPrice(uint256 interval) view returns (uint256)',
+];
+
+const fromWei = (amount: BigNumber, decimals: number): Big =>
+  new Big(utils.formatUnits(amount, decimals));
+
+const toWei = (amount: Big, decimals: number): BigNumber =>
+  utils.parseUnits(amount.toFixed(decimals, Big.roundDown), decimals);
+
+// price = 1.0001 ^ tick, with base as token0 of every perp pool
+export const tickToPrice = (tick: number): Big =>
+  new Big(Math.pow(1.0001, tick).toPrecision(15));
+
+// the tick of the price rounded to a multiple of the tick spacing, the
+// closest one unless another rounding is given
+export const priceToTick = (
+  price: Big,
+  tickSpacing: number,
+  round: (tick: number) => number = Math.round
+): number => {
+  const tick = Math.log(Number(price)) / Math.log(1.0001);
+  return round(tick / tickSpacing) * tickSpacing;
+};
+
+/**
+ * The tick range of a limit order at a price. A LONG only holds quote, so its
+ * range ends at or below the limit, and a SHORT only holds base, so its range
+ * starts at or above it. Fails for a range the mark price falls into.
+ */
+export const limitOrderRange = (
+  isLong: boolean,
+  limitPrice: Big,
+  markPrice: Big,
+  tickSpacing: number
+): { lowerTick: number; upperTick: number } => {
+  const tick = priceToTick(
+    limitPrice,
+    tickSpacing,
+    isLong ? Math.floor : Math.ceil
+  );
+  const lowerTick = isLong ? tick - tickSpacing : tick;
+  const upperTick = isLong ? tick : tick + tickSpacing;
+  const markTick = Math.log(Number(markPrice)) / Math.log(1.0001);
+  if (isLong ? upperTick > markTick : lowerTick < markTick) {
+    throw new Error(
+      `The range of ticks ${lowerTick} to ${upperTick} holds the mark price ${markPrice.toString()}.`
+    );
+  }
+  return { lowerTick, upperTick };
+};
+
+// the price of a Uniswap sqrtPriceX96
+export const sqrtPriceX96ToPrice = (sqrtPriceX96: BigNumber): Big =>
+  new Big(sqrtPriceX96.toString()).div(new Big(2).pow(96)).pow(2);
+
+// the funding rate per 24 hours, as the premium of the mark over the index
+export const fundingRate = (markPrice: Big, indexPrice: Big): Big =>
+  markPrice.minus(indexPrice).div(indexPrice);
+
+// the base and quote held by liquidity on a tick range at the given price,
+// the liquidity is in wei and both virtual tokens have the same decimals
+export const rangeAmounts = (
+  liquidity: Big,
+  lowerTick: number,
+  upperTick: number,
+  price: Big
+): { baseAmount: Big; quoteAmount: Big } => {
+  const sqrtLower = tickToPrice(lowerTick).sqrt();
+  const sqrtUpper = tickToPrice(upperTick).sqrt();
+  let sqrtPrice = price.sqrt();
+  if (sqrtPrice.lt(sqrtLower)) sqrtPrice = sqrtLower;
+  if (sqrtPrice.gt(sqrtUpper)) sqrtPrice = sqrtUpper;
+  const base = liquidity
+    .times(sqrtUpper.minus(sqrtPrice))
+    .div(sqrtPrice.times(sqrtUpper));
+  const quote = liquidity.times(sqrtPrice.minus(sqrtLower));
+  return {
+    baseAmount: base.div(new Big(10).pow(VIRTUAL_TOKEN_DECIMALS)),
+    quoteAmount: quote.div(new Big(10).pow(VIRTUAL_TOKEN_DECIMALS)),
+  };
+};
+
+// the mark price at which the account value falls to the maintenance margin
+// of the position, 0 if it can't be liquidated:
+//   long:  accountValue + size * (p - mark) = mmRatio * size * p
+//   short: accountValue - size * (p - mark) = mmRatio * size * p
+export const liquidationPrice = (
+  isLong: boolean,
+  size: Big,
+  markPrice: Big,
+  accountValue: Big,
+  mmRatio: Big
+): Big => {
+  if (size.lte(0)) return new Big(0);
+  const positionValue = size.times(markPrice);
+  const price = isLong
+    ? positionValue
+        .minus(accountValue)
+        .div(size.times(new Big(1).minus(mmRatio)))
+    : positionValue.plus(accountValue).div(size.times(mmRatio.plus(1)));
+  return price.gt(0) ? price : new Big(0);
+};
+
 export class Perp implements Perpish {
   private static _instances: { [name: string]: Perp };
   private ethereum: Ethereum;
@@ -81,6 +246,10 @@ export class Perp implements Perpish {
     return Perp._instances[chain + network + address];
   }
 
+  public static getConnectedInstances(): { [name: string]: Perp } {
+    return Perp._instances;
+  }
+
   /**
    * Given a token's address, return the connector's native representation of
    * the token.
@@ -97,6 +266,7 @@ export class Perp implements Perpish {
         SERVICE_UNITIALIZED_ERROR_MESSAGE('ETH'),
         SERVICE_UNITIALIZED_ERROR_CODE
       );
+    this.tokenList = {};
     for (const token of this.ethereum.storedTokenList) {
       this.tokenList[token.address] = new Token(
         this.chainId,
@@ -107,6 +277,15 @@ export class Perp implements Perpish {
       );
     }
     await this._perp.init();
+    PerpPriceHistory.getInstance(this.ethereum.chain).start(
+      this,
+      this.ethereum
+    );
+    // the first scan of the position logs can take a while, it starts now
+    const clearingHouse = this._perp.contracts.clearingHouse.address;
+    PerpOpenInterest.getInstance(this.ethereum.chain)
+      .update(this.ethereum.provider, clearingHouse)
+      .catch((e) => logger.error(`Perp open interest update failed: ${e}`));
     if (this._address !== '') {
       try {
         this._wallet = await this.ethereum.getWallet(this._address);
@@ -179,6 +358,99 @@ export class Perp implements Perpish {
     return (await market.getStatus()) === MarketStatus.ACTIVE ? true : false;
   }
 
+  /**
+   * Queries the funding rate of a market, as Perp computes it from the mark
+   * and index TWAPs over the clearing house TWAP interval.
+   * @param tickerSymbol Market pair
+   */
+  async fundingRates(tickerSymbol: string): Promise<PerpFundingRate> {
+    const contracts = this._perp.contracts;
+    const market = this._perp.markets.getMarket({ tickerSymbol });
+    const twapInterval: number =
+      await contracts.clearingHouseConfig.getTwapInterval();
+    const markTwapPrice = sqrtPriceX96ToPrice(
+      await contracts.exchange.getSqrtMarkTwapX96(
+        market.baseAddress,
+        twapInterval
+      )
+    );
+    const baseToken = new Contract(
+      market.baseAddress,
+      baseTokenAbi,
+      this.ethereum.provider
+    );
+    const indexTwapPrice = fromWei(
+      await baseToken.getIndexPrice(twapInterval),
+      VIRTUAL_TOKEN_DECIMALS
+    );
+    const { markPrice, indexPrice } = await this.prices(tickerSymbol);
+    return {
+      tickerSymbol,
+      fundingRate: fundingRate(markTwapPrice, indexTwapPrice).toString(),
+      predictedFundingRate: fundingRate(markPrice, indexPrice).toString(),
+      markTwapPrice: markTwapPrice.toString(),
+      indexTwapPrice: indexTwapPrice.toString(),
+      twapInterval,
+    };
+  }
+
+  /**
+   * Queries the margin requirements, tick size and fees of a market.
+   * @param tickerSymbol Market pair
+   */
+  async marketInfo(tickerSymbol: string): Promise<PerpMarketInfo> {
+    const contracts = this._perp.contracts;
+    const market = this._perp.markets.getMarket({ tickerSymbol });
+    const ratio = (value: BigNumber | number): Big =>
+      new Big(utils.formatUnits(value, RATIO_DECIMALS));
+    const imRatio = ratio(await contracts.clearingHouseConfig.getImRatio());
+    const mmRatio = ratio(await contracts.clearingHouseConfig.getMmRatio());
+    const info = await contracts.marketRegistry.getMarketInfo(
+      market.baseAddress
+    );
+    const tickSpacing = await this.tickSpacing(market.poolAddress);
+    const { markPrice } = await this.prices(tickerSymbol);
+    const openInterest = await PerpOpenInterest.getInstance(
+      this.ethereum.chain
+    ).openInterest(
+      this.ethereum.provider,
+      contracts.clearingHouse.address,
+      market.baseAddress
+    );
+    return {
+      tickerSymbol,
+      baseToken: market.baseAddress,
+      pool: market.poolAddress,
+      maxLeverage: imRatio.gt(0) ? new Big(1).div(imRatio).toFixed(2) : '0',
+      initialMarginRatio: imRatio.toString(),
+      maintenanceMarginRatio: mmRatio.toString(),
+      tickSpacing,
+      tickSize: markPrice
+        .times(tickToPrice(tickSpacing).minus(1))
+        .toPrecision(6),
+      exchangeFeeRatio: ratio(info.exchangeFeeRatio).toString(),
+      insuranceFundFeeRatio: ratio(info.insuranceFundFeeRatio).toString(),
+      maxPriceSpreadRatio: ratio(info.maxPriceSpreadRatio).toString(),
+      openInterestLong: openInterest ? openInterest.long.toString() : null,
+      openInterestShort: openInterest ? openInterest.short.toString() : null,
+      openInterest: openInterest
+        ? openInterest.long.plus(openInterest.short).times(markPrice).toString()
+        : null,
+    };
+  }
+
+  /**
+   * @returns the mark and index prices of a market sampled since the gateway
+   * started, oldest first.
+   * @param from Only the samples taken at or after this time, in ms.
+   */
+  priceHistory(tickerSymbol: string, from?: number): PerpPriceSample[] {
+    return PerpPriceHistory.getInstance(this.ethereum.chain).samples(
+      tickerSymbol,
+      from
+    );
+  }
+
   /**
    * Gets available Position.
    * @param tickerSymbol An optional parameter to get specific position.
@@ -189,9 +461,11 @@ export class Perp implements Perpish {
     let positionAmt: string = '0',
       positionSide: string = '',
       unrealizedProfit: string = '0',
-      leverage: string = '1',
+      leverage: string = '0',
       entryPrice: string = '0',
-      pendingFundingPayment: string = '0';
+      pendingFundingPayment: string = '0',
+      marginRatio: string = '0',
+      liquidationPrice: string = '0';
     if (positions && tickerSymbol) {
       const fp = await positions.getTotalPendingFundingPayments({
         cache: false,
@@ -209,9 +483,16 @@ export class Perp implements Perpish {
         unrealizedProfit = (
           await position.getUnrealizedPnl({ cache: false })
         ).toString();
-        leverage = '1';
         entryPrice = position.entryPrice.toString();
         positionAmt = position.sizeAbs.toString();
+        const risk = await this.positionRisk(
+          tickerSymbol,
+          position.side === PositionSide.LONG,
+          position.sizeAbs
+        );
+        leverage = risk.leverage;
+        marginRatio = risk.marginRatio;
+        liquidationPrice = risk.liquidationPrice;
       }
     }
     return {
@@ -222,6 +503,62 @@ export class Perp implements Perpish {
       entryPrice,
       tickerSymbol,
       pendingFundingPayment,
+      marginRatio,
+      liquidationPrice,
+    };
+  }
+
+  /**
+   * Computes the leverage of a position, the margin ratio of the account and
+   * the mark price at which the position would be liquidated. Perp margins
+   * every position of an account together, so the liquidation price assumes
+   * the other positions keep their value.
+   * @param tickerSymbol The market of the position.
+   * @param isLong The side of the position.
+   * @param size The absolute size of the position in base.
+   */
+  async positionRisk(
+    tickerSymbol: string,
+    isLong: boolean,
+    size: Big
+  ): Promise<{
+    leverage: string;
+    marginRatio: string;
+    liquidationPrice: string;
+  }> {
+    const contracts = this._perp.contracts;
+    const { markPrice } = await this.prices(tickerSymbol);
+    const accountValue = await this.getAccountValue();
+    const totalPositionValue = fromWei(
+      await contracts.accountBalance.getTotalAbsPositionValue(
+        this.walletAddress()
+      ),
+      VIRTUAL_TOKEN_DECIMALS
+    );
+    const mmRatio = new Big(
+      utils.formatUnits(
+        await contracts.clearingHouseConfig.getMmRatio(),
+        RATIO_DECIMALS
+      )
+    );
+    const positionValue = size.times(markPrice);
+
+    let leverage = new Big(0);
+    let marginRatio = new Big(0);
+    if (accountValue.gt(0)) leverage = positionValue.div(accountValue);
+    if (totalPositionValue.gt(0))
+      marginRatio = accountValue.div(totalPositionValue);
+
+    return {
+      leverage: leverage.toFixed(4),
+      marginRatio: marginRatio.toFixed(4),
+      liquidationPrice: liquidationPrice(
+        isLong,
+        size,
+        markPrice,
+        accountValue,
+        mmRatio
+      ).toString(),
     };
   }
 
@@ -282,6 +619,199 @@ export class Perp implements Perpish {
       .transaction;
   }
 
+  /**
+   * Reduces an open position by the given base amount, by trading the
+   * opposite side. The trade can't flip the position.
+   * @param tickerSymbol The market on which we want to reduce the position.
+   * @param amount The amount of base to close.
+   * @returns An ethers transaction object.
+   */
+  async reducePosition(
+    tickerSymbol: string,
+    amount: string,
+    allowedSlippage?: string
+  ): Promise<Transaction> {
+    const slippage = new Big(
+      this.getAllowedSlippage(allowedSlippage).toString()
+    );
+    const clearingHouse = this._perp.clearingHouse as ClearingHouse;
+    const positions = this._perp.positions as Positions;
+    const position = await positions.getTakerPositionByTickerSymbol(
+      tickerSymbol
+    );
+    if (!position) {
+      throw new Error(`No active position on ${tickerSymbol}.`);
+    }
+    const amountInput = new Big(amount);
+    if (amountInput.gt(position.sizeAbs)) {
+      throw new Error(
+        `Cannot reduce the ${position.sizeAbs.toString()} position on ${tickerSymbol} by ${amount}.`
+      );
+    }
+    const draft = clearingHouse.createPositionDraft({
+      tickerSymbol,
+      side:
+        position.side === PositionSide.LONG
+          ? PositionSide.SHORT
+          : PositionSide.LONG,
+      amountInput,
+      isAmountInputBase: true,
+    });
+    return (await clearingHouse.openPosition(draft, slippage)).transaction;
+  }
+
+  /**
+   * Places a limit order as liquidity on the tick range next to the price, so
+   * it fills as the market crosses it. A LONG has to be below the market and
+   * a SHORT above it. A filled order stays in the pool, on the other token,
+   * until it is cancelled.
+   * @param isLong Buys base if true, sells it otherwise.
+   * @param tickerSymbol The market to place the order on.
+   * @param amount The amount of base to buy or sell.
+   * @param price The limit price.
+   */
+  async createLimitOrder(
+    isLong: boolean,
+    tickerSymbol: string,
+    amount: string,
+    price: string
+  ): Promise<{ orderId: string; transaction: Transaction }> {
+    const market = this._perp.markets.getMarket({ tickerSymbol });
+    const { markPrice } = await this.prices(tickerSymbol);
+    const limitPrice = new Big(price);
+    if (isLong ? limitPrice.gte(markPrice) : limitPrice.lte(markPrice)) {
+      throw new Error(
+        `A ${isLong ? 'LONG' : 'SHORT'} limit order on ${tickerSymbol} has to be ${isLong ? 'below' : 'above'} the mark price ${markPrice.toString()}.`
+      );
+    }
+    const tickSpacing = await this.tickSpacing(market.poolAddress);
+    const { lowerTick, upperTick } = limitOrderRange(
+      isLong,
+      limitPrice,
+      markPrice,
+      tickSpacing
+    );
+    const base = new Big(amount);
+    const quote = isLong ? base.times(limitPrice) : new Big(0);
+
+    const clearingHouse = this._perp.contracts.clearingHouse.connect(
+      this.wallet()
+    );
+    const transaction = await clearingHouse.addLiquidity(
+      {
+        baseToken: market.baseAddress,
+        base: toWei(isLong ? new Big(0) : base, VIRTUAL_TOKEN_DECIMALS),
+        quote: toWei(quote, VIRTUAL_TOKEN_DECIMALS),
+        lowerTick,
+        upperTick,
+        minBase: 0,
+        minQuote: 0,
+        useTakerBalance: false,
+        deadline: this.deadline(),
+      },
+      { gasLimit: this.gasLimit }
+    );
+    return {
+      orderId: this.orderId(market.baseAddress, lowerTick, upperTick),
+      transaction,
+    };
+  }
+
+  /**
+   * @returns the open limit orders of the wallet on a market, with the base
+   * and quote they hold at the mark price.
+   */
+  async getLimitOrders(tickerSymbol: string): Promise<PerpLimitOrder[]> {
+    const market = this._perp.markets.getMarket({ tickerSymbol });
+    const orderBook = this._perp.contracts.orderBook;
+    const orderIds: string[] = await orderBook.getOpenOrderIds(
+      this.walletAddress(),
+      market.baseAddress
+    );
+    const { markPrice } = await this.prices(tickerSymbol);
+    const orders: PerpLimitOrder[] = [];
+    for (const orderId of orderIds) {
+      const order = await orderBook.getOpenOrderById(orderId);
+      const { baseAmount, quoteAmount } = rangeAmounts(
+        new Big(order.liquidity.toString()),
+        order.lowerTick,
+        order.upperTick,
+        markPrice
+      );
+      orders.push({
+        orderId,
+        side: BigNumber.from(order.baseDebt).gt(0) ? 'SHORT' : 'LONG',
+        lowerTick: order.lowerTick,
+        upperTick: order.upperTick,
+        lowerPrice: tickToPrice(order.lowerTick).toString(),
+        upperPrice: tickToPrice(order.upperTick).toString(),
+        liquidity: order.liquidity.toString(),
+        baseAmount: baseAmount.toString(),
+        quoteAmount: quoteAmount.toString(),
+      });
+    }
+    return orders;
+  }
+
+  /**
+   * Cancels a limit order by removing all of its liquidity, whatever part
+   * of it has filled is settled into the position.
+   * @param tickerSymbol The market of the order.
+   * @param orderId The id returned when the order was placed.
+   */
+  async cancelLimitOrder(
+    tickerSymbol: string,
+    orderId: string
+  ): Promise<Transaction> {
+    const market = this._perp.markets.getMarket({ tickerSymbol });
+    const order = await this._perp.contracts.orderBook.getOpenOrderById(
+      orderId
+    );
+    if (BigNumber.from(order.liquidity).isZero()) {
+      throw new Error(`No open order ${orderId} on ${tickerSymbol}.`);
+    }
+    const clearingHouse = this._perp.contracts.clearingHouse.connect(
+      this.wallet()
+    );
+    return await clearingHouse.removeLiquidity(
+      {
+        baseToken: market.baseAddress,
+        lowerTick: order.lowerTick,
+        upperTick: order.upperTick,
+        liquidity: order.liquidity,
+        minBase: 0,
+        minQuote: 0,
+        deadline: this.deadline(),
+      },
+      { gasLimit: this.gasLimit }
+    );
+  }
+
+  /**
+   * Deposits collateral from the wallet into the vault. The vault has to be
+   * approved to spend the settlement token first, see /evm/approve.
+   * @param amount The amount of the settlement token.
+   */
+  async addMargin(amount: string): Promise<Transaction> {
+    const vault = this._perp.contracts.vault.connect(this.wallet());
+    const { address, decimals } = await this.settlementToken();
+    return await vault.deposit(address, toWei(new Big(amount), decimals), {
+      gasLimit: this.gasLimit,
+    });
+  }
+
+  /**
+   * Withdraws free collateral from the vault back to the wallet.
+   * @param amount The amount of the settlement token.
+   */
+  async removeMargin(amount: string): Promise<Transaction> {
+    const vault = this._perp.contracts.vault.connect(this.wallet());
+    const { address, decimals } = await this.settlementToken();
+    return await vault.withdraw(address, toWei(new Big(amount), decimals), {
+      gasLimit: this.gasLimit,
+    });
+  }
+
   /**
    * Function for getting account value
    * @returns account value
@@ -290,4 +820,46 @@ export class Perp implements Perpish {
     const clearingHouse = this._perp.clearingHouse as ClearingHouse;
     return await clearingHouse.getAccountValue();
   }
+
+  private wallet(): Wallet {
+    if (!this._wallet) {
+      throw new Error('A wallet address is required for this request.');
+    }
+    return this._wallet;
+  }
+
+  private walletAddress(): string {
+    return this.wallet().address;
+  }
+
+  private deadline(): number {
+    return Math.floor(Date.now() / 1000) + PerpConfig.config.ttl;
+  }
+
+  // the id the order book keeps an order under
+  private orderId(
+    baseToken: string,
+    lowerTick: number,
+    upperTick: number
+  ): string {
+    return utils.solidityKeccak256(
+      ['address', 'address', 'int24', 'int24'],
+      [this.walletAddress(), baseToken, lowerTick, upperTick]
+    );
+  }
+
+  private async tickSpacing(poolAddress: string): Promise<number> {
+    const pool = new Contract(poolAddress, poolAbi, this.ethereum.provider);
+    return await pool.tickSpacing();
+  }
+
+  private async settlementToken(): Promise<{
+    address: string;
+    decimals: number;
+  }> {
+    const address: string =
+      await this._perp.contracts.vault.getSettlementToken();
+    const token = new Contract(address, erc20Abi, this.ethereum.provider);
+    return { address, decimals: await token.decimals() };
+  }
 }
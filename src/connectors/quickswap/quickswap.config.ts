@@ -12,13 +12,15 @@ export namespace QuickswapConfig {
   }
 
   export const config: NetworkConfig = {
-    allowedSlippage: ConfigManagerV2.getInstance().get(
-      'quickswap.allowedSlippage'
-    ),
-    gasLimitEstimate: ConfigManagerV2.getInstance().get(
-      'quickswap.gasLimitEstimate'
-    ),
-    ttl: ConfigManagerV2.getInstance().get('quickswap.ttl'),
+    get allowedSlippage() {
+      return ConfigManagerV2.getInstance().get('quickswap.allowedSlippage');
+    },
+    get gasLimitEstimate() {
+      return ConfigManagerV2.getInstance().get('quickswap.gasLimitEstimate');
+    },
+    get ttl() {
+      return ConfigManagerV2.getInstance().get('quickswap.ttl');
+    },
     routerAddress: (network: string) =>
       ConfigManagerV2.getInstance().get(
         'quickswap.contractAddresses.' + network + '.routerAddress'
@@ -9,7 +9,7 @@ import {
 } from 'ethers';
 import { isFractionString } from '../../services/validators';
 import { QuickswapConfig } from './quickswap.config';
-import routerAbi from '../traderjoe/IJoeRouter02.json';
+import routerAbi from '../uniswap/uniswap_v2_router_abi.json';
 import {
   Fetcher,
   Percent,
@@ -21,7 +21,12 @@ import {
 } from 'quickswap-sdk';
 import { logger } from '../../services/logger';
 import { Polygon } from '../../chains/polygon/polygon';
-import { ExpectedTrade, Uniswapish } from '../../services/common-interfaces';
+import {
+  ExpectedTrade,
+  NativeSwap,
+  Uniswapish,
+} from '../../services/common-interfaces';
+import { toNativeSwap } from '../uniswap/uniswap.native-swap';
 
 export class Quickswap implements Uniswapish {
   private static _instances: { [name: string]: Quickswap };
@@ -55,6 +60,10 @@ export class Quickswap implements Uniswapish {
     return Quickswap._instances[chain + network];
   }
 
+  public static getConnectedInstances(): { [name: string]: Quickswap } {
+    return Quickswap._instances;
+  }
+
   /**
    * Given a token's address, return the connector's native representation of
    * the token.
@@ -69,6 +78,7 @@ export class Quickswap implements Uniswapish {
     if (!this.polygon.ready()) {
       await this.polygon.init();
     }
+    this.tokenList = {};
     for (const token of this.polygon.storedTokenList) {
       this.tokenList[token.address] = new Token(
         this.chainId,
@@ -255,13 +265,18 @@ export class Quickswap implements Uniswapish {
     nonce?: number,
     maxFeePerGas?: BigNumber,
     maxPriorityFeePerGas?: BigNumber,
-    allowedSlippage?: string
+    allowedSlippage?: string,
+    nativeSwap?: NativeSwap
   ): Promise<Transaction> {
-    const result = Router.swapCallParameters(trade, {
-      ttl,
-      recipient: wallet.address,
-      allowedSlippage: this.getAllowedSlippage(allowedSlippage),
-    });
+    const result = toNativeSwap(
+      Router.swapCallParameters(trade, {
+        ttl,
+        recipient: wallet.address,
+        allowedSlippage: this.getAllowedSlippage(allowedSlippage),
+      }),
+      abi,
+      nativeSwap
+    );
 
     const contract = new Contract(quickswapRouter, abi, wallet);
     return this.polygon.nonceManager.provideNonce(
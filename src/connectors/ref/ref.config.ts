@@ -11,9 +11,15 @@ export namespace RefConfig {
   }
 
   export const config: NetworkConfig = {
-    allowedSlippage: ConfigManagerV2.getInstance().get(`ref.allowedSlippage`),
-    gasLimitEstimate: ConfigManagerV2.getInstance().get(`ref.gasLimitEstimate`),
-    ttl: ConfigManagerV2.getInstance().get(`ref.ttl`),
+    get allowedSlippage() {
+      return ConfigManagerV2.getInstance().get(`ref.allowedSlippage`);
+    },
+    get gasLimitEstimate() {
+      return ConfigManagerV2.getInstance().get(`ref.gasLimitEstimate`);
+    },
+    get ttl() {
+      return ConfigManagerV2.getInstance().get(`ref.ttl`);
+    },
     routerAddress: (network: string) =>
       ConfigManagerV2.getInstance().get(
         `ref.contractAddresses.${network}.routerAddress`
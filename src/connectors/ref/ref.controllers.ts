@@ -18,6 +18,7 @@ import { TokenInfo } from '../../chains/ethereum/ethereum-base';
 import { latency } from '../../services/base';
 import { Nearish, RefAMMish } from '../../services/common-interfaces';
 import { logger } from '../../services/logger';
+import { checkRisk } from '../../services/risk-manager';
 import {
   EstimateGasResponse,
   PriceRequest,
@@ -190,6 +191,15 @@ export async function trade(
       `limit price is ${limitPrice}.`
   );
 
+  await checkRisk({
+    wallet: account.accountId,
+    connector: req.connector,
+    tokens: [req.base, req.quote],
+    amounts: [req.amount, expectedAmount],
+    allowedSlippage: req.allowedSlippage,
+    journal: nearish.journal,
+  });
+
   if (req.side === 'BUY') {
     if (limitPrice && new Decimal(estimatedPrice).gt(new Decimal(limitPrice))) {
       logger.error('Swap price exceeded limit price.');
@@ -216,6 +226,21 @@ export async function trade(
       req.allowedSlippage
     );
 
+    await nearish.journal.record({
+      txHash: tx.transaction_outcome.id,
+      chain: nearish.chainName,
+      network: nearish.chain,
+      type: 'trade',
+      connector: req.connector,
+      wallet: account.accountId,
+      tokens: [req.base, req.quote],
+      amounts: [req.amount, expectedAmount],
+      side: req.side,
+      price: estimatedPrice,
+      gasPrice,
+      gasLimit: gasLimitTransaction,
+    });
+
     logger.info(`Buy Ref swap has been executed.`);
 
     return {
@@ -256,6 +281,21 @@ export async function trade(
       req.allowedSlippage
     );
 
+    await nearish.journal.record({
+      txHash: tx.transaction_outcome.id,
+      chain: nearish.chainName,
+      network: nearish.chain,
+      type: 'trade',
+      connector: req.connector,
+      wallet: account.accountId,
+      tokens: [req.base, req.quote],
+      amounts: [req.amount, expectedAmount],
+      side: req.side,
+      price: estimatedPrice,
+      gasPrice,
+      gasLimit: gasLimitTransaction,
+    });
+
     logger.info(`Sell Ref swap has been executed.`);
 
     return {
@@ -16,10 +16,11 @@ import { logger } from '../../services/logger';
 import { percentRegexp } from '../../services/config-manager-v2';
 import { RefAMMish } from '../../services/common-interfaces';
 import { Near } from '../../chains/near/near';
-import { Account } from 'near-api-js';
+import { Account, utils } from 'near-api-js';
 import { SignedTransaction } from 'near-api-js/lib/transaction';
 import { getSignedTransactions, sendTransactions } from './ref.helper';
 import { FinalExecutionOutcome } from 'near-api-js/lib/providers';
+import { STORAGE_DEPOSIT_GAS } from '../../chains/near/near.base';
 
 export type ExpectedTrade = {
   trade: EstimateSwapView[];
@@ -59,6 +60,10 @@ export class Ref implements RefAMMish {
     return Ref._instances[chain + network];
   }
 
+  public static getConnectedInstances(): { [name: string]: Ref } {
+    return Ref._instances;
+  }
+
   /**
    * Given a token's address, return the connector's native representation of
    * the token.
@@ -73,6 +78,7 @@ export class Ref implements RefAMMish {
     if (!this.near.ready()) {
       await this.near.init();
     }
+    this.tokenList = {};
     for (const token of this.near.storedTokenList) {
       this.tokenList[token.address] = {
         id: token.address,
@@ -301,6 +307,9 @@ export class Ref implements RefAMMish {
       swapTodos: trade,
       AccountId: account.accountId,
     });
+    // the swap is sent after the registration, if the account needs one
+    const registration = await this.registration(account, tokenOut);
+    transactionsRef.unshift(...registration);
 
     const signedTransactions: SignedTransaction[] = await getSignedTransactions(
       { transactionsRef, account }
@@ -311,6 +320,36 @@ export class Ref implements RefAMMish {
     });
 
     logger.info(JSON.stringify(transaction));
-    return transaction[0];
+    return transaction[registration.length];
+  }
+
+  /**
+   * The transaction that registers an account on a token contract before it
+   * receives the token, none if it is already registered. Tokens sent to an
+   * unregistered account are refunded or lost.
+   *
+   * @param account Account
+   * @param token Token to be received
+   */
+  async registration(
+    account: Account,
+    token: TokenMetadata
+  ): Promise<Transaction[]> {
+    const contract = this.near.getContract(token.id, account);
+    const deposits = await this.near.getStorageDeposits(contract, [
+      account.accountId,
+    ]);
+    if (deposits.length === 0) return [];
+    return [
+      {
+        receiverId: token.id,
+        functionCalls: deposits.map((deposit) => ({
+          methodName: 'storage_deposit',
+          args: { account_id: deposit.accountId, registration_only: true },
+          gas: STORAGE_DEPOSIT_GAS,
+          amount: utils.format.formatNearAmount(deposit.amount),
+        })),
+      },
+    ];
   }
 }
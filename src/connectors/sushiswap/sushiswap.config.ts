@@ -12,13 +12,15 @@ export namespace SushiswapConfig {
   }
 
   export const config: NetworkConfig = {
-    allowedSlippage: ConfigManagerV2.getInstance().get(
-      'sushiswap.allowedSlippage'
-    ),
-    gasLimitEstimate: ConfigManagerV2.getInstance().get(
-      'sushiswap.gasLimitEstimate'
-    ),
-    ttl: ConfigManagerV2.getInstance().get('sushiswap.ttl'),
+    get allowedSlippage() {
+      return ConfigManagerV2.getInstance().get('sushiswap.allowedSlippage');
+    },
+    get gasLimitEstimate() {
+      return ConfigManagerV2.getInstance().get('sushiswap.gasLimitEstimate');
+    },
+    get ttl() {
+      return ConfigManagerV2.getInstance().get('sushiswap.ttl');
+    },
     sushiswapRouterAddress: (chain: string, network: string) =>
       ConfigManagerV2.getInstance().get(
         'sushiswap.contractAddresses.' +
@@ -15,7 +15,12 @@ import {
   TradeType,
 } from '@sushiswap/sdk';
 import IUniswapV2Pair from '@uniswap/v2-core/build/IUniswapV2Pair.json';
-import { ExpectedTrade, Uniswapish } from '../../services/common-interfaces';
+import {
+  ExpectedTrade,
+  NativeSwap,
+  Uniswapish,
+} from '../../services/common-interfaces';
+import { toNativeSwap } from '../uniswap/uniswap.native-swap';
 import { Ethereum } from '../../chains/ethereum/ethereum';
 import { BinanceSmartChain } from '../../chains/binance-smart-chain/binance-smart-chain';
 import { Polygon } from '../../chains/polygon/polygon';
@@ -69,6 +74,10 @@ export class Sushiswap implements Uniswapish {
     return Sushiswap._instances[chain + network];
   }
 
+  public static getConnectedInstances(): { [name: string]: Sushiswap } {
+    return Sushiswap._instances;
+  }
+
   /**
    * Given a token's address, return the connector's native representation of
    * the token.
@@ -83,6 +92,7 @@ export class Sushiswap implements Uniswapish {
     if (!this.chain.ready()) {
       await this.chain.init();
     }
+    this.tokenList = {};
     for (const token of this.chain.storedTokenList) {
       this.tokenList[token.address] = new Token(
         this.chainId,
@@ -265,13 +275,19 @@ export class Sushiswap implements Uniswapish {
     gasLimit: number,
     nonce?: number,
     maxFeePerGas?: BigNumber,
-    maxPriorityFeePerGas?: BigNumber
+    maxPriorityFeePerGas?: BigNumber,
+    _allowedSlippage?: string,
+    nativeSwap?: NativeSwap
   ): Promise<Transaction> {
-    const result: SwapParameters = Router.swapCallParameters(trade, {
-      ttl,
-      recipient: wallet.address,
-      allowedSlippage: this.getSlippagePercentage(),
-    });
+    const result: SwapParameters = toNativeSwap(
+      Router.swapCallParameters(trade, {
+        ttl,
+        recipient: wallet.address,
+        allowedSlippage: this.getSlippagePercentage(),
+      }),
+      abi,
+      nativeSwap
+    );
     const contract: Contract = new Contract(sushswapRouter, abi, wallet);
     return this.chain.nonceManager.provideNonce(
       nonce,
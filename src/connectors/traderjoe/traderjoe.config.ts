@@ -12,13 +12,15 @@ export namespace TraderjoeConfig {
   }
 
   export const config: NetworkConfig = {
-    allowedSlippage: ConfigManagerV2.getInstance().get(
-      'traderjoe.allowedSlippage'
-    ),
-    gasLimitEstimate: ConfigManagerV2.getInstance().get(
-      'traderjoe.gasLimitEstimate'
-    ),
-    ttl: ConfigManagerV2.getInstance().get('traderjoe.ttl'),
+    get allowedSlippage() {
+      return ConfigManagerV2.getInstance().get('traderjoe.allowedSlippage');
+    },
+    get gasLimitEstimate() {
+      return ConfigManagerV2.getInstance().get('traderjoe.gasLimitEstimate');
+    },
+    get ttl() {
+      return ConfigManagerV2.getInstance().get('traderjoe.ttl');
+    },
     routerAddress: (network: string) =>
       ConfigManagerV2.getInstance().get(
         'traderjoe.contractAddresses.' + network + '.routerAddress'
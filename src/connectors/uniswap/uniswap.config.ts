@@ -8,19 +8,24 @@ export namespace UniswapConfig {
     maximumHops: number;
     uniswapV3SmartOrderRouterAddress: (network: string) => string;
     uniswapV3NftManagerAddress: (network: string) => string;
+    uniswapV3QuoterV2Address: (network: string) => string | undefined;
     tradingTypes: (type: string) => Array<string>;
     availableNetworks: Array<AvailableNetworks>;
   }
 
   export const config: NetworkConfig = {
-    allowedSlippage: ConfigManagerV2.getInstance().get(
-      `uniswap.allowedSlippage`
-    ),
-    gasLimitEstimate: ConfigManagerV2.getInstance().get(
-      `uniswap.gasLimitEstimate`
-    ),
-    ttl: ConfigManagerV2.getInstance().get(`uniswap.ttl`),
-    maximumHops: ConfigManagerV2.getInstance().get(`uniswap.maximumHops`),
+    get allowedSlippage() {
+      return ConfigManagerV2.getInstance().get(`uniswap.allowedSlippage`);
+    },
+    get gasLimitEstimate() {
+      return ConfigManagerV2.getInstance().get(`uniswap.gasLimitEstimate`);
+    },
+    get ttl() {
+      return ConfigManagerV2.getInstance().get(`uniswap.ttl`);
+    },
+    get maximumHops() {
+      return ConfigManagerV2.getInstance().get(`uniswap.maximumHops`);
+    },
     uniswapV3SmartOrderRouterAddress: (network: string) =>
       ConfigManagerV2.getInstance().get(
         `uniswap.contractAddresses.${network}.uniswapV3SmartOrderRouterAddress`
@@ -29,6 +34,10 @@ export namespace UniswapConfig {
       ConfigManagerV2.getInstance().get(
         `uniswap.contractAddresses.${network}.uniswapV3NftManagerAddress`
       ),
+    uniswapV3QuoterV2Address: (network: string) =>
+      ConfigManagerV2.getInstance().get(
+        `uniswap.contractAddresses.${network}.uniswapV3QuoterV2Address`
+      ),
     tradingTypes: (type: string) => {
       return type === 'swap' ? ['EVM_AMM'] : ['EVM_AMM_LP'];
     },
@@ -26,10 +26,17 @@ import {
   ExpectedTrade,
   Uniswapish,
   UniswapLPish,
+  UniswapV2LPish,
   Tokenish,
   Fractionish,
+  NativeSwap,
+  TradeRouteOptions,
 } from '../../services/common-interfaces';
 import { logger } from '../../services/logger';
+import { routeViolation, tradeRoute } from './uniswap.route';
+import { routerNativeName } from './uniswap.native-swap';
+import { simulateTransaction } from '../../evm/evm.simulation';
+import { checkRisk } from '../../services/risk-manager';
 import {
   EstimateGasResponse,
   PriceRequest,
@@ -40,11 +47,25 @@ import {
   AddLiquidityResponse,
   RemoveLiquidityRequest,
   RemoveLiquidityResponse,
+  RebalanceRequest,
+  RebalanceResponse,
   CollectEarnedFeesRequest,
   PositionRequest,
   PositionResponse,
+  PositionsRequest,
+  PositionsResponse,
   PoolPriceRequest,
   PoolPriceResponse,
+  PoolInfoRequest,
+  PoolInfoResponse,
+  PoolObservationsRequest,
+  PoolObservationsResponse,
+  V2AddLiquidityRequest,
+  V2AddLiquidityResponse,
+  V2RemoveLiquidityRequest,
+  V2RemoveLiquidityResponse,
+  V2PositionRequest,
+  V2PositionResponse,
 } from '../../amm/amm.requests';
 
 export interface TradeInfo {
@@ -72,6 +93,14 @@ export async function txWriteData(
   if (maxPriorityFeePerGas) {
     maxPriorityFeePerGasBigNumber = BigNumber.from(maxPriorityFeePerGas);
   }
+  // use the network's fee strategy unless the client set the fees
+  if (!maxFeePerGasBigNumber && !maxPriorityFeePerGasBigNumber) {
+    const fees = await ethereumish.getEIP1559Fees();
+    if (fees) {
+      maxFeePerGasBigNumber = fees.maxFeePerGas;
+      maxPriorityFeePerGasBigNumber = fees.maxPriorityFeePerGas;
+    }
+  }
 
   let wallet: Wallet;
   try {
@@ -94,7 +123,8 @@ export async function getTradeInfo(
   quoteAsset: string,
   baseAmount: Decimal,
   tradeSide: string,
-  allowedSlippage?: string
+  allowedSlippage?: string,
+  routeOptions?: TradeRouteOptions
 ): Promise<TradeInfo> {
   const baseToken: Tokenish = getFullTokenFromSymbol(
     ethereumish,
@@ -116,14 +146,16 @@ export async function getTradeInfo(
       quoteToken,
       baseToken,
       requestAmount,
-      allowedSlippage
+      allowedSlippage,
+      routeOptions
     );
   } else {
     expectedTrade = await uniswapish.estimateSellTrade(
       baseToken,
       quoteToken,
       requestAmount,
-      allowedSlippage
+      allowedSlippage,
+      routeOptions
     );
   }
 
@@ -197,9 +229,10 @@ export async function price(
 export async function trade(
   ethereumish: Ethereumish,
   uniswapish: Uniswapish,
-  req: TradeRequest
+  request: TradeRequest
 ): Promise<TradeResponse> {
   const startTimestamp: number = Date.now();
+  const { req, nativeSwap } = nativeSwapFor(ethereumish, uniswapish, request);
 
   const limitPrice = req.limitPrice;
   const { wallet, maxFeePerGasBigNumber, maxPriorityFeePerGasBigNumber } =
@@ -209,6 +242,7 @@ export async function trade(
       req.maxFeePerGas,
       req.maxPriorityFeePerGas
     );
+  const routeOptions = getRouteOptions(ethereumish, req);
 
   let tradeInfo: TradeInfo;
   try {
@@ -218,7 +252,9 @@ export async function trade(
       req.base,
       req.quote,
       new Decimal(req.amount),
-      req.side
+      req.side,
+      req.allowedSlippage,
+      routeOptions
     );
   } catch (e) {
     if (e instanceof Error) {
@@ -238,6 +274,32 @@ export async function trade(
     }
   }
 
+  // connectors that can't route as asked must not trade another way
+  const route = tradeRoute(tradeInfo.expectedTrade.trade);
+  if (routeOptions) {
+    const violation = routeViolation(route, routeOptions);
+    if (violation) {
+      logger.error(`Trade route rejected, ${violation}.`);
+      throw new HttpException(
+        500,
+        TRADE_FAILED_ERROR_MESSAGE + violation,
+        TRADE_FAILED_ERROR_CODE
+      );
+    }
+  }
+
+  await checkRisk({
+    wallet: wallet.address,
+    connector: req.connector,
+    tokens: [req.base, req.quote],
+    amounts: [
+      req.amount,
+      tradeInfo.expectedTrade.expectedAmount.toSignificant(8),
+    ],
+    allowedSlippage: req.allowedSlippage,
+    journal: ethereumish.journal,
+  });
+
   const gasPrice: number = ethereumish.gasPrice;
   const gasLimitTransaction: number = ethereumish.gasLimitTransaction;
   const gasLimitEstimate: number = uniswapish.gasLimitEstimate;
@@ -260,6 +322,53 @@ export async function trade(
       );
     }
 
+    if (req.simulate) {
+      const simulation = await simulateTransaction(
+        ethereumish.provider,
+        wallet,
+        (signer: Wallet, signerNonce: number) =>
+          uniswapish.executeTrade(
+            signer,
+            tradeInfo.expectedTrade.trade,
+            gasPrice,
+            uniswapish.router,
+            uniswapish.ttl,
+            uniswapish.routerAbi,
+            gasLimitTransaction,
+            signerNonce,
+            maxFeePerGasBigNumber,
+            maxPriorityFeePerGasBigNumber,
+            req.allowedSlippage,
+            nativeSwap
+          ),
+        req.nonce,
+        req.simulationBlock,
+        true
+      );
+      logger.info(
+        `Trade simulated against block ${simulation.blockNumber}, success is ${simulation.success}.`
+      );
+      return {
+        network: ethereumish.chain,
+        timestamp: startTimestamp,
+        latency: latency(startTimestamp, Date.now()),
+        base: tradeInfo.baseToken.address,
+        quote: tradeInfo.quoteToken.address,
+        amount: new Decimal(req.amount).toFixed(tradeInfo.baseToken.decimals),
+        rawAmount: tradeInfo.requestAmount.toString(),
+        expectedIn: tradeInfo.expectedTrade.expectedAmount.toSignificant(8),
+        price: price.toSignificant(8),
+        gasPrice: gasPrice,
+        gasPriceToken: ethereumish.nativeTokenSymbol,
+        gasLimit: gasLimitTransaction,
+        gasCost: gasCostInEthString(gasPrice, gasLimitEstimate),
+        nonce: simulation.nonce,
+        txHash: undefined,
+        simulation,
+        route,
+      };
+    }
+
     const tx = await uniswapish.executeTrade(
       wallet,
       tradeInfo.expectedTrade.trade,
@@ -271,7 +380,8 @@ export async function trade(
       req.nonce,
       maxFeePerGasBigNumber,
       maxPriorityFeePerGasBigNumber,
-      req.allowedSlippage
+      req.allowedSlippage,
+      nativeSwap
     );
 
     if (tx.hash) {
@@ -284,6 +394,27 @@ export async function trade(
       );
     }
 
+    if (tx.hash) {
+      await ethereumish.journal.record({
+        txHash: tx.hash,
+        chain: ethereumish.chainName,
+        network: ethereumish.chain,
+        type: 'trade',
+        connector: req.connector,
+        wallet: wallet.address,
+        nonce: tx.nonce,
+        tokens: [req.base, req.quote],
+        amounts: [
+          req.amount,
+          tradeInfo.expectedTrade.expectedAmount.toSignificant(8),
+        ],
+        side: req.side,
+        price: price.toSignificant(8),
+        gasPrice,
+        gasLimit: gasLimitTransaction,
+      });
+    }
+
     logger.info(
       `Trade has been executed, txHash is ${tx.hash}, nonce is ${tx.nonce}, gasPrice is ${gasPrice}.`
     );
@@ -304,6 +435,9 @@ export async function trade(
       gasCost: gasCostInEthString(gasPrice, gasLimitEstimate),
       nonce: tx.nonce,
       txHash: tx.hash,
+      maxFeePerGas: tx.maxFeePerGas?.toString(),
+      maxPriorityFeePerGas: tx.maxPriorityFeePerGas?.toString(),
+      route,
     };
   } else {
     const price: Fractionish = tradeInfo.expectedTrade.trade.executionPrice;
@@ -326,6 +460,53 @@ export async function trade(
       );
     }
 
+    if (req.simulate) {
+      const simulation = await simulateTransaction(
+        ethereumish.provider,
+        wallet,
+        (signer: Wallet, signerNonce: number) =>
+          uniswapish.executeTrade(
+            signer,
+            tradeInfo.expectedTrade.trade,
+            gasPrice,
+            uniswapish.router,
+            uniswapish.ttl,
+            uniswapish.routerAbi,
+            gasLimitTransaction,
+            signerNonce,
+            maxFeePerGasBigNumber,
+            maxPriorityFeePerGasBigNumber,
+            req.allowedSlippage,
+            nativeSwap
+          ),
+        req.nonce,
+        req.simulationBlock,
+        true
+      );
+      logger.info(
+        `Trade simulated against block ${simulation.blockNumber}, success is ${simulation.success}.`
+      );
+      return {
+        network: ethereumish.chain,
+        timestamp: startTimestamp,
+        latency: latency(startTimestamp, Date.now()),
+        base: tradeInfo.baseToken.address,
+        quote: tradeInfo.quoteToken.address,
+        amount: new Decimal(req.amount).toFixed(tradeInfo.baseToken.decimals),
+        rawAmount: tradeInfo.requestAmount.toString(),
+        expectedOut: tradeInfo.expectedTrade.expectedAmount.toSignificant(8),
+        price: price.toSignificant(8),
+        gasPrice: gasPrice,
+        gasPriceToken: ethereumish.nativeTokenSymbol,
+        gasLimit: gasLimitTransaction,
+        gasCost: gasCostInEthString(gasPrice, gasLimitEstimate),
+        nonce: simulation.nonce,
+        txHash: undefined,
+        simulation,
+        route,
+      };
+    }
+
     const tx = await uniswapish.executeTrade(
       wallet,
       tradeInfo.expectedTrade.trade,
@@ -336,9 +517,32 @@ export async function trade(
       gasLimitTransaction,
       req.nonce,
       maxFeePerGasBigNumber,
-      maxPriorityFeePerGasBigNumber
+      maxPriorityFeePerGasBigNumber,
+      req.allowedSlippage,
+      nativeSwap
     );
 
+    if (tx.hash) {
+      await ethereumish.journal.record({
+        txHash: tx.hash,
+        chain: ethereumish.chainName,
+        network: ethereumish.chain,
+        type: 'trade',
+        connector: req.connector,
+        wallet: wallet.address,
+        nonce: tx.nonce,
+        tokens: [req.base, req.quote],
+        amounts: [
+          req.amount,
+          tradeInfo.expectedTrade.expectedAmount.toSignificant(8),
+        ],
+        side: req.side,
+        price: price.toSignificant(8),
+        gasPrice,
+        gasLimit: gasLimitTransaction,
+      });
+    }
+
     logger.info(
       `Trade has been executed, txHash is ${tx.hash}, nonce is ${tx.nonce}, gasPrice is ${gasPrice}.`
     );
@@ -359,6 +563,9 @@ export async function trade(
       gasCost: gasCostInEthString(gasPrice, gasLimitEstimate),
       nonce: tx.nonce,
       txHash: tx.hash,
+      maxFeePerGas: tx.maxFeePerGas?.toString(),
+      maxPriorityFeePerGas: tx.maxPriorityFeePerGas?.toString(),
+      route,
     };
   }
 }
@@ -392,10 +599,62 @@ export async function addLiquidity(
     req.token1
   ) as Token;
 
+  await checkRisk({
+    wallet: wallet.address,
+    connector: req.connector,
+    tokens: [req.token0, req.token1],
+    amounts: [req.amount0, req.amount1],
+  });
+
   const gasPrice: number = ethereumish.gasPrice;
   const gasLimitTransaction: number = ethereumish.gasLimitTransaction;
   const gasLimitEstimate: number = uniswapish.gasLimitEstimate;
 
+  if (req.simulate) {
+    const simulation = await simulateTransaction(
+      ethereumish.provider,
+      wallet,
+      (signer: Wallet, signerNonce: number) =>
+        uniswapish.addPosition(
+          signer,
+          token0,
+          token1,
+          req.amount0,
+          req.amount1,
+          fee,
+          Number(req.lowerPrice),
+          Number(req.upperPrice),
+          req.tokenId ? req.tokenId : 0,
+          gasLimitTransaction,
+          gasPrice,
+          signerNonce,
+          maxFeePerGasBigNumber,
+          maxPriorityFeePerGasBigNumber
+        ),
+      req.nonce,
+      req.simulationBlock
+    );
+    logger.info(
+      `Adding liquidity simulated against block ${simulation.blockNumber}, success is ${simulation.success}.`
+    );
+    return {
+      network: ethereumish.chain,
+      timestamp: startTimestamp,
+      latency: latency(startTimestamp, Date.now()),
+      token0: token0.address,
+      token1: token1.address,
+      fee: req.fee,
+      tokenId: req.tokenId ? req.tokenId : 0,
+      gasPrice: gasPrice,
+      gasPriceToken: ethereumish.nativeTokenSymbol,
+      gasLimit: gasLimitTransaction,
+      gasCost: gasCostInEthString(gasPrice, gasLimitEstimate),
+      nonce: simulation.nonce,
+      txHash: undefined,
+      simulation,
+    };
+  }
+
   const tx = await uniswapish.addPosition(
     wallet,
     token0,
@@ -413,6 +672,22 @@ export async function addLiquidity(
     maxPriorityFeePerGasBigNumber
   );
 
+  if (tx.hash) {
+    await ethereumish.journal.record({
+      txHash: tx.hash,
+      chain: ethereumish.chainName,
+      network: ethereumish.chain,
+      type: 'addLiquidity',
+      connector: req.connector,
+      wallet: wallet.address,
+      nonce: tx.nonce,
+      tokens: [req.token0, req.token1],
+      amounts: [req.amount0, req.amount1],
+      gasPrice,
+      gasLimit: gasLimitTransaction,
+    });
+  }
+
   logger.info(
     `Liquidity added, txHash is ${tx.hash}, nonce is ${tx.nonce}, gasPrice is ${gasPrice}.`
   );
@@ -431,6 +706,8 @@ export async function addLiquidity(
     gasCost: gasCostInEthString(gasPrice, gasLimitEstimate),
     nonce: tx.nonce,
     txHash: tx.hash,
+    maxFeePerGas: tx.maxFeePerGas?.toString(),
+    maxPriorityFeePerGas: tx.maxPriorityFeePerGas?.toString(),
   };
 }
 
@@ -453,6 +730,42 @@ export async function removeLiquidity(
   const gasLimitTransaction: number = ethereumish.gasLimitTransaction;
   const gasLimitEstimate: number = uniswapish.gasLimitEstimate;
 
+  if (req.simulate) {
+    const simulation = await simulateTransaction(
+      ethereumish.provider,
+      wallet,
+      (signer: Wallet, signerNonce: number) =>
+        uniswapish.reducePosition(
+          signer,
+          req.tokenId,
+          req.decreasePercent ? req.decreasePercent : 100,
+          gasLimitTransaction,
+          gasPrice,
+          signerNonce,
+          maxFeePerGasBigNumber,
+          maxPriorityFeePerGasBigNumber
+        ),
+      req.nonce,
+      req.simulationBlock
+    );
+    logger.info(
+      `Removing liquidity simulated against block ${simulation.blockNumber}, success is ${simulation.success}.`
+    );
+    return {
+      network: ethereumish.chain,
+      timestamp: startTimestamp,
+      latency: latency(startTimestamp, Date.now()),
+      tokenId: req.tokenId,
+      gasPrice: gasPrice,
+      gasPriceToken: ethereumish.nativeTokenSymbol,
+      gasLimit: gasLimitTransaction,
+      gasCost: gasCostInEthString(gasPrice, gasLimitEstimate),
+      nonce: simulation.nonce,
+      txHash: undefined,
+      simulation,
+    };
+  }
+
   const tx = await uniswapish.reducePosition(
     wallet,
     req.tokenId,
@@ -464,6 +777,22 @@ export async function removeLiquidity(
     maxPriorityFeePerGasBigNumber
   );
 
+  if (tx.hash) {
+    await ethereumish.journal.record({
+      txHash: tx.hash,
+      chain: ethereumish.chainName,
+      network: ethereumish.chain,
+      type: 'removeLiquidity',
+      connector: req.connector,
+      wallet: wallet.address,
+      nonce: tx.nonce,
+      tokens: [],
+      amounts: [],
+      gasPrice,
+      gasLimit: gasLimitTransaction,
+    });
+  }
+
   logger.info(
     `Liquidity removed, txHash is ${tx.hash}, nonce is ${tx.nonce}, gasPrice is ${gasPrice}.`
   );
@@ -479,6 +808,135 @@ export async function removeLiquidity(
     gasCost: gasCostInEthString(gasPrice, gasLimitEstimate),
     nonce: tx.nonce,
     txHash: tx.hash,
+    maxFeePerGas: tx.maxFeePerGas?.toString(),
+    maxPriorityFeePerGas: tx.maxPriorityFeePerGas?.toString(),
+  };
+}
+
+export async function rebalanceLiquidity(
+  ethereumish: Ethereumish,
+  uniswapish: UniswapLPish,
+  req: RebalanceRequest
+): Promise<RebalanceResponse> {
+  const startTimestamp: number = Date.now();
+
+  const { wallet, maxFeePerGasBigNumber, maxPriorityFeePerGasBigNumber } =
+    await txWriteData(
+      ethereumish,
+      req.address,
+      req.maxFeePerGas,
+      req.maxPriorityFeePerGas
+    );
+
+  // what comes out of the position goes back into the new one
+  const position = await uniswapish.getPosition(req.tokenId);
+  const token0 = position.token0 as string;
+  const token1 = position.token1 as string;
+  await checkRisk({
+    wallet: wallet.address,
+    connector: req.connector,
+    tokens: [token0, token1],
+    amounts: [
+      new Decimal(position.amount0).add(position.unclaimedToken0).toString(),
+      new Decimal(position.amount1).add(position.unclaimedToken1).toString(),
+    ],
+  });
+
+  const gasPrice: number = ethereumish.gasPrice;
+  const gasLimitTransaction: number = ethereumish.gasLimitTransaction;
+  const gasLimitEstimate: number = uniswapish.gasLimitEstimate;
+
+  const response = {
+    network: ethereumish.chain,
+    tokenId: req.tokenId,
+    token0,
+    token1,
+    gasPrice: gasPrice,
+    gasPriceToken: ethereumish.nativeTokenSymbol,
+    gasLimit: gasLimitTransaction,
+    gasCost: gasCostInEthString(gasPrice, gasLimitEstimate),
+  };
+
+  if (req.simulate) {
+    const simulation = await simulateTransaction(
+      ethereumish.provider,
+      wallet,
+      (signer: Wallet, signerNonce: number) =>
+        uniswapish.rebalancePosition(
+          signer,
+          req.tokenId,
+          Number(req.lowerPrice),
+          Number(req.upperPrice),
+          gasLimitTransaction,
+          gasPrice,
+          signerNonce,
+          maxFeePerGasBigNumber,
+          maxPriorityFeePerGasBigNumber
+        ),
+      req.nonce,
+      req.simulationBlock
+    );
+    logger.info(
+      `Rebalancing liquidity simulated against block ${simulation.blockNumber}, success is ${simulation.success}.`
+    );
+    return {
+      ...response,
+      timestamp: startTimestamp,
+      latency: latency(startTimestamp, Date.now()),
+      amount0: '0',
+      amount1: '0',
+      swapRequired: false,
+      nonce: simulation.nonce,
+      txHash: undefined,
+      simulation,
+    };
+  }
+
+  const rebalanced = await uniswapish.rebalancePosition(
+    wallet,
+    req.tokenId,
+    Number(req.lowerPrice),
+    Number(req.upperPrice),
+    gasLimitTransaction,
+    gasPrice,
+    req.nonce,
+    maxFeePerGasBigNumber,
+    maxPriorityFeePerGasBigNumber
+  );
+  const tx = rebalanced.transaction;
+
+  if (tx.hash) {
+    await ethereumish.journal.record({
+      txHash: tx.hash,
+      chain: ethereumish.chainName,
+      network: ethereumish.chain,
+      type: 'rebalanceLiquidity',
+      connector: req.connector,
+      wallet: wallet.address,
+      nonce: tx.nonce,
+      tokens: [token0, token1],
+      amounts: [rebalanced.amount0, rebalanced.amount1],
+      gasPrice,
+      gasLimit: gasLimitTransaction,
+    });
+  }
+
+  logger.info(
+    `Liquidity rebalanced into position ${rebalanced.tokenId}, txHash is ${tx.hash}, nonce is ${tx.nonce}, gasPrice is ${gasPrice}.`
+  );
+
+  return {
+    ...response,
+    timestamp: startTimestamp,
+    latency: latency(startTimestamp, Date.now()),
+    newTokenId: rebalanced.tokenId,
+    amount0: rebalanced.amount0,
+    amount1: rebalanced.amount1,
+    swapRequired: rebalanced.swapRequired,
+    nonce: tx.nonce,
+    txHash: tx.hash,
+    maxFeePerGas: tx.maxFeePerGas?.toString(),
+    maxPriorityFeePerGas: tx.maxPriorityFeePerGas?.toString(),
   };
 }
 
@@ -501,6 +959,41 @@ export async function collectEarnedFees(
   const gasLimitTransaction: number = ethereumish.gasLimitTransaction;
   const gasLimitEstimate: number = uniswapish.gasLimitEstimate;
 
+  if (req.simulate) {
+    const simulation = await simulateTransaction(
+      ethereumish.provider,
+      wallet,
+      (signer: Wallet, signerNonce: number) =>
+        uniswapish.collectFees(
+          signer,
+          req.tokenId,
+          gasLimitTransaction,
+          gasPrice,
+          signerNonce,
+          maxFeePerGasBigNumber,
+          maxPriorityFeePerGasBigNumber
+        ),
+      req.nonce,
+      req.simulationBlock
+    );
+    logger.info(
+      `Collecting fees simulated against block ${simulation.blockNumber}, success is ${simulation.success}.`
+    );
+    return {
+      network: ethereumish.chain,
+      timestamp: startTimestamp,
+      latency: latency(startTimestamp, Date.now()),
+      tokenId: req.tokenId,
+      gasPrice: gasPrice,
+      gasPriceToken: ethereumish.nativeTokenSymbol,
+      gasLimit: gasLimitTransaction,
+      gasCost: gasCostInEthString(gasPrice, gasLimitEstimate),
+      nonce: simulation.nonce,
+      txHash: undefined,
+      simulation,
+    };
+  }
+
   const tx: Transaction = <Transaction>(
     await uniswapish.collectFees(
       wallet,
@@ -513,6 +1006,22 @@ export async function collectEarnedFees(
     )
   );
 
+  if (tx.hash) {
+    await ethereumish.journal.record({
+      txHash: tx.hash,
+      chain: ethereumish.chainName,
+      network: ethereumish.chain,
+      type: 'collectFees',
+      connector: req.connector,
+      wallet: wallet.address,
+      nonce: tx.nonce,
+      tokens: [],
+      amounts: [],
+      gasPrice,
+      gasLimit: gasLimitTransaction,
+    });
+  }
+
   logger.info(
     `Fees collected, txHash is ${tx.hash}, nonce is ${tx.nonce}, gasPrice is ${gasPrice}.`
   );
@@ -528,6 +1037,8 @@ export async function collectEarnedFees(
     gasCost: gasCostInEthString(gasPrice, gasLimitEstimate),
     nonce: tx.nonce,
     txHash: tx.hash,
+    maxFeePerGas: tx.maxFeePerGas?.toString(),
+    maxPriorityFeePerGas: tx.maxPriorityFeePerGas?.toString(),
   };
 }
 
@@ -550,6 +1061,26 @@ export async function positionInfo(
   };
 }
 
+export async function positionsOfWallet(
+  ethereumish: Ethereumish,
+  uniswapish: UniswapLPish,
+  req: PositionsRequest
+): Promise<PositionsResponse> {
+  const startTimestamp: number = Date.now();
+
+  const positions = await uniswapish.getPositions(req.address);
+
+  logger.info(`${positions.length} positions of ${req.address} retrieved.`);
+
+  return {
+    network: ethereumish.chain,
+    timestamp: startTimestamp,
+    latency: latency(startTimestamp, Date.now()),
+    address: req.address,
+    positions,
+  };
+}
+
 export async function poolPrice(
   ethereumish: Ethereumish,
   uniswapish: UniswapLPish,
@@ -592,6 +1123,529 @@ export async function poolPrice(
   };
 }
 
+export async function poolInfo(
+  ethereumish: Ethereumish,
+  uniswapish: UniswapLPish,
+  req: PoolInfoRequest
+): Promise<PoolInfoResponse> {
+  const startTimestamp: number = Date.now();
+
+  const token0: Token = getFullTokenFromSymbol(
+    ethereumish,
+    uniswapish,
+    req.token0
+  ) as Token;
+
+  const token1: Token = getFullTokenFromSymbol(
+    ethereumish,
+    uniswapish,
+    req.token1
+  ) as Token;
+
+  const fee = FeeAmount[req.fee.toUpperCase() as keyof typeof FeeAmount];
+
+  const info = await uniswapish.getPoolInfo(
+    token0,
+    token1,
+    fee,
+    req.rangePercent ?? 10,
+    req.depthPercents ?? [1, 2, 5],
+    req.twapWindow ?? 0
+  );
+
+  return {
+    network: ethereumish.chain,
+    timestamp: startTimestamp,
+    latency: latency(startTimestamp, Date.now()),
+    ...info,
+  };
+}
+
+export async function increaseObservations(
+  ethereumish: Ethereumish,
+  uniswapish: UniswapLPish,
+  req: PoolObservationsRequest
+): Promise<PoolObservationsResponse> {
+  const startTimestamp: number = Date.now();
+
+  const { wallet, maxFeePerGasBigNumber, maxPriorityFeePerGasBigNumber } =
+    await txWriteData(
+      ethereumish,
+      req.address,
+      req.maxFeePerGas,
+      req.maxPriorityFeePerGas
+    );
+
+  const token0: Token = getFullTokenFromSymbol(
+    ethereumish,
+    uniswapish,
+    req.token0
+  ) as Token;
+
+  const token1: Token = getFullTokenFromSymbol(
+    ethereumish,
+    uniswapish,
+    req.token1
+  ) as Token;
+
+  const fee = FeeAmount[req.fee.toUpperCase() as keyof typeof FeeAmount];
+
+  const gasPrice: number = ethereumish.gasPrice;
+  const gasLimitTransaction: number = ethereumish.gasLimitTransaction;
+  const gasLimitEstimate: number = uniswapish.gasLimitEstimate;
+
+  const tx = await uniswapish.increaseObservationCardinality(
+    wallet,
+    token0,
+    token1,
+    fee,
+    req.cardinality,
+    gasLimitTransaction,
+    gasPrice,
+    req.nonce,
+    maxFeePerGasBigNumber,
+    maxPriorityFeePerGasBigNumber
+  );
+
+  if (tx.hash) {
+    await ethereumish.journal.record({
+      txHash: tx.hash,
+      chain: ethereumish.chainName,
+      network: ethereumish.chain,
+      type: 'increaseObservations',
+      connector: req.connector,
+      wallet: wallet.address,
+      nonce: tx.nonce,
+      tokens: [req.token0, req.token1],
+      amounts: [],
+      gasPrice,
+      gasLimit: gasLimitTransaction,
+    });
+  }
+
+  logger.info(
+    `Pool observations increased to ${req.cardinality}, txHash is ${tx.hash}, nonce is ${tx.nonce}, gasPrice is ${gasPrice}.`
+  );
+
+  return {
+    network: ethereumish.chain,
+    timestamp: startTimestamp,
+    latency: latency(startTimestamp, Date.now()),
+    pool: tx.to, // the transaction goes to the pool
+    cardinality: req.cardinality,
+    gasPrice: gasPrice,
+    gasPriceToken: ethereumish.nativeTokenSymbol,
+    gasLimit: gasLimitTransaction,
+    gasCost: gasCostInEthString(gasPrice, gasLimitEstimate),
+    nonce: tx.nonce,
+    txHash: tx.hash,
+    maxFeePerGas: tx.maxFeePerGas?.toString(),
+    maxPriorityFeePerGas: tx.maxPriorityFeePerGas?.toString(),
+  };
+}
+
+export async function v2AddLiquidity(
+  ethereumish: Ethereumish,
+  uniswapish: UniswapV2LPish,
+  req: V2AddLiquidityRequest
+): Promise<V2AddLiquidityResponse> {
+  const startTimestamp: number = Date.now();
+
+  const { wallet, maxFeePerGasBigNumber, maxPriorityFeePerGasBigNumber } =
+    await txWriteData(
+      ethereumish,
+      req.address,
+      req.maxFeePerGas,
+      req.maxPriorityFeePerGas
+    );
+
+  const token0 = getTokenInfoFromSymbol(ethereumish, req.token0);
+  const token1 = getTokenInfoFromSymbol(ethereumish, req.token1);
+
+  await checkRisk({
+    wallet: wallet.address,
+    connector: req.connector,
+    tokens: [req.token0, req.token1],
+    amounts: [req.amount0, req.amount1],
+  });
+
+  const gasPrice: number = ethereumish.gasPrice;
+  const gasLimitTransaction: number = ethereumish.gasLimitTransaction;
+  const gasLimitEstimate: number = uniswapish.gasLimitEstimate;
+
+  const response = {
+    network: ethereumish.chain,
+    timestamp: startTimestamp,
+    token0: token0.address,
+    token1: token1.address,
+    amount0: req.amount0,
+    amount1: req.amount1,
+    gasPrice: gasPrice,
+    gasPriceToken: ethereumish.nativeTokenSymbol,
+    gasLimit: gasLimitTransaction,
+    gasCost: gasCostInEthString(gasPrice, gasLimitEstimate),
+  };
+
+  if (req.simulate) {
+    const simulation = await simulateTransaction(
+      ethereumish.provider,
+      wallet,
+      (signer: Wallet, signerNonce: number) =>
+        uniswapish.addLiquidity(
+          signer,
+          token0,
+          token1,
+          req.amount0,
+          req.amount1,
+          gasLimitTransaction,
+          gasPrice,
+          signerNonce,
+          maxFeePerGasBigNumber,
+          maxPriorityFeePerGasBigNumber,
+          req.allowedSlippage
+        ),
+      req.nonce,
+      req.simulationBlock
+    );
+    logger.info(
+      `Adding liquidity simulated against block ${simulation.blockNumber}, success is ${simulation.success}.`
+    );
+    return {
+      ...response,
+      latency: latency(startTimestamp, Date.now()),
+      nonce: simulation.nonce,
+      txHash: undefined,
+      simulation,
+    };
+  }
+
+  const tx = await uniswapish.addLiquidity(
+    wallet,
+    token0,
+    token1,
+    req.amount0,
+    req.amount1,
+    gasLimitTransaction,
+    gasPrice,
+    req.nonce,
+    maxFeePerGasBigNumber,
+    maxPriorityFeePerGasBigNumber,
+    req.allowedSlippage
+  );
+
+  if (tx.hash) {
+    await ethereumish.journal.record({
+      txHash: tx.hash,
+      chain: ethereumish.chainName,
+      network: ethereumish.chain,
+      type: 'addLiquidity',
+      connector: req.connector,
+      wallet: wallet.address,
+      nonce: tx.nonce,
+      tokens: [req.token0, req.token1],
+      amounts: [req.amount0, req.amount1],
+      gasPrice,
+      gasLimit: gasLimitTransaction,
+    });
+  }
+
+  logger.info(
+    `Liquidity added, txHash is ${tx.hash}, nonce is ${tx.nonce}, gasPrice is ${gasPrice}.`
+  );
+
+  return {
+    ...response,
+    latency: latency(startTimestamp, Date.now()),
+    nonce: tx.nonce,
+    txHash: tx.hash,
+    maxFeePerGas: tx.maxFeePerGas?.toString(),
+    maxPriorityFeePerGas: tx.maxPriorityFeePerGas?.toString(),
+  };
+}
+
+export async function v2RemoveLiquidity(
+  ethereumish: Ethereumish,
+  uniswapish: UniswapV2LPish,
+  req: V2RemoveLiquidityRequest
+): Promise<V2RemoveLiquidityResponse> {
+  const startTimestamp: number = Date.now();
+
+  const { wallet, maxFeePerGasBigNumber, maxPriorityFeePerGasBigNumber } =
+    await txWriteData(
+      ethereumish,
+      req.address,
+      req.maxFeePerGas,
+      req.maxPriorityFeePerGas
+    );
+
+  const token0 = getTokenInfoFromSymbol(ethereumish, req.token0);
+  const token1 = getTokenInfoFromSymbol(ethereumish, req.token1);
+
+  const gasPrice: number = ethereumish.gasPrice;
+  const gasLimitTransaction: number = ethereumish.gasLimitTransaction;
+  const gasLimitEstimate: number = uniswapish.gasLimitEstimate;
+  const decreasePercent =
+    req.decreasePercent !== undefined ? req.decreasePercent : 100;
+
+  const response = {
+    network: ethereumish.chain,
+    timestamp: startTimestamp,
+    token0: token0.address,
+    token1: token1.address,
+    gasPrice: gasPrice,
+    gasPriceToken: ethereumish.nativeTokenSymbol,
+    gasLimit: gasLimitTransaction,
+    gasCost: gasCostInEthString(gasPrice, gasLimitEstimate),
+  };
+
+  if (req.simulate) {
+    const simulation = await simulateTransaction(
+      ethereumish.provider,
+      wallet,
+      (signer: Wallet, signerNonce: number) =>
+        uniswapish.removeLiquidity(
+          signer,
+          token0,
+          token1,
+          decreasePercent,
+          gasLimitTransaction,
+          gasPrice,
+          signerNonce,
+          maxFeePerGasBigNumber,
+          maxPriorityFeePerGasBigNumber,
+          req.allowedSlippage
+        ),
+      req.nonce,
+      req.simulationBlock
+    );
+    logger.info(
+      `Removing liquidity simulated against block ${simulation.blockNumber}, success is ${simulation.success}.`
+    );
+    return {
+      ...response,
+      latency: latency(startTimestamp, Date.now()),
+      nonce: simulation.nonce,
+      txHash: undefined,
+      simulation,
+    };
+  }
+
+  const { approval, transaction, liquidity } =
+    await uniswapish.removeLiquidity(
+      wallet,
+      token0,
+      token1,
+      decreasePercent,
+      gasLimitTransaction,
+      gasPrice,
+      req.nonce,
+      maxFeePerGasBigNumber,
+      maxPriorityFeePerGasBigNumber,
+      req.allowedSlippage
+    );
+
+  if (approval && approval.hash) {
+    await ethereumish.journal.record({
+      txHash: approval.hash,
+      chain: ethereumish.chainName,
+      network: ethereumish.chain,
+      type: 'approve',
+      connector: req.connector,
+      wallet: wallet.address,
+      nonce: approval.nonce,
+      tokens: [`${req.token0}-${req.token1}`],
+      amounts: [liquidity],
+      gasPrice,
+      gasLimit: gasLimitTransaction,
+    });
+  }
+  if (transaction.hash) {
+    await ethereumish.journal.record({
+      txHash: transaction.hash,
+      chain: ethereumish.chainName,
+      network: ethereumish.chain,
+      type: 'removeLiquidity',
+      connector: req.connector,
+      wallet: wallet.address,
+      nonce: transaction.nonce,
+      tokens: [req.token0, req.token1],
+      amounts: [],
+      gasPrice,
+      gasLimit: gasLimitTransaction,
+    });
+  }
+
+  logger.info(
+    `Liquidity removed, txHash is ${transaction.hash}, nonce is ${transaction.nonce}, gasPrice is ${gasPrice}.`
+  );
+
+  return {
+    ...response,
+    latency: latency(startTimestamp, Date.now()),
+    liquidity,
+    approvalTxHash: approval?.hash,
+    nonce: transaction.nonce,
+    txHash: transaction.hash,
+    maxFeePerGas: transaction.maxFeePerGas?.toString(),
+    maxPriorityFeePerGas: transaction.maxPriorityFeePerGas?.toString(),
+  };
+}
+
+export async function v2Position(
+  ethereumish: Ethereumish,
+  uniswapish: UniswapV2LPish,
+  req: V2PositionRequest
+): Promise<V2PositionResponse> {
+  const startTimestamp: number = Date.now();
+
+  const position = await uniswapish.getPosition(
+    req.address,
+    getTokenInfoFromSymbol(ethereumish, req.token0),
+    getTokenInfoFromSymbol(ethereumish, req.token1)
+  );
+
+  logger.info(
+    `${req.token0}-${req.token1} liquidity of ${req.address} retrieved.`
+  );
+
+  return {
+    network: ethereumish.chain,
+    timestamp: startTimestamp,
+    latency: latency(startTimestamp, Date.now()),
+    ...position,
+  };
+}
+
+/**
+ * The route restrictions of a trade request with its tokens resolved to
+ * addresses, undefined when the request has none. A path runs from the token
+ * paid to the token received and the fees, if given, match its hops.
+ */
+export function getRouteOptions(
+  ethereumish: Ethereumish,
+  req: TradeRequest
+): TradeRouteOptions | undefined {
+  if (!req.path && !req.intermediateTokens && !req.excludedPools) {
+    return undefined;
+  }
+  const resolve = (token: string): string => {
+    const tokenInfo = /^0x[0-9a-fA-F]{40}$/.test(token)
+      ? ethereumish.storedTokenList.find(
+          (info) => info.address.toLowerCase() === token.toLowerCase()
+        )
+      : ethereumish.getTokenBySymbol(token);
+    if (!tokenInfo)
+      throw new HttpException(
+        500,
+        TOKEN_NOT_SUPPORTED_ERROR_MESSAGE + token,
+        TOKEN_NOT_SUPPORTED_ERROR_CODE
+      );
+    return tokenInfo.address;
+  };
+
+  const path = req.path ? req.path.map(resolve) : undefined;
+  const [tokenIn, tokenOut] =
+    req.side === 'BUY' ? [req.quote, req.base] : [req.base, req.quote];
+  if (
+    path &&
+    (path[0] !== resolve(tokenIn) ||
+      path[path.length - 1] !== resolve(tokenOut))
+  ) {
+    throw new HttpException(
+      500,
+      TRADE_FAILED_ERROR_MESSAGE +
+        `the path must run from ${tokenIn} to ${tokenOut}.`,
+      TRADE_FAILED_ERROR_CODE
+    );
+  }
+  if (req.fees && (!path || req.fees.length !== path.length - 1)) {
+    throw new HttpException(
+      500,
+      TRADE_FAILED_ERROR_MESSAGE +
+        'fees must give the fee tier of each hop of the path.',
+      TRADE_FAILED_ERROR_CODE
+    );
+  }
+  return {
+    path,
+    fees: req.fees
+      ? req.fees.map(
+          (fee) => FeeAmount[fee.toUpperCase() as keyof typeof FeeAmount]
+        )
+      : undefined,
+    intermediateTokens: req.intermediateTokens
+      ? req.intermediateTokens.map(resolve)
+      : undefined,
+    excludedPools: req.excludedPools,
+  };
+}
+
+/**
+ * With autoWrap, a trade of the native currency goes through its wrapped
+ * token and is sent to the router methods that pay or receive the native
+ * currency. Returns the request with the wrapped token in place of the native
+ * one, and the side of the trade that is native.
+ */
+export function nativeSwapFor(
+  ethereumish: Ethereumish,
+  uniswapish: Uniswapish,
+  req: TradeRequest
+): { req: TradeRequest; nativeSwap?: NativeSwap } {
+  const native = ethereumish.nativeTokenSymbol;
+  const isNative = (symbol: string) =>
+    symbol.toUpperCase() === native.toUpperCase();
+  if (!req.autoWrap || (!isNative(req.base) && !isNative(req.quote))) {
+    return { req };
+  }
+
+  const wrappedAddress = ethereumish.wrappedNativeAddress;
+  const supportsNativeSwap =
+    uniswapish.supportsNativeSwap ??
+    routerNativeName(uniswapish.routerAbi) !== undefined;
+  if (!wrappedAddress || !supportsNativeSwap) {
+    throw new HttpException(
+      500,
+      TRADE_FAILED_ERROR_MESSAGE +
+        `${req.connector} cannot swap ${native}, wrap it with /evm/wrap first.`,
+      TRADE_FAILED_ERROR_CODE
+    );
+  }
+  const wrapped = ethereumish.storedTokenList.find(
+    (token) => token.address.toLowerCase() === wrappedAddress.toLowerCase()
+  );
+  if (!wrapped) {
+    throw new HttpException(
+      500,
+      TOKEN_NOT_SUPPORTED_ERROR_MESSAGE + `wrapped ${native}`,
+      TOKEN_NOT_SUPPORTED_ERROR_CODE
+    );
+  }
+
+  const paid = req.side === 'BUY' ? req.quote : req.base;
+  return {
+    req: {
+      ...req,
+      base: isNative(req.base) ? wrapped.symbol : req.base,
+      quote: isNative(req.quote) ? wrapped.symbol : req.quote,
+    },
+    nativeSwap: isNative(paid) ? 'in' : 'out',
+  };
+}
+
+export function getTokenInfoFromSymbol(
+  ethereumish: Ethereumish,
+  tokenSymbol: string
+): TokenInfo {
+  const tokenInfo = ethereumish.getTokenBySymbol(tokenSymbol);
+  if (!tokenInfo)
+    throw new HttpException(
+      500,
+      TOKEN_NOT_SUPPORTED_ERROR_MESSAGE + tokenSymbol,
+      TOKEN_NOT_SUPPORTED_ERROR_CODE
+    );
+  return tokenInfo;
+}
+
 export function getFullTokenFromSymbol(
   ethereumish: Ethereumish,
   uniswapish: Uniswapish | UniswapLPish,
@@ -0,0 +1,203 @@
+import Decimal from 'decimal.js-light';
+import { BigNumber } from 'ethers';
+import * as uniV3 from '@uniswap/v3-sdk';
+
+export interface InitializedTick {
+  index: number;
+  liquidityNet: BigNumber;
+}
+
+// a range between two initialized ticks, the liquidity is the same throughout
+export interface LiquidityRange {
+  tickLower: number;
+  tickUpper: number;
+  liquidity: BigNumber;
+}
+
+// the amount of token1 to pay in to raise the price by percent, and of token0
+// to lower it by percent
+export interface PoolDepth {
+  percent: number;
+  upperPrice: string;
+  token1In: string;
+  lowerPrice: string;
+  token0In: string;
+}
+
+export interface PoolLiquidity {
+  tickLower: number;
+  tickUpper: number;
+  priceLower: string;
+  priceUpper: string;
+  liquidity: string;
+  // what the range holds at the current price
+  amount0: string;
+  amount1: string;
+}
+
+// prices are of token0 in token1, the tokens sorted by address
+export interface PoolInfo {
+  address: string;
+  token0: string;
+  token1: string;
+  fee: string;
+  tick: number;
+  tickSpacing: number;
+  price: string;
+  liquidity: string;
+  observationIndex: number;
+  observationCardinality: number;
+  observationCardinalityNext: number;
+  // missing when the pool holds no observation as old as the window
+  twap?: string;
+  ranges: PoolLiquidity[];
+  depth: PoolDepth[];
+}
+
+const LOG_TICK_BASE = Math.log(1.0001);
+
+// the square root of the price at a tick, in raw token units
+export const sqrtPriceAtTick = (tick: number): number =>
+  Math.pow(1.0001, tick / 2);
+
+export const sqrtPriceX96ToNumber = (sqrtPriceX96: BigNumber): number =>
+  Number(new Decimal(sqrtPriceX96.toString()).div(new Decimal(2).pow(96)));
+
+// formats an amount computed with floats to 8 significant digits
+export const formatAmount = (amount: number): string =>
+  new Decimal(amount.toPrecision(8)).toFixed();
+
+/**
+ * The ticks to look at to cover prices within percent of the current one.
+ */
+export function tickWindow(
+  currentTick: number,
+  percent: number
+): { lowerTick: number; upperTick: number } {
+  return {
+    lowerTick: Math.max(
+      uniV3.TickMath.MIN_TICK,
+      Math.floor(currentTick + Math.log(1 - percent / 100) / LOG_TICK_BASE)
+    ),
+    upperTick: Math.min(
+      uniV3.TickMath.MAX_TICK,
+      Math.ceil(currentTick + Math.log(1 + percent / 100) / LOG_TICK_BASE)
+    ),
+  };
+}
+
+/**
+ * The ticks a tickBitmap word marks as initialized. Ticks are stored divided
+ * by the tick spacing, 256 to a word.
+ */
+export function initializedTicksOfWord(
+  wordPos: number,
+  bitmap: BigNumber,
+  tickSpacing: number
+): number[] {
+  const ticks = [];
+  for (let bit = 0; bit < 256; bit++) {
+    if (!bitmap.shr(bit).and(1).isZero()) {
+      ticks.push((wordPos * 256 + bit) * tickSpacing);
+    }
+  }
+  return ticks;
+}
+
+// the word of tickBitmap holding a tick, rounding down like the pool does
+export const wordPosOfTick = (tick: number, tickSpacing: number): number =>
+  Math.floor(Math.floor(tick / tickSpacing) / 256);
+
+/**
+ * Splits lowerTick to upperTick into the ranges between the initialized
+ * ticks, working out their liquidity from the liquidity active at the
+ * current tick and what crossing each initialized tick adds or removes.
+ */
+export function liquidityRanges(
+  currentTick: number,
+  liquidity: BigNumber,
+  ticks: InitializedTick[],
+  lowerTick: number,
+  upperTick: number
+): LiquidityRange[] {
+  const net: Record<number, BigNumber> = {};
+  for (const tick of ticks) net[tick.index] = tick.liquidityNet;
+  const bounds = [
+    lowerTick,
+    ...ticks
+      .map((tick) => tick.index)
+      .filter((index) => index > lowerTick && index < upperTick)
+      .sort((a, b) => a - b),
+    upperTick,
+  ];
+
+  const ranges: LiquidityRange[] = [];
+  for (let i = 0; i < bounds.length - 1; i++) {
+    ranges.push({
+      tickLower: bounds[i],
+      tickUpper: bounds[i + 1],
+      liquidity: BigNumber.from(0),
+    });
+  }
+  let current = ranges.findIndex(
+    (range) => range.tickLower <= currentTick && currentTick < range.tickUpper
+  );
+  if (current === -1) current = currentTick < lowerTick ? 0 : ranges.length - 1;
+  ranges[current].liquidity = liquidity;
+  for (let i = current + 1; i < ranges.length; i++) {
+    ranges[i].liquidity = ranges[i - 1].liquidity.add(
+      net[ranges[i].tickLower] || 0
+    );
+  }
+  for (let i = current - 1; i >= 0; i--) {
+    ranges[i].liquidity = ranges[i + 1].liquidity.sub(
+      net[ranges[i].tickUpper] || 0
+    );
+  }
+  return ranges;
+}
+
+/**
+ * The amounts of token0 and token1, in raw units, a range holds at the given
+ * price.
+ */
+export function rangeAmounts(
+  range: LiquidityRange,
+  sqrtPrice: number
+): { amount0: number; amount1: number } {
+  const liquidity = Number(range.liquidity.toString());
+  const sqrtLower = sqrtPriceAtTick(range.tickLower);
+  const sqrtUpper = sqrtPriceAtTick(range.tickUpper);
+  const sqrtAbove = Math.min(Math.max(sqrtPrice, sqrtLower), sqrtUpper);
+  return {
+    amount0: liquidity * (1 / sqrtAbove - 1 / sqrtUpper),
+    amount1: liquidity * (sqrtAbove - sqrtLower),
+  };
+}
+
+/**
+ * How much it takes to move the price by percent either way, in raw units,
+ * swapping through the given ranges only.
+ */
+export function depthAt(
+  ranges: LiquidityRange[],
+  sqrtPrice: number,
+  percent: number
+): { token1In: number; token0In: number } {
+  const sqrtTargetUp = sqrtPrice * Math.sqrt(1 + percent / 100);
+  const sqrtTargetDown = sqrtPrice * Math.sqrt(1 - percent / 100);
+  let token1In = 0;
+  let token0In = 0;
+  for (const range of ranges) {
+    const liquidity = Number(range.liquidity.toString());
+    const sqrtLower = sqrtPriceAtTick(range.tickLower);
+    const sqrtUpper = sqrtPriceAtTick(range.tickUpper);
+    const upFrom = Math.max(sqrtPrice, sqrtLower);
+    const upTo = Math.min(sqrtTargetUp, sqrtUpper);
+    if (upTo > upFrom) token1In += liquidity * (upTo - upFrom);
+    const downFrom = Math.min(sqrtPrice, sqrtUpper);
+    const downTo = Math.max(sqrtTargetDown, sqrtLower);
+    if (downFrom > downTo) token0In += liquidity * (1 / downTo - 1 / downFrom);
+  }
+  return { token1In, token0In };
+}
@@ -18,16 +18,41 @@ import {
   SwapToRatioResponse,
   SwapToRatioStatus,
 } from '@uniswap/smart-order-router';
-import { providers, Wallet, Signer, utils } from 'ethers';
+import {
+  BigNumber,
+  BigNumberish,
+  providers,
+  Wallet,
+  Signer,
+  utils,
+} from 'ethers';
 import { percentRegexp } from '../../services/config-manager-v2';
 import { Ethereum } from '../../chains/ethereum/ethereum';
 import {
   PoolState,
   RawPosition,
   AddPosReturn,
+  RebalancePosReturn,
   ReduceLiquidityData,
 } from './uniswap.lp.interfaces';
 import * as math from 'mathjs';
+import {
+  depthAt,
+  formatAmount,
+  initializedTicksOfWord,
+  liquidityRanges,
+  PoolInfo,
+  rangeAmounts,
+  sqrtPriceX96ToNumber,
+  tickWindow,
+  wordPosOfTick,
+} from './uniswap.lp.analytics';
+
+// the parts of SwapRouter02 a rebalance with a swap goes through
+const SWAP_ROUTER_02_INTERFACE = new utils.Interface([
+  'function multicall(bytes[] data) payable returns (bytes[] results)',
+  'function callPositionManager(bytes data) payable returns (bytes result)',
+]);
 
 export class UniswapLPHelper {
   protected ethereum: Ethereum;
@@ -115,6 +140,7 @@ export class UniswapLPHelper {
         SERVICE_UNITIALIZED_ERROR_MESSAGE('ETH'),
         SERVICE_UNITIALIZED_ERROR_CODE
       );
+    this.tokenList = {};
     for (const token of this.ethereum.storedTokenList) {
       this.tokenList[token.address] = new Token(
         this.chainId,
@@ -219,6 +245,146 @@ export class UniswapLPHelper {
     };
   }
 
+  /**
+   * Reads the state of a pool, the liquidity between its initialized ticks
+   * within rangePercent of its price and what it takes to move its price by
+   * each of depthPercents.
+   *
+   * @param twapWindow Seconds to average the price over, none when 0
+   */
+  async getPoolInfo(
+    token0: Token,
+    token1: Token,
+    fee: uniV3.FeeAmount,
+    rangePercent: number,
+    depthPercents: number[],
+    twapWindow: number = 0
+  ): Promise<PoolInfo> {
+    if (token1.sortsBefore(token0)) [token0, token1] = [token1, token0];
+    const address = uniV3.Pool.getAddress(token0, token1, fee);
+    const poolContract = this.getPoolContract(address, this.ethereum.provider);
+    const poolData = await this.getPoolState(address, fee);
+    const tickSpacing = uniV3.TICK_SPACINGS[fee];
+
+    // the range has to cover the depth asked for
+    const { lowerTick, upperTick } = tickWindow(
+      poolData.tick,
+      Math.max(rangePercent, ...depthPercents)
+    );
+    const words = [];
+    for (
+      let word = wordPosOfTick(lowerTick, tickSpacing);
+      word <= wordPosOfTick(upperTick, tickSpacing);
+      word++
+    ) {
+      words.push(word);
+    }
+    const indexes = (
+      await Promise.all(
+        words.map(async (word) =>
+          initializedTicksOfWord(
+            word,
+            await poolContract.tickBitmap(word),
+            tickSpacing
+          )
+        )
+      )
+    )
+      .flat()
+      .filter((index) => index > lowerTick && index < upperTick);
+    const ticks = await Promise.all(
+      indexes.map(async (index) => ({
+        index,
+        liquidityNet: BigNumber.from((await poolContract.ticks(index))[1]),
+      }))
+    );
+    const ranges = liquidityRanges(
+      poolData.tick,
+      BigNumber.from(poolData.liquidity),
+      ticks,
+      lowerTick,
+      upperTick
+    );
+
+    const sqrtPrice = sqrtPriceX96ToNumber(poolData.sqrtPriceX96);
+    const pool = new uniV3.Pool(
+      token0,
+      token1,
+      fee,
+      poolData.sqrtPriceX96.toString(),
+      poolData.liquidity.toString(),
+      poolData.tick
+    );
+    const price = Number(pool.token0Price.toSignificant(8));
+    const priceAt = (tick: number) =>
+      uniV3.tickToPrice(token0, token1, tick).toSignificant(8);
+    const units = (amount: number, token: Token) =>
+      formatAmount(amount / 10 ** token.decimals);
+
+    return {
+      address,
+      token0: token0.symbol as string,
+      token1: token1.symbol as string,
+      fee: uniV3.FeeAmount[fee],
+      tick: poolData.tick,
+      tickSpacing,
+      price: pool.token0Price.toSignificant(8),
+      liquidity: poolData.liquidity.toString(),
+      observationIndex: Number(poolData.observationIndex),
+      observationCardinality: Number(poolData.observationCardinality),
+      observationCardinalityNext: Number(poolData.observationCardinalityNext),
+      twap:
+        twapWindow > 0
+          ? await this.twap(poolContract, token0, token1, twapWindow)
+          : undefined,
+      ranges: ranges.map((range) => {
+        const { amount0, amount1 } = rangeAmounts(range, sqrtPrice);
+        return {
+          tickLower: range.tickLower,
+          tickUpper: range.tickUpper,
+          priceLower: priceAt(range.tickLower),
+          priceUpper: priceAt(range.tickUpper),
+          liquidity: range.liquidity.toString(),
+          amount0: units(amount0, token0),
+          amount1: units(amount1, token1),
+        };
+      }),
+      depth: depthPercents.map((percent) => {
+        const { token1In, token0In } = depthAt(ranges, sqrtPrice, percent);
+        return {
+          percent,
+          upperPrice: formatAmount(price * (1 + percent / 100)),
+          token1In: units(token1In, token1),
+          lowerPrice: formatAmount(price * (1 - percent / 100)),
+          token0In: units(token0In, token0),
+        };
+      }),
+    };
+  }
+
+  /**
+   * The time weighted average price of token0 in token1 over the last window
+   * seconds, undefined when the pool doesn't hold observations that old.
+   */
+  async twap(
+    poolContract: Contract,
+    token0: Token,
+    token1: Token,
+    window: number
+  ): Promise<string | undefined> {
+    let tickCumulatives: BigNumber[];
+    try {
+      ({ tickCumulatives } = await poolContract.observe([window, 0]));
+    } catch (e) {
+      return undefined;
+    }
+    // the mean tick rounds down, as the oracle library does
+    const meanTick = Math.floor(
+      tickCumulatives[1].sub(tickCumulatives[0]).toNumber() / window
+    );
+    return uniV3.tickToPrice(token0, token1, meanTick).toSignificant(8);
+  }
+
   async poolPrice(
     token0: Token,
     token1: Token,
@@ -430,7 +596,143 @@ export class UniswapLPHelper {
     decreasePercent: number
   ): Promise<uniV3.MethodParameters> {
     // Reduce position and burn
+    const position = await this.getPositionInstance(
+      await this.getRawPosition(wallet, tokenId)
+    );
+    return uniV3.NonfungiblePositionManager.removeCallParameters(
+      position,
+      this.getReduceLiquidityData(
+        decreasePercent,
+        tokenId,
+        position.pool.token0,
+        position.pool.token1,
+        wallet
+      )
+    );
+  }
+
+  /**
+   * Builds a single transaction that removes a position, collects its fees
+   * and mints a new position between lowerPrice and upperPrice with what came
+   * out, swapping first when that doesn't fit the new range. The prices are
+   * of token0 in token1, the tokens of the position sorted by address.
+   *
+   * Without a swap this is a multicall of the position manager. With one it
+   * is a multicall of the router, which removes the position through
+   * callPositionManager after a permit for it signed by the wallet.
+   *
+   * @param fees The fees owed to the position, as collect returns them
+   */
+  async rebalancePositionHelper(
+    wallet: Wallet,
+    tokenId: number,
+    lowerPrice: number,
+    upperPrice: number,
+    fees: { amount0: BigNumber; amount1: BigNumber }
+  ): Promise<RebalancePosReturn> {
     const positionData = await this.getRawPosition(wallet, tokenId);
+    const position = await this.getPositionInstance(positionData);
+    const { token0, token1 } = position.pool;
+    const amount0 = BigNumber.from(position.amount0.quotient.toString()).add(
+      fees.amount0
+    );
+    const amount1 = BigNumber.from(position.amount1.quotient.toString()).add(
+      fees.amount1
+    );
+    const add = await this.addPositionHelper(
+      wallet,
+      token0,
+      token1,
+      utils.formatUnits(amount0, token0.decimals),
+      utils.formatUnits(amount1, token1.decimals),
+      positionData.fee,
+      lowerPrice,
+      upperPrice
+    );
+    const removeOptions = {
+      ...this.getReduceLiquidityData(100, tokenId, token0, token1, wallet),
+      burnToken: true,
+    };
+
+    if (!add.swapRequired) {
+      const remove = uniV3.NonfungiblePositionManager.removeCallParameters(
+        position,
+        removeOptions
+      );
+      return {
+        calldata: uniV3.NonfungiblePositionManager.INTERFACE.encodeFunctionData(
+          'multicall',
+          [[remove.calldata, add.calldata]]
+        ),
+        value: add.value,
+        swapRequired: false,
+        token0,
+        token1,
+      };
+    }
+
+    const remove = uniV3.NonfungiblePositionManager.removeCallParameters(
+      position,
+      {
+        ...removeOptions,
+        permit: await this.signPositionPermit(
+          wallet,
+          tokenId,
+          positionData.nonce,
+          this.ttl
+        ),
+      }
+    );
+    return {
+      calldata: SWAP_ROUTER_02_INTERFACE.encodeFunctionData('multicall', [
+        [
+          SWAP_ROUTER_02_INTERFACE.encodeFunctionData('callPositionManager', [
+            remove.calldata,
+          ]),
+          add.calldata,
+        ],
+      ]),
+      value: add.value,
+      swapRequired: true,
+      token0,
+      token1,
+    };
+  }
+
+  /**
+   * Signs an EIP-712 permit letting the router manage a position of the
+   * wallet until deadline.
+   */
+  async signPositionPermit(
+    wallet: Wallet,
+    tokenId: number,
+    nonce: BigNumberish,
+    deadline: number
+  ): Promise<uniV3.NFTPermitOptions> {
+    const signature = await wallet._signTypedData(
+      {
+        name: 'Uniswap V3 Positions NFT-V1',
+        version: '1',
+        chainId: this.chainId,
+        verifyingContract: this.nftManager,
+      },
+      {
+        Permit: [
+          { name: 'spender', type: 'address' },
+          { name: 'tokenId', type: 'uint256' },
+          { name: 'nonce', type: 'uint256' },
+          { name: 'deadline', type: 'uint256' },
+        ],
+      },
+      { spender: this.router, tokenId, nonce, deadline }
+    );
+    const { v, r, s } = utils.splitSignature(signature);
+    return { v: v as 27 | 28, r, s, deadline, spender: this.router };
+  }
+
+  async getPositionInstance(
+    positionData: RawPosition
+  ): Promise<uniV3.Position> {
     const token0 = this.getTokenByAddress(positionData.token0);
     const token1 = this.getTokenByAddress(positionData.token1);
     const fee = positionData.fee;
@@ -441,7 +743,7 @@ export class UniswapLPHelper {
     }
     const poolAddress = uniV3.Pool.getAddress(token0, token1, fee);
     const poolData = await this.getPoolState(poolAddress, fee);
-    const position = new uniV3.Position({
+    return new uniV3.Position({
       pool: new uniV3.Pool(
         token0,
         token1,
@@ -454,15 +756,34 @@ export class UniswapLPHelper {
       tickUpper: positionData.tickUpper,
       liquidity: positionData.liquidity,
     });
-    return uniV3.NonfungiblePositionManager.removeCallParameters(
-      position,
-      this.getReduceLiquidityData(
-        decreasePercent,
-        tokenId,
-        token0,
-        token1,
-        wallet
-      )
-    );
   }
 }
+
+// mint returns (tokenId, liquidity, amount0, amount1)
+const MINT_OUTPUT = ['uint256', 'uint128', 'uint256', 'uint256'];
+
+/**
+ * Finds what a mint returned in the output of a rebalance, through the
+ * results of the multicalls and of the router's callPositionManager and
+ * approveAndCall, which are returned ABI encoded as bytes.
+ */
+export function findMintOutput(output: string): utils.Result | undefined {
+  if (utils.hexDataLength(output) === 32 * MINT_OUTPUT.length) {
+    return utils.defaultAbiCoder.decode(MINT_OUTPUT, output);
+  }
+  // bytes[] first, a bytes[] would also decode as some bytes
+  for (const type of ['bytes[]', 'bytes']) {
+    let decoded: string | string[];
+    try {
+      [decoded] = utils.defaultAbiCoder.decode([type], output);
+    } catch (_e) {
+      continue;
+    }
+    for (const result of ([] as string[]).concat(decoded)) {
+      const found = findMintOutput(result);
+      if (found) return found;
+    }
+    return undefined;
+  }
+  return undefined;
+}
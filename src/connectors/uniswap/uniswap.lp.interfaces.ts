@@ -38,6 +38,11 @@ export interface AddPosReturn extends uniV3.MethodParameters {
   swapRequired: boolean;
 }
 
+export interface RebalancePosReturn extends AddPosReturn {
+  token0: Token;
+  token1: Token;
+}
+
 export interface ReduceLiquidityData {
   tokenId: number;
   liquidityPercentage: Percent;
@@ -1,5 +1,9 @@
 import { logger } from '../../services/logger';
-import { PositionInfo, UniswapLPish } from '../../services/common-interfaces';
+import {
+  PositionInfo,
+  RebalancedPosition,
+  UniswapLPish,
+} from '../../services/common-interfaces';
 import { UniswapConfig } from './uniswap.config';
 import { Token } from '@uniswap/sdk-core';
 import * as uniV3 from '@uniswap/v3-sdk';
@@ -11,8 +15,12 @@ import {
   constants,
   providers,
 } from 'ethers';
-import { UniswapLPHelper } from './uniswap.lp.helper';
+import { UniswapLPHelper, findMintOutput } from './uniswap.lp.helper';
 import { AddPosReturn } from './uniswap.lp.interfaces';
+import {
+  decodeRevertReason,
+  SimulationWallet,
+} from '../../evm/evm.simulation';
 
 const MaxUint128 = BigNumber.from(2).pow(128).sub(1);
 
@@ -45,6 +53,10 @@ export class UniswapLP extends UniswapLPHelper implements UniswapLPish {
     return UniswapLP._instances[chain + network];
   }
 
+  public static getConnectedInstances(): { [name: string]: UniswapLP } {
+    return UniswapLP._instances;
+  }
+
   /**
    * Default gas limit for swap transactions.
    */
@@ -92,7 +104,9 @@ export class UniswapLP extends UniswapLPHelper implements UniswapLPish {
       tickUpper: position.tickUpper,
       liquidity: position.liquidity,
     });
+    const pool = positionInst.pool;
     return {
+      tokenId,
       token0: token0.symbol,
       token1: token1.symbol,
       fee: uniV3.FeeAmount[position.fee],
@@ -108,9 +122,42 @@ export class UniswapLP extends UniswapLPHelper implements UniswapLPish {
         feeInfo.amount1.toString(),
         token1.decimals
       ),
+      liquidity: position.liquidity.toString(),
+      inRange:
+        pool.tickCurrent >= position.tickLower &&
+        pool.tickCurrent < position.tickUpper,
+      valueInToken0: positionInst.amount0
+        .add(pool.token1Price.quote(positionInst.amount1))
+        .toFixed(),
+      valueInToken1: positionInst.amount1
+        .add(pool.token0Price.quote(positionInst.amount0))
+        .toFixed(),
     };
   }
 
+  async getPositions(address: string): Promise<PositionInfo[]> {
+    const contract = this.getContract('nft', this.ethereum.provider);
+    const balance: BigNumber = await contract.balanceOf(address);
+    const tokenIds: number[] = await Promise.all(
+      Array.from(Array(balance.toNumber()).keys()).map(async (index) =>
+        (await contract.tokenOfOwnerByIndex(address, index)).toNumber()
+      )
+    );
+    const positions = await Promise.allSettled(
+      tokenIds.map((tokenId) => this.getPosition(tokenId))
+    );
+    return positions
+      .filter((result, i) => {
+        if (result.status === 'rejected') {
+          logger.warn(
+            `Skipping position ${tokenIds[i]} of ${address}: ${result.reason}`
+          );
+        }
+        return result.status === 'fulfilled';
+      })
+      .map((result) => (result as PromiseFulfilledResult<PositionInfo>).value);
+  }
+
   async addPosition(
     wallet: Wallet,
     token0: Token,
@@ -196,6 +243,106 @@ export class UniswapLP extends UniswapLPHelper implements UniswapLPish {
     return tx;
   }
 
+  async rebalancePosition(
+    wallet: Wallet,
+    tokenId: number,
+    lowerPrice: number,
+    upperPrice: number,
+    gasLimit: number,
+    gasPrice: number,
+    nonce?: number,
+    maxFeePerGas?: BigNumber,
+    maxPriorityFeePerGas?: BigNumber
+  ): Promise<RebalancedPosition> {
+    const fees = <{ amount0: BigNumber; amount1: BigNumber }>(
+      await this.collectFees(this.ethereum.provider, tokenId)
+    );
+    const { calldata, value, swapRequired, token0, token1 } =
+      await this.rebalancePositionHelper(
+        wallet,
+        tokenId,
+        lowerPrice,
+        upperPrice,
+        fees
+      );
+    const to = swapRequired ? this.router : this.nftManager;
+
+    // the new position is only known once minted, a dry run tells it
+    let minted: utils.Result | undefined;
+    try {
+      minted = findMintOutput(
+        await wallet.call({ to, data: calldata, value: BigNumber.from(value) })
+      );
+    } catch (e) {
+      // a simulation reports why the transaction reverts instead
+      if (!(wallet instanceof SimulationWallet)) {
+        throw new Error(
+          `Unable to rebalance position ${tokenId}: ${decodeRevertReason(e)}`
+        );
+      }
+    }
+
+    if (nonce === undefined) {
+      nonce = await this.ethereum.nonceManager.getNextNonce(wallet.address);
+    }
+
+    const tx = await wallet.sendTransaction({
+      data: calldata,
+      to,
+      ...this.generateOverrides(
+        gasLimit,
+        gasPrice,
+        nonce,
+        maxFeePerGas,
+        maxPriorityFeePerGas,
+        value
+      ),
+    });
+    logger.info(`Uniswap V3 Rebalance position Tx Hash: ${tx.hash}`);
+    return {
+      transaction: tx,
+      tokenId: minted ? minted[0].toNumber() : undefined,
+      amount0: minted ? utils.formatUnits(minted[2], token0.decimals) : '0',
+      amount1: minted ? utils.formatUnits(minted[3], token1.decimals) : '0',
+      swapRequired,
+    };
+  }
+
+  async increaseObservationCardinality(
+    wallet: Wallet,
+    token0: Token,
+    token1: Token,
+    fee: uniV3.FeeAmount,
+    cardinality: number,
+    gasLimit: number,
+    gasPrice: number,
+    nonce?: number,
+    maxFeePerGas?: BigNumber,
+    maxPriorityFeePerGas?: BigNumber
+  ): Promise<Transaction> {
+    const pool = this.getPoolContract(
+      uniV3.Pool.getAddress(token0, token1, fee),
+      wallet
+    );
+
+    if (nonce === undefined) {
+      nonce = await this.ethereum.nonceManager.getNextNonce(wallet.address);
+    }
+
+    const tx = await pool.increaseObservationCardinalityNext(
+      cardinality,
+      this.generateOverrides(
+        gasLimit,
+        gasPrice,
+        nonce,
+        maxFeePerGas,
+        maxPriorityFeePerGas
+      )
+    );
+    logger.info(`Uniswap V3 Increase observations Tx Hash: ${tx.hash}`);
+    return tx;
+  }
+
   async collectFees(
     wallet: Wallet | providers.StaticJsonRpcProvider,
     tokenId: number,
@@ -214,7 +361,10 @@ export class UniswapLP extends UniswapLPHelper implements UniswapLPish {
     };
 
     if (wallet instanceof providers.StaticJsonRpcProvider) {
-      return await contract.callStatic.collect(collectData);
+      // only the owner of the position may collect its fees
+      return await contract.callStatic.collect(collectData, {
+        from: await contract.ownerOf(tokenId),
+      });
     } else {
       collectData.recipient = wallet.address;
       if (nonce === undefined) {
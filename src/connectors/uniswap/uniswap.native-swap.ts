@@ -0,0 +1,129 @@
+import { ContractInterface, utils } from 'ethers';
+import { Protocol, RouteV2, RouteV3, Trade } from '@uniswap/router-sdk';
+import {
+  Currency,
+  CurrencyAmount,
+  Ether,
+  TradeType,
+  WETH9,
+} from '@uniswap/sdk-core';
+import { NativeSwap } from '../../services/common-interfaces';
+
+// what Router.swapCallParameters returns in the V2 sdks and their forks
+interface SwapCall {
+  methodName: string;
+  args: (string | string[])[];
+  value: string;
+}
+
+/**
+ * The name a V2 router gives the native currency in its swap methods, ETH
+ * for swapExactETHForTokens or AVAX for swapExactAVAXForTokens. Undefined for
+ * routers that cannot swap it, like the V3 router.
+ */
+export function routerNativeName(abi: ContractInterface): string | undefined {
+  let routerInterface: utils.Interface;
+  try {
+    routerInterface =
+      abi instanceof utils.Interface ? abi : new utils.Interface(abi);
+  } catch (e) {
+    return undefined;
+  }
+  for (const fragment of Object.values(routerInterface.functions)) {
+    const match = fragment.name.match(/^swapExact(\w+)ForTokens$/);
+    if (match && match[1] !== 'Tokens') return match[1];
+  }
+  return undefined;
+}
+
+/**
+ * Turns a token to token swap of a V2 router into the swap that pays or
+ * receives the native currency in place of its wrapped token. The router
+ * wraps and unwraps it, so the wallet needs no wrapped balance nor allowance
+ * for it.
+ */
+export function toNativeSwap<T extends SwapCall>(
+  call: T,
+  abi: ContractInterface,
+  nativeSwap?: NativeSwap
+): T {
+  if (nativeSwap === undefined) return call;
+  const native = routerNativeName(abi);
+  if (native === undefined) {
+    throw new Error('the router cannot swap the native currency');
+  }
+
+  // both methods take (amount, limit, path, to, deadline)
+  const [amount, limit, ...rest] = call.args as string[];
+  if (call.methodName === 'swapExactTokensForTokens') {
+    return nativeSwap === 'in'
+      ? {
+          ...call,
+          methodName: `swapExact${native}ForTokens`,
+          args: [limit, ...rest],
+          value: amount,
+        }
+      : { ...call, methodName: `swapExactTokensFor${native}` };
+  }
+  if (call.methodName === 'swapTokensForExactTokens') {
+    // the router refunds what is left of the maximum input
+    return nativeSwap === 'in'
+      ? {
+          ...call,
+          methodName: `swap${native}ForExactTokens`,
+          args: [amount, ...rest],
+          value: limit,
+        }
+      : { ...call, methodName: `swapTokensForExact${native}` };
+  }
+  throw new Error(`${call.methodName} has no native currency counterpart`);
+}
+
+/**
+ * The trade of the smart order router with the native currency in place of
+ * its wrapped token on the native side. SwapRouter02 then takes the native
+ * currency as the value of the transaction, or unwraps what it receives.
+ */
+export function toNativeTrade(
+  trade: Trade<Currency, Currency, TradeType>,
+  chainId: number,
+  nativeSwap?: NativeSwap
+): Trade<Currency, Currency, TradeType> {
+  if (nativeSwap === undefined) return trade;
+  if (WETH9[chainId] === undefined) {
+    throw new Error('the router cannot swap the native currency');
+  }
+  const native = Ether.onChain(chainId);
+  const toNative = (amount: CurrencyAmount<Currency>) =>
+    CurrencyAmount.fromRawAmount(native, amount.quotient);
+
+  // the routes still go through the wrapped token, only the amounts change
+  const swaps = trade.swaps.map(({ route, inputAmount, outputAmount }) => {
+    if (route.protocol !== Protocol.V2 && route.protocol !== Protocol.V3) {
+      throw new Error(`${route.protocol} routes can not swap ${native.symbol}`);
+    }
+    return {
+      route,
+      inputAmount: nativeSwap === 'in' ? toNative(inputAmount) : inputAmount,
+      outputAmount:
+        nativeSwap === 'out' ? toNative(outputAmount) : outputAmount,
+    };
+  });
+  return new Trade({
+    v2Routes: swaps
+      .filter(({ route }) => route.protocol === Protocol.V2)
+      .map(({ route, inputAmount, outputAmount }) => ({
+        routev2: route as RouteV2<Currency, Currency>,
+        inputAmount,
+        outputAmount,
+      })),
+    v3Routes: swaps
+      .filter(({ route }) => route.protocol === Protocol.V3)
+      .map(({ route, inputAmount, outputAmount }) => ({
+        routev3: route as RouteV3<Currency, Currency>,
+        inputAmount,
+        outputAmount,
+      })),
+    tradeType: trade.tradeType,
+  });
+}
@@ -0,0 +1,219 @@
+import Decimal from 'decimal.js-light';
+import { utils } from 'ethers';
+import { Token } from '@uniswap/sdk-core';
+import * as uniV3 from '@uniswap/v3-sdk';
+import { UniswapishPriceError } from '../../services/error-handler';
+import {
+  TradeRoute,
+  TradeRouteHop,
+  TradeRouteOptions,
+} from '../../services/common-interfaces';
+
+export const V3_FEE_TIERS: number[] = [
+  uniV3.FeeAmount.LOWEST,
+  uniV3.FeeAmount.LOW,
+  uniV3.FeeAmount.MEDIUM,
+  uniV3.FeeAmount.HIGH,
+];
+
+// the most routes quoted for one trade, each one is a call to the quoter
+export const MAX_CANDIDATE_ROUTES = 256;
+
+// a path through V3 pools, fees[i] is the fee tier of the pool from
+// tokens[i] to tokens[i + 1]
+export interface CandidateRoute {
+  tokens: Token[];
+  fees: number[];
+}
+
+export const hasRouteOptions = (options?: TradeRouteOptions): boolean =>
+  options !== undefined &&
+  (options.path !== undefined ||
+    options.intermediateTokens !== undefined ||
+    options.excludedPools !== undefined);
+
+const sameAddress = (a: string, b: string): boolean =>
+  a.toLowerCase() === b.toLowerCase();
+
+const feeCombinations = (hops: number): number[][] =>
+  hops === 0
+    ? [[]]
+    : feeCombinations(hops - 1).flatMap((fees) =>
+        V3_FEE_TIERS.map((fee) => [...fees, fee])
+      );
+
+/**
+ * The V3 routes to quote for a trade: the path given, through every fee tier
+ * unless the fees are given too, and a swap through each intermediate token.
+ * Routes through an excluded pool are left out. Throws if the fees don't
+ * match the hops of the path or if there are more than MAX_CANDIDATE_ROUTES
+ * routes.
+ */
+export function candidateRoutes(
+  path: Token[],
+  intermediates: Token[],
+  options: TradeRouteOptions
+): CandidateRoute[] {
+  if (options.fees && options.fees.length !== path.length - 1) {
+    throw new UniswapishPriceError(
+      `The path has ${path.length - 1} hops but ${options.fees.length} ` +
+        'fee tiers were given.'
+    );
+  }
+  const tokenIn = path[0];
+  const tokenOut = path[path.length - 1];
+  const paths: Token[][] = [path];
+  for (const token of intermediates) {
+    if (!path.some((traded) => sameAddress(traded.address, token.address))) {
+      paths.push([tokenIn, token, tokenOut]);
+    }
+  }
+
+  // count the routes before building them, a long path has 4^hops
+  const fixedFees = (tokens: Token[]) => options.fees && tokens === path;
+  const count = paths.reduce(
+    (total, tokens) =>
+      total +
+      (fixedFees(tokens) ? 1 : V3_FEE_TIERS.length ** (tokens.length - 1)),
+    0
+  );
+  if (count > MAX_CANDIDATE_ROUTES) {
+    throw new UniswapishPriceError(
+      `The trade has ${count} routes to quote, more than ` +
+        `${MAX_CANDIDATE_ROUTES}. Give the fees of the path or fewer ` +
+        'intermediate tokens.'
+    );
+  }
+
+  const candidates: CandidateRoute[] = [];
+  for (const tokens of paths) {
+    const combinations = fixedFees(tokens)
+      ? [options.fees as number[]]
+      : feeCombinations(tokens.length - 1);
+    for (const fees of combinations) {
+      const pools = fees.map((fee, i) =>
+        uniV3.Pool.getAddress(tokens[i], tokens[i + 1], fee)
+      );
+      const excluded = (options.excludedPools || []).some((excludedPool) =>
+        pools.some((pool) => sameAddress(pool, excludedPool))
+      );
+      if (!excluded) candidates.push({ tokens, fees });
+    }
+  }
+  return candidates;
+}
+
+/**
+ * Encodes a route the way the V3 quoter and router expect it, from the token
+ * out for exact output swaps.
+ */
+export function encodeRoutePath(
+  route: CandidateRoute,
+  exactOutput: boolean
+): string {
+  const types: string[] = [];
+  const values: Array<string | number> = [];
+  route.tokens.forEach((token, i) => {
+    types.push('address');
+    values.push(token.address);
+    if (i < route.fees.length) {
+      types.push('uint24');
+      values.push(route.fees[i]);
+    }
+  });
+  if (exactOutput) {
+    types.reverse();
+    values.reverse();
+  }
+  return utils.solidityPack(types, values);
+}
+
+const poolHop = (pool: any, tokenIn: Token, tokenOut: Token): TradeRouteHop =>
+  pool.fee !== undefined
+    ? {
+        tokenIn: tokenIn.address,
+        tokenOut: tokenOut.address,
+        pool: uniV3.Pool.getAddress(pool.token0, pool.token1, pool.fee),
+        fee: pool.fee,
+      }
+    : {
+        tokenIn: tokenIn.address,
+        tokenOut: tokenOut.address,
+        pool: pool.liquidityToken.address,
+      };
+
+/**
+ * The pools a trade swaps through, from the routes of a router-sdk trade or
+ * the route of a Uniswap V2 style trade. Undefined for trades that don't say.
+ */
+export function tradeRoute(trade: any): TradeRoute[] | undefined {
+  if (trade.swaps !== undefined) {
+    const total = new Decimal(trade.inputAmount.quotient.toString());
+    return trade.swaps.map((swap: any) => ({
+      percent: total.isZero()
+        ? 100
+        : Number(
+            new Decimal(swap.inputAmount.quotient.toString())
+              .div(total)
+              .mul(100)
+              .toFixed(2)
+          ),
+      hops: swap.route.pools.map((pool: any, i: number) =>
+        poolHop(pool, swap.route.path[i], swap.route.path[i + 1])
+      ),
+    }));
+  }
+  if (trade.route !== undefined && trade.route.pairs !== undefined) {
+    return [
+      {
+        percent: 100,
+        hops: trade.route.pairs.map((pair: any, i: number) =>
+          poolHop(pair, trade.route.path[i], trade.route.path[i + 1])
+        ),
+      },
+    ];
+  }
+  return undefined;
+}
+
+/**
+ * Why a route breaks the restrictions asked for, or undefined if it doesn't.
+ */
+export function routeViolation(
+  route: TradeRoute[] | undefined,
+  options: TradeRouteOptions
+): string | undefined {
+  if (route === undefined) {
+    return 'the connector does not report the route of its trades';
+  }
+  const excludedPools = options.excludedPools || [];
+  for (const { hops } of route) {
+    for (const hop of hops) {
+      if (excludedPools.some((pool) => sameAddress(pool, hop.pool))) {
+        return `the route goes through the excluded pool ${hop.pool}`;
+      }
+    }
+    const tokens = [hops[0].tokenIn, ...hops.map((hop) => hop.tokenOut)];
+    if (options.path) {
+      const path = options.path;
+      if (
+        tokens.length !== path.length ||
+        tokens.some((token, i) => !sameAddress(token, path[i])) ||
+        (options.fees &&
+          hops.some((hop, i) => hop.fee !== (options.fees as number[])[i]))
+      ) {
+        return `the route ${tokens.join(' > ')} is not the path asked for`;
+      }
+    }
+    const allowed = options.intermediateTokens;
+    if (allowed) {
+      const intermediate = tokens
+        .slice(1, -1)
+        .find((token) => !allowed.some((a) => sameAddress(a, token)));
+      if (intermediate) {
+        return `the route goes through ${intermediate}, which is not an allowed intermediate token`;
+      }
+    }
+  }
+  return undefined;
+}
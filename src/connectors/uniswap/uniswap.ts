@@ -1,14 +1,18 @@
 import { UniswapishPriceError } from '../../services/error-handler';
 import { isFractionString } from '../../services/validators';
 import { UniswapConfig } from './uniswap.config';
-import routerAbi from './uniswap_v2_router_abi.json';
 import {
+  Contract,
   ContractInterface,
   ContractTransaction,
 } from '@ethersproject/contracts';
 import { AlphaRouter } from '@uniswap/smart-order-router';
 import { Trade, SwapRouter } from '@uniswap/router-sdk';
-import { MethodParameters } from '@uniswap/v3-sdk';
+import {
+  MethodParameters,
+  Pool as V3Pool,
+  Route as V3Route,
+} from '@uniswap/v3-sdk';
 import {
   Token,
   CurrencyAmount,
@@ -21,7 +25,31 @@ import { logger } from '../../services/logger';
 import { percentRegexp } from '../../services/config-manager-v2';
 import { Ethereum } from '../../chains/ethereum/ethereum';
 import { Polygon } from '../../chains/polygon/polygon';
-import { ExpectedTrade, Uniswapish } from '../../services/common-interfaces';
+import {
+  ExpectedTrade,
+  NativeSwap,
+  TradeRouteOptions,
+  Uniswapish,
+} from '../../services/common-interfaces';
+import { toNativeTrade } from './uniswap.native-swap';
+import {
+  CandidateRoute,
+  candidateRoutes,
+  encodeRoutePath,
+  hasRouteOptions,
+  routeViolation,
+  tradeRoute,
+} from './uniswap.route';
+
+const QUOTER_V2_ABI = [
+  'function quoteExactInput(bytes path, uint256 amountIn) returns (uint256 amountOut, uint160[] sqrtPriceX96AfterList, uint32[] initializedTicksCrossedList, uint256 gasEstimate)',
+  'function quoteExactOutput(bytes path, uint256 amountOut) returns (uint256 amountIn, uint160[] sqrtPriceX96AfterList, uint32[] initializedTicksCrossedList, uint256 gasEstimate)',
+];
+
+const POOL_STATE_ABI = [
+  'function slot0() view returns (uint160 sqrtPriceX96, int24 tick, uint16 observationIndex, uint16 observationCardinality, uint16 observationCardinalityNext, uint8 feeProtocol, bool unlocked)',
+  'function liquidity() view returns (uint128)',
+];
 
 export class Uniswap implements Uniswapish {
   private static _instances: { [name: string]: Uniswap };
@@ -32,6 +60,7 @@ export class Uniswap implements Uniswapish {
   private _gasLimitEstimate: number;
   private _ttl: number;
   private _maximumHops: number;
+  private _quoter?: string;
   private chainId;
   private tokenList: Record<string, Token> = {};
   private _ready: boolean = false;
@@ -50,9 +79,11 @@ export class Uniswap implements Uniswapish {
       chainId: this.chainId,
       provider: this.chain.provider,
     });
-    this._routerAbi = routerAbi.abi;
+    this._routerAbi =
+      require('@uniswap/v3-periphery/artifacts/contracts/SwapRouter.sol/SwapRouter.json').abi;
     this._gasLimitEstimate = UniswapConfig.config.gasLimitEstimate;
     this._router = config.uniswapV3SmartOrderRouterAddress(network);
+    this._quoter = config.uniswapV3QuoterV2Address(network);
   }
 
   public static getInstance(chain: string, network: string): Uniswap {
@@ -66,6 +97,10 @@ export class Uniswap implements Uniswapish {
     return Uniswap._instances[chain + network];
   }
 
+  public static getConnectedInstances(): { [name: string]: Uniswap } {
+    return Uniswap._instances;
+  }
+
   /**
    * Given a token's address, return the connector's native representation of
    * the token.
@@ -80,6 +115,7 @@ export class Uniswap implements Uniswapish {
     if (!this.chain.ready()) {
       await this.chain.init();
     }
+    this.tokenList = {};
     for (const token of this.chain.storedTokenList) {
       this.tokenList[token.address] = new Token(
         this.chainId,
@@ -117,6 +153,14 @@ export class Uniswap implements Uniswapish {
     return this._routerAbi;
   }
 
+  /**
+   * The trades of the smart order router are turned into trades of the
+   * native currency, see toNativeTrade.
+   */
+  public get supportsNativeSwap(): boolean {
+    return true;
+  }
+
   /**
    * Default gas limit used to estimate gasCost for swap transactions.
    */
@@ -172,7 +216,8 @@ export class Uniswap implements Uniswapish {
     baseToken: Token,
     quoteToken: Token,
     amount: BigNumber,
-    allowedSlippage?: string
+    allowedSlippage?: string,
+    routeOptions?: TradeRouteOptions
   ): Promise<ExpectedTrade> {
     const nativeTokenAmount: CurrencyAmount<Token> =
       CurrencyAmount.fromRawAmount(baseToken, amount.toString());
@@ -181,30 +226,27 @@ export class Uniswap implements Uniswapish {
       `Fetching trade data for ${baseToken.address}-${quoteToken.address}.`
     );
 
-    const route = await this._alphaRouter.route(
+    const trade = await this.routeTrade(
       nativeTokenAmount,
       quoteToken,
       TradeType.EXACT_INPUT,
-      undefined,
-      {
-        maxSwapsPerPath: this.maximumHops,
-      }
+      routeOptions
     );
 
-    if (!route) {
+    if (!trade) {
       throw new UniswapishPriceError(
         `priceSwapIn: no trade pair found for ${baseToken} to ${quoteToken}.`
       );
     }
     logger.info(
       `Best trade for ${baseToken.address}-${quoteToken.address}: ` +
-        `${route.trade.executionPrice.toFixed(6)}` +
+        `${trade.executionPrice.toFixed(6)}` +
         `${baseToken.symbol}.`
     );
-    const expectedAmount = route.trade.minimumAmountOut(
+    const expectedAmount = trade.minimumAmountOut(
       this.getAllowedSlippage(allowedSlippage)
     );
-    return { trade: route.trade, expectedAmount };
+    return { trade, expectedAmount };
   }
 
   /**
@@ -221,37 +263,213 @@ export class Uniswap implements Uniswapish {
     quoteToken: Token,
     baseToken: Token,
     amount: BigNumber,
-    allowedSlippage?: string
+    allowedSlippage?: string,
+    routeOptions?: TradeRouteOptions
   ): Promise<ExpectedTrade> {
     const nativeTokenAmount: CurrencyAmount<Token> =
       CurrencyAmount.fromRawAmount(baseToken, amount.toString());
     logger.info(
       `Fetching pair data for ${quoteToken.address}-${baseToken.address}.`
     );
-    const route = await this._alphaRouter.route(
+    const trade = await this.routeTrade(
       nativeTokenAmount,
       quoteToken,
       TradeType.EXACT_OUTPUT,
-      undefined,
-      {
-        maxSwapsPerPath: this.maximumHops,
-      }
+      routeOptions
     );
-    if (!route) {
+    if (!trade) {
       throw new UniswapishPriceError(
         `priceSwapOut: no trade pair found for ${quoteToken.address} to ${baseToken.address}.`
       );
     }
     logger.info(
       `Best trade for ${quoteToken.address}-${baseToken.address}: ` +
-        `${route.trade.executionPrice.invert().toFixed(6)} ` +
+        `${trade.executionPrice.invert().toFixed(6)} ` +
         `${baseToken.symbol}.`
     );
 
-    const expectedAmount = route.trade.maximumAmountIn(
+    const expectedAmount = trade.maximumAmountIn(
       this.getAllowedSlippage(allowedSlippage)
     );
-    return { trade: route.trade, expectedAmount };
+    return { trade, expectedAmount };
+  }
+
+  /**
+   * The best trade of an amount, found by the smart order router unless its
+   * route is restricted. A path or intermediate tokens are quoted route by
+   * route instead. With excluded pools only, the smart order router's trade
+   * is kept when it avoids them, else the direct pools are quoted.
+   *
+   * @param amount The amount paid for exact input trades, else received
+   * @param otherToken The token received for exact input trades, else paid
+   * @param tradeType Exact input or output
+   * @param routeOptions (Optional) Restrictions on the route
+   */
+  async routeTrade(
+    amount: CurrencyAmount<Token>,
+    otherToken: Token,
+    tradeType: TradeType,
+    routeOptions?: TradeRouteOptions
+  ): Promise<Trade<Currency, Currency, TradeType> | undefined> {
+    const options = routeOptions || {};
+    if (!options.path && !options.intermediateTokens) {
+      const route = await this._alphaRouter.route(
+        amount,
+        otherToken,
+        tradeType,
+        undefined,
+        {
+          maxSwapsPerPath: this.maximumHops,
+        }
+      );
+      if (
+        !hasRouteOptions(options) ||
+        (route && !routeViolation(tradeRoute(route.trade), options))
+      ) {
+        return route ? route.trade : undefined;
+      }
+    }
+    return this.quoteRoutes(amount, otherToken, tradeType, options);
+  }
+
+  /**
+   * Quotes the candidate V3 routes of a trade with the quoter contract and
+   * builds the trade of the best one. A path may have at most maximumHops
+   * hops.
+   */
+  async quoteRoutes(
+    amount: CurrencyAmount<Token>,
+    otherToken: Token,
+    tradeType: TradeType,
+    options: TradeRouteOptions
+  ): Promise<Trade<Currency, Currency, TradeType> | undefined> {
+    if (options.path && options.path.length - 1 > this.maximumHops) {
+      throw new UniswapishPriceError(
+        `The path has ${options.path.length - 1} hops, more than the ` +
+          `${this.maximumHops} allowed.`
+      );
+    }
+    const exactOutput = tradeType === TradeType.EXACT_OUTPUT;
+    const tokenIn = exactOutput ? otherToken : amount.currency;
+    const tokenOut = exactOutput ? amount.currency : otherToken;
+    const path = options.path
+      ? options.path.map((address) => this.getKnownToken(address))
+      : [tokenIn, tokenOut];
+    const intermediates =
+      options.path || this.maximumHops < 2
+        ? []
+        : (options.intermediateTokens || []).map((address) =>
+            this.getKnownToken(address)
+          );
+
+    const quoter = this.getQuoterContract();
+    const quotes = await Promise.all(
+      candidateRoutes(path, intermediates, options).map(async (route) => {
+        try {
+          const result = exactOutput
+            ? await quoter.callStatic.quoteExactOutput(
+                encodeRoutePath(route, true),
+                amount.quotient.toString()
+              )
+            : await quoter.callStatic.quoteExactInput(
+                encodeRoutePath(route, false),
+                amount.quotient.toString()
+              );
+          return { route, quoted: BigNumber.from(result[0]) };
+        } catch (e) {
+          // a pool of the route doesn't exist or can't fill the amount
+          return undefined;
+        }
+      })
+    );
+
+    let best: { route: CandidateRoute; quoted: BigNumber } | undefined;
+    for (const quote of quotes) {
+      if (
+        quote &&
+        (!best ||
+          (exactOutput
+            ? quote.quoted.lt(best.quoted)
+            : quote.quoted.gt(best.quoted)))
+      ) {
+        best = quote;
+      }
+    }
+    if (!best) return undefined;
+    logger.info(
+      `Quoted ${quotes.length} routes, the best goes through ` +
+        `${best.route.tokens.map((token) => token.symbol).join('-')}.`
+    );
+    return this.buildTrade(best.route, amount, best.quoted, tradeType);
+  }
+
+  /**
+   * A trade through a single V3 route, of the amounts quoted for it.
+   */
+  async buildTrade(
+    route: CandidateRoute,
+    amount: CurrencyAmount<Token>,
+    quoted: BigNumber,
+    tradeType: TradeType
+  ): Promise<Trade<Currency, Currency, TradeType>> {
+    const pools = await Promise.all(
+      route.fees.map(async (fee, i) => {
+        const tokenA = route.tokens[i];
+        const tokenB = route.tokens[i + 1];
+        const contract = this.getPoolStateContract(
+          V3Pool.getAddress(tokenA, tokenB, fee)
+        );
+        const [slot0, liquidity] = await Promise.all([
+          contract.slot0(),
+          contract.liquidity(),
+        ]);
+        return new V3Pool(
+          tokenA,
+          tokenB,
+          fee,
+          slot0.sqrtPriceX96.toString(),
+          liquidity.toString(),
+          slot0.tick
+        );
+      })
+    );
+    const tokenIn = route.tokens[0];
+    const tokenOut = route.tokens[route.tokens.length - 1];
+    const exactInput = tradeType === TradeType.EXACT_INPUT;
+    const quotedAmount = CurrencyAmount.fromRawAmount(
+      exactInput ? tokenOut : tokenIn,
+      quoted.toString()
+    );
+    return new Trade({
+      v2Routes: [],
+      v3Routes: [
+        {
+          routev3: new V3Route(pools, tokenIn, tokenOut),
+          inputAmount: exactInput ? amount : quotedAmount,
+          outputAmount: exactInput ? quotedAmount : amount,
+        },
+      ],
+      tradeType,
+    });
+  }
+
+  private getKnownToken(address: string): Token {
+    const token = this.getTokenByAddress(address);
+    if (!token) throw new Error(`Token ${address} is not in the token list.`);
+    return token;
+  }
+
+  getQuoterContract(): Contract {
+    if (!this._quoter) {
+      throw new Error(
+        `There is no Uniswap V3 quoter configured for ${this.chain.chain}.`
+      );
+    }
+    return new Contract(this._quoter, QUOTER_V2_ABI, this.chain.provider);
+  }
+
+  getPoolStateContract(pool: string): Contract {
+    return new Contract(pool, POOL_STATE_ABI, this.chain.provider);
   }
 
   /**
@@ -267,6 +485,8 @@ export class Uniswap implements Uniswapish {
    * @param nonce (Optional) EVM transaction nonce
    * @param maxFeePerGas (Optional) Maximum total fee per gas you want to pay
    * @param maxPriorityFeePerGas (Optional) Maximum tip per gas you want to pay
+   * @param allowedSlippage (Optional) Fraction, e.g. '1/100'
+   * @param nativeSwap (Optional) Side of the trade that is the native currency
    */
   async executeTrade(
     wallet: Wallet,
@@ -279,10 +499,11 @@ export class Uniswap implements Uniswapish {
     nonce?: number,
     maxFeePerGas?: BigNumber,
     maxPriorityFeePerGas?: BigNumber,
-    allowedSlippage?: string
+    allowedSlippage?: string,
+    nativeSwap?: NativeSwap
   ): Promise<Transaction> {
     const methodParameters: MethodParameters = SwapRouter.swapCallParameters(
-      trade,
+      toNativeTrade(trade, this.chainId, nativeSwap),
       {
         deadlineOrPreviousBlockhash: Math.floor(Date.now() / 1000 + ttl),
         recipient: wallet.address,
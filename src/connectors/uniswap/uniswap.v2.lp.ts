@@ -0,0 +1,384 @@
+import Decimal from 'decimal.js-light';
+import {
+  BigNumber,
+  constants,
+  Contract,
+  ContractTransaction,
+  Signer,
+  Transaction,
+  utils,
+  Wallet,
+} from 'ethers';
+import { Provider } from '@ethersproject/abstract-provider';
+import IUniswapV2Pair from '@uniswap/v2-core/build/IUniswapV2Pair.json';
+import { TokenInfo } from '../../chains/ethereum/ethereum-base';
+import {
+  Ethereumish,
+  Uniswapish,
+  UniswapV2LPish,
+  V2PositionInfo,
+  V2RemovedLiquidity,
+} from '../../services/common-interfaces';
+import {
+  ConfigManagerV2,
+  percentRegexp,
+} from '../../services/config-manager-v2';
+import { SimulationWallet } from '../../evm/evm.simulation';
+import { logger } from '../../services/logger';
+
+const FACTORY_ABI = [
+  'function getPair(address tokenA, address tokenB) view returns (address pair)',
+];
+
+// every Uniswap V2 style pair mints LP tokens with 18 decimals
+const LP_TOKEN_DECIMALS = 18;
+
+/**
+ * Liquidity provision to the pairs of a Uniswap V2 style exchange, through
+ * the addLiquidity and removeLiquidity functions its router shares with
+ * Uniswap V2's. Pairs are looked up from the router's factory, so the
+ * exchange's SDK and pair init code hash aren't needed.
+ */
+export class UniswapV2LP implements UniswapV2LPish {
+  private static _instances: { [name: string]: UniswapV2LP };
+  private _chain: Ethereumish;
+  private _connector: Uniswapish;
+  private _name: string;
+  private _factory?: string;
+
+  constructor(chain: Ethereumish, connector: Uniswapish, name: string) {
+    this._chain = chain;
+    this._connector = connector;
+    this._name = name;
+  }
+
+  public static getInstance(
+    chain: Ethereumish,
+    connector: Uniswapish,
+    name: string
+  ): UniswapV2LP {
+    if (UniswapV2LP._instances === undefined) {
+      UniswapV2LP._instances = {};
+    }
+    const key = chain.chainName + chain.chain + name;
+    if (!(key in UniswapV2LP._instances)) {
+      UniswapV2LP._instances[key] = new UniswapV2LP(chain, connector, name);
+    }
+    return UniswapV2LP._instances[key];
+  }
+
+  public static getConnectedInstances(): { [name: string]: UniswapV2LP } {
+    return UniswapV2LP._instances;
+  }
+
+  /**
+   * Router address.
+   */
+  public get router(): string {
+    return this._connector.router;
+  }
+
+  /**
+   * Default time-to-live for liquidity transactions, in seconds.
+   */
+  public get ttl(): number {
+    return this._connector.ttl;
+  }
+
+  /**
+   * Default gas limit used to estimate gasCost for liquidity transactions.
+   */
+  public get gasLimitEstimate(): number {
+    return this._connector.gasLimitEstimate;
+  }
+
+  /**
+   * Gets the allowed slippage from the request or, when not set, from the
+   * connector's configuration, as a numerator and a denominator.
+   */
+  getAllowedSlippage(allowedSlippage?: string): [BigNumber, BigNumber] {
+    const slippage =
+      allowedSlippage ||
+      ConfigManagerV2.getInstance().get(`${this._name}.allowedSlippage`);
+    const nd = slippage ? slippage.match(percentRegexp) : null;
+    if (nd) return [BigNumber.from(nd[1]), BigNumber.from(nd[2])];
+    throw new Error(
+      'Encountered a malformed percent string in the config for ALLOWED_SLIPPAGE.'
+    );
+  }
+
+  private minAmount(amount: BigNumber, slippage: [BigNumber, BigNumber]) {
+    const [numerator, denominator] = slippage;
+    return amount.mul(denominator.sub(numerator)).div(denominator);
+  }
+
+  private deadline(): number {
+    return Math.floor(Date.now() / 1000) + this.ttl;
+  }
+
+  getRouterContract(signerOrProvider: Signer | Provider): Contract {
+    return new Contract(
+      this.router,
+      this._connector.routerAbi,
+      signerOrProvider
+    );
+  }
+
+  getPairContract(pair: string, signerOrProvider: Signer | Provider): Contract {
+    return new Contract(pair, IUniswapV2Pair.abi, signerOrProvider);
+  }
+
+  async getPairAddress(tokenA: TokenInfo, tokenB: TokenInfo): Promise<string> {
+    if (!this._factory) {
+      this._factory = await this.getRouterContract(
+        this._chain.provider
+      ).factory();
+    }
+    const factory = new Contract(
+      this._factory as string,
+      FACTORY_ABI,
+      this._chain.provider
+    );
+    const pair: string = await factory.getPair(tokenA.address, tokenB.address);
+    if (pair === constants.AddressZero) {
+      throw new Error(
+        `There is no ${tokenA.symbol}-${tokenB.symbol} pair on ${this._name}.`
+      );
+    }
+    return pair;
+  }
+
+  async getPosition(
+    address: string,
+    tokenA: TokenInfo,
+    tokenB: TokenInfo
+  ): Promise<V2PositionInfo> {
+    const pairAddress = await this.getPairAddress(tokenA, tokenB);
+    const pair = this.getPairContract(pairAddress, this._chain.provider);
+    const [token0, reserves, totalSupply, balance] = await Promise.all([
+      pair.token0(),
+      pair.getReserves(),
+      pair.totalSupply(),
+      pair.balanceOf(address),
+    ]);
+    const aIsToken0 = token0.toLowerCase() === tokenA.address.toLowerCase();
+    const reserveA: BigNumber = aIsToken0 ? reserves[0] : reserves[1];
+    const reserveB: BigNumber = aIsToken0 ? reserves[1] : reserves[0];
+    const redeemable = (reserve: BigNumber) =>
+      totalSupply.isZero()
+        ? BigNumber.from(0)
+        : reserve.mul(balance).div(totalSupply);
+
+    return {
+      pair: pairAddress,
+      tokenA: tokenA.symbol,
+      tokenB: tokenB.symbol,
+      reserveA: utils.formatUnits(reserveA, tokenA.decimals),
+      reserveB: utils.formatUnits(reserveB, tokenB.decimals),
+      lpTokenBalance: utils.formatUnits(balance, LP_TOKEN_DECIMALS),
+      lpTotalSupply: utils.formatUnits(totalSupply, LP_TOKEN_DECIMALS),
+      poolShare: totalSupply.isZero()
+        ? '0'
+        : new Decimal(balance.toString())
+            .div(new Decimal(totalSupply.toString()))
+            .toSignificantDigits(8)
+            .toFixed(),
+      amountA: utils.formatUnits(redeemable(reserveA), tokenA.decimals),
+      amountB: utils.formatUnits(redeemable(reserveB), tokenB.decimals),
+    };
+  }
+
+  async addLiquidity(
+    wallet: Wallet,
+    tokenA: TokenInfo,
+    tokenB: TokenInfo,
+    amountA: string,
+    amountB: string,
+    gasLimit: number,
+    gasPrice: number,
+    nonce?: number,
+    maxFeePerGas?: BigNumber,
+    maxPriorityFeePerGas?: BigNumber,
+    allowedSlippage?: string
+  ): Promise<Transaction> {
+    const slippage = this.getAllowedSlippage(allowedSlippage);
+    const amountADesired = utils.parseUnits(amountA, tokenA.decimals);
+    const amountBDesired = utils.parseUnits(amountB, tokenB.decimals);
+    await Promise.all([
+      this.checkAllowance(
+        wallet,
+        this._chain.getContract(tokenA.address, this._chain.provider),
+        tokenA,
+        amountADesired
+      ),
+      this.checkAllowance(
+        wallet,
+        this._chain.getContract(tokenB.address, this._chain.provider),
+        tokenB,
+        amountBDesired
+      ),
+    ]);
+    const router = this.getRouterContract(wallet);
+
+    return this._chain.nonceManager.provideNonce(
+      nonce,
+      wallet.address,
+      async (nextNonce) => {
+        const tx: ContractTransaction = await router.addLiquidity(
+          tokenA.address,
+          tokenB.address,
+          amountADesired,
+          amountBDesired,
+          this.minAmount(amountADesired, slippage),
+          this.minAmount(amountBDesired, slippage),
+          wallet.address,
+          this.deadline(),
+          this.overrides(
+            gasLimit,
+            gasPrice,
+            nextNonce,
+            maxFeePerGas,
+            maxPriorityFeePerGas
+          )
+        );
+        logger.info(`${this._name} add liquidity Tx Hash: ${tx.hash}`);
+        return tx;
+      }
+    );
+  }
+
+  async removeLiquidity(
+    wallet: Wallet,
+    tokenA: TokenInfo,
+    tokenB: TokenInfo,
+    decreasePercent: number,
+    gasLimit: number,
+    gasPrice: number,
+    nonce?: number,
+    maxFeePerGas?: BigNumber,
+    maxPriorityFeePerGas?: BigNumber,
+    allowedSlippage?: string
+  ): Promise<V2RemovedLiquidity> {
+    const slippage = this.getAllowedSlippage(allowedSlippage);
+    const pairAddress = await this.getPairAddress(tokenA, tokenB);
+    const pair = this.getPairContract(pairAddress, wallet);
+    const [token0, reserves, totalSupply, balance, allowance] =
+      await Promise.all([
+        pair.token0(),
+        pair.getReserves(),
+        pair.totalSupply(),
+        pair.balanceOf(wallet.address),
+        pair.allowance(wallet.address, this.router),
+      ]);
+    const liquidity: BigNumber = balance
+      .mul(Math.round(Math.min(decreasePercent, 100) * 100))
+      .div(10000);
+    if (liquidity.isZero()) {
+      throw new Error(
+        `${wallet.address} holds no ${tokenA.symbol}-${tokenB.symbol} liquidity to remove.`
+      );
+    }
+    const aIsToken0 = token0.toLowerCase() === tokenA.address.toLowerCase();
+    const reserveA: BigNumber = aIsToken0 ? reserves[0] : reserves[1];
+    const reserveB: BigNumber = aIsToken0 ? reserves[1] : reserves[0];
+
+    // a simulation can't send the approval, and the removal would revert
+    // without it
+    let approval: Transaction | undefined;
+    if (allowance.lt(liquidity) && wallet instanceof SimulationWallet) {
+      throw new Error(
+        `${wallet.address} has not approved ${this._name} to spend ` +
+          `${utils.formatUnits(liquidity, LP_TOKEN_DECIMALS)} ` +
+          `${tokenA.symbol}-${tokenB.symbol} LP tokens, a removal that ` +
+          'is not simulated approves them first.'
+      );
+    }
+    if (allowance.lt(liquidity)) {
+      approval = await this._chain.approveERC20(
+        pair,
+        wallet,
+        this.router,
+        liquidity,
+        nonce,
+        maxFeePerGas,
+        maxPriorityFeePerGas,
+        gasPrice
+      );
+      if (nonce !== undefined) nonce += 1;
+    }
+
+    const router = this.getRouterContract(wallet);
+    const transaction: Transaction =
+      await this._chain.nonceManager.provideNonce(
+        nonce,
+        wallet.address,
+        async (nextNonce) => {
+          const tx: ContractTransaction = await router.removeLiquidity(
+            tokenA.address,
+            tokenB.address,
+            liquidity,
+            this.minAmount(reserveA.mul(liquidity).div(totalSupply), slippage),
+            this.minAmount(reserveB.mul(liquidity).div(totalSupply), slippage),
+            wallet.address,
+            this.deadline(),
+            this.overrides(
+              gasLimit,
+              gasPrice,
+              nextNonce,
+              maxFeePerGas,
+              maxPriorityFeePerGas
+            )
+          );
+          logger.info(`${this._name} remove liquidity Tx Hash: ${tx.hash}`);
+          return tx;
+        }
+      );
+    return {
+      approval,
+      transaction,
+      liquidity: utils.formatUnits(liquidity, LP_TOKEN_DECIMALS),
+    };
+  }
+
+  // the router pulls the tokens from the wallet, it must be allowed to
+  private async checkAllowance(
+    wallet: Wallet,
+    contract: Contract,
+    token: TokenInfo,
+    amount: BigNumber
+  ): Promise<void> {
+    const allowance: BigNumber = await contract.allowance(
+      wallet.address,
+      this.router
+    );
+    if (allowance.lt(amount)) {
+      throw new Error(
+        `${wallet.address} has not approved ${this._name} to spend ` +
+          `${utils.formatUnits(amount, token.decimals)} ${token.symbol}, ` +
+          'approve it with /evm/approve first.'
+      );
+    }
+  }
+
+  private overrides(
+    gasLimit: number,
+    gasPrice: number,
+    nonce: number,
+    maxFeePerGas?: BigNumber,
+    maxPriorityFeePerGas?: BigNumber
+  ): Record<string, any> {
+    if (maxFeePerGas !== undefined || maxPriorityFeePerGas !== undefined) {
+      return {
+        gasLimit: gasLimit.toFixed(0),
+        nonce,
+        maxFeePerGas,
+        maxPriorityFeePerGas,
+      };
+    }
+    return {
+      gasPrice: (gasPrice * 1e9).toFixed(0),
+      gasLimit: gasLimit.toFixed(0),
+      nonce,
+    };
+  }
+}
@@ -10,6 +10,7 @@ import {
 import { LocalStorage } from '../services/local-storage';
 import { logger } from '../services/logger';
 import { ReferenceCountingCloseable } from '../services/refcounting-closeable';
+import { TransactionSimulated } from './evm.simulation';
 
 export class NonceInfo {
   constructor(readonly nonce: number, public expiry: number) {}
@@ -460,6 +461,8 @@ export class EVMNonceManager extends ReferenceCountingCloseable {
       await this.commitNonce(ethAddress, nextNonce);
       return result;
     } catch (err) {
+      // nothing was sent, the pending nonces are still valid
+      if (err instanceof TransactionSimulated) throw err;
       logger.error(
         `Transaction with nonce ${nextNonce} for address ${ethAddress} failed : ${err}`
       );
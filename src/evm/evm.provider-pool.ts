@@ -0,0 +1,96 @@
+import { providers } from 'ethers';
+import {
+  RpcEndpointConfig,
+  RpcEndpointHealth,
+  RpcEndpointPool,
+} from '../services/rpc-endpoint-pool';
+
+// balances and receipts are what trading decisions are made on, so a lagging
+// or misbehaving node must not be able to answer them alone. A receipt that
+// only some nodes have yet is pending rather than a disagreement.
+const QUORUM_METHODS = ['eth_getBalance', 'eth_getTransactionReceipt'];
+const BALANCE_OF_SELECTOR = '0x70a08231';
+
+// a signed transaction sent again through another endpoint after a timeout
+// would come back as "already known" or "nonce too low" though it went out
+const SEND_METHODS = ['eth_sendRawTransaction'];
+
+// JSON-RPC error codes that rate limited endpoints answer with
+const RATE_LIMIT_ERROR_CODES = [-32005, 429];
+
+export function isQuorumRead(method: string, params: Array<any>): boolean {
+  if (QUORUM_METHODS.includes(method)) return true;
+  return (
+    method === 'eth_call' &&
+    params.length > 0 &&
+    typeof params[0].data === 'string' &&
+    params[0].data.startsWith(BALANCE_OF_SELECTOR)
+  );
+}
+
+// transport failures and rate limits are worth another endpoint, JSON-RPC
+// errors such as a reverted call or a nonce that is too low are not
+export function isRetryableEvmError(error: any): boolean {
+  if (['SERVER_ERROR', 'TIMEOUT', 'NETWORK_ERROR'].includes(error.code)) {
+    return true;
+  }
+  if (RATE_LIMIT_ERROR_CODES.includes(error.code)) return true;
+  return /rate limit|too many requests/i.test(String(error.message));
+}
+
+/**
+ * A JSON-RPC provider backed by several endpoints. It can be used anywhere a
+ * StaticJsonRpcProvider can (contracts, wallets, connector SDKs), since every
+ * request ends up in `send`, which hands it to the endpoint pool.
+ */
+export class EvmProviderPool extends providers.StaticJsonRpcProvider {
+  readonly pool: RpcEndpointPool<providers.StaticJsonRpcProvider>;
+
+  constructor(endpoints: RpcEndpointConfig[], chainId: number, quorum = 1) {
+    super(endpoints[0].url);
+    this.pool = new RpcEndpointPool(
+      endpoints,
+      // the pool does the retrying, the endpoints give up on the first 429
+      (url: string) =>
+        new providers.StaticJsonRpcProvider(
+          { url, throttleLimit: 1 },
+          chainId
+        ),
+      { quorum, isRetryable: isRetryableEvmError }
+    );
+  }
+
+  public health(): RpcEndpointHealth[] {
+    return this.pool.health();
+  }
+
+  async send(method: string, params: Array<any>): Promise<any> {
+    const request = { method, params, id: this._nextId++, jsonrpc: '2.0' };
+    this.emit('debug', { action: 'request', request, provider: this });
+
+    const call = (provider: providers.StaticJsonRpcProvider) =>
+      provider.send(method, params);
+    try {
+      const result = SEND_METHODS.includes(method)
+        ? await this.pool.executeOnce(call)
+        : isQuorumRead(method, params)
+        ? await this.pool.executeQuorum(call)
+        : await this.pool.execute(call);
+      this.emit('debug', {
+        action: 'response',
+        request,
+        response: result,
+        provider: this,
+      });
+      return result;
+    } catch (error) {
+      this.emit('debug', {
+        action: 'response',
+        error,
+        request,
+        provider: this,
+      });
+      throw error;
+    }
+  }
+}
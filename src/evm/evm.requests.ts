@@ -2,6 +2,7 @@ import {
   CustomTransaction,
   NetworkSelectionRequest,
 } from '../services/common-interfaces';
+import { SimulationResult } from './evm.simulation';
 
 export interface NonceRequest extends NetworkSelectionRequest {
   address: string; // the users public Ethereum key
@@ -32,6 +33,8 @@ export interface ApproveRequest extends NetworkSelectionRequest {
   address: string; // the user's public Ethereum key
   spender: string; // the address of the spend (or a pre-defined string like 'uniswap', 'balancer', etc.)
   token: string; // the token symbol the spender will be approved for
+  simulate?: boolean; // dry-run the approval instead of sending it
+  simulationBlock?: number; // defaults to the latest block
 }
 
 export interface ApproveResponse {
@@ -42,7 +45,57 @@ export interface ApproveResponse {
   spender: string;
   amount: string;
   nonce: number;
-  approval: CustomTransaction;
+  approval?: CustomTransaction; // not set when simulating
+  simulation?: SimulationResult;
+}
+
+export interface TransferRequest extends NetworkSelectionRequest {
+  address: string; // the sender's public Ethereum key
+  to: string; // the receiving address
+  token: string; // a token symbol, or the native currency symbol
+  amount: string; // in units of the token, e.g. '1.5'
+  nonce?: number; // the address's next nonce
+  maxFeePerGas?: string;
+  maxPriorityFeePerGas?: string;
+  simulate?: boolean; // dry-run the transfer instead of sending it
+  simulationBlock?: number; // defaults to the latest block
+}
+
+export interface TransferResponse {
+  network: string;
+  timestamp: number;
+  latency: number;
+  token: string;
+  tokenAddress?: string; // not set for the native currency
+  to: string;
+  amount: string;
+  nonce: number;
+  txHash?: string; // not set when simulating
+  transfer?: CustomTransaction; // not set when simulating
+  simulation?: SimulationResult;
+}
+
+export interface WrapRequest extends NetworkSelectionRequest {
+  address: string; // the user's public Ethereum key
+  amount: string; // in units of the native currency, e.g. '1.5'
+  nonce?: number; // the address's next nonce
+  maxFeePerGas?: string;
+  maxPriorityFeePerGas?: string;
+  simulate?: boolean; // dry-run the transaction instead of sending it
+  simulationBlock?: number; // defaults to the latest block
+}
+
+export interface WrapResponse {
+  network: string;
+  timestamp: number;
+  latency: number;
+  token: string; // the symbol of the wrapped native token
+  tokenAddress: string;
+  amount: string;
+  nonce: number;
+  txHash?: string; // not set when simulating
+  transaction?: CustomTransaction; // not set when simulating
+  simulation?: SimulationResult;
 }
 
 export interface CancelRequest extends NetworkSelectionRequest {
@@ -56,3 +109,20 @@ export interface CancelResponse {
   latency: number;
   txHash: string | undefined;
 }
+
+export interface SpeedUpRequest extends NetworkSelectionRequest {
+  txHash: string; // the hash of the pending transaction to replace
+  address: string; // the user's public Ethereum key
+}
+
+export interface SpeedUpResponse {
+  network: string;
+  timestamp: number;
+  latency: number;
+  originalTxHash: string;
+  txHash: string | undefined;
+  nonce: number;
+  gasPrice: string | null;
+  maxFeePerGas: string | null;
+  maxPriorityFeePerGas: string | null;
+}
@@ -9,6 +9,10 @@ import {
   nonce,
   nextNonce,
   cancel,
+  speedUp,
+  transfer,
+  unwrap,
+  wrap,
 } from '../chains/ethereum/ethereum.controllers';
 
 import {
@@ -16,6 +20,9 @@ import {
   validateApproveRequest,
   validateCancelRequest,
   validateNonceRequest,
+  validateSpeedUpRequest,
+  validateTransferRequest,
+  validateWrapRequest,
 } from '../chains/ethereum/ethereum.validators';
 import { getChain } from '../services/connection-manager';
 import {
@@ -27,6 +34,12 @@ import {
   CancelResponse,
   NonceRequest,
   NonceResponse,
+  SpeedUpRequest,
+  SpeedUpResponse,
+  TransferRequest,
+  TransferResponse,
+  WrapRequest,
+  WrapResponse,
 } from './evm.requests';
 
 export namespace EVMRoutes {
@@ -100,6 +113,57 @@ export namespace EVMRoutes {
     )
   );
 
+  router.post(
+    '/transfer',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, TransferRequest>,
+        res: Response<TransferResponse, {}>
+      ) => {
+        validateTransferRequest(req.body);
+        const chain = await getChain<Ethereumish>(
+          req.body.chain,
+          req.body.network
+        );
+        res.status(200).json(await transfer(chain, req.body));
+      }
+    )
+  );
+
+  router.post(
+    '/wrap',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, WrapRequest>,
+        res: Response<WrapResponse, {}>
+      ) => {
+        validateWrapRequest(req.body);
+        const chain = await getChain<Ethereumish>(
+          req.body.chain,
+          req.body.network
+        );
+        res.status(200).json(await wrap(chain, req.body));
+      }
+    )
+  );
+
+  router.post(
+    '/unwrap',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, WrapRequest>,
+        res: Response<WrapResponse, {}>
+      ) => {
+        validateWrapRequest(req.body);
+        const chain = await getChain<Ethereumish>(
+          req.body.chain,
+          req.body.network
+        );
+        res.status(200).json(await unwrap(chain, req.body));
+      }
+    )
+  );
+
   router.post(
     '/cancel',
     asyncHandler(
@@ -116,4 +180,21 @@ export namespace EVMRoutes {
       }
     )
   );
+
+  router.post(
+    '/speedup',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, SpeedUpRequest>,
+        res: Response<SpeedUpResponse, {}>
+      ) => {
+        validateSpeedUpRequest(req.body);
+        const chain = await getChain<Ethereumish>(
+          req.body.chain,
+          req.body.network
+        );
+        res.status(200).json(await speedUp(chain, req.body));
+      }
+    )
+  );
 }
@@ -0,0 +1,206 @@
+import { BigNumber, providers, utils, Wallet } from 'ethers';
+
+export interface SimulationResult {
+  success: boolean;
+  blockNumber: number; // the block the transaction was simulated against
+  nonce: number; // the nonce the transaction would have been sent with
+  to: string;
+  data: string;
+  value: string;
+  gasUsed?: string;
+  output?: string; // the raw return data
+  amounts?: string[]; // the amounts returned by a swap, in token units
+  revertReason?: string;
+}
+
+// Error(string) and Panic(uint256), see
+// https://docs.soliditylang.org/en/latest/control-structures.html#revert
+const ERROR_SELECTOR = '0x08c379a0';
+const PANIC_SELECTOR = '0x4e487b71';
+
+// multicall(bytes[]), multicall(uint256,bytes[]) and multicall(bytes32,bytes[])
+// of the Uniswap V3 routers
+const MULTICALL_SELECTORS = ['0xac9650d8', '0x5ae401dc', '0x1f0464d1'];
+
+/**
+ * Thrown by SimulationWallet in place of broadcasting a transaction. It
+ * carries the simulation out of the connector's executeTrade, past the nonce
+ * manager, which must not count the nonce as used.
+ */
+export class TransactionSimulated extends Error {
+  readonly result: SimulationResult;
+
+  constructor(result: SimulationResult) {
+    super('The transaction was simulated, not sent.');
+    this.name = 'TransactionSimulated';
+    this.result = result;
+  }
+}
+
+/**
+ * A wallet that runs the transactions it is asked to send through eth_call and
+ * eth_estimateGas instead. Connectors build and "send" their transactions
+ * exactly as they would with the real wallet, so the exact calldata is
+ * simulated.
+ */
+export class SimulationWallet extends Wallet {
+  readonly blockNumber: number;
+  readonly nonce: number;
+  readonly decodeAmounts: boolean;
+
+  constructor(
+    wallet: Wallet,
+    blockNumber: number,
+    nonce: number,
+    decodeAmounts: boolean
+  ) {
+    super(wallet.privateKey, wallet.provider);
+    this.blockNumber = blockNumber;
+    this.nonce = nonce;
+    this.decodeAmounts = decodeAmounts;
+  }
+
+  async sendTransaction(
+    transaction: utils.Deferrable<providers.TransactionRequest>
+  ): Promise<providers.TransactionResponse> {
+    const tx = await utils.resolveProperties(transaction);
+    throw new TransactionSimulated(
+      await simulateCall(
+        <providers.JsonRpcProvider>this.provider,
+        {
+          from: this.address,
+          to: tx.to,
+          data: tx.data,
+          value: tx.value,
+          gasLimit: tx.gasLimit,
+        },
+        this.blockNumber,
+        this.nonce,
+        this.decodeAmounts
+      )
+    );
+  }
+}
+
+/**
+ * Runs send, which should build and send a transaction with the wallet and
+ * nonce it is given, against the given block (the latest by default) without
+ * broadcasting anything. The nonce defaults to the account's next one
+ * according to the node; the nonce manager is left alone.
+ */
+export async function simulateTransaction(
+  provider: providers.JsonRpcProvider,
+  wallet: Wallet,
+  send: (wallet: Wallet, nonce: number) => Promise<any>,
+  nonce?: number,
+  blockNumber?: number,
+  decodeAmounts: boolean = false
+): Promise<SimulationResult> {
+  const block =
+    blockNumber !== undefined ? blockNumber : await provider.getBlockNumber();
+  if (nonce === undefined) {
+    nonce = await provider.getTransactionCount(
+      wallet.address,
+      blockNumber !== undefined ? blockNumber : 'pending'
+    );
+  }
+  try {
+    await send(
+      new SimulationWallet(wallet, block, nonce, decodeAmounts),
+      nonce
+    );
+  } catch (e) {
+    if (e instanceof TransactionSimulated) return e.result;
+    throw e;
+  }
+  throw new Error('No transaction was sent, there is nothing to simulate.');
+}
+
+export async function simulateCall(
+  provider: providers.JsonRpcProvider,
+  tx: providers.TransactionRequest,
+  blockNumber: number,
+  nonce: number,
+  decodeAmounts: boolean
+): Promise<SimulationResult> {
+  const request: Record<string, any> =
+    providers.JsonRpcProvider.hexlifyTransaction(tx, { from: true });
+  const blockTag = utils.hexValue(blockNumber);
+  const result: SimulationResult = {
+    success: false,
+    blockNumber,
+    nonce,
+    to: request.to,
+    data: request.data || '0x',
+    value: BigNumber.from(tx.value || 0).toString(),
+  };
+  try {
+    result.output = await provider.send('eth_call', [request, blockTag]);
+    // the gas actually needed, rather than the limit the connector set
+    delete request.gas;
+    const gasUsed = await provider.send('eth_estimateGas', [request, blockTag]);
+    result.gasUsed = BigNumber.from(gasUsed).toString();
+    result.success = true;
+  } catch (e) {
+    result.revertReason = decodeRevertReason(e);
+    return result;
+  }
+  if (decodeAmounts && result.output) {
+    result.amounts = decodeOutputAmounts(result.data, result.output);
+  }
+  return result;
+}
+
+// the revert data may be nested in the errors of the providers involved
+function findRevertData(error: any): string | undefined {
+  for (let e = error; e; e = e.error) {
+    if (typeof e.data === 'string' && utils.isHexString(e.data)) return e.data;
+    if (e.data && typeof e.data.data === 'string') return e.data.data;
+  }
+  return undefined;
+}
+
+export function decodeRevertReason(error: any): string {
+  const data = findRevertData(error);
+  try {
+    if (data && data.startsWith(ERROR_SELECTOR)) {
+      return utils.defaultAbiCoder.decode(
+        ['string'],
+        utils.hexDataSlice(data, 4)
+      )[0];
+    }
+    if (data && data.startsWith(PANIC_SELECTOR)) {
+      return `Panic(${BigNumber.from(
+        utils.hexDataSlice(data, 4)
+      ).toHexString()})`;
+    }
+  } catch (_e) {
+    // not ABI encoded after all, return it as is
+  }
+  // custom errors can only be decoded with the contract's ABI
+  if (data && data !== '0x') return data;
+  return error.reason || error.message || String(error);
+}
+
+// swaps return the amounts as uint256 (Uniswap V3 exact input/output), as
+// uint256[] (V2 style routers), or one of those per call of a multicall
+export function decodeOutputAmounts(
+  calldata: string,
+  output: string
+): string[] | undefined {
+  try {
+    if (utils.hexDataLength(output) === 32) {
+      return [BigNumber.from(output).toString()];
+    }
+    if (MULTICALL_SELECTORS.includes(calldata.slice(0, 10))) {
+      const [results] = utils.defaultAbiCoder.decode(['bytes[]'], output);
+      return results
+        .filter((result: string) => utils.hexDataLength(result) === 32)
+        .map((result: string) => BigNumber.from(result).toString());
+    }
+    const [amounts] = utils.defaultAbiCoder.decode(['uint256[]'], output);
+    return amounts.map((amount: BigNumber) => amount.toString());
+  } catch (_e) {
+    return undefined;
+  }
+}
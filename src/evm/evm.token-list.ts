@@ -0,0 +1,79 @@
+import { promises as fs } from 'fs';
+import path from 'path';
+import { TokenInfo } from '../chains/ethereum/ethereum-base';
+import { TokenListType } from '../services/base';
+import { logger } from '../services/logger';
+
+// user tokens live apart from the lists shipped in src/chains, so that
+// updating gateway doesn't overwrite them
+export const tokenOverlayPath = './conf/tokens';
+
+export interface TokenListSource {
+  type: TokenListType;
+  source: string;
+}
+
+// what a user changed on top of the configured token list of a chain
+export interface TokenOverlay {
+  sources: TokenListSource[]; // imported lists, in the order they were added
+  tokens: TokenInfo[]; // tokens added one by one
+  removed: string[]; // addresses hidden from every list
+}
+
+export const emptyTokenOverlay = (): TokenOverlay => ({
+  sources: [],
+  tokens: [],
+  removed: [],
+});
+
+export const tokenOverlayFile = (chainName: string, chainId: number) =>
+  path.join(tokenOverlayPath, `${chainName}-${chainId}.json`);
+
+export async function readTokenOverlay(file: string): Promise<TokenOverlay> {
+  try {
+    return {
+      ...emptyTokenOverlay(),
+      ...JSON.parse(await fs.readFile(file, 'utf8')),
+    };
+  } catch (e) {
+    if ((e as NodeJS.ErrnoException).code === 'ENOENT') {
+      return emptyTokenOverlay();
+    }
+    throw e;
+  }
+}
+
+export async function writeTokenOverlay(
+  file: string,
+  overlay: TokenOverlay
+): Promise<void> {
+  await fs.mkdir(path.dirname(file), { recursive: true });
+  await fs.writeFile(file, JSON.stringify(overlay, null, 2));
+}
+
+/**
+ * Merges token lists given from the lowest to the highest priority. A token
+ * replaces the tokens of earlier lists that share its address or its symbol,
+ * so a symbol always resolves to the token of the list that was added last.
+ * Tokens within a single list are kept as they are.
+ */
+export function mergeTokenLists(lists: TokenInfo[][]): TokenInfo[] {
+  let merged: TokenInfo[] = [];
+  for (const list of lists) {
+    const addresses = new Set(list.map((t) => t.address.toLowerCase()));
+    const symbols = new Set(list.map((t) => t.symbol.toUpperCase()));
+    const kept = merged.filter(
+      (token) =>
+        !addresses.has(token.address.toLowerCase()) &&
+        !symbols.has(token.symbol.toUpperCase())
+    );
+    if (kept.length < merged.length) {
+      logger.info(
+        `${merged.length - kept.length} tokens were replaced by tokens of ` +
+          'a later token list with the same address or symbol.'
+      );
+    }
+    merged = kept.concat(list);
+  }
+  return merged;
+}
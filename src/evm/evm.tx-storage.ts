@@ -61,6 +61,51 @@ export class EvmTxStorage extends ReferenceCountingCloseable {
     });
   }
 
+  // link a transaction to the one that replaced it (e.g. after a speed up).
+  // These keys have four parts, so getTxs doesn't pick them up.
+  public async saveReplacement(
+    chain: string,
+    chainId: number,
+    tx: string,
+    replacementTx: string
+  ): Promise<void> {
+    return this.localStorage.save(
+      chain + '/' + String(chainId) + '/' + tx + '/replacement',
+      replacementTx
+    );
+  }
+
+  // follow the replacement links and return the latest replacement, if any
+  public async getReplacement(
+    chain: string,
+    chainId: number,
+    tx: string
+  ): Promise<string | undefined> {
+    const replacements: Record<string, string> = await this.localStorage.get(
+      (key: string, value: string) => {
+        const splitKey = key.split('/');
+        if (
+          splitKey.length === 4 &&
+          splitKey[0] === chain &&
+          splitKey[1] === String(chainId) &&
+          splitKey[3] === 'replacement'
+        ) {
+          return [splitKey[2], value];
+        }
+        return;
+      }
+    );
+
+    let replacement: string | undefined;
+    const seen = new Set<string>([tx]);
+    while (replacements[tx] && !seen.has(replacements[tx])) {
+      tx = replacements[tx];
+      replacement = tx;
+      seen.add(tx);
+    }
+    return replacement;
+  }
+
   public async close(handle: string): Promise<void> {
     await super.close(handle);
     if (this.refCount < 1) {
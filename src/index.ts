@@ -1,3 +1,20 @@
 import { startGateway } from './app';
+import { GatewayLifecycle } from './lifecycle';
+import { logger } from './services/logger';
 
-startGateway();
+const lifecycle = GatewayLifecycle.getInstance();
+
+// docker stop sends SIGTERM, ctrl-c sends SIGINT
+for (const signal of ['SIGTERM', 'SIGINT']) {
+  process.once(signal, () => {
+    lifecycle
+      .shutdown(signal)
+      .then(() => process.exit(0))
+      .catch((e) => {
+        logger.error(`Failed to shut down the gateway: ${e}`);
+        process.exit(1);
+      });
+  });
+}
+
+lifecycle.start(startGateway);
@@ -0,0 +1,172 @@
+import { NextFunction, Request, Response } from 'express';
+import { Server as HttpServer } from 'http';
+import { Server as HttpsServer } from 'https';
+import { EventStreamManager } from './network/network.events';
+import { ConfigManagerV2 } from './services/config-manager-v2';
+import {
+  closeAllInstances,
+  registerConfigReloaders,
+} from './services/config-reload';
+import {
+  HttpException,
+  SERVER_STOPPING_ERROR_CODE,
+  SERVER_STOPPING_ERROR_MESSAGE,
+} from './services/error-handler';
+import { closeJournal } from './services/history/history.controllers';
+import { logger } from './services/logger';
+
+export type GatewayServer = HttpServer | HttpsServer;
+
+// docker stop sends SIGKILL 10 seconds after SIGTERM, whatever is still
+// running by then has to be given up before
+export const DRAIN_TIMEOUT = 8000;
+
+// resolves to false if the promise didn't settle in time
+const within = async (
+  promise: Promise<unknown>,
+  timeout: number
+): Promise<boolean> => {
+  let timer: ReturnType<typeof setTimeout> | undefined;
+  const timedOut = new Promise<boolean>((resolve) => {
+    timer = setTimeout(() => resolve(false), timeout);
+  });
+  const settled = await Promise.race([promise.then(() => true), timedOut]);
+  clearTimeout(timer);
+  return settled;
+};
+
+/**
+ * Stops and starts the gateway without leaving the process. A stop refuses
+ * new requests, waits for the running ones, then closes the servers and every
+ * chain instance, which releases the nonce and transaction databases. All of
+ * it waits at most DRAIN_TIMEOUT in total.
+ */
+export class GatewayLifecycle {
+  private static _instance: GatewayLifecycle;
+  private _start: (() => Promise<GatewayServer[]>) | null = null;
+  private _servers: GatewayServer[] = [];
+  private _accepting: boolean = true;
+  private _inFlight: number = 0;
+  private _drained: Array<() => void> = [];
+  private _stopping: Promise<void> | null = null;
+
+  public static getInstance(): GatewayLifecycle {
+    if (!GatewayLifecycle._instance) {
+      GatewayLifecycle._instance = new GatewayLifecycle();
+    }
+    return GatewayLifecycle._instance;
+  }
+
+  public get accepting(): boolean {
+    return this._accepting;
+  }
+
+  public get inFlight(): number {
+    return this._inFlight;
+  }
+
+  /**
+   * Counts the running requests, and turns new ones away while stopping.
+   */
+  public readonly trackRequests = (
+    _req: Request,
+    res: Response,
+    next: NextFunction
+  ): void => {
+    if (!this._accepting) {
+      res.set('Connection', 'close');
+      return next(
+        new HttpException(
+          503,
+          SERVER_STOPPING_ERROR_MESSAGE,
+          SERVER_STOPPING_ERROR_CODE
+        )
+      );
+    }
+
+    this._inFlight += 1;
+    let done = false;
+    const finished = () => {
+      if (done) return;
+      done = true;
+      this._inFlight -= 1;
+      if (this._inFlight === 0) {
+        this._drained.splice(0).forEach((resolve) => resolve());
+      }
+    };
+    res.on('finish', finished);
+    res.on('close', finished);
+    next();
+  };
+
+  /**
+   * Resolves once no request is running, or after the timeout.
+   */
+  public async drain(timeout: number = DRAIN_TIMEOUT): Promise<void> {
+    if (this._inFlight === 0) return;
+    const drained = new Promise<void>((resolve) => this._drained.push(resolve));
+    if (!(await within(drained, timeout))) {
+      logger.warn(`Gave up waiting for ${this._inFlight} requests to finish.`);
+    }
+  }
+
+  public async start(start: () => Promise<GatewayServer[]>): Promise<void> {
+    this._start = start;
+    this._servers = await start();
+    this._accepting = true;
+  }
+
+  public async stop(timeout: number = DRAIN_TIMEOUT): Promise<void> {
+    if (this._stopping) return this._stopping;
+    this._stopping = this.doStop(timeout).finally(() => {
+      this._stopping = null;
+    });
+    return this._stopping;
+  }
+
+  private async doStop(timeout: number): Promise<void> {
+    const deadline = Date.now() + timeout;
+    const remaining = () => Math.max(0, deadline - Date.now());
+    this._accepting = false;
+    // no new connections, the open ones keep being served until drained
+    const closed = this._servers.map(
+      (server) => new Promise<void>((resolve) => server.close(() => resolve()))
+    );
+    await this.drain(remaining());
+
+    await EventStreamManager.getInstance().close();
+    const instances = await closeAllInstances();
+    logger.info(`Closed ${instances.length} chain and connector instances.`);
+    await closeJournal();
+
+    // idle keep-alive connections can hold a server open until they time out
+    if (!(await within(Promise.all(closed), remaining()))) {
+      logger.warn('Gave up waiting for the servers to close.');
+    }
+    this._servers = [];
+  }
+
+  /**
+   * Stops the gateway, then starts it again with the configuration files as
+   * they are now.
+   */
+  public async restart(): Promise<void> {
+    if (this._start === null) {
+      throw new Error('The gateway cannot restart before it started.');
+    }
+    if (!this._accepting) return; // already stopping
+    logger.info('Restarting the gateway.');
+    await this.stop();
+    ConfigManagerV2.reload();
+    registerConfigReloaders();
+    await this.start(this._start);
+  }
+
+  /**
+   * Stops the gateway for good, on SIGTERM or SIGINT.
+   */
+  public async shutdown(signal: string): Promise<void> {
+    logger.info(`Received ${signal}, shutting down the gateway.`);
+    await this.stop();
+  }
+}
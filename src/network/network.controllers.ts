@@ -1,8 +1,13 @@
 import {
+  AddTokenRequest,
+  ImportTokensRequest,
+  ImportTokensResponse,
+  RemoveTokenRequest,
   StatusRequest,
   StatusResponse,
   TokensRequest,
   TokensResponse,
+  TokenUpdateResponse,
 } from './network.requests';
 import { Avalanche } from '../chains/avalanche/avalanche';
 import { BinanceSmartChain } from '../chains/binance-smart-chain/binance-smart-chain';
@@ -12,13 +17,20 @@ import { Polygon } from '../chains/polygon/polygon';
 import { TokenInfo } from '../chains/ethereum/ethereum-base';
 import {
   HttpException,
+  TOKEN_LIST_UPDATE_FAILED_ERROR_CODE,
+  TOKEN_LIST_UPDATE_FAILED_ERROR_MESSAGE,
   UNKNOWN_CHAIN_ERROR_CODE,
   UNKNOWN_KNOWN_CHAIN_ERROR_MESSAGE,
 } from '../services/error-handler';
 import { EthereumBase } from '../chains/ethereum/ethereum-base';
 import { Cronos } from '../chains/cronos/cronos';
 import { Near } from '../chains/near/near';
-import { Nearish } from '../services/common-interfaces';
+import {
+  Ethereumish,
+  Nearish,
+  NetworkSelectionRequest,
+} from '../services/common-interfaces';
+import { ChainUnion, getChain } from '../services/connection-manager';
 
 export async function getStatus(
   req: StatusRequest
@@ -110,6 +122,7 @@ export async function getStatus(
       rpcUrl,
       currentBlockNumber,
       nativeCurrency,
+      rpcEndpoints: connection.rpcHealth(),
     });
   }
 
@@ -164,3 +177,63 @@ export async function getTokens(req: TokensRequest): Promise<TokensResponse> {
 
   return { tokens };
 }
+
+// only EVM chains keep user tokens, read from ERC-20 contracts
+async function getTokenListChain(
+  req: NetworkSelectionRequest
+): Promise<Ethereumish> {
+  const chain = await getChain<ChainUnion>(req.chain, req.network);
+  if (!('tokenListRevision' in chain)) {
+    throw new HttpException(
+      500,
+      TOKEN_LIST_UPDATE_FAILED_ERROR_MESSAGE(
+        `the tokens of ${req.chain} can't be changed`
+      ),
+      TOKEN_LIST_UPDATE_FAILED_ERROR_CODE
+    );
+  }
+  return chain;
+}
+
+async function updateTokenList<T>(update: () => Promise<T>): Promise<T> {
+  try {
+    return await update();
+  } catch (e) {
+    throw new HttpException(
+      500,
+      TOKEN_LIST_UPDATE_FAILED_ERROR_MESSAGE((e as Error).message),
+      TOKEN_LIST_UPDATE_FAILED_ERROR_CODE
+    );
+  }
+}
+
+export async function addToken(
+  req: AddTokenRequest
+): Promise<TokenUpdateResponse> {
+  const chain = await getTokenListChain(req);
+  const token = await updateTokenList(() => chain.addToken(req.address));
+  return { network: chain.chain, timestamp: Date.now(), token };
+}
+
+export async function removeToken(
+  req: RemoveTokenRequest
+): Promise<TokenUpdateResponse> {
+  const chain = await getTokenListChain(req);
+  const token = await updateTokenList(() => chain.removeToken(req.token));
+  return { network: chain.chain, timestamp: Date.now(), token };
+}
+
+export async function importTokens(
+  req: ImportTokensRequest
+): Promise<ImportTokensResponse> {
+  const chain = await getTokenListChain(req);
+  const tokens = await updateTokenList(() =>
+    chain.importTokenList(req.source, req.type)
+  );
+  return {
+    network: chain.chain,
+    timestamp: Date.now(),
+    source: req.source,
+    tokens: tokens.length,
+  };
+}
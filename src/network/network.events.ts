@@ -0,0 +1,414 @@
+import { Server as HttpServer } from 'http';
+import { Server as HttpsServer } from 'https';
+import { v4 as uuidv4 } from 'uuid';
+import { WebSocket, WebSocketServer } from 'ws';
+import { Cosmos } from '../chains/cosmos/cosmos';
+import { balances as cosmosBalances } from '../chains/cosmos/cosmos.controllers';
+import { NewBlockHandler } from '../chains/ethereum/ethereum-base';
+import {
+  balances as ethereumBalances,
+  toEthereumTransactionReceipt,
+} from '../chains/ethereum/ethereum.controllers';
+import {
+  validateChain,
+  validateNetwork,
+} from '../chains/ethereum/ethereum.validators';
+import { authenticator } from '../services/auth';
+import { Ethereumish } from '../services/common-interfaces';
+import { getChain } from '../services/connection-manager';
+import {
+  gatewayErrorMiddleware,
+  HttpException,
+} from '../services/error-handler';
+import { logger } from '../services/logger';
+import {
+  mkRequestValidator,
+  mkSelectingValidator,
+  mkValidator,
+  RequestValidator,
+  validateTokenSymbols,
+  validateTxHash,
+  Validator,
+} from '../services/validators';
+import {
+  EventChannel,
+  EventMessage,
+  EventSubscribeRequest,
+} from './network.requests';
+
+export const EVENT_STREAM_PATH = '/ws';
+
+export const invalidSubscriptionIdError: string =
+  'The id param must be the string returned when subscribing.';
+
+export const unknownSubscriptionError = (id: string): string =>
+  `This connection has no subscription ${id}.`;
+
+export const invalidEventAddressError: string =
+  'The address param must be a string.';
+
+const validateSubscriptionId: Validator = mkValidator(
+  'id',
+  invalidSubscriptionIdError,
+  (val) => typeof val === 'string'
+);
+
+const validateEventAddress: Validator = mkValidator(
+  'address',
+  invalidEventAddressError,
+  (val) => typeof val === 'string'
+);
+
+const validateChannel: Validator = mkSelectingValidator(
+  'channel',
+  (req, key) => req[key],
+  {
+    blocks: () => [],
+    tx: validateTxHash,
+    balances: (req) =>
+      validateEventAddress(req).concat(validateTokenSymbols(req)),
+  }
+);
+
+export const validateEventRequest: RequestValidator = mkRequestValidator([
+  mkSelectingValidator('action', (req, key) => req[key], {
+    subscribe: (req) =>
+      validateChain(req).concat(validateNetwork(req), validateChannel(req)),
+    unsubscribe: validateSubscriptionId,
+  }),
+]);
+
+type BlockSource = Ethereumish | Cosmos;
+
+interface Subscription {
+  id: string;
+  socket: WebSocket;
+  channel: EventChannel;
+  request: EventSubscribeRequest;
+  lastValue?: string; // serialized payload of the last push, to skip repeats
+}
+
+interface NetworkWatcher {
+  source: BlockSource;
+  handler: NewBlockHandler;
+  subscriptions: Set<string>;
+}
+
+/**
+ * Pushes new blocks, transaction receipts and balance changes to WebSocket
+ * clients, so they don't need to poll `/network/poll` and `/network/balances`.
+ *
+ * A single block listener is registered per chain/network, no matter how many
+ * clients subscribe to it. Every new block re-evaluates the subscriptions of
+ * that network; the listener is removed when the last one goes away.
+ */
+export class EventStreamManager {
+  private static _instance: EventStreamManager;
+  private _server: WebSocketServer | null = null;
+  private _subscriptions: Record<string, Subscription> = {};
+  private _watchers: Record<string, NetworkWatcher> = {};
+  // watchers whose block source is still being set up, so that concurrent
+  // subscriptions to a network share one listener
+  private _pendingWatchers: Record<string, Promise<NetworkWatcher>> = {};
+
+  public static getInstance(): EventStreamManager {
+    if (!EventStreamManager._instance) {
+      EventStreamManager._instance = new EventStreamManager();
+    }
+    return EventStreamManager._instance;
+  }
+
+  public get subscriptions(): Record<string, Subscription> {
+    return this._subscriptions;
+  }
+
+  public get watchers(): Record<string, NetworkWatcher> {
+    return this._watchers;
+  }
+
+  public attach(server: HttpServer | HttpsServer): void {
+    this._server = new WebSocketServer({
+      server,
+      path: EVENT_STREAM_PATH,
+      // the upgrade request doesn't go through the express middlewares
+      verifyClient: ({ req }, done) => {
+        try {
+          authenticator.authenticate({
+            ip: req.socket.remoteAddress,
+            method: req.method || 'GET',
+            path: req.url || EVENT_STREAM_PATH,
+            headers: req.headers,
+          });
+          done(true);
+        } catch (e) {
+          const response = gatewayErrorMiddleware(<Error>e);
+          logger.warn(`Rejected event stream connection: ${response.message}`);
+          done(false, response.httpErrorCode, response.message);
+        }
+      },
+    });
+    this._server.on('connection', (socket: WebSocket) => {
+      socket.on('message', (data) =>
+        this.handleMessage(socket, data.toString())
+      );
+      socket.on('close', () => this.removeSocket(socket));
+    });
+    logger.info(`Event stream available at ${EVENT_STREAM_PATH}.`);
+  }
+
+  public async handleMessage(socket: WebSocket, raw: string): Promise<void> {
+    try {
+      const req: EventSubscribeRequest = JSON.parse(raw);
+      validateEventRequest(req);
+      if (req.action === 'subscribe') {
+        await this.subscribe(socket, req);
+      } else {
+        await this.unsubscribe(<string>req.id, socket);
+        this.send(socket, { event: 'unsubscribed', id: req.id });
+      }
+    } catch (e) {
+      const response = gatewayErrorMiddleware(<Error>e);
+      this.send(socket, {
+        event: 'error',
+        message: response.message,
+        errorCode: response.errorCode,
+      });
+    }
+  }
+
+  public async subscribe(
+    socket: WebSocket,
+    req: EventSubscribeRequest
+  ): Promise<string> {
+    const key = `${req.chain}/${req.network}`;
+    const watcher =
+      this._watchers[key] ||
+      (await this.startWatcher(key, req.chain, req.network));
+    // the socket may have closed, or the watcher gone, while it started
+    if (socket.readyState !== WebSocket.OPEN) {
+      this.releaseWatcher(key);
+      throw new Error('The connection closed before the subscription started.');
+    }
+    if (this._watchers[key] !== watcher) return this.subscribe(socket, req);
+
+    const subscription: Subscription = {
+      id: uuidv4(),
+      socket,
+      channel: <EventChannel>req.channel,
+      request: req,
+    };
+    this._subscriptions[subscription.id] = subscription;
+    watcher.subscriptions.add(subscription.id);
+    this.send(socket, {
+      event: 'subscribed',
+      id: subscription.id,
+      chain: req.chain,
+      network: req.network,
+      channel: subscription.channel,
+    });
+
+    // tx and balance subscribers get the current state right away
+    if (subscription.channel !== 'blocks') {
+      await this.update(watcher.source, subscription);
+    }
+    return subscription.id;
+  }
+
+  private startWatcher(
+    key: string,
+    chain: string,
+    network: string
+  ): Promise<NetworkWatcher> {
+    if (!(key in this._pendingWatchers)) {
+      this._pendingWatchers[key] = (async () => {
+        const source = await this.getBlockSource(chain, network);
+        const handler: NewBlockHandler = (blockNumber: number) =>
+          this.onNewBlock(key, blockNumber);
+        source.onNewBlock(handler);
+        const watcher = { source, handler, subscriptions: new Set<string>() };
+        this._watchers[key] = watcher;
+        return watcher;
+      })().finally(() => delete this._pendingWatchers[key]);
+    }
+    return this._pendingWatchers[key];
+  }
+
+  // removes the block listener of a network once it has no subscriptions
+  private releaseWatcher(key: string): void {
+    const watcher = this._watchers[key];
+    if (watcher && watcher.subscriptions.size === 0) {
+      watcher.source.offNewBlock(watcher.handler);
+      delete this._watchers[key];
+    }
+  }
+
+  /**
+   * Ends a subscription. When the socket asking for it is given, only its
+   * own subscriptions can be ended.
+   */
+  public async unsubscribe(id: string, socket?: WebSocket): Promise<void> {
+    const subscription = this._subscriptions[id];
+    if (socket !== undefined && subscription?.socket !== socket) {
+      throw new HttpException(404, unknownSubscriptionError(id));
+    }
+    if (!subscription) return;
+    delete this._subscriptions[id];
+
+    const key = `${subscription.request.chain}/${subscription.request.network}`;
+    this._watchers[key]?.subscriptions.delete(id);
+    this.releaseWatcher(key);
+  }
+
+  // moves the block listener of a network to the instance that replaced a
+  // reloaded chain instance, the subscriptions are kept
+  public async reconnect(chain: string, network: string): Promise<void> {
+    const watcher = this._watchers[`${chain}/${network}`];
+    if (!watcher) return;
+    watcher.source.offNewBlock(watcher.handler);
+    watcher.source = await this.getBlockSource(chain, network);
+    watcher.source.onNewBlock(watcher.handler);
+  }
+
+  public async removeSocket(socket: WebSocket): Promise<void> {
+    for (const subscription of Object.values(this._subscriptions)) {
+      if (subscription.socket === socket) {
+        await this.unsubscribe(subscription.id);
+      }
+    }
+  }
+
+  async onNewBlock(key: string, blockNumber: number): Promise<void> {
+    const watcher = this._watchers[key];
+    if (!watcher) return;
+
+    await Promise.all(
+      Array.from(watcher.subscriptions).map(async (id) => {
+        const subscription = this._subscriptions[id];
+        if (!subscription) return;
+        if (subscription.channel === 'blocks') {
+          this.push(subscription, 'block', { blockNumber });
+        } else {
+          await this.update(watcher.source, subscription);
+        }
+      })
+    );
+  }
+
+  async update(source: BlockSource, subscription: Subscription) {
+    try {
+      if (subscription.channel === 'tx') {
+        await this.updateTx(source, subscription);
+      } else if (subscription.channel === 'balances') {
+        await this.updateBalances(source, subscription);
+      }
+    } catch (e) {
+      logger.error(
+        `Event stream update for subscription ${subscription.id} failed: ${e}`
+      );
+      const response = gatewayErrorMiddleware(<Error>e);
+      this.push(subscription, 'error', {
+        message: response.message,
+        errorCode: response.errorCode,
+      });
+    }
+  }
+
+  // pushes the receipt once the transaction is mined, then drops the
+  // subscription since there is nothing left to report
+  async updateTx(source: BlockSource, subscription: Subscription) {
+    const txHash = <string>subscription.request.txHash;
+    if (source instanceof Cosmos) {
+      let transaction;
+      try {
+        transaction = await source.getTransaction(txHash);
+      } catch (_e) {
+        return; // not included in a block yet
+      }
+      this.push(subscription, 'tx', {
+        txHash,
+        txBlock: transaction.height,
+        txStatus: transaction.code === 0 ? 1 : -1,
+        gasUsed: transaction.gasUsed,
+        gasWanted: transaction.gasWanted,
+      });
+    } else {
+      // getTransactionReceipt caches the receipt and refreshes the cache when
+      // the transaction is mined, so this doesn't hit the node on every block
+      const receipt = await source.getTransactionReceipt(txHash);
+      if (!receipt) return;
+      this.push(subscription, 'tx', {
+        txHash,
+        txBlock: receipt.blockNumber,
+        txStatus: receipt.status === 1 ? 1 : -1,
+        txReceipt: toEthereumTransactionReceipt(receipt),
+      });
+    }
+    await this.unsubscribe(subscription.id);
+  }
+
+  async updateBalances(source: BlockSource, subscription: Subscription) {
+    const req = {
+      chain: subscription.request.chain,
+      network: subscription.request.network,
+      address: <string>subscription.request.address,
+      tokenSymbols: <string[]>subscription.request.tokenSymbols,
+    };
+    const response =
+      source instanceof Cosmos
+        ? await cosmosBalances(source, req)
+        : await ethereumBalances(source, req);
+    if (typeof response === 'string') return;
+
+    const serialized = JSON.stringify(response.balances);
+    if (serialized !== subscription.lastValue) {
+      subscription.lastValue = serialized;
+      this.push(subscription, 'balances', {
+        address: req.address,
+        balances: response.balances,
+      });
+    }
+  }
+
+  async getBlockSource(chain: string, network: string): Promise<BlockSource> {
+    if (chain === 'cosmos') {
+      const cosmos = Cosmos.getInstance(network);
+      await cosmos.init();
+      return cosmos;
+    } else if (chain === 'near' || chain === 'osmosis') {
+      throw new Error(`The event stream does not support chain ${chain}.`);
+    }
+    return await getChain<Ethereumish>(chain, network);
+  }
+
+  push(
+    subscription: Subscription,
+    event: EventMessage['event'],
+    payload: Record<string, any>
+  ) {
+    this.send(subscription.socket, {
+      event,
+      id: subscription.id,
+      chain: subscription.request.chain,
+      network: subscription.request.network,
+      ...payload,
+    });
+  }
+
+  send(socket: WebSocket, message: Omit<EventMessage, 'timestamp'>) {
+    if (socket.readyState === WebSocket.OPEN) {
+      socket.send(JSON.stringify({ ...message, timestamp: Date.now() }));
+    }
+  }
+
+  public async close(): Promise<void> {
+    for (const id of Object.keys(this._subscriptions)) {
+      await this.unsubscribe(id);
+    }
+    if (this._server) {
+      // open sockets would keep the http server they upgraded from open
+      this._server.clients.forEach((client) => client.terminate());
+      this._server.close();
+      this._server = null;
+    }
+  }
+}
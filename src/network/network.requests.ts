@@ -5,6 +5,8 @@ import {
 } from '../services/common-interfaces';
 
 import { TokenInfo } from '../chains/ethereum/ethereum-base';
+import { TokenListType } from '../services/base';
+import { RpcEndpointHealth } from '../services/rpc-endpoint-pool';
 
 export interface BalanceRequest extends NetworkSelectionRequest {
   address: string; // the users public Ethereum key
@@ -31,6 +33,7 @@ export interface PollResponse {
   txBlock: number;
   txData: CustomTransactionResponse | null;
   txReceipt: CustomTransactionReceipt | null;
+  replacementTxHash?: string; // set when the transaction was sped up
 }
 
 export interface StatusRequest {
@@ -44,6 +47,7 @@ export interface StatusResponse {
   rpcUrl: string;
   nativeCurrency: string;
   currentBlockNumber?: number; // only reachable if connected
+  rpcEndpoints: RpcEndpointHealth[];
 }
 
 export interface TokensRequest {
@@ -55,3 +59,49 @@ export interface TokensRequest {
 export interface TokensResponse {
   tokens: TokenInfo[];
 }
+
+export interface AddTokenRequest extends NetworkSelectionRequest {
+  address: string; // the token contract, symbol and decimals are read from it
+}
+
+export interface RemoveTokenRequest extends NetworkSelectionRequest {
+  token: string; // symbol or address
+}
+
+export interface TokenUpdateResponse {
+  network: string;
+  timestamp: number;
+  token: TokenInfo;
+}
+
+export interface ImportTokensRequest extends NetworkSelectionRequest {
+  source: string; // URL or file path of a token list
+  type: TokenListType;
+}
+
+export interface ImportTokensResponse {
+  network: string;
+  timestamp: number;
+  source: string;
+  tokens: number; // tokens of the chain in the list
+}
+
+export type EventChannel = 'blocks' | 'tx' | 'balances';
+
+export interface EventSubscribeRequest extends NetworkSelectionRequest {
+  action: 'subscribe' | 'unsubscribe';
+  channel?: EventChannel;
+  id?: string; // the subscription id, only needed to unsubscribe
+  txHash?: string; // required for the tx channel
+  address?: string; // required for the balances channel
+  tokenSymbols?: string[]; // required for the balances channel
+}
+
+export interface EventMessage {
+  event: 'subscribed' | 'unsubscribed' | 'block' | 'tx' | 'balances' | 'error';
+  id?: string;
+  chain?: string;
+  network?: string;
+  timestamp: number;
+  [key: string]: any;
+}
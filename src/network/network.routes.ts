@@ -3,25 +3,41 @@ import { NextFunction, Request, Response, Router } from 'express';
 import * as ethereumControllers from '../chains/ethereum/ethereum.controllers';
 import { Ethereumish } from '../services/common-interfaces';
 import { ConfigManagerV2 } from '../services/config-manager-v2';
+import { redactSecrets } from '../services/auth';
 import { getChain } from '../services/connection-manager';
 import { asyncHandler } from '../services/error-handler';
 import {
   mkRequestValidator,
+  mkValidator,
   RequestValidator,
+  validateToken,
   validateTxHash,
+  Validator,
 } from '../services/validators';
-import { getStatus, getTokens } from './network.controllers';
 import {
+  addToken,
+  getStatus,
+  getTokens,
+  importTokens,
+  removeToken,
+} from './network.controllers';
+import {
+  AddTokenRequest,
   BalanceRequest,
   BalanceResponse,
+  ImportTokensRequest,
+  ImportTokensResponse,
   PollRequest,
   PollResponse,
+  RemoveTokenRequest,
   StatusRequest,
   StatusResponse,
   TokensRequest,
   TokensResponse,
+  TokenUpdateResponse,
 } from './network.requests';
 import {
+  isAddress,
   validateBalanceRequest as validateEthereumBalanceRequest,
   validateChain as validateEthereumChain,
   validateNetwork as validateEthereumNetwork,
@@ -36,6 +52,54 @@ export const validateTokensRequest: RequestValidator = mkRequestValidator([
   validateEthereumNetwork,
 ]);
 
+export const invalidTokenAddressError: string =
+  'The address param is not a token contract address (0x followed by 40 hexidecimal characters).';
+
+export const invalidTokenListSourceError: string =
+  'The source param must be the URL or file path of a token list.';
+
+export const invalidTokenListTypeError: string =
+  'The type param must be URL or FILE.';
+
+export const validateTokenAddress: Validator = mkValidator(
+  'address',
+  invalidTokenAddressError,
+  (val) => typeof val === 'string' && isAddress(val)
+);
+
+export const validateTokenListSource: Validator = mkValidator(
+  'source',
+  invalidTokenListSourceError,
+  (val) => typeof val === 'string' && val.length > 0
+);
+
+export const validateTokenListType: Validator = mkValidator(
+  'type',
+  invalidTokenListTypeError,
+  (val) => val === 'URL' || val === 'FILE'
+);
+
+export const validateAddTokenRequest: RequestValidator = mkRequestValidator([
+  validateEthereumChain,
+  validateEthereumNetwork,
+  validateTokenAddress,
+]);
+
+export const validateRemoveTokenRequest: RequestValidator =
+  mkRequestValidator([
+    validateEthereumChain,
+    validateEthereumNetwork,
+    validateToken,
+  ]);
+
+export const validateImportTokensRequest: RequestValidator =
+  mkRequestValidator([
+    validateEthereumChain,
+    validateEthereumNetwork,
+    validateTokenListSource,
+    validateTokenListType,
+  ]);
+
 export namespace NetworkRoutes {
   export const router = Router();
 
@@ -52,7 +116,9 @@ export namespace NetworkRoutes {
   );
 
   router.get('/config', (_req: Request, res: Response<any, any>) => {
-    res.status(200).json(ConfigManagerV2.getInstance().allConfigurations);
+    res
+      .status(200)
+      .json(redactSecrets(ConfigManagerV2.getInstance().allConfigurations));
   });
 
   router.post(
@@ -107,4 +173,43 @@ export namespace NetworkRoutes {
       }
     )
   );
+
+  router.post(
+    '/tokens/add',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, AddTokenRequest>,
+        res: Response<TokenUpdateResponse, {}>
+      ) => {
+        validateAddTokenRequest(req.body);
+        res.status(200).json(await addToken(req.body));
+      }
+    )
+  );
+
+  router.post(
+    '/tokens/remove',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, RemoveTokenRequest>,
+        res: Response<TokenUpdateResponse, {}>
+      ) => {
+        validateRemoveTokenRequest(req.body);
+        res.status(200).json(await removeToken(req.body));
+      }
+    )
+  );
+
+  router.post(
+    '/tokens/import',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, ImportTokensRequest>,
+        res: Response<ImportTokensResponse, {}>
+      ) => {
+        validateImportTokensRequest(req.body);
+        res.status(200).json(await importTokens(req.body));
+      }
+    )
+  );
 }
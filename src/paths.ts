@@ -15,3 +15,14 @@ export function rootPath(): string {
   }
   return fs.realpathSync(path.join(__dirname, '../'), 'utf8');
 }
+
+/**
+ * Resolves a database path from the server config. Relative paths are placed
+ * under the db/ folder of the project root, which is created if missing.
+ */
+export function resolveDBPath(oldPath: string): string {
+  if (oldPath.charAt(0) === '/') return oldPath;
+  const dbDir: string = path.join(rootPath(), 'db/');
+  fs.mkdirSync(dbDir, { recursive: true });
+  return path.join(dbDir, oldPath);
+}
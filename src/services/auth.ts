@@ -0,0 +1,329 @@
+import { createHmac, timingSafeEqual } from 'crypto';
+import { IncomingHttpHeaders, IncomingMessage } from 'http';
+import { NextFunction, Request, Response } from 'express';
+import { ConfigManagerV2 } from './config-manager-v2';
+import {
+  FORBIDDEN_ERROR_CODE,
+  FORBIDDEN_ERROR_MESSAGE,
+  HttpException,
+  UNAUTHORIZED_ERROR_CODE,
+  UNAUTHORIZED_ERROR_MESSAGE,
+} from './error-handler';
+import { logger } from './logger';
+
+// An API key as configured in server.yml, e.g.
+//   apiKeys:
+//     - name: market-data
+//       secret: <at least 32 random characters>
+//       scopes: [read]
+// Once a key is configured, every request has to be signed with one of them.
+export type ApiKeyScope = 'read' | 'trade' | 'admin';
+
+export interface ApiKeyConfig {
+  name: string;
+  secret: string;
+  scopes: ApiKeyScope[];
+}
+
+export const API_KEY_HEADER = 'x-gateway-key';
+export const API_TIMESTAMP_HEADER = 'x-gateway-timestamp';
+export const API_SIGNATURE_HEADER = 'x-gateway-signature';
+
+// how old a signed request may be, in seconds, when not configured
+export const DEFAULT_SIGNATURE_MAX_AGE = 30;
+
+const LOCALHOST = ['127.0.0.1', '::1'];
+
+// requests that change the server itself, its wallets or its tokens, or that
+// send funds out of its wallets
+const ADMIN_ROUTES = [
+  '/config/update',
+  '/wallet/add',
+  '/wallet/remove',
+  '/network/tokens/add',
+  '/network/tokens/remove',
+  '/network/tokens/import',
+  '/evm/transfer',
+  '/near/transfer',
+  '/cosmos/transfer',
+  '/cosmos/ibcTransfer',
+];
+
+// requests other than GET that only read state, every other one needs the
+// trade scope
+const READ_ROUTES = [
+  '/network/balances',
+  '/network/poll',
+  '/evm/nextNonce',
+  '/evm/nonce',
+  '/evm/allowances',
+  '/amm/price',
+  '/amm/quote',
+  '/amm/estimateGas',
+  '/amm/liquidity/position',
+  '/amm/liquidity/positions',
+  '/amm/liquidity/price',
+  '/amm/liquidity/pool',
+  '/amm/liquidity/v2/position',
+  '/amm/perp/market-prices',
+  '/amm/perp/market-status',
+  '/amm/perp/funding',
+  '/amm/perp/market-info',
+  '/amm/perp/price-history',
+  '/amm/perp/pairs',
+  '/amm/perp/position',
+  '/amm/perp/orders',
+  '/amm/perp/balance',
+  '/amm/perp/estimateGas',
+  '/cosmos/balances',
+  '/cosmos/poll',
+  '/near/balances',
+  '/near/poll',
+];
+
+// request bodies are signed as received, express.json keeps them through this
+export const keepRawBody = (
+  req: IncomingMessage,
+  _res: unknown,
+  buf: Buffer
+): void => {
+  (req as any).rawBody = buf.toString('utf8');
+};
+
+export const signRequest = (
+  secret: string,
+  timestamp: string,
+  method: string,
+  path: string,
+  body: string = ''
+): string =>
+  createHmac('sha256', secret)
+    .update(timestamp + method.toUpperCase() + path + body)
+    .digest('hex');
+
+// express matches routes case-insensitively and tolerates repeated slashes,
+// so the scope is looked up on the same normalized form
+const normalizeRoute = (path: string): string =>
+  path
+    .split('?')[0]
+    .replace(/\/{2,}/g, '/')
+    .replace(/\/+$/, '')
+    .toLowerCase();
+
+const ADMIN_ROUTES_NORMALIZED = ['/restart', ...ADMIN_ROUTES].map(
+  normalizeRoute
+);
+const READ_ROUTES_NORMALIZED = READ_ROUTES.map(normalizeRoute);
+
+export const requiredScope = (method: string, path: string): ApiKeyScope => {
+  const route = normalizeRoute(path);
+  if (ADMIN_ROUTES_NORMALIZED.includes(route)) return 'admin';
+  if (
+    method.toUpperCase() === 'GET' ||
+    READ_ROUTES_NORMALIZED.includes(route)
+  )
+    return 'read';
+  return 'trade';
+};
+
+// the configurations served to clients, with the secrets of the API keys
+// left out so that a read key can not learn the others
+export const redactSecrets = (configurations: {
+  [key: string]: any;
+}): { [key: string]: any } => {
+  const server = configurations.server;
+  if (!server || !Array.isArray(server.apiKeys)) return configurations;
+  return {
+    ...configurations,
+    server: {
+      ...server,
+      apiKeys: server.apiKeys.map((key: ApiKeyConfig) => ({
+        ...key,
+        secret: '<redacted>',
+      })),
+    },
+  };
+};
+
+export const normalizeIp = (ip: string | undefined): string =>
+  (ip || '').replace(/^::ffff:/, '');
+
+// an empty whitelist lets every IP in, localhost is always allowed
+export const isIpAllowed = (
+  ip: string | undefined,
+  whitelist: string[]
+): boolean => {
+  const address = normalizeIp(ip);
+  return (
+    whitelist.length === 0 ||
+    LOCALHOST.includes(address) ||
+    whitelist.map(normalizeIp).includes(address)
+  );
+};
+
+const header = (
+  headers: IncomingHttpHeaders,
+  name: string
+): string | undefined => {
+  const value = headers[name];
+  return Array.isArray(value) ? value[0] : value;
+};
+
+const signaturesMatch = (expected: string, actual: string): boolean => {
+  const expectedBuffer = Buffer.from(expected, 'hex');
+  const actualBuffer = Buffer.from(actual, 'hex');
+  return (
+    expectedBuffer.length === actualBuffer.length &&
+    timingSafeEqual(expectedBuffer, actualBuffer)
+  );
+};
+
+export interface AuthRequest {
+  ip: string | undefined;
+  method: string;
+  path: string; // with the query string, as it was signed
+  headers: IncomingHttpHeaders;
+  body?: string;
+}
+
+export class ApiKeyAuthenticator {
+  // signatures seen within the max age, so a captured request can't be
+  // replayed
+  private _seen: Map<string, number> = new Map();
+
+  /**
+   * Checks the request against the IP whitelist and, when API keys are
+   * configured, its signature and the scope of its key. Returns the key, or
+   * undefined when no keys are configured.
+   */
+  public authenticate(request: AuthRequest): ApiKeyConfig | undefined {
+    const config = ConfigManagerV2.getInstance();
+    const whitelist: string[] = config.get('server.ipWhitelist') || [];
+    if (!isIpAllowed(request.ip, whitelist)) {
+      throw new HttpException(
+        403,
+        FORBIDDEN_ERROR_MESSAGE(`IP ${normalizeIp(request.ip)}`),
+        FORBIDDEN_ERROR_CODE
+      );
+    }
+
+    const keys: ApiKeyConfig[] = config.get('server.apiKeys') || [];
+    if (keys.length === 0) return undefined;
+
+    const name = header(request.headers, API_KEY_HEADER);
+    const timestamp = header(request.headers, API_TIMESTAMP_HEADER);
+    const signature = header(request.headers, API_SIGNATURE_HEADER);
+    if (!name || !timestamp || !signature) {
+      throw new HttpException(
+        401,
+        UNAUTHORIZED_ERROR_MESSAGE('missing API key headers'),
+        UNAUTHORIZED_ERROR_CODE
+      );
+    }
+
+    const key = keys.find((k) => k.name === name);
+    if (!key) {
+      throw new HttpException(
+        401,
+        UNAUTHORIZED_ERROR_MESSAGE(`unknown API key ${name}`),
+        UNAUTHORIZED_ERROR_CODE
+      );
+    }
+    const maxAge =
+      (config.get('server.apiKeySignatureMaxAge') ||
+        DEFAULT_SIGNATURE_MAX_AGE) * 1000;
+    const now = Date.now();
+    if (!(Math.abs(now - Number(timestamp)) <= maxAge)) {
+      throw new HttpException(
+        401,
+        UNAUTHORIZED_ERROR_MESSAGE('expired timestamp'),
+        UNAUTHORIZED_ERROR_CODE
+      );
+    }
+    const expected = signRequest(
+      key.secret,
+      timestamp,
+      request.method,
+      request.path,
+      request.body
+    );
+    if (!signaturesMatch(expected, signature)) {
+      throw new HttpException(
+        401,
+        UNAUTHORIZED_ERROR_MESSAGE('invalid signature'),
+        UNAUTHORIZED_ERROR_CODE
+      );
+    }
+    this.pruneSeen(now);
+    if (this._seen.has(signature)) {
+      throw new HttpException(
+        401,
+        UNAUTHORIZED_ERROR_MESSAGE('replayed request'),
+        UNAUTHORIZED_ERROR_CODE
+      );
+    }
+    this._seen.set(signature, now + maxAge);
+
+    const scope = requiredScope(request.method, request.path);
+    if (!key.scopes.includes(scope)) {
+      throw new HttpException(
+        403,
+        FORBIDDEN_ERROR_MESSAGE(`API key ${key.name} lacks the ${scope} scope`),
+        FORBIDDEN_ERROR_CODE
+      );
+    }
+    return key;
+  }
+
+  private pruneSeen(now: number): void {
+    for (const [signature, expiry] of this._seen) {
+      if (expiry < now) this._seen.delete(signature);
+    }
+  }
+}
+
+export const authenticator = new ApiKeyAuthenticator();
+
+// audit trail of what each key did, reads are left out as they are frequent
+// and change nothing
+const audit = (
+  req: Request,
+  res: Response,
+  key: ApiKeyConfig | undefined
+): void => {
+  const ip = normalizeIp(req.socket.remoteAddress);
+  if (!key) {
+    logger.warn(
+      `Rejected ${req.method} ${req.originalUrl} from ${ip}: ${res.statusCode}`
+    );
+  } else if (requiredScope(req.method, req.originalUrl) !== 'read') {
+    logger.info(
+      `API key ${key.name} from ${ip}: ${req.method} ${req.originalUrl} ${res.statusCode}`
+    );
+  }
+};
+
+export const authMiddleware = (
+  req: Request,
+  res: Response,
+  next: NextFunction
+): void => {
+  let key: ApiKeyConfig | undefined;
+  try {
+    key = authenticator.authenticate({
+      ip: req.socket.remoteAddress,
+      method: req.method,
+      path: req.originalUrl,
+      headers: req.headers,
+      body: (req as any).rawBody,
+    });
+  } catch (e) {
+    res.on('finish', () => audit(req, res, undefined));
+    return next(e);
+  }
+  if (key) {
+    const authorized = key;
+    res.on('finish', () => audit(req, res, authorized));
+  }
+  next();
+};
@@ -7,8 +7,11 @@ import {
   BigNumber,
   ethers,
 } from 'ethers';
-import { EthereumBase } from '../chains/ethereum/ethereum-base';
-import { CosmosBase } from '../chains/cosmos/cosmos-base';
+import { EthereumBase, TokenInfo } from '../chains/ethereum/ethereum-base';
+import {
+  CosmosBase,
+  Token as CosmosToken,
+} from '../chains/cosmos/cosmos-base';
 import { Provider } from '@ethersproject/abstract-provider';
 import { CurrencyAmount, Token, Trade as TradeUniswap } from '@uniswap/sdk';
 import { Trade } from '@uniswap/router-sdk';
@@ -86,11 +89,20 @@ import {
   Trade as PancakeSwapTrade,
   Fraction as PancakeSwapFraction,
 } from '@pancakeswap/sdk';
-import { PerpPosition } from '../connectors/perp/perp';
+import {
+  PerpFundingRate,
+  PerpLimitOrder,
+  PerpMarketInfo,
+  PerpPosition,
+} from '../connectors/perp/perp';
+import { PerpPriceSample } from '../connectors/perp/perp.history';
+import { PoolInfo } from '../connectors/uniswap/uniswap.lp.analytics';
 import { NearBase } from '../chains/near/near.base';
 import { Account, Contract as NearContract } from 'near-api-js';
 import { EstimateSwapView, TokenMetadata } from 'coinalpha-ref-sdk';
 import { FinalExecutionOutcome } from 'near-api-js/lib/providers';
+import { EncodeObject } from '@cosmjs/proto-signing';
+import { OsmosisTrade } from '../connectors/osmosis/osmosis';
 
 // TODO Check the possibility to have clob/solana/serum equivalents here
 //  Check this link https://hummingbot.org/developers/gateway/building-gateway-connectors/#5-add-sdk-classes-to-uniswapish-interface
@@ -166,7 +178,36 @@ export interface ExpectedTrade {
   expectedAmount: UniswapishAmount;
 }
 
+// restricts the route of a trade, tokens and pools are given by address
+export interface TradeRouteOptions {
+  // the tokens to swap through, from the token paid to the token received
+  path?: string[];
+  // the V3 fee tier of each hop of the path, every tier is tried if not set
+  fees?: number[];
+  // the only tokens a route may go through besides the traded ones
+  intermediateTokens?: string[];
+  excludedPools?: string[];
+}
+
+// the side of a trade that pays (in) or receives (out) the native currency
+// instead of its wrapped token
+export type NativeSwap = 'in' | 'out';
+
+export interface TradeRouteHop {
+  tokenIn: string;
+  tokenOut: string;
+  pool: string;
+  fee?: number; // of V3 pools, in hundredths of a bip
+}
+
+// a trade may be split between several routes
+export interface TradeRoute {
+  percent: number;
+  hops: TradeRouteHop[];
+}
+
 export interface PositionInfo {
+  tokenId: number;
   token0: string | undefined;
   token1: string | undefined;
   fee: string | undefined;
@@ -176,6 +217,42 @@ export interface PositionInfo {
   amount1: string;
   unclaimedToken0: string;
   unclaimedToken1: string;
+  liquidity: string;
+  inRange: boolean;
+  // the amounts of the position priced in one of its tokens at the pool price
+  valueInToken0: string;
+  valueInToken1: string;
+}
+
+export interface RebalancedPosition {
+  transaction: Transaction;
+  // the minted position, known from a dry run of the transaction
+  tokenId?: number;
+  amount0: string;
+  amount1: string;
+  swapRequired: boolean;
+}
+
+// a wallet's share of a Uniswap V2 style pair, amounts in token units
+export interface V2PositionInfo {
+  pair: string;
+  tokenA: string;
+  tokenB: string;
+  reserveA: string;
+  reserveB: string;
+  lpTokenBalance: string;
+  lpTotalSupply: string;
+  poolShare: string; // the fraction of the pair's liquidity the wallet holds
+  // what the wallet's LP tokens can be redeemed for
+  amountA: string;
+  amountB: string;
+}
+
+export interface V2RemovedLiquidity {
+  // sent first when the router isn't allowed to spend the LP tokens
+  approval?: Transaction;
+  transaction: Transaction;
+  liquidity: string;
 }
 
 export interface Uniswapish {
@@ -189,6 +266,13 @@ export interface Uniswapish {
    */
   routerAbi: ContractInterface;
 
+  /**
+   * Whether executeTrade can pay or receive the native currency. When unset,
+   * it can if the router ABI has the native currency swap methods of a V2
+   * router.
+   */
+  supportsNativeSwap?: boolean;
+
   /**
    * Interface for decoding transaction logs
    */
@@ -225,12 +309,16 @@ export interface Uniswapish {
    * @param baseToken Token input for the transaction
    * @param quoteToken Output from the transaction
    * @param amount Amount of `baseToken` to put into the transaction
+   * @param allowedSlippage (Optional) Fraction, e.g. '1/100'
+   * @param routeOptions (Optional) Restrictions on the route, for the
+   * connectors that can route through several pools
    */
   estimateSellTrade(
     baseToken: Tokenish,
     quoteToken: Tokenish,
     amount: BigNumber,
-    allowedSlippage?: string
+    allowedSlippage?: string,
+    routeOptions?: TradeRouteOptions
   ): Promise<ExpectedTrade>;
 
   /**
@@ -242,12 +330,16 @@ export interface Uniswapish {
    * @param quoteToken Token input for the transaction
    * @param baseToken Token output from the transaction
    * @param amount Amount of `baseToken` desired from the transaction
+   * @param allowedSlippage (Optional) Fraction, e.g. '1/100'
+   * @param routeOptions (Optional) Restrictions on the route, for the
+   * connectors that can route through several pools
    */
   estimateBuyTrade(
     quoteToken: Tokenish,
     baseToken: Tokenish,
     amount: BigNumber,
-    allowedSlippage?: string
+    allowedSlippage?: string,
+    routeOptions?: TradeRouteOptions
   ): Promise<ExpectedTrade>;
 
   /**
@@ -263,6 +355,9 @@ export interface Uniswapish {
    * @param nonce (Optional) EVM transaction nonce
    * @param maxFeePerGas (Optional) Maximum total fee per gas you want to pay
    * @param maxPriorityFeePerGas (Optional) Maximum tip per gas you want to pay
+   * @param allowedSlippage (Optional) Fraction, e.g. '1/100'
+   * @param nativeSwap (Optional) Side of the trade that is the native
+   * currency, for the connectors whose router can swap it
    */
   executeTrade(
     wallet: Wallet,
@@ -275,7 +370,8 @@ export interface Uniswapish {
     nonce?: number,
     maxFeePerGas?: BigNumber,
     maxPriorityFeePerGas?: BigNumber,
-    allowedSlippage?: string
+    allowedSlippage?: string,
+    nativeSwap?: NativeSwap
   ): Promise<Transaction>;
 }
 
@@ -373,6 +469,71 @@ export interface RefAMMish {
   ): Promise<FinalExecutionOutcome>;
 }
 
+export interface CosmosAMMish {
+  /**
+   * Default gas estimate for swap transactions.
+   */
+  gasLimitEstimate: number;
+
+  init(): Promise<void>;
+
+  ready(): boolean;
+
+  /**
+   * Gets the allowed slippage percent from the optional parameter or the value
+   * in the configuration.
+   *
+   * @param allowedSlippageStr (Optional) should be of the form '1/10'.
+   */
+  getAllowedSlippage(allowedSlippageStr?: string): number;
+
+  /**
+   * Given the amount of `baseToken` to put into a transaction, calculate the
+   * amount of `quoteToken` that can be expected from the transaction.
+   *
+   * This is typically used for calculating token sell prices.
+   *
+   * @param baseToken Token input for the transaction
+   * @param quoteToken Output from the transaction
+   * @param amount Amount of `baseToken` to put into the transaction
+   */
+  estimateSellTrade(
+    baseToken: CosmosToken,
+    quoteToken: CosmosToken,
+    amount: string
+  ): Promise<OsmosisTrade>;
+
+  /**
+   * Given the amount of `baseToken` desired to acquire from a transaction,
+   * calculate the amount of `quoteToken` needed for the transaction.
+   *
+   * This is typically used for calculating token buy prices.
+   *
+   * @param quoteToken Token input for the transaction
+   * @param baseToken Token output from the transaction
+   * @param amount Amount of `baseToken` desired from the transaction
+   */
+  estimateBuyTrade(
+    quoteToken: CosmosToken,
+    baseToken: CosmosToken,
+    amount: string
+  ): Promise<OsmosisTrade>;
+
+  /**
+   * The swap message of a trade, which receives at least or pays at most its
+   * estimate less the allowed slippage.
+   *
+   * @param sender Address of the wallet swapping
+   * @param trade Expected trade
+   * @param allowedSlippage (Optional) Maximum allowable slippage
+   */
+  swapMessage(
+    sender: string,
+    trade: OsmosisTrade,
+    allowedSlippage?: string
+  ): EncodeObject;
+}
+
 export interface UniswapLPish {
   /**
    * Router address.
@@ -433,6 +594,14 @@ export interface UniswapLPish {
    */
   getPosition(tokenId: number): Promise<PositionInfo>;
 
+  /**
+   * Lists every position NFT owned by a wallet, skipping the ones in tokens
+   * the connector doesn't know.
+   *
+   * @param address Wallet address
+   */
+  getPositions(address: string): Promise<PositionInfo[]>;
+
   /**
    * Given a wallet, add/increase liquidity for a position.
    *
@@ -490,6 +659,32 @@ export interface UniswapLPish {
     maxPriorityFeePerGas?: BigNumber
   ): Promise<Transaction>;
 
+  /**
+   * Given a wallet, moves a position to a new price range in one transaction:
+   * removes it, collects its fees and mints a new position with the
+   * proceeds, swapping to the ratio of the new range when needed.
+   *
+   * @param wallet Wallet for the transaction
+   * @param tokenId id of the position to move
+   * @param lowerPrice lower price bound of the new position
+   * @param upperPrice upper price bound of the new position
+   * @param gasLimit Gas limit
+   * @param nonce (Optional) EVM transaction nonce
+   * @param maxFeePerGas (Optional) Maximum total fee per gas you want to pay
+   * @param maxPriorityFeePerGas (Optional) Maximum tip per gas you want to pay
+   */
+  rebalancePosition(
+    wallet: Wallet,
+    tokenId: number,
+    lowerPrice: number,
+    upperPrice: number,
+    gasLimit: number,
+    gasPrice: number,
+    nonce?: number,
+    maxFeePerGas?: BigNumber,
+    maxPriorityFeePerGas?: BigNumber
+  ): Promise<RebalancedPosition>;
+
   /**
    * Given a wallet and tokenId, collect earned fees on position.
    *
@@ -510,6 +705,53 @@ export interface UniswapLPish {
     maxPriorityFeePerGas?: BigNumber
   ): Promise<Transaction | { amount0: BigNumber; amount1: BigNumber }>;
 
+  /**
+   * Given a wallet, makes a pool keep more price observations, so a TWAP can
+   * go further back.
+   *
+   * @param wallet Wallet for the transaction
+   * @param token0 Token in pool
+   * @param token1 Token in pool
+   * @param fee fee tier
+   * @param cardinality number of observations to keep
+   * @param gasLimit Gas limit
+   * @param nonce (Optional) EVM transaction nonce
+   * @param maxFeePerGas (Optional) Maximum total fee per gas you want to pay
+   * @param maxPriorityFeePerGas (Optional) Maximum tip per gas you want to pay
+   */
+  increaseObservationCardinality(
+    wallet: Wallet,
+    token0: UniswapCoreToken,
+    token1: UniswapCoreToken,
+    fee: number,
+    cardinality: number,
+    gasLimit: number,
+    gasPrice: number,
+    nonce?: number,
+    maxFeePerGas?: BigNumber,
+    maxPriorityFeePerGas?: BigNumber
+  ): Promise<Transaction>;
+
+  /**
+   * Given a fee tier and tokens, fetch the state of the pool, the liquidity
+   * around its price, its depth and optionally a TWAP.
+   *
+   * @param token0 Token in pool
+   * @param token1 Token in pool
+   * @param fee fee tier
+   * @param rangePercent how far from the price to report the liquidity of
+   * @param depthPercents price moves to report the depth for
+   * @param twapWindow seconds to average the price over, none when 0
+   */
+  getPoolInfo(
+    token0: UniswapCoreToken,
+    token1: UniswapCoreToken,
+    fee: number,
+    rangePercent: number,
+    depthPercents: number[],
+    twapWindow?: number
+  ): Promise<PoolInfo>;
+
   /**
    * Given a fee tier, tokens and time parameters, fetch historical pool prices.
    *
@@ -528,6 +770,101 @@ export interface UniswapLPish {
   ): Promise<string[]>;
 }
 
+export interface UniswapV2LPish {
+  /**
+   * Router address.
+   */
+  router: string;
+
+  /**
+   * Default time-to-live for liquidity transactions, in seconds.
+   */
+  ttl: number;
+
+  /**
+   * Default gas limit used to estimate gasCost for liquidity transactions.
+   */
+  gasLimitEstimate: number;
+
+  /**
+   * The address of the pair of two tokens, looked up from the router's
+   * factory.
+   */
+  getPairAddress(tokenA: TokenInfo, tokenB: TokenInfo): Promise<string>;
+
+  /**
+   * Given a wallet address, fetch its LP token balance in a pair, its share
+   * of the pair and the reserves it can redeem.
+   *
+   * @param address Wallet address
+   * @param tokenA Token in pair
+   * @param tokenB Token in pair
+   */
+  getPosition(
+    address: string,
+    tokenA: TokenInfo,
+    tokenB: TokenInfo
+  ): Promise<V2PositionInfo>;
+
+  /**
+   * Given a wallet, add liquidity to a pair, creating it if need be. The
+   * router adds the amounts in the pair's ratio, within the allowed slippage.
+   *
+   * @param wallet Wallet for the transaction
+   * @param tokenA Token in pair
+   * @param tokenB Token in pair
+   * @param amountA Amount of `tokenA` to put into the pair
+   * @param amountB Amount of `tokenB` to put into the pair
+   * @param gasLimit Gas limit
+   * @param gasPrice Base gas price, for pre-EIP1559 transactions
+   * @param nonce (Optional) EVM transaction nonce
+   * @param maxFeePerGas (Optional) Maximum total fee per gas you want to pay
+   * @param maxPriorityFeePerGas (Optional) Maximum tip per gas you want to pay
+   * @param allowedSlippage (Optional) Fraction, e.g. '1/100'
+   */
+  addLiquidity(
+    wallet: Wallet,
+    tokenA: TokenInfo,
+    tokenB: TokenInfo,
+    amountA: string,
+    amountB: string,
+    gasLimit: number,
+    gasPrice: number,
+    nonce?: number,
+    maxFeePerGas?: BigNumber,
+    maxPriorityFeePerGas?: BigNumber,
+    allowedSlippage?: string
+  ): Promise<Transaction>;
+
+  /**
+   * Given a wallet, redeem a percentage of its LP tokens in a pair. The
+   * router is approved to spend the LP tokens first when it needs to be.
+   *
+   * @param wallet Wallet for the transaction
+   * @param tokenA Token in pair
+   * @param tokenB Token in pair
+   * @param decreasePercent percentage of the LP tokens to redeem
+   * @param gasLimit Gas limit
+   * @param gasPrice Base gas price, for pre-EIP1559 transactions
+   * @param nonce (Optional) EVM transaction nonce
+   * @param maxFeePerGas (Optional) Maximum total fee per gas you want to pay
+   * @param maxPriorityFeePerGas (Optional) Maximum tip per gas you want to pay
+   * @param allowedSlippage (Optional) Fraction, e.g. '1/100'
+   */
+  removeLiquidity(
+    wallet: Wallet,
+    tokenA: TokenInfo,
+    tokenB: TokenInfo,
+    decreasePercent: number,
+    gasLimit: number,
+    gasPrice: number,
+    nonce?: number,
+    maxFeePerGas?: BigNumber,
+    maxPriorityFeePerGas?: BigNumber,
+    allowedSlippage?: string
+  ): Promise<V2RemovedLiquidity>;
+}
+
 export interface Perpish {
   gasLimit: number;
 
@@ -566,6 +903,25 @@ export interface Perpish {
    */
   isMarketActive(tickerSymbol: string): Promise<boolean>;
 
+  /**
+   * Gives the current and predicted funding rates of a market.
+   * @param tickerSymbol Market pair
+   */
+  fundingRates(tickerSymbol: string): Promise<PerpFundingRate>;
+
+  /**
+   * Gives the margin requirements, tick size and fees of a market.
+   * @param tickerSymbol Market pair
+   */
+  marketInfo(tickerSymbol: string): Promise<PerpMarketInfo>;
+
+  /**
+   * Gives the mark and index prices of a market sampled on new blocks.
+   * @param tickerSymbol Market pair
+   * @param from Only the samples taken at or after this time, in ms.
+   */
+  priceHistory(tickerSymbol: string, from?: number): PerpPriceSample[];
+
   /**
    * Gets available Positions/Position.
    * @param tickerSymbol An optional parameter to get specific position.
@@ -601,6 +957,58 @@ export interface Perpish {
     tickerSymbol: string,
     allowedSlippage?: string
   ): Promise<Transaction>;
+
+  /**
+   * Reduces an open position by a base amount without flipping it.
+   * @param tickerSymbol The market on which we want to reduce the position.
+   * @param amount The amount of base to close.
+   * @returns An ethers transaction object.
+   */
+  reducePosition(
+    tickerSymbol: string,
+    amount: string,
+    allowedSlippage?: string
+  ): Promise<Transaction>;
+
+  /**
+   * Places a maker order at a limit price.
+   * @param isLong Buys base if true, sells it otherwise.
+   * @param tickerSymbol The market to place the order on.
+   * @param amount The amount of base to buy or sell.
+   * @param price The limit price.
+   * @returns The id of the order and an ethers transaction object.
+   */
+  createLimitOrder(
+    isLong: boolean,
+    tickerSymbol: string,
+    amount: string,
+    price: string
+  ): Promise<{ orderId: string; transaction: Transaction }>;
+
+  /**
+   * @returns the open limit orders of the connected account on a market.
+   */
+  getLimitOrders(tickerSymbol: string): Promise<PerpLimitOrder[]>;
+
+  /**
+   * Cancels an open limit order.
+   * @param tickerSymbol The market of the order.
+   * @param orderId The id returned when the order was placed.
+   * @returns An ethers transaction object.
+   */
+  cancelLimitOrder(tickerSymbol: string, orderId: string): Promise<Transaction>;
+
+  /**
+   * Deposits collateral into the vault.
+   * @param amount The amount of the settlement token.
+   */
+  addMargin(amount: string): Promise<Transaction>;
+
+  /**
+   * Withdraws collateral from the vault.
+   * @param amount The amount of the settlement token.
+   */
+  removeMargin(amount: string): Promise<Transaction>;
 }
 
 export interface BasicChainMethods {
@@ -624,6 +1032,11 @@ export interface Nearish extends BasicChainMethods, NearBase {
 }
 export interface Cosmosish extends CosmosBase {
   gasPrice: number;
+  minimumGasPrice: number;
+  gasAdjustment: number;
+  ibcChannels: Record<string, string>;
+  ibcTimeout: number;
+  bech32Prefix: string;
   nativeTokenSymbol: string;
   chain: string;
 }
@@ -65,6 +65,14 @@ export function initiateWithTemplate(templateFile: string, configFile: string) {
 
 const ajv: Ajv = new Ajv();
 
+// called once a value of a namespace was set, returns the components it
+// reloaded to apply the new value
+export type ConfigChangeListener = (
+  namespaceId: string,
+  configPath: string,
+  value: any
+) => Promise<string[]> | string[];
+
 export const percentRegexp = new RegExp(/^(\d+)\/(\d+)$/);
 
 export class ConfigurationNamespace {
@@ -99,6 +107,7 @@ export class ConfigurationNamespace {
   readonly #configurationPath: string;
   readonly #templatePath: string;
   readonly #validator: ValidateFunction;
+  readonly #listeners: ConfigChangeListener[] = [];
   #configuration: Configuration;
 
   constructor(
@@ -227,6 +236,27 @@ export class ConfigurationNamespace {
     this.#configuration = configClone;
     this.saveConfig();
   }
+
+  onChange(listener: ConfigChangeListener): void {
+    if (!this.#listeners.includes(listener)) this.#listeners.push(listener);
+  }
+
+  offChange(listener: ConfigChangeListener): void {
+    const index = this.#listeners.indexOf(listener);
+    if (index !== -1) this.#listeners.splice(index, 1);
+  }
+
+  /**
+   * Tells the listeners of the namespace that a value was set, and returns
+   * the components they reloaded.
+   */
+  async notifyChange(configPath: string, value: any): Promise<string[]> {
+    const reloaded: string[] = [];
+    for (const listener of this.#listeners) {
+      reloaded.push(...(await listener(this.id, configPath, value)));
+    }
+    return reloaded;
+  }
 }
 
 export class ConfigManagerV2 {
@@ -259,7 +289,7 @@ export class ConfigManagerV2 {
    */
   readonly #namespaces: { [key: string]: ConfigurationNamespace };
 
-  private static _instance: ConfigManagerV2;
+  private static _instance: ConfigManagerV2 | undefined;
 
   public static getInstance(): ConfigManagerV2 {
     if (!ConfigManagerV2._instance) {
@@ -273,6 +303,14 @@ export class ConfigManagerV2 {
     return ConfigManagerV2._instance;
   }
 
+  /**
+   * Forgets the loaded configuration, the next getInstance() reads the
+   * configuration files again.
+   */
+  public static reload(): void {
+    ConfigManagerV2._instance = undefined;
+  }
+
   static defaults: ConfigurationDefaults = {};
 
   constructor(configRootPath: string) {
@@ -368,6 +406,16 @@ export class ConfigManagerV2 {
     namespace.set(configPath, value);
   }
 
+  /**
+   * Sets a configuration value like set(), then lets the components built
+   * from the previous value reload. Returns the components that reloaded.
+   */
+  async update(fullConfigPath: string, value: any): Promise<string[]> {
+    const { namespace, configPath } = this.unpackFullConfigPath(fullConfigPath);
+    namespace.set(configPath, value);
+    return namespace.notifyChange(configPath, value);
+  }
+
   loadConfigRoot(configRootPath: string) {
     // Load the config root file.
     const configRootFullPath: string = fs.realpathSync(configRootPath);
@@ -0,0 +1,223 @@
+import { Avalanche } from '../chains/avalanche/avalanche';
+import { BinanceSmartChain } from '../chains/binance-smart-chain/binance-smart-chain';
+import { Cosmos } from '../chains/cosmos/cosmos';
+import { Cronos } from '../chains/cronos/cronos';
+import { CronosBaseUniswapishConnector } from '../chains/cronos/cronos-base/cronos-base-uniswapish-connector';
+import { Ethereum } from '../chains/ethereum/ethereum';
+import { Harmony } from '../chains/harmony/harmony';
+import { Near } from '../chains/near/near';
+import { Polygon } from '../chains/polygon/polygon';
+import { Defikingdoms } from '../connectors/defikingdoms/defikingdoms';
+import { Defira } from '../connectors/defira/defira';
+import { MadMeerkat } from '../connectors/mad_meerkat/mad_meerkat';
+import { Openocean } from '../connectors/openocean/openocean';
+import { OsmosisConnector } from '../connectors/osmosis/osmosis';
+import { PancakeSwap } from '../connectors/pancakeswap/pancakeswap';
+import { Pangolin } from '../connectors/pangolin/pangolin';
+import { Perp } from '../connectors/perp/perp';
+import { PerpPriceHistory } from '../connectors/perp/perp.history';
+import { Quickswap } from '../connectors/quickswap/quickswap';
+import { Ref } from '../connectors/ref/ref';
+import { Sushiswap } from '../connectors/sushiswap/sushiswap';
+import { Traderjoe } from '../connectors/traderjoe/traderjoe';
+import { Uniswap } from '../connectors/uniswap/uniswap';
+import { UniswapLP } from '../connectors/uniswap/uniswap.lp';
+import { UniswapV2LP } from '../connectors/uniswap/uniswap.v2.lp';
+import { VVSConnector } from '../connectors/vvs/vvs';
+import { EventStreamManager } from '../network/network.events';
+import { ConfigChangeListener, ConfigManagerV2 } from './config-manager-v2';
+
+// the singletons a namespace configures, as they are kept by their class
+interface ReloadTarget {
+  instances: () => { [key: string]: any } | undefined;
+  // set when a class keeps the instances of several namespaces in one map
+  suffix?: string;
+}
+
+const CHAINS: Record<string, ReloadTarget> = {
+  ethereum: { instances: () => Ethereum.getConnectedInstances() },
+  avalanche: { instances: () => Avalanche.getConnectedInstances() },
+  polygon: { instances: () => Polygon.getConnectedInstances() },
+  harmony: { instances: () => Harmony.getConnectedInstances() },
+  'binance-smart-chain': {
+    instances: () => BinanceSmartChain.getConnectedInstances(),
+  },
+  cronos: { instances: () => Cronos.getConnectedInstances() },
+  near: { instances: () => Near.getConnectedInstances() },
+  cosmos: {
+    instances: () => Cosmos.getConnectedInstances(),
+    suffix: 'cosmos',
+  },
+  osmosis: {
+    instances: () => Cosmos.getConnectedInstances(),
+    suffix: 'osmosis',
+  },
+};
+
+const v2LP = (connector: string): ReloadTarget => ({
+  instances: () => UniswapV2LP.getConnectedInstances(),
+  suffix: connector,
+});
+
+const cronosConnector = (className: string): ReloadTarget => ({
+  instances: () => CronosBaseUniswapishConnector.getConnectedInstances(),
+  suffix: className,
+});
+
+const CONNECTORS: Record<string, ReloadTarget[]> = {
+  uniswap: [
+    { instances: () => Uniswap.getConnectedInstances() },
+    { instances: () => UniswapLP.getConnectedInstances() },
+  ],
+  sushiswap: [
+    { instances: () => Sushiswap.getConnectedInstances() },
+    v2LP('sushiswap'),
+  ],
+  pangolin: [
+    { instances: () => Pangolin.getConnectedInstances() },
+    v2LP('pangolin'),
+  ],
+  traderjoe: [
+    { instances: () => Traderjoe.getConnectedInstances() },
+    v2LP('traderjoe'),
+  ],
+  quickswap: [
+    { instances: () => Quickswap.getConnectedInstances() },
+    v2LP('quickswap'),
+  ],
+  pancakeswap: [
+    { instances: () => PancakeSwap.getConnectedInstances() },
+    v2LP('pancakeswap'),
+  ],
+  openocean: [{ instances: () => Openocean.getConnectedInstances() }],
+  defikingdoms: [
+    { instances: () => Defikingdoms.getConnectedInstances() },
+    v2LP('defikingdoms'),
+  ],
+  defira: [
+    { instances: () => Defira.getConnectedInstances() },
+    v2LP('defira'),
+  ],
+  ref: [{ instances: () => Ref.getConnectedInstances() }],
+  // configured in the namespace of its chain, so reloaded with the chain
+  osmosis: [{ instances: () => OsmosisConnector.getConnectedInstances() }],
+  perp: [{ instances: () => Perp.getConnectedInstances() }],
+  mad_meerkat: [cronosConnector(MadMeerkat.name), v2LP('mad_meerkat')],
+  vvs: [cronosConnector(VVSConnector.name), v2LP('vvs')],
+};
+
+/**
+ * Closes and forgets the instances of a target whose key passes the filter,
+ * so that the next getInstance() builds them from the current config. Returns
+ * the dropped instances as `<namespace>.<key>`.
+ */
+async function dropInstances(
+  namespaceId: string,
+  target: ReloadTarget,
+  filter: (key: string) => boolean
+): Promise<string[]> {
+  const instances = target.instances();
+  if (instances === undefined) return [];
+
+  const dropped: string[] = [];
+  for (const key of Object.keys(instances)) {
+    if (target.suffix !== undefined && !key.endsWith(target.suffix)) continue;
+    const name = key.slice(0, key.length - (target.suffix ?? '').length);
+    if (!filter(name)) continue;
+
+    const instance = instances[key];
+    delete instances[key];
+    if (typeof instance.close === 'function') await instance.close();
+    dropped.push(`${namespaceId}.${name}`);
+  }
+  return dropped;
+}
+
+async function dropConnectors(
+  filter: (namespaceId: string, key: string) => boolean
+): Promise<string[]> {
+  const dropped: string[] = [];
+  for (const [namespaceId, targets] of Object.entries(CONNECTORS)) {
+    const matches = (key: string) => filter(namespaceId, key);
+    for (const target of targets) {
+      dropped.push(...(await dropInstances(namespaceId, target, matches)));
+    }
+  }
+  // the price history samples through the blocks of the dropped chain
+  if (dropped.some((name) => name.startsWith('perp.'))) {
+    PerpPriceHistory.stopAll();
+  }
+  return dropped;
+}
+
+/**
+ * A change under `<chain>.networks.<network>` reloads that network, any other
+ * change of a chain namespace reloads all of its networks. The connectors
+ * built on a reloaded network are reloaded with it, since they hold on to
+ * the chain instance they were created with.
+ */
+export const reloadChain: ConfigChangeListener = async (
+  namespaceId: string,
+  configPath: string
+): Promise<string[]> => {
+  const match = configPath.match(/^networks\.([^.]+)/);
+  const network = match ? match[1] : undefined;
+
+  const chains = await dropInstances(
+    namespaceId,
+    CHAINS[namespaceId],
+    (key) => network === undefined || key === network
+  );
+  const connectors = await dropConnectors(
+    (_connector, key) =>
+      key.startsWith(namespaceId + (network === undefined ? '' : network))
+  );
+
+  for (const chain of chains) {
+    await EventStreamManager.getInstance().reconnect(
+      namespaceId,
+      chain.slice(namespaceId.length + 1)
+    );
+  }
+  return [...new Set(chains.concat(connectors))];
+};
+
+export const reloadConnector: ConfigChangeListener = async (
+  namespaceId: string
+): Promise<string[]> => {
+  const dropped = await dropConnectors(
+    (connector) => connector === namespaceId
+  );
+  return [...new Set(dropped)];
+};
+
+/**
+ * Closes every chain and connector instance, for a restart or a shutdown.
+ */
+export async function closeAllInstances(): Promise<string[]> {
+  const chains: string[] = [];
+  for (const [namespaceId, target] of Object.entries(CHAINS)) {
+    chains.push(...(await dropInstances(namespaceId, target, () => true)));
+  }
+  const connectors = await dropConnectors(() => true);
+  PerpPriceHistory.stopAll();
+  return [...new Set(chains.concat(connectors))];
+}
+
+/**
+ * Lets the chains and connectors reload when /config/update changes their
+ * namespace. Namespaces that are only read on demand, like server or risk,
+ * have nothing to reload.
+ */
+export function registerConfigReloaders(): void {
+  const configManager = ConfigManagerV2.getInstance();
+  for (const [namespaceId, namespace] of Object.entries(
+    configManager.namespaces
+  )) {
+    if (namespaceId in CHAINS) {
+      namespace.onChange(reloadChain);
+    } else if (namespaceId in CONNECTORS) {
+      namespace.onChange(reloadConnector);
+    }
+  }
+}
@@ -2,3 +2,8 @@ export interface ConfigUpdateRequest {
   configPath: string;
   configValue: any;
 }
+
+export interface ConfigUpdateResponse {
+  message: string;
+  reloaded: string[]; // the chain and connector instances built again
+}
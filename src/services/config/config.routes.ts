@@ -2,7 +2,7 @@
 /* eslint-disable @typescript-eslint/ban-types */
 import { Router, Request, Response } from 'express';
 import { asyncHandler } from '../error-handler';
-import { ConfigUpdateRequest } from './config.requests';
+import { ConfigUpdateRequest, ConfigUpdateResponse } from './config.requests';
 import {
   validateConfigUpdateRequest,
   updateAllowedSlippageToFraction,
@@ -17,7 +17,7 @@ export namespace ConfigRoutes {
     asyncHandler(
       async (
         req: Request<unknown, unknown, ConfigUpdateRequest>,
-        res: Response
+        res: Response<ConfigUpdateResponse>
       ) => {
         validateConfigUpdateRequest(req.body);
         const config = ConfigManagerV2.getInstance().get(req.body.configPath);
@@ -36,12 +36,15 @@ export namespace ConfigRoutes {
           updateAllowedSlippageToFraction(req.body);
         }
 
-        ConfigManagerV2.getInstance().set(
+        const reloaded = await ConfigManagerV2.getInstance().update(
           req.body.configPath,
           req.body.configValue
         );
 
-        res.status(200).json({ message: 'The config has been updated' });
+        res.status(200).json({
+          message: 'The config has been updated',
+          reloaded,
+        });
       }
     )
   );
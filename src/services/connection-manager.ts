@@ -12,14 +12,18 @@ import { Quickswap } from '../connectors/quickswap/quickswap';
 import { PancakeSwap } from '../connectors/pancakeswap/pancakeswap';
 import { Uniswap } from '../connectors/uniswap/uniswap';
 import { UniswapLP } from '../connectors/uniswap/uniswap.lp';
+import { UniswapV2LP } from '../connectors/uniswap/uniswap.v2.lp';
 import { VVSConnector } from '../connectors/vvs/vvs';
 import {
+  CosmosAMMish,
+  Cosmosish,
   Ethereumish,
   Nearish,
   Perpish,
   RefAMMish,
   Uniswapish,
   UniswapLPish,
+  UniswapV2LPish,
 } from './common-interfaces';
 import { Traderjoe } from '../connectors/traderjoe/traderjoe';
 import { Sushiswap } from '../connectors/sushiswap/sushiswap';
@@ -27,13 +31,31 @@ import { Defikingdoms } from '../connectors/defikingdoms/defikingdoms';
 import { Defira } from '../connectors/defira/defira';
 import { Near } from '../chains/near/near';
 import { Ref } from '../connectors/ref/ref';
+import { Cosmos } from '../chains/cosmos/cosmos';
+import { OsmosisConnector } from '../connectors/osmosis/osmosis';
+import { AvailableNetworks } from './config-manager-types';
+import { DefikingdomsConfig } from '../connectors/defikingdoms/defikingdoms.config';
+import { DefiraConfig } from '../connectors/defira/defira.config';
+import { MadMeerkatConfig } from '../connectors/mad_meerkat/mad_meerkat.config';
+import { OpenoceanConfig } from '../connectors/openocean/openocean.config';
+import { OsmosisConfig } from '../connectors/osmosis/osmosis.config';
+import { PancakeSwapConfig } from '../connectors/pancakeswap/pancakeswap.config';
+import { PangolinConfig } from '../connectors/pangolin/pangolin.config';
+import { QuickswapConfig } from '../connectors/quickswap/quickswap.config';
+import { RefConfig } from '../connectors/ref/ref.config';
+import { SushiswapConfig } from '../connectors/sushiswap/sushiswap.config';
+import { TraderjoeConfig } from '../connectors/traderjoe/traderjoe.config';
+import { UniswapConfig } from '../connectors/uniswap/uniswap.config';
+import { VVSConfig } from '../connectors/vvs/vvs.config';
 
-export type ChainUnion = Ethereumish | Nearish;
+export type ChainUnion = Ethereumish | Nearish | Cosmosish;
 
 export type Chain<T> = T extends Ethereumish
   ? Ethereumish
   : T extends Nearish
   ? Nearish
+  : T extends Cosmosish
+  ? Cosmosish
   : never;
 
 export async function getChain<T>(
@@ -51,6 +73,8 @@ export async function getChain<T>(
   else if (chain === 'binance-smart-chain')
     chainInstance = BinanceSmartChain.getInstance(network);
   else if (chain === 'cronos') chainInstance = Cronos.getInstance(network);
+  else if (chain === 'osmosis')
+    chainInstance = Cosmos.getInstance(network, 'osmosis');
   else throw new Error('unsupported chain');
 
   if (!chainInstance.ready()) {
@@ -60,7 +84,15 @@ export async function getChain<T>(
   return chainInstance as Chain<T>;
 }
 
-type ConnectorUnion = Uniswapish | UniswapLPish | Perpish | RefAMMish;
+type ConnectorUnion =
+  | Uniswapish
+  | UniswapLPish
+  | Perpish
+  | RefAMMish
+  | CosmosAMMish;
+
+// the token list revision of its chain each connector last loaded tokens from
+const connectorTokenRevisions: WeakMap<ConnectorUnion, number> = new WeakMap();
 
 export type Connector<T> = T extends Uniswapish
   ? Uniswapish
@@ -70,6 +102,8 @@ export type Connector<T> = T extends Uniswapish
   ? Perpish
   : T extends RefAMMish
   ? RefAMMish
+  : T extends CosmosAMMish
+  ? CosmosAMMish
   : never;
 
 export async function getConnector<T>(
@@ -110,6 +144,8 @@ export async function getConnector<T>(
     connectorInstance = VVSConnector.getInstance(chain, network);
   } else if (chain === 'near' && connector === 'ref') {
     connectorInstance = Ref.getInstance(chain, network);
+  } else if (chain === 'osmosis' && connector === 'osmosis') {
+    connectorInstance = OsmosisConnector.getInstance(chain, network);
   } else if (chain === 'binance-smart-chain' && connector === 'pancakeswap') {
     connectorInstance = PancakeSwap.getInstance(chain, network);
   } else if (connector === 'sushiswap') {
@@ -122,5 +158,70 @@ export async function getConnector<T>(
     await connectorInstance.init();
   }
 
+  // connectors copy the tokens of their chain when they are initialized, so
+  // they are initialized again, which rebuilds their token map, once tokens
+  // were added, removed or imported
+  const chainInstance = await getChain<ChainUnion>(chain, network);
+  if ('tokenListRevision' in chainInstance) {
+    const revision = chainInstance.tokenListRevision;
+    const loaded = connectorTokenRevisions.get(connectorInstance);
+    if (loaded !== undefined && loaded !== revision) {
+      await connectorInstance.init();
+    }
+    connectorTokenRevisions.set(connectorInstance, revision);
+  }
+
   return connectorInstance as Connector<T>;
 }
+
+// the Uniswap V2 forks liquidity can be provided to through their router
+export const V2_LP_CONNECTORS: string[] = [
+  'sushiswap',
+  'pangolin',
+  'traderjoe',
+  'quickswap',
+  'pancakeswap',
+  'defikingdoms',
+  'defira',
+  'vvs',
+  'mad_meerkat',
+];
+
+export async function getV2LPConnector(
+  chain: string,
+  network: string,
+  connector: string | undefined
+): Promise<UniswapV2LPish> {
+  if (connector === undefined || !V2_LP_CONNECTORS.includes(connector)) {
+    throw new Error('unsupported chain or connector');
+  }
+  const chainInstance = await getChain<Ethereumish>(chain, network);
+  const uniswapish = await getConnector<Uniswapish>(chain, network, connector);
+  return UniswapV2LP.getInstance(chainInstance, uniswapish, connector);
+}
+
+// the connectors that swap tokens, with the networks each one is available on
+export const SWAP_CONNECTORS: Record<string, Array<AvailableNetworks>> = {
+  uniswap: UniswapConfig.config.availableNetworks,
+  pangolin: PangolinConfig.config.availableNetworks,
+  openocean: OpenoceanConfig.config.availableNetworks,
+  quickswap: QuickswapConfig.config.availableNetworks,
+  sushiswap: SushiswapConfig.config.availableNetworks,
+  traderjoe: TraderjoeConfig.config.availableNetworks,
+  defikingdoms: DefikingdomsConfig.config.availableNetworks,
+  defira: DefiraConfig.config.availableNetworks,
+  mad_meerkat: MadMeerkatConfig.config.availableNetworks,
+  vvs: VVSConfig.config.availableNetworks,
+  ref: RefConfig.config.availableNetworks,
+  osmosis: OsmosisConfig.config.availableNetworks,
+  pancakeswap: PancakeSwapConfig.config.availableNetworks,
+};
+
+export function getSwapConnectors(chain: string, network: string): string[] {
+  return Object.keys(SWAP_CONNECTORS).filter((connector) =>
+    SWAP_CONNECTORS[connector].some(
+      (available) =>
+        available.chain === chain && available.networks.includes(network)
+    )
+  );
+}
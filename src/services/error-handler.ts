@@ -101,6 +101,14 @@ export const PRICE_FAILED_ERROR_CODE = 1013;
 export const INCOMPLETE_REQUEST_PARAM_CODE = 1014;
 export const ERROR_RETRIEVING_WALLET_ADDRESS_ERROR_CODE = 1015;
 export const ACCOUNT_NOT_SPECIFIED_CODE = 1016;
+export const TRANSACTION_NOT_FOUND_ERROR_CODE = 1017;
+export const TRANSACTION_NOT_REPLACEABLE_ERROR_CODE = 1018;
+export const UNAUTHORIZED_ERROR_CODE = 1019;
+export const FORBIDDEN_ERROR_CODE = 1020;
+export const RISK_LIMIT_EXCEEDED_ERROR_CODE = 1021;
+export const TOKEN_LIST_UPDATE_FAILED_ERROR_CODE = 1022;
+export const SERVER_STOPPING_ERROR_CODE = 1023;
+export const IBC_CHANNEL_NOT_FOUND_ERROR_CODE = 1024;
 export const UNKNOWN_ERROR_ERROR_CODE = 1099;
 
 export const NETWORK_ERROR_MESSAGE =
@@ -140,6 +148,38 @@ export const ERROR_RETRIEVING_WALLET_ADDRESS_ERROR_MESSAGE = (
     5
   )}`;
 
+export const TRANSACTION_NOT_FOUND_ERROR_MESSAGE = (txHash: string) =>
+  `Transaction ${txHash} was not found.`;
+
+export const TRANSACTION_NOT_REPLACEABLE_ERROR_MESSAGE = (
+  txHash: string,
+  reason: string
+) => `Transaction ${txHash} cannot be replaced: ${reason}`;
+
+export const UNAUTHORIZED_ERROR_MESSAGE = (reason: string) =>
+  `Unauthorized request: ${reason}.`;
+
+export const FORBIDDEN_ERROR_MESSAGE = (reason: string) =>
+  `Forbidden: ${reason} is not allowed to make this request.`;
+
+export const RISK_LIMIT_EXCEEDED_ERROR_MESSAGE = (reason: string) =>
+  `Risk limit exceeded: ${reason}.`;
+
+export const TOKEN_LIST_UPDATE_FAILED_ERROR_MESSAGE = (reason: string) =>
+  `Token list update failed: ${reason}.`;
+
+export const SERVER_STOPPING_ERROR_MESSAGE =
+  'The gateway is restarting or shutting down, retry the request shortly.';
+
+export const GAS_PRICE_TOO_LOW_ERROR_MESSAGE = (
+  gasPrice: number,
+  minimumGasPrice: number
+) =>
+  `Gas price ${gasPrice} is lower than the minimum gas price ${minimumGasPrice}.`;
+
+export const IBC_CHANNEL_NOT_FOUND_ERROR_MESSAGE = (destination: string) =>
+  `No IBC channel to ${destination} is configured.`;
+
 export const UNKNOWN_ERROR_MESSAGE = 'Unknown error.';
 
 export const PRICE_FAILED_ERROR_MESSAGE = 'Price query failed: ';
@@ -0,0 +1,44 @@
+import { latency } from '../base';
+import { ConfigManagerV2 } from '../config-manager-v2';
+import { ReferenceCountingCloseable } from '../refcounting-closeable';
+import { TransactionJournal } from '../transaction-journal';
+import { resolveDBPath } from '../../paths';
+import { HistoryRequest, HistoryResponse } from './history.requests';
+
+const historyHandle: string = ReferenceCountingCloseable.createHandle();
+
+// the journal is shared with the chain instances through the transaction db,
+// so the history is available even if no chain has been initialized yet
+export function getJournal(): TransactionJournal {
+  return TransactionJournal.getInstance(
+    resolveDBPath(
+      ConfigManagerV2.getInstance().get('server.transactionDbPath')
+    ),
+    historyHandle
+  );
+}
+
+export async function closeJournal(): Promise<void> {
+  await getJournal().close(historyHandle);
+}
+
+export async function getHistory(
+  req: HistoryRequest
+): Promise<HistoryResponse> {
+  const initTime = Date.now();
+  const transactions = await getJournal().query({
+    chain: req.chain,
+    network: req.network,
+    wallet: req.wallet,
+    connector: req.connector,
+    type: req.type,
+    from: req.from ? parseInt(req.from) : undefined,
+    to: req.to ? parseInt(req.to) : undefined,
+  });
+
+  return {
+    timestamp: initTime,
+    latency: latency(initTime, Date.now()),
+    transactions,
+  };
+}
@@ -0,0 +1,17 @@
+import { JournalEntry } from '../transaction-journal';
+
+export interface HistoryRequest {
+  chain?: string; // the chain the transactions were sent on (e.g. ethereum)
+  network?: string; // the network of the chain (e.g. mainnet)
+  wallet?: string; // the address that sent the transactions
+  connector?: string; // the connector that built them (e.g. uniswap)
+  type?: string; // trade, approve, cancel, addLiquidity, etc.
+  from?: string; // POSIX timestamp in milliseconds, inclusive
+  to?: string; // POSIX timestamp in milliseconds, inclusive
+}
+
+export interface HistoryResponse {
+  timestamp: number;
+  latency: number;
+  transactions: JournalEntry[];
+}
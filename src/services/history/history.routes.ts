@@ -0,0 +1,27 @@
+/* eslint-disable @typescript-eslint/ban-types */
+import { Router, Request, Response } from 'express';
+
+import { asyncHandler } from '../error-handler';
+
+import { getHistory } from './history.controllers';
+
+import { HistoryRequest, HistoryResponse } from './history.requests';
+
+import { validateHistoryRequest } from './history.validators';
+
+export namespace HistoryRoutes {
+  export const router = Router();
+
+  router.get(
+    '/',
+    asyncHandler(
+      async (
+        req: Request<{}, {}, {}, HistoryRequest>,
+        res: Response<HistoryResponse, {}>
+      ) => {
+        validateHistoryRequest(req.query);
+        res.status(200).json(await getHistory(req.query));
+      }
+    )
+  );
+}
@@ -0,0 +1,48 @@
+import {
+  mkRequestValidator,
+  mkValidator,
+  RequestValidator,
+  Validator,
+} from '../validators';
+import { JOURNAL_TX_TYPES } from '../transaction-journal';
+
+export const invalidHistoryTypeError: string =
+  'The type param must be one of ' + JOURNAL_TX_TYPES.join(', ') + '.';
+
+export const invalidFromError: string =
+  'The from param must be a POSIX timestamp in milliseconds.';
+
+export const invalidToError: string =
+  'The to param must be a POSIX timestamp in milliseconds.';
+
+const isTimestamp = (val: any): boolean =>
+  typeof val === 'string' && /^\d+$/.test(val);
+
+export const validateHistoryType: Validator = mkValidator(
+  'type',
+  invalidHistoryTypeError,
+  (val) =>
+    typeof val === 'string' &&
+    (JOURNAL_TX_TYPES as readonly string[]).includes(val),
+  true
+);
+
+export const validateFrom: Validator = mkValidator(
+  'from',
+  invalidFromError,
+  isTimestamp,
+  true
+);
+
+export const validateTo: Validator = mkValidator(
+  'to',
+  invalidToError,
+  isTimestamp,
+  true
+);
+
+export const validateHistoryRequest: RequestValidator = mkRequestValidator([
+  validateHistoryType,
+  validateFrom,
+  validateTo,
+]);
@@ -54,6 +54,17 @@ export class LocalStorage extends ReferenceCountingCloseable {
     await this.#db.del(key);
   }
 
+  // the value of a single key, undefined if it isn't set
+  public async getValue(key: string): Promise<any> {
+    await this.assertDbOpen();
+    try {
+      return await this.#db.get(key);
+    } catch (e) {
+      if (e.code === 'LEVEL_NOT_FOUND') return undefined;
+      throw e;
+    }
+  }
+
   public async get(
     readFunc: (key: string, string: any) => [string, any] | undefined
   ): Promise<Record<string, any>> {
@@ -0,0 +1,213 @@
+import Decimal from 'decimal.js-light';
+import { ConfigManagerV2, percentRegexp } from './config-manager-v2';
+import {
+  HttpException,
+  RISK_LIMIT_EXCEEDED_ERROR_CODE,
+  RISK_LIMIT_EXCEEDED_ERROR_MESSAGE,
+} from './error-handler';
+import { logger } from './logger';
+import { JournalEntry, TransactionJournal } from './transaction-journal';
+
+// the journal entries that count towards the daily volume
+const VOLUME_TX_TYPES = ['trade', 'perpOpen', 'perpLimitOrder'];
+
+// connectors that read their configuration from another connector's
+// namespace
+const CONNECTOR_NAMESPACES: Record<string, string> = { uniswapLP: 'uniswap' };
+
+export interface RiskCheckRequest {
+  wallet: string;
+  connector?: string;
+  // the tokens the transaction moves, by symbol, and the amount of each in
+  // the same order. An approval only has tokens.
+  tokens: string[];
+  amounts?: string[];
+  allowedSlippage?: string; // the connector's configured one when not set
+  // the journal of the chain, for the daily volume. Transactions that don't
+  // count towards it leave it out.
+  journal?: TransactionJournal;
+}
+
+// the limits from the risk namespace, see src/templates/risk.yml
+export interface RiskLimits {
+  maxNotional: Record<string, number>;
+  maxDailyVolume: Record<string, number>;
+  tokenAllowList: string[];
+  tokenDenyList: string[];
+  maxAllowedSlippage?: string;
+}
+
+export const getRiskLimits = (): RiskLimits => {
+  const config = ConfigManagerV2.getInstance();
+  return {
+    maxNotional: config.get('risk.maxNotional') || {},
+    maxDailyVolume: config.get('risk.maxDailyVolume') || {},
+    tokenAllowList: config.get('risk.tokenAllowList') || [],
+    tokenDenyList: config.get('risk.tokenDenyList') || [],
+    maxAllowedSlippage: config.get('risk.maxAllowedSlippage') || undefined,
+  };
+};
+
+const reject = (reason: string): never => {
+  logger.error(`Risk check failed: ${reason}`);
+  throw new HttpException(
+    403,
+    RISK_LIMIT_EXCEEDED_ERROR_MESSAGE(reason),
+    RISK_LIMIT_EXCEEDED_ERROR_CODE
+  );
+};
+
+// token symbols are matched regardless of case
+const limitFor = (
+  limits: Record<string, number>,
+  token: string
+): number | undefined => {
+  const key = Object.keys(limits).find(
+    (symbol) => symbol.toUpperCase() === token.toUpperCase()
+  );
+  return key !== undefined ? limits[key] : undefined;
+};
+
+const includesToken = (list: string[], token: string): boolean =>
+  list.some((symbol) => symbol.toUpperCase() === token.toUpperCase());
+
+const parseFraction = (fraction: string): Decimal | undefined => {
+  const nd = fraction.match(percentRegexp);
+  if (!nd || Number(nd[2]) === 0) return undefined;
+  return new Decimal(nd[1]).div(nd[2]);
+};
+
+export function checkTokens(limits: RiskLimits, tokens: string[]): void {
+  for (const token of tokens) {
+    if (includesToken(limits.tokenDenyList, token)) {
+      reject(`${token} is on the token deny list`);
+    }
+    if (
+      limits.tokenAllowList.length > 0 &&
+      !includesToken(limits.tokenAllowList, token)
+    ) {
+      reject(`${token} is not on the token allow list`);
+    }
+  }
+}
+
+// the slippage a connector applies when the request doesn't set one
+export const connectorSlippage = (connector?: string): string | undefined => {
+  if (!connector) return undefined;
+  const namespace = CONNECTOR_NAMESPACES[connector] || connector;
+  try {
+    return (
+      ConfigManagerV2.getInstance().get(`${namespace}.allowedSlippage`) ||
+      undefined
+    );
+  } catch (_e) {
+    return undefined; // the connector has no configuration of its own
+  }
+};
+
+export function checkSlippage(
+  limits: RiskLimits,
+  requestSlippage?: string,
+  connector?: string
+): void {
+  const allowedSlippage = requestSlippage || connectorSlippage(connector);
+  if (!limits.maxAllowedSlippage || !allowedSlippage) return;
+  const maxSlippage = parseFraction(limits.maxAllowedSlippage);
+  const slippage = parseFraction(allowedSlippage);
+  if (maxSlippage && slippage && slippage.gt(maxSlippage)) {
+    reject(
+      `allowedSlippage ${allowedSlippage} exceeds ${limits.maxAllowedSlippage}`
+    );
+  }
+}
+
+export function checkNotional(
+  limits: RiskLimits,
+  tokens: string[],
+  amounts: string[]
+): void {
+  tokens.forEach((token, i) => {
+    const max = limitFor(limits.maxNotional, token);
+    if (max !== undefined && amounts[i] && new Decimal(amounts[i]).gt(max)) {
+      reject(`${amounts[i]} ${token} exceeds the maximum of ${max} per trade`);
+    }
+  });
+}
+
+const startOfUtcDay = (now: number): number => {
+  const date = new Date(now);
+  return Date.UTC(date.getUTCFullYear(), date.getUTCMonth(), date.getUTCDate());
+};
+
+// the volume of each token the wallet traded through the connector since the
+// start of the UTC day, from the transaction journal
+export async function dailyVolume(
+  journal: TransactionJournal,
+  wallet: string,
+  connector?: string,
+  now: number = Date.now()
+): Promise<Record<string, Decimal>> {
+  const entries: JournalEntry[] = await journal.query({
+    wallet,
+    connector,
+    from: startOfUtcDay(now),
+  });
+  const volume: Record<string, Decimal> = {};
+  for (const entry of entries) {
+    // a replaced transaction is journaled again under its replacement
+    if (
+      !VOLUME_TX_TYPES.includes(entry.type) ||
+      ['FAILED', 'REPLACED'].includes(entry.status)
+    ) {
+      continue;
+    }
+    entry.tokens.forEach((token, i) => {
+      if (!entry.amounts[i]) return;
+      const symbol = token.toUpperCase();
+      volume[symbol] = (volume[symbol] || new Decimal(0)).add(
+        new Decimal(entry.amounts[i])
+      );
+    });
+  }
+  return volume;
+}
+
+export async function checkDailyVolume(
+  limits: RiskLimits,
+  req: RiskCheckRequest
+): Promise<void> {
+  if (!req.journal || !req.amounts) return;
+  const limited = req.tokens.filter(
+    (token) => limitFor(limits.maxDailyVolume, token) !== undefined
+  );
+  if (limited.length === 0) return;
+
+  const volume = await dailyVolume(req.journal, req.wallet, req.connector);
+  req.tokens.forEach((token, i) => {
+    const max = limitFor(limits.maxDailyVolume, token);
+    if (max === undefined || !req.amounts || !req.amounts[i]) return;
+    const total = (volume[token.toUpperCase()] || new Decimal(0)).add(
+      new Decimal(req.amounts[i])
+    );
+    if (total.gt(max)) {
+      reject(
+        `${req.wallet} would trade ${total.toString()} ${token} today through ${req.connector}, the maximum is ${max}`
+      );
+    }
+  });
+}
+
+/**
+ * Checks a transaction against the limits in risk.yml before it is sent, and
+ * throws an HttpException with RISK_LIMIT_EXCEEDED_ERROR_CODE if it breaks
+ * one of them.
+ */
+export async function checkRisk(req: RiskCheckRequest): Promise<void> {
+  const limits = getRiskLimits();
+  checkTokens(limits, req.tokens);
+  checkSlippage(limits, req.allowedSlippage, req.connector);
+  if (req.amounts) {
+    checkNotional(limits, req.tokens, req.amounts);
+  }
+  await checkDailyVolume(limits, req);
+}
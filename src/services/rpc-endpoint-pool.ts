@@ -0,0 +1,291 @@
+import { logger } from './logger';
+
+// an RPC endpoint as configured in a chain template, e.g.
+//   nodeURLs:
+//     - url: https://mainnet.infura.io/v3/<key>
+//       weight: 2
+//     - url: https://eth.llamarpc.com
+export interface RpcEndpointConfig {
+  url: string;
+  weight?: number; // share of the requests this endpoint gets, defaults to 1
+}
+
+export interface RpcEndpointHealth {
+  url: string;
+  weight: number;
+  healthy: boolean; // false while the endpoint is cooling down after errors
+  score: number;
+  requests: number;
+  errors: number;
+  latency: number; // moving average, in milliseconds
+  lastError?: string;
+  lastErrorTimestamp?: number;
+}
+
+export interface RpcEndpointPoolOptions {
+  quorum?: number;
+  // whether an error is the endpoint's fault, and another endpoint may do
+  // better. Errors that every node would return (a reverted call, an unknown
+  // account) are passed on to the caller right away.
+  isRetryable?: (error: any) => boolean;
+}
+
+// an endpoint that fails is skipped for a while, doubling up to the maximum
+// for every consecutive failure
+export const RPC_ENDPOINT_COOLDOWN = 5000;
+export const RPC_ENDPOINT_MAX_COOLDOWN = 120000;
+
+// weight of the latest sample in the latency and reliability averages
+const SMOOTHING_FACTOR = 0.2;
+
+export class NoQuorumError extends Error {
+  constructor(quorum: number, reason?: string) {
+    super(
+      `Fewer than ${quorum} RPC endpoints agreed on the result` +
+        (reason ? `: ${reason}` : '.')
+    );
+    this.name = 'NoQuorumError';
+  }
+}
+
+export class RpcEndpoint<T> {
+  readonly url: string;
+  readonly weight: number;
+  requests: number = 0;
+  errors: number = 0;
+  latency: number = 0;
+  reliability: number = 1; // moving average of the success rate
+  consecutiveErrors: number = 0;
+  cooldownUntil: number = 0;
+  lastError?: string;
+  lastErrorTimestamp?: number;
+  private _client: T | undefined;
+  private readonly _createClient: (url: string) => T;
+
+  constructor(config: RpcEndpointConfig, createClient: (url: string) => T) {
+    this.url = config.url;
+    this.weight = config.weight !== undefined ? config.weight : 1;
+    this._createClient = createClient;
+  }
+
+  // clients are created on first use, and again after a failure in case the
+  // connection itself went bad
+  get client(): T {
+    if (this._client === undefined) {
+      this._client = this._createClient(this.url);
+    }
+    return this._client;
+  }
+
+  get healthy(): boolean {
+    return Date.now() >= this.cooldownUntil;
+  }
+
+  // favors reliable, fast endpoints in proportion to their weight
+  get score(): number {
+    return (this.weight * this.reliability) / (1 + this.latency / 1000);
+  }
+
+  recordSuccess(latency: number) {
+    this.requests += 1;
+    this.latency =
+      this.requests === 1
+        ? latency
+        : this.latency + SMOOTHING_FACTOR * (latency - this.latency);
+    this.reliability += SMOOTHING_FACTOR * (1 - this.reliability);
+    this.consecutiveErrors = 0;
+  }
+
+  recordError(error: any) {
+    this.requests += 1;
+    this.errors += 1;
+    this.reliability -= SMOOTHING_FACTOR * this.reliability;
+    this.consecutiveErrors += 1;
+    this.cooldownUntil =
+      Date.now() +
+      Math.min(
+        RPC_ENDPOINT_COOLDOWN * 2 ** (this.consecutiveErrors - 1),
+        RPC_ENDPOINT_MAX_COOLDOWN
+      );
+    this.lastError = error instanceof Error ? error.message : String(error);
+    this.lastErrorTimestamp = Date.now();
+    this._client = undefined;
+  }
+
+  health(): RpcEndpointHealth {
+    return {
+      url: this.url,
+      weight: this.weight,
+      healthy: this.healthy,
+      score: this.score,
+      requests: this.requests,
+      errors: this.errors,
+      latency: Math.round(this.latency),
+      lastError: this.lastError,
+      lastErrorTimestamp: this.lastErrorTimestamp,
+    };
+  }
+}
+
+/**
+ * Spreads RPC calls over several endpoints of the same network.
+ *
+ * Every call goes to a healthy endpoint picked at random in proportion to its
+ * score. When it fails with a retryable error, the endpoint is put on a
+ * cooldown and the call moves on to the next best one, so a single rate
+ * limited or unreachable node doesn't take the chain down with it.
+ *
+ * Quorum reads ask several endpoints and only return a result once `quorum`
+ * of them agree on it.
+ *
+ * Calls that must not be repeated, such as broadcasting a signed transaction,
+ * go to a single endpoint with `executeOnce`.
+ */
+export class RpcEndpointPool<T> {
+  readonly endpoints: RpcEndpoint<T>[];
+  readonly quorum: number;
+  private readonly _isRetryable: (error: any) => boolean;
+
+  constructor(
+    configs: RpcEndpointConfig[],
+    createClient: (url: string) => T,
+    options: RpcEndpointPoolOptions = {}
+  ) {
+    if (configs.length === 0) {
+      throw new Error('At least one RPC endpoint is required.');
+    }
+    this.endpoints = configs.map(
+      (config) => new RpcEndpoint(config, createClient)
+    );
+    this.quorum = Math.min(
+      Math.max(options.quorum || 1, 1),
+      this.endpoints.length
+    );
+    if (options.quorum && options.quorum > this.endpoints.length) {
+      logger.warn(
+        `RPC quorum of ${options.quorum} is more than the ${this.endpoints.length} configured endpoints, using ${this.quorum}.`
+      );
+    }
+    this._isRetryable = options.isRetryable || (() => true);
+  }
+
+  public health(): RpcEndpointHealth[] {
+    return this.endpoints.map((endpoint) => endpoint.health());
+  }
+
+  // healthy endpoints first, the first one drawn by score. Endpoints that are
+  // cooling down are still tried last, soonest available first, rather than
+  // failing outright.
+  public ordered(): RpcEndpoint<T>[] {
+    const healthy = this.endpoints
+      .filter((endpoint) => endpoint.healthy)
+      .sort((a, b) => b.score - a.score);
+    const cooling = this.endpoints
+      .filter((endpoint) => !endpoint.healthy)
+      .sort((a, b) => a.cooldownUntil - b.cooldownUntil);
+
+    const total = healthy.reduce((sum, endpoint) => sum + endpoint.score, 0);
+    if (healthy.length > 1 && total > 0) {
+      let draw = Math.random() * total;
+      const index = healthy.findIndex((endpoint) => {
+        draw -= endpoint.score;
+        return draw < 0;
+      });
+      if (index > 0) healthy.unshift(...healthy.splice(index, 1));
+    }
+    return healthy.concat(cooling);
+  }
+
+  public async execute<R>(call: (client: T) => Promise<R>): Promise<R> {
+    let lastError: any;
+    for (const endpoint of this.ordered()) {
+      try {
+        return await this.call(endpoint, call);
+      } catch (e) {
+        if (!this._isRetryable(e)) throw e;
+        lastError = e;
+        logger.warn(`RPC endpoint ${endpoint.url} failed: ${e}`);
+      }
+    }
+    throw lastError;
+  }
+
+  // a failed broadcast may still have reached the network, so it is left to
+  // the caller rather than sent again through another endpoint
+  public async executeOnce<R>(call: (client: T) => Promise<R>): Promise<R> {
+    return this.call(this.ordered()[0], call);
+  }
+
+  // asks the best endpoints first, and only widens the request to the others
+  // while they fail or disagree. An endpoint that doesn't know the result yet
+  // (a receipt or transaction it hasn't seen) answers null and doesn't vote:
+  // unless the others agree on a result, the read is null, i.e. pending.
+  public async executeQuorum<R>(call: (client: T) => Promise<R>): Promise<R> {
+    if (this.quorum <= 1) return this.execute(call);
+
+    const votes: Record<string, { result: R; count: number }> = {};
+    let best = 0;
+    let pending: { result: R } | undefined;
+    let lastError: any;
+    const endpoints = this.ordered();
+    let next = 0;
+    while (next < endpoints.length) {
+      const batch = endpoints.slice(next, next + this.quorum - best);
+      next += batch.length;
+      const outcomes = await Promise.all(
+        batch.map((endpoint) =>
+          this.call(endpoint, call).then(
+            (result) => ({ result, error: undefined }),
+            (error) => ({ result: undefined, error })
+          )
+        )
+      );
+      for (const outcome of outcomes) {
+        if (outcome.error !== undefined) {
+          if (!this._isRetryable(outcome.error)) throw outcome.error;
+          lastError = outcome.error;
+          continue;
+        }
+        if (outcome.result === null || outcome.result === undefined) {
+          pending = { result: <R>outcome.result };
+          continue;
+        }
+        const key = JSON.stringify(outcome.result);
+        if (!(key in votes)) {
+          votes[key] = { result: <R>outcome.result, count: 0 };
+        }
+        votes[key].count += 1;
+        if (votes[key].count >= this.quorum) return votes[key].result;
+        best = Math.max(best, votes[key].count);
+      }
+    }
+    // endpoints lagging behind the others are not a disagreement
+    if (pending !== undefined && Object.keys(votes).length <= 1) {
+      return pending.result;
+    }
+    throw new NoQuorumError(
+      this.quorum,
+      lastError !== undefined ? String(lastError) : undefined
+    );
+  }
+
+  private async call<R>(
+    endpoint: RpcEndpoint<T>,
+    call: (client: T) => Promise<R>
+  ): Promise<R> {
+    const start = Date.now();
+    try {
+      const result = await call(endpoint.client);
+      endpoint.recordSuccess(Date.now() - start);
+      return result;
+    } catch (e) {
+      if (this._isRetryable(e)) {
+        endpoint.recordError(e);
+      } else {
+        // the node answered, it's the request that's wrong
+        endpoint.recordSuccess(Date.now() - start);
+      }
+      throw e;
+    }
+  }
+}
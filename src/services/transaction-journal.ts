@@ -0,0 +1,191 @@
+import { LocalStorage } from './local-storage';
+import { logger } from './logger';
+import { ReferenceCountingCloseable } from './refcounting-closeable';
+
+// every kind of transaction the journal records, the /history filter accepts
+// exactly these
+export const JOURNAL_TX_TYPES = [
+  'trade',
+  'approve',
+  'transfer',
+  'wrap',
+  'unwrap',
+  'cancel',
+  'storageDeposit',
+  'addLiquidity',
+  'removeLiquidity',
+  'collectFees',
+  'rebalanceLiquidity',
+  'increaseObservations',
+  'perpOpen',
+  'perpClose',
+  'perpLimitOrder',
+  'perpCancelOrder',
+  'perpAddMargin',
+  'perpRemoveMargin',
+] as const;
+
+export type JournalTxType = typeof JOURNAL_TX_TYPES[number];
+
+export type JournalTxStatus = 'PENDING' | 'CONFIRMED' | 'FAILED' | 'REPLACED';
+
+export interface JournalEntry {
+  txHash: string;
+  chain: string;
+  network: string;
+  type: JournalTxType;
+  connector?: string;
+  wallet: string;
+  timestamp: number;
+  nonce?: number;
+  tokens: string[];
+  amounts: string[];
+  side?: string;
+  price?: string;
+  gasPrice?: number;
+  gasLimit?: number;
+  status: JournalTxStatus;
+  receipt?: any;
+  replacedBy?: string; // hash of the transaction that replaced this one
+}
+
+export type NewJournalEntry = Omit<JournalEntry, 'timestamp' | 'status'>;
+
+export interface JournalFilter {
+  chain?: string;
+  network?: string;
+  wallet?: string;
+  connector?: string;
+  type?: string;
+  from?: number;
+  to?: number;
+}
+
+const JOURNAL_PREFIX = 'journal/';
+
+// keeps a record of every transaction sent through the gateway, so it can be
+// reconciled against on-chain activity. The entries live in the transaction
+// db next to the EvmTxStorage timestamps; the values are stored as JSON
+// strings under 'journal/<txHash>', which EvmTxStorage.getTxs skips.
+export class TransactionJournal extends ReferenceCountingCloseable {
+  readonly localStorage: LocalStorage;
+
+  protected constructor(dbPath: string) {
+    super(dbPath);
+    this.localStorage = LocalStorage.getInstance(dbPath, this.handle);
+  }
+
+  public async init(): Promise<void> {
+    await this.localStorage.init();
+  }
+
+  // failing to journal a transaction must not fail the request that sent it
+  public async record(entry: NewJournalEntry): Promise<void> {
+    try {
+      await this.save({ ...entry, timestamp: Date.now(), status: 'PENDING' });
+    } catch (e) {
+      logger.error(`Could not journal transaction ${entry.txHash}: ${e}`);
+    }
+  }
+
+  public async updateStatus(
+    txHash: string,
+    status: JournalTxStatus,
+    receipt?: any
+  ): Promise<void> {
+    try {
+      const entry = await this.getEntry(txHash);
+      if (entry && entry.status !== status) {
+        await this.save({ ...entry, status, receipt });
+      }
+    } catch (e) {
+      logger.error(`Could not update journal for ${txHash}: ${e}`);
+    }
+  }
+
+  // the replacement inherits the original entry, so a sped up trade still
+  // shows up as a trade in the history
+  public async recordReplacement(
+    txHash: string,
+    replacementTxHash: string,
+    gasPrice?: number
+  ): Promise<void> {
+    try {
+      const entry = await this.getEntry(txHash);
+      if (!entry) return;
+      await this.save({
+        ...entry,
+        status: 'REPLACED',
+        replacedBy: replacementTxHash,
+      });
+      await this.save({
+        ...entry,
+        txHash: replacementTxHash,
+        timestamp: Date.now(),
+        gasPrice,
+        status: 'PENDING',
+      });
+    } catch (e) {
+      logger.error(`Could not journal replacement of ${txHash}: ${e}`);
+    }
+  }
+
+  public async getEntry(txHash: string): Promise<JournalEntry | undefined> {
+    const value = await this.localStorage.getValue(JOURNAL_PREFIX + txHash);
+    return value === undefined ? undefined : JSON.parse(value);
+  }
+
+  // returns the matching entries, most recent first
+  public async query(filter: JournalFilter): Promise<JournalEntry[]> {
+    const results = await this.localStorage.get(
+      (key: string, value: string) => {
+        if (!key.startsWith(JOURNAL_PREFIX)) return;
+        const entry: JournalEntry = JSON.parse(value);
+        if (matchesFilter(entry, filter)) {
+          return [key, entry];
+        }
+        return;
+      }
+    );
+    return Object.values(results).sort(
+      (a: JournalEntry, b: JournalEntry) => b.timestamp - a.timestamp
+    );
+  }
+
+  public async deleteEntry(txHash: string): Promise<void> {
+    return this.localStorage.del(JOURNAL_PREFIX + txHash);
+  }
+
+  private async save(entry: JournalEntry): Promise<void> {
+    return this.localStorage.save(
+      JOURNAL_PREFIX + entry.txHash,
+      JSON.stringify(entry)
+    );
+  }
+
+  public async close(handle: string): Promise<void> {
+    await super.close(handle);
+    if (this.refCount < 1) {
+      await this.localStorage.close(this.handle);
+    }
+  }
+}
+
+export function matchesFilter(
+  entry: JournalEntry,
+  filter: JournalFilter
+): boolean {
+  if (filter.chain && entry.chain !== filter.chain) return false;
+  if (filter.network && entry.network !== filter.network) return false;
+  // EVM addresses may come checksummed or not
+  if (
+    filter.wallet &&
+    entry.wallet.toLowerCase() !== filter.wallet.toLowerCase()
+  )
+    return false;
+  if (filter.connector && entry.connector !== filter.connector) return false;
+  if (filter.type && entry.type !== filter.type) return false;
+  if (filter.from && entry.timestamp < filter.from) return false;
+  if (filter.to && entry.timestamp > filter.to) return false;
+  return true;
+}
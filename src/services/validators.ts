@@ -3,6 +3,9 @@ import { HttpException } from './error-handler';
 export const invalidAmountError: string =
   'If amount is included it must be a string of a non-negative integer.';
 
+export const invalidTransferAmountError: string =
+  'The amount param must be a string of a positive number.';
+
 export const invalidTokenError: string = 'The token param should be a string.';
 
 export const invalidTxHashError: string = 'The txHash param must be a string.';
@@ -175,6 +178,13 @@ export const validateAmount: Validator = mkValidator(
   true
 );
 
+// a transfer has to move something, in units of the token
+export const validateTransferAmount: Validator = mkValidator(
+  'amount',
+  invalidTransferAmountError,
+  (val) => typeof val === 'string' && isFloatString(val) && parseFloat(val) > 0
+);
+
 export const validateTxHash: Validator = mkValidator(
   'txHash',
   invalidTxHashError,
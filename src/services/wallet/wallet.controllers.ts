@@ -57,8 +57,8 @@ export async function addWallet(
     connection = Cronos.getInstance(req.network);
   } else if (req.chain === 'polygon') {
     connection = Polygon.getInstance(req.network);
-  } else if (req.chain === 'cosmos') {
-    connection = Cosmos.getInstance(req.network);
+  } else if (req.chain === 'cosmos' || req.chain === 'osmosis') {
+    connection = Cosmos.getInstance(req.network, req.chain);
   } else if (req.chain === 'near') {
     if (!('address' in req))
       throw new HttpException(
@@ -91,7 +91,7 @@ export async function addWallet(
     } else if (connection instanceof Cosmos) {
       const wallet = await connection.getAccountsfromPrivateKey(
         req.privateKey,
-        'cosmos'
+        connection.bech32Prefix
       );
       address = wallet.address;
       encryptedPrivateKey = await connection.encrypt(
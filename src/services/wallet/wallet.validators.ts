@@ -72,6 +72,11 @@ export const validatePrivateKey: Validator = mkSelectingValidator(
       invalidCosmosPrivateKeyError,
       (val) => typeof val === 'string' && isCosmosPrivateKey(val)
     ),
+    osmosis: mkValidator(
+      'privateKey',
+      invalidCosmosPrivateKeyError,
+      (val) => typeof val === 'string' && isCosmosPrivateKey(val)
+    ),
     polygon: mkValidator(
       'privateKey',
       invalidEthPrivateKeyError,
@@ -86,7 +91,7 @@ export const validatePrivateKey: Validator = mkSelectingValidator(
 );
 
 export const invalidChainError: string =
-  'chain must be "ethereum", "avalanche", "near", "harmony", "cosmos" or "binance-smart-chain"';
+  'chain must be "ethereum", "avalanche", "near", "harmony", "cosmos", "osmosis" or "binance-smart-chain"';
 
 export const invalidNetworkError: string =
   'expected a string for the network key';
@@ -105,6 +110,7 @@ export const validateChain: Validator = mkValidator(
       val === 'harmony' ||
       val === 'cronos' ||
       val === 'cosmos' ||
+      val === 'osmosis' ||
       val === 'binance-smart-chain')
 );
 
@@ -0,0 +1,154 @@
+import express from 'express';
+import { Express } from 'express-serve-static-core';
+import request from 'supertest';
+import { Ethereum } from '../../src/chains/ethereum/ethereum';
+import { Sushiswap } from '../../src/connectors/sushiswap/sushiswap';
+import { Uniswap } from '../../src/connectors/uniswap/uniswap';
+import { AmmRoutes } from '../../src/amm/amm.routes';
+import { patch, unpatch } from '../services/patch';
+import { gasCostInEthString } from '../../src/services/base';
+
+let app: Express;
+let ethereum: Ethereum;
+let uniswap: Uniswap;
+let sushiswap: Sushiswap;
+
+beforeAll(async () => {
+  app = express();
+  app.use(express.json());
+  ethereum = Ethereum.getInstance('goerli');
+  await ethereum.init();
+  uniswap = Uniswap.getInstance('ethereum', 'goerli');
+  await uniswap.init();
+  sushiswap = Sushiswap.getInstance('ethereum', 'goerli');
+  await sushiswap.init();
+  app.use('/amm', AmmRoutes.router);
+});
+
+afterEach(() => {
+  unpatch();
+});
+
+afterAll(async () => {
+  await ethereum.close();
+});
+
+const WETH = {
+  chainId: 5,
+  name: 'WETH',
+  symbol: 'WETH',
+  address: '0xB4FBF271143F4FBf7B91A5ded31805e42b2208d6',
+  decimals: 18,
+};
+
+const DAI = {
+  chainId: 5,
+  name: 'DAI',
+  symbol: 'DAI',
+  address: '0xdc31Ee1784292379Fbb2964b3B9C4124D8F89C60',
+  decimals: 18,
+};
+
+const patchTokens = () => {
+  patch(ethereum, 'getTokenBySymbol', (symbol: string) =>
+    symbol === 'WETH' ? WETH : symbol === 'DAI' ? DAI : undefined
+  );
+  for (const connector of [uniswap, sushiswap]) {
+    patch(connector, 'getTokenByAddress', (address: string) =>
+      address === WETH.address ? WETH : DAI
+    );
+  }
+};
+
+// a sell of WETH for DAI at the given price
+const patchEstimateSellTrade = (connector: any, price: number) => {
+  patch(connector, 'estimateSellTrade', () => {
+    return {
+      expectedAmount: { toSignificant: () => String(price) },
+      trade: {
+        executionPrice: {
+          toSignificant: () => String(price),
+          toFixed: () => String(price),
+        },
+      },
+    };
+  });
+};
+
+describe('POST /amm/quote', () => {
+  it('ranks the connectors by the amount received after gas', async () => {
+    patchTokens();
+    patch(ethereum, 'gasPrice', () => 100);
+    patchEstimateSellTrade(uniswap, 2000);
+    patchEstimateSellTrade(sushiswap, 2010);
+    // sushiswap pays the better price but its gas costs 1 WETH
+    patch(sushiswap, '_gasLimitEstimate', 10000000);
+    patch(uniswap, '_gasLimitEstimate', 100000);
+
+    const res = await request(app)
+      .post('/amm/quote')
+      .send({
+        chain: 'ethereum',
+        network: 'goerli',
+        base: 'WETH',
+        quote: 'DAI',
+        amount: '1',
+        side: 'SELL',
+      })
+      .expect(200);
+
+    expect(res.body.errors).toEqual([]);
+    expect(res.body.gasPriceToken).toEqual('ETH');
+    const ranked = res.body.quotes.map((quote: any) => quote.connector);
+    expect(ranked).toStrictEqual(['uniswap', 'sushiswap']);
+
+    const [best, second] = res.body.quotes;
+    // the gas costs are converted at the best price, 2010 DAI per WETH
+    expect(best.expectedAmount).toEqual('2000');
+    expect(best.gasCost).toEqual(gasCostInEthString(100, 100000));
+    expect(best.gasCostInQuote).toEqual('20.1');
+    expect(best.netAmount).toEqual('1979.9');
+    expect(second.gasCostInQuote).toEqual('2010');
+    expect(second.netAmount).toEqual('0');
+  });
+
+  it('reports the connectors that failed', async () => {
+    patchTokens();
+    patch(ethereum, 'gasPrice', () => 100);
+    patchEstimateSellTrade(uniswap, 2000);
+
+    const res = await request(app)
+      .post('/amm/quote')
+      .send({
+        chain: 'ethereum',
+        network: 'goerli',
+        base: 'WETH',
+        quote: 'DAI',
+        amount: '1',
+        side: 'SELL',
+        connectors: ['uniswap', 'pangolin'],
+      })
+      .expect(200);
+
+    expect(res.body.quotes.length).toEqual(1);
+    expect(res.body.quotes[0].connector).toEqual('uniswap');
+    expect(res.body.errors).toStrictEqual([
+      { connector: 'pangolin', message: 'unsupported chain or connector' },
+    ]);
+  });
+
+  it('should return 404 when connectors is not a list', async () => {
+    await request(app)
+      .post('/amm/quote')
+      .send({
+        chain: 'ethereum',
+        network: 'goerli',
+        base: 'WETH',
+        quote: 'DAI',
+        amount: '1',
+        side: 'SELL',
+        connectors: 'uniswap',
+      })
+      .expect(404);
+  });
+});
@@ -0,0 +1,215 @@
+import { Cosmos } from '../../../src/chains/cosmos/cosmos';
+import {
+  ibcTransfer,
+  poll,
+  transfer,
+} from '../../../src/chains/cosmos/cosmos.controllers';
+import {
+  GAS_PRICE_TOO_LOW_ERROR_MESSAGE,
+  HttpException,
+  IBC_CHANNEL_NOT_FOUND_ERROR_CODE,
+  IBC_CHANNEL_NOT_FOUND_ERROR_MESSAGE,
+  TRANSACTION_GAS_PRICE_TOO_LOW,
+} from '../../../src/services/error-handler';
+import { patch, unpatch } from '../../services/patch';
+import * as getTransactionData from './fixtures/getTransaction.json';
+import { publicKey } from './cosmos.validators.test';
+
+let cosmos: Cosmos;
+const receiver = 'cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu';
+const txHash =
+  '43785B183B154C3701CD62C07187CBFBE0A938B27D032094FBE9F9FA288BC6ED'; // noqa: mock
+const fee = { amount: [{ denom: 'uatom', amount: '2351' }], gas: '94008' };
+
+beforeAll(async () => {
+  cosmos = Cosmos.getInstance('mainnet');
+  patch(cosmos, 'getTokenList', () => [
+    {
+      base: 'uatom',
+      address: '',
+      name: 'Cosmos Hub Atom',
+      symbol: 'ATOM',
+      decimals: 6,
+    },
+  ]);
+  await cosmos.init();
+});
+
+afterEach(() => {
+  unpatch();
+});
+
+afterAll(async () => {
+  await cosmos.close();
+});
+
+describe('transfer', () => {
+  let messages: any[] = [];
+
+  beforeEach(() => {
+    patch(cosmos, 'getWallet', () => {
+      return {};
+    });
+    patch(cosmos, 'estimateFee', (_wallet: any, msgs: any[]) => {
+      messages = msgs;
+      return fee;
+    });
+    patch(cosmos, 'signAndBroadcast', () => {
+      return {
+        transactionHash: txHash,
+        height: 11829902,
+        code: 0,
+        rawLog: '[]',
+        gasUsed: 72314,
+        gasWanted: 94008,
+      };
+    });
+  });
+
+  it('sends a bank denom in its smallest unit', async () => {
+    const result = await transfer(cosmos, {
+      address: publicKey,
+      to: receiver,
+      token: 'ATOM',
+      amount: '1.5',
+    });
+    expect(messages[0].typeUrl).toEqual('/cosmos.bank.v1beta1.MsgSend');
+    expect(messages[0].value.amount).toEqual([
+      { denom: 'uatom', amount: '1500000' },
+    ]);
+    expect(result.gasLimit).toEqual(94008);
+    expect(result.fee).toEqual('0.002351');
+    expect(result.txHash).toEqual(txHash);
+    expect(result.code).toEqual(0);
+
+    expect(await cosmos.journal.getEntry(txHash)).toMatchObject({
+      chain: 'cosmos',
+      type: 'transfer',
+      wallet: publicKey,
+      tokens: ['ATOM'],
+      amounts: ['1.5'],
+      status: 'CONFIRMED',
+    });
+    await cosmos.journal.deleteEntry(txHash);
+  });
+
+  it('only estimates the fee of a simulation', async () => {
+    const result = await transfer(cosmos, {
+      address: publicKey,
+      to: receiver,
+      token: 'ATOM',
+      amount: '1.5',
+      simulate: true,
+    });
+    expect(result.fee).toEqual('0.002351');
+    expect(result.txHash).toBeUndefined();
+  });
+
+  it('fail if the gas price is below the minimum', async () => {
+    await expect(
+      transfer(cosmos, {
+        address: publicKey,
+        to: receiver,
+        token: 'ATOM',
+        amount: '1.5',
+        gasPrice: 0.001,
+      })
+    ).rejects.toThrow(
+      new HttpException(
+        500,
+        GAS_PRICE_TOO_LOW_ERROR_MESSAGE(0.001, cosmos.minimumGasPrice),
+        TRANSACTION_GAS_PRICE_TOO_LOW
+      )
+    );
+  });
+});
+
+describe('ibcTransfer', () => {
+  let messages: any[] = [];
+
+  beforeEach(() => {
+    patch(cosmos, 'getWallet', () => {
+      return {};
+    });
+    patch(cosmos, 'estimateFee', (_wallet: any, msgs: any[]) => {
+      messages = msgs;
+      return fee;
+    });
+  });
+
+  it('sends over the channel to the destination', async () => {
+    const result = await ibcTransfer(cosmos, {
+      address: publicKey,
+      to: 'osmo1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5helwsw',
+      token: 'ATOM',
+      amount: '2',
+      destination: 'osmosis',
+      simulate: true,
+    });
+    expect(messages[0].typeUrl).toEqual(
+      '/ibc.applications.transfer.v1.MsgTransfer'
+    );
+    expect(messages[0].value.sourceChannel).toEqual('channel-141');
+    expect(messages[0].value.token).toEqual({
+      denom: 'uatom',
+      amount: '2000000',
+    });
+    expect(result.channel).toEqual('channel-141');
+  });
+
+  it('fail if no channel to the destination is configured', async () => {
+    await expect(
+      ibcTransfer(cosmos, {
+        address: publicKey,
+        to: receiver,
+        token: 'ATOM',
+        amount: '2',
+        destination: 'juno',
+      })
+    ).rejects.toThrow(
+      new HttpException(
+        500,
+        IBC_CHANNEL_NOT_FOUND_ERROR_MESSAGE('juno'),
+        IBC_CHANNEL_NOT_FOUND_ERROR_CODE
+      )
+    );
+  });
+});
+
+describe('poll', () => {
+  beforeEach(() => {
+    patch(cosmos, 'getCurrentBlockNumber', () => 11829910);
+  });
+
+  it('reports the code and logs of a transaction', async () => {
+    patch(cosmos, 'getTransaction', () => {
+      return {
+        ...getTransactionData,
+        tx: Uint8Array.from(Object.values(getTransactionData.tx)),
+      };
+    });
+
+    const result = await poll(cosmos, { txHash });
+    expect(result.txStatus).toEqual(1);
+    expect(result.code).toEqual(0);
+    expect(result.logs.length).toBeGreaterThan(0);
+    expect(result.gasUsed).toEqual(116639);
+  });
+
+  it('reports a failed transaction without logs', async () => {
+    patch(cosmos, 'getTransaction', () => {
+      return {
+        ...getTransactionData,
+        code: 5,
+        rawLog: 'insufficient funds',
+        tx: Uint8Array.from(Object.values(getTransactionData.tx)),
+      };
+    });
+
+    const result = await poll(cosmos, { txHash });
+    expect(result.txStatus).toEqual(-1);
+    expect(result.code).toEqual(5);
+    expect(result.rawLog).toEqual('insufficient funds');
+    expect(result.logs).toEqual([]);
+  });
+});
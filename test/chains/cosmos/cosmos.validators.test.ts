@@ -1,6 +1,10 @@
 import {
   invalidCosmosAddressError,
+  invalidGasAdjustmentError,
+  invalidGasPriceError,
   isValidCosmosAddress,
+  validateGasAdjustment,
+  validateGasPrice,
   validatePublicKey,
 } from '../../../src/chains/cosmos/cosmos.validators';
 import { missingParameter } from '../../../src/services/validators';
@@ -49,3 +53,29 @@ describe('validatePublicKey', () => {
     ).toEqual([invalidCosmosAddressError]);
   });
 });
+
+describe('validateGasPrice', () => {
+  it('valid when req.gasPrice is a positive number or missing', () => {
+    expect(validateGasPrice({ gasPrice: 0.025 })).toEqual([]);
+    expect(validateGasPrice({})).toEqual([]);
+  });
+
+  it('return error when req.gasPrice is not a positive number', () => {
+    expect(validateGasPrice({ gasPrice: '0.025' })).toEqual([
+      invalidGasPriceError,
+    ]);
+    expect(validateGasPrice({ gasPrice: 0 })).toEqual([invalidGasPriceError]);
+  });
+});
+
+describe('validateGasAdjustment', () => {
+  it('valid when req.gasAdjustment is at least 1', () => {
+    expect(validateGasAdjustment({ gasAdjustment: 1.5 })).toEqual([]);
+  });
+
+  it('return error when req.gasAdjustment is below 1', () => {
+    expect(validateGasAdjustment({ gasAdjustment: 0.9 })).toEqual([
+      invalidGasAdjustmentError,
+    ]);
+  });
+});
@@ -1,7 +1,10 @@
 import { BigNumber } from 'ethers';
 import { Ethereum } from '../../../src/chains/ethereum/ethereum';
 import { patch, unpatch } from '../../services/patch';
-import { TokenInfo } from '../../../src/chains/ethereum/ethereum-base';
+import {
+  EthereumBase,
+  TokenInfo,
+} from '../../../src/chains/ethereum/ethereum-base';
 import {
   nonce,
   nextNonce,
@@ -10,7 +13,12 @@ import {
   approve,
   balances,
   cancel,
+  poll,
+  speedUp,
+  transfer,
+  unwrap,
   willTxSucceed,
+  wrap,
 } from '../../../src/chains/ethereum/ethereum.controllers';
 import {
   HttpException,
@@ -18,8 +26,15 @@ import {
   LOAD_WALLET_ERROR_MESSAGE,
   TOKEN_NOT_SUPPORTED_ERROR_MESSAGE,
   TOKEN_NOT_SUPPORTED_ERROR_CODE,
+  TRANSACTION_NOT_REPLACEABLE_ERROR_CODE,
+  TRANSACTION_NOT_REPLACEABLE_ERROR_MESSAGE,
 } from '../../../src/services/error-handler';
 import { patchEVMNonceManager } from '../../evm.nonce.mock';
+import { ConfigManagerV2 } from '../../../src/services/config-manager-v2';
+import {
+  getGasPriceBumpPercent,
+  MIN_GAS_PRICE_BUMP_PERCENT,
+} from '../../../src/chains/ethereum/ethereum.config';
 let eth: Ethereum;
 
 beforeAll(async () => {
@@ -36,6 +51,7 @@ beforeEach(() => {
 
 afterEach(() => {
   unpatch();
+  jest.restoreAllMocks();
 });
 
 afterAll(async () => {
@@ -229,6 +245,140 @@ describe('approve', () => {
   });
 });
 
+describe('transfer', () => {
+  const receiver = '0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D';
+  let sent: any[] = [];
+
+  beforeEach(() => {
+    patch(eth, 'getWallet', () => {
+      return { address: '0xFaA12FD102FE8623C9299c72B03E45107F2772B5' };
+    });
+    patch(eth, 'getContract', (address: string) => {
+      return { address };
+    });
+    patch(eth, 'transfer', (...args: any[]) => {
+      sent = args;
+      return { nonce: 13, value: BigNumber.from(0) };
+    });
+  });
+
+  it('sends the native currency without a token contract', async () => {
+    const result = await transfer(eth, {
+      chain: 'ethereum',
+      network: 'goerli',
+      address: zeroAddress,
+      to: receiver,
+      token: eth.nativeTokenSymbol,
+      amount: '1.5',
+    });
+    expect(sent[1]).toEqual(receiver);
+    expect(sent[2].toString()).toEqual('1500000000000000000');
+    expect(sent[3]).toBeUndefined();
+    expect(result.tokenAddress).toBeUndefined();
+    expect(result.amount).toEqual('1.500000000000000000');
+    expect(result.nonce).toEqual(13);
+  });
+
+  it('sends a token through its contract in its decimals', async () => {
+    patch(eth, 'getTokenBySymbol', () => weth);
+
+    const result = await transfer(eth, {
+      chain: 'ethereum',
+      network: 'goerli',
+      address: zeroAddress,
+      to: receiver,
+      token: 'WETH',
+      amount: '2',
+    });
+    expect(sent[2].toString()).toEqual('2000000000000000000');
+    expect(sent[3]).toEqual({ address: weth.address });
+    expect(result.tokenAddress).toEqual(weth.address);
+  });
+
+  it('fail if token not found', async () => {
+    patch(eth, 'getTokenBySymbol', () => undefined);
+
+    await expect(
+      transfer(eth, {
+        chain: 'ethereum',
+        network: 'goerli',
+        address: zeroAddress,
+        to: receiver,
+        token: 'WETH',
+        amount: '2',
+      })
+    ).rejects.toThrow(
+      new HttpException(
+        500,
+        TOKEN_NOT_SUPPORTED_ERROR_MESSAGE + 'WETH',
+        TOKEN_NOT_SUPPORTED_ERROR_CODE
+      )
+    );
+  });
+});
+
+describe('wrap', () => {
+  let sent: any[] = [];
+
+  beforeEach(() => {
+    patch(eth, 'wrappedNativeAddress', weth.address);
+    patch(eth, 'getWallet', () => {
+      return { address: '0xFaA12FD102FE8623C9299c72B03E45107F2772B5' };
+    });
+    patch(eth, 'wrapNative', (...args: any[]) => {
+      sent = args;
+      return { nonce: 14, value: args[1] };
+    });
+    patch(eth, 'unwrapNative', (...args: any[]) => {
+      sent = args;
+      return { nonce: 15, value: BigNumber.from(0) };
+    });
+  });
+
+  it('deposits the native currency into the wrapped token', async () => {
+    const result = await wrap(eth, {
+      chain: 'ethereum',
+      network: 'goerli',
+      address: zeroAddress,
+      amount: '0.25',
+    });
+    expect(sent[1].toString()).toEqual('250000000000000000');
+    expect(result.tokenAddress).toEqual(weth.address);
+    expect(result.amount).toEqual('0.250000000000000000');
+    expect(result.nonce).toEqual(14);
+  });
+
+  it('withdraws the wrapped token into the native currency', async () => {
+    const result = await unwrap(eth, {
+      chain: 'ethereum',
+      network: 'goerli',
+      address: zeroAddress,
+      amount: '3',
+    });
+    expect(sent[1].toString()).toEqual('3000000000000000000');
+    expect(result.nonce).toEqual(15);
+  });
+
+  it('fail if the network has no wrapped native token', async () => {
+    patch(eth, 'wrappedNativeAddress', undefined);
+
+    await expect(
+      wrap(eth, {
+        chain: 'ethereum',
+        network: 'goerli',
+        address: zeroAddress,
+        amount: '1',
+      })
+    ).rejects.toThrow(
+      new HttpException(
+        500,
+        TOKEN_NOT_SUPPORTED_ERROR_MESSAGE + 'wrapped ETH',
+        TOKEN_NOT_SUPPORTED_ERROR_CODE
+      )
+    );
+  });
+});
+
 describe('balances', () => {
   it('fail if wallet not found', async () => {
     const err = 'wallet does not exist';
@@ -277,6 +427,241 @@ describe('cancel', () => {
   });
 });
 
+describe('speedUp', () => {
+  const address = '0xFaA12FD102FE8623C9299c72B03E45107F2772B5';
+  const txHash =
+    '0x75f98675a8f64dcf14927ccde9a1d59b67fa09b72cc2642ad055dae4074853d9'; // noqa: mock
+  const replacementHash =
+    '0x2faeb1aa55f96c1db55f643a8cf19b0f76bf091d0b7d1b068d2e829414576362'; // noqa: mock
+  const original = {
+    hash: txHash,
+    from: address,
+    to: '0x4F96Fe3b7A6Cf9725f59d353F723c1bDb64CA6Aa',
+    data: '0x',
+    value: BigNumber.from(0),
+    nonce: 14,
+    gasLimit: BigNumber.from(100000),
+    chainId: 5,
+    blockNumber: null,
+    maxFeePerGas: BigNumber.from(100000000000),
+    maxPriorityFeePerGas: BigNumber.from(2000000000),
+  };
+
+  it('resends the transaction with bumped fees and links it', async () => {
+    let sent: any;
+    patch(eth, 'getWallet', () => {
+      return {
+        address,
+        sendTransaction: (tx: any) => {
+          sent = tx;
+          return { ...tx, hash: replacementHash };
+        },
+      };
+    });
+    patch(eth, 'getTransaction', (hash: string) =>
+      hash === txHash ? original : null
+    );
+    patch(eth.nonceManager, 'getNonceFromMemory', () => 14);
+    // the getter is defined on EthereumBase, which patch can't reach
+    jest
+      .spyOn(EthereumBase.prototype, 'gasPriceBumpPercent', 'get')
+      .mockReturnValue(15);
+
+    const result = await speedUp(eth, {
+      chain: 'ethereum',
+      network: 'goerli',
+      txHash,
+      address,
+    });
+    expect(sent.nonce).toEqual(14);
+    expect(sent.to).toEqual(original.to);
+    expect(sent.maxFeePerGas.toString()).toEqual('115000000000');
+    expect(sent.maxPriorityFeePerGas.toString()).toEqual('2300000000');
+    expect(result.originalTxHash).toEqual(txHash);
+    expect(result.txHash).toEqual(replacementHash);
+
+    patch(eth, 'getCurrentBlockNumber', () => 100);
+    patch(eth, 'getTransactionReceipt', () => null);
+    const pollResult = await poll(eth, {
+      chain: 'ethereum',
+      network: 'goerli',
+      txHash,
+    });
+    expect(pollResult.replacementTxHash).toEqual(replacementHash);
+  });
+
+  it('fail if the transaction was already mined', async () => {
+    patch(eth, 'getWallet', () => {
+      return { address };
+    });
+    patch(eth, 'getTransaction', () => {
+      return { ...original, blockNumber: 100 };
+    });
+
+    await expect(
+      speedUp(eth, {
+        chain: 'ethereum',
+        network: 'goerli',
+        txHash,
+        address,
+      })
+    ).rejects.toThrow(
+      new HttpException(
+        500,
+        TRANSACTION_NOT_REPLACEABLE_ERROR_MESSAGE(
+          txHash,
+          'it was already mined in block 100.'
+        ),
+        TRANSACTION_NOT_REPLACEABLE_ERROR_CODE
+      )
+    );
+  });
+});
+
+describe('getGasPriceBumpPercent', () => {
+  const configManager = ConfigManagerV2.getInstance();
+  let configured: number;
+
+  beforeAll(() => {
+    configured = configManager.get('ethereum.gasPriceBumpPercent');
+  });
+
+  it('returns the configured bump', () => {
+    expect(getGasPriceBumpPercent('ethereum')).toEqual(configured);
+  });
+
+  it('rejects a configured bump below the minimum', () => {
+    expect(() =>
+      configManager.set('ethereum.gasPriceBumpPercent', 5)
+    ).toThrow();
+    expect(configManager.get('ethereum.gasPriceBumpPercent')).toEqual(
+      configured
+    );
+  });
+
+  it('raises a bump below the minimum to the minimum', () => {
+    const get = jest
+      .spyOn(ConfigManagerV2.prototype, 'get')
+      .mockReturnValue(5);
+    expect(getGasPriceBumpPercent('ethereum')).toEqual(
+      MIN_GAS_PRICE_BUMP_PERCENT
+    );
+    get.mockReturnValue(0);
+    expect(getGasPriceBumpPercent('ethereum')).toEqual(
+      MIN_GAS_PRICE_BUMP_PERCENT
+    );
+  });
+});
+
+describe('EIP-1559 fees', () => {
+  // base fees of 100, 100 and 120 gwei, tips of 1, 2 and 80 gwei
+  const feeHistory = {
+    baseFeePerGas: ['0x174876e800', '0x174876e800', '0x1bf08eb000'],
+    reward: [['0x3b9aca00'], ['0x77359400'], ['0x12a05f2000']],
+  };
+
+  beforeEach(() => {
+    eth.cache.del('eip1559Fees');
+  });
+
+  afterEach(() => {
+    eth.cache.del('eip1559Fees');
+  });
+
+  it('uses the median tip and the next base fee', async () => {
+    patch(eth, 'feeStrategy', {
+      rewardPercentile: 50,
+      blockHistory: 3,
+      baseFeeMultiplier: 2,
+    });
+    patch(eth, '_provider', { send: () => feeHistory });
+
+    const fees = await eth.getEIP1559Fees();
+    expect(fees?.baseFeePerGas.toString()).toEqual('120000000000');
+    expect(fees?.maxPriorityFeePerGas.toString()).toEqual('2000000000');
+    expect(fees?.maxFeePerGas.toString()).toEqual('242000000000');
+    expect(await eth.getGasPrice()).toBeCloseTo(122);
+  });
+
+  it('applies the configured caps', async () => {
+    patch(eth, 'feeStrategy', {
+      rewardPercentile: 50,
+      blockHistory: 3,
+      baseFeeMultiplier: 2,
+      maxFeePerGasCap: 150,
+      maxPriorityFeePerGasCap: 1.5,
+    });
+    patch(eth, '_provider', { send: () => feeHistory });
+
+    const fees = await eth.getEIP1559Fees();
+    expect(fees?.maxPriorityFeePerGas.toString()).toEqual('1500000000');
+    expect(fees?.maxFeePerGas.toString()).toEqual('150000000000');
+  });
+
+  it('falls back to the legacy gas price without a fee strategy', async () => {
+    patch(eth, 'feeStrategy', undefined);
+    patch(eth, '_provider', {
+      getFeeData: () => {
+        return {
+          gasPrice: BigNumber.from('30000000000'),
+          maxPriorityFeePerGas: BigNumber.from('1500000000'),
+        };
+      },
+    });
+
+    expect(await eth.getEIP1559Fees()).toBeNull();
+    // the tip is already part of the node's gas price
+    expect(await eth.getGasPrice()).toBeCloseTo(30);
+  });
+
+  it('approve sends type-2 fees from the fee strategy', async () => {
+    patch(eth, 'getEIP1559Fees', () => {
+      return {
+        baseFeePerGas: BigNumber.from('100000000000'),
+        maxFeePerGas: BigNumber.from('202000000000'),
+        maxPriorityFeePerGas: BigNumber.from('2000000000'),
+      };
+    });
+    patch(eth, 'getSpender', () => uniswap);
+    patch(eth, 'getContract', () => {
+      return { address: '0xFaA12FD102FE8623C9299c72B03E45107F2772B5' };
+    });
+    patch(eth, 'getWallet', () => {
+      return { address: '0xFaA12FD102FE8623C9299c72B03E45107F2772B5' };
+    });
+    patch(eth, 'getTokenBySymbol', () => weth);
+    let sentFees: BigNumber[] = [];
+    patch(
+      eth,
+      'approveERC20',
+      (
+        _contract: any,
+        _wallet: any,
+        _spender: string,
+        _amount: BigNumber,
+        _nonce: number,
+        maxFeePerGas: BigNumber,
+        maxPriorityFeePerGas: BigNumber
+      ) => {
+        sentFees = [maxFeePerGas, maxPriorityFeePerGas];
+        return { spender: uniswap, value: { toString: () => '9999999' } };
+      }
+    );
+
+    await approve(eth, {
+      chain: 'ethereum',
+      network: 'goerli',
+      address: zeroAddress,
+      spender: uniswap,
+      token: 'WETH',
+    });
+    expect(sentFees.map((fee) => fee.toString())).toStrictEqual([
+      '202000000000',
+      '2000000000',
+    ]);
+  });
+});
+
 describe('willTxSucceed', () => {
   it('time limit met and gas price higher than that of the tx', () => {
     expect(willTxSucceed(100, 10, 10, 100)).toEqual(false);
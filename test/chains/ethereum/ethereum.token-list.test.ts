@@ -0,0 +1,143 @@
+import { promises as fs } from 'fs';
+import os from 'os';
+import path from 'path';
+import { CurrencyAmount, TradeType } from '@uniswap/sdk-core';
+import { Ethereum } from '../../../src/chains/ethereum/ethereum';
+import { Uniswap } from '../../../src/connectors/uniswap/uniswap';
+import { getConnector } from '../../../src/services/connection-manager';
+import { mergeTokenLists } from '../../../src/evm/evm.token-list';
+import { patch, unpatch } from '../../services/patch';
+import { patchEVMNonceManager } from '../../evm.nonce.mock';
+
+let ethereum: Ethereum;
+let overlayDir: string;
+
+const DAI = {
+  chainId: 5,
+  address: '0xdc31Ee1784292379Fbb2964b3B9C4124D8F89C60',
+  name: 'Dai Stablecoin',
+  symbol: 'DAI',
+  decimals: 18,
+};
+
+// a token that isn't in the goerli list
+const GTK = {
+  chainId: 5,
+  address: '0x1111111111111111111111111111111111111111',
+  name: 'Gateway Test Token',
+  symbol: 'GTK',
+  decimals: 6,
+};
+
+beforeAll(async () => {
+  ethereum = Ethereum.getInstance('goerli');
+  patchEVMNonceManager(ethereum.nonceManager);
+  await ethereum.init();
+});
+
+beforeEach(async () => {
+  patchEVMNonceManager(ethereum.nonceManager);
+  overlayDir = await fs.mkdtemp(path.join(os.tmpdir(), 'tokens-'));
+  patch(ethereum, 'tokenOverlayFile', () =>
+    path.join(overlayDir, 'ethereum-5.json')
+  );
+});
+
+afterEach(async () => {
+  unpatch();
+  await ethereum.loadTokens(ethereum.tokenListSource, ethereum.tokenListType);
+  await fs.rm(overlayDir, { recursive: true, force: true });
+});
+
+afterAll(async () => {
+  await ethereum.close();
+});
+
+describe('verify mergeTokenLists', () => {
+  it('should let later lists take over addresses and symbols', () => {
+    const otherDai = { ...DAI, address: GTK.address };
+    const renamed = { ...GTK, symbol: 'GTK2' };
+
+    expect(mergeTokenLists([[DAI, GTK], [otherDai]])).toStrictEqual([
+      otherDai,
+    ]);
+    expect(mergeTokenLists([[DAI, GTK], [renamed]])).toStrictEqual([
+      DAI,
+      renamed,
+    ]);
+  });
+});
+
+describe('verify token list updates', () => {
+  it('should add a token described by its contract', async () => {
+    patch(ethereum, 'getTokenMetadata', async () => GTK);
+    const revision = ethereum.tokenListRevision;
+
+    await ethereum.addToken(GTK.address);
+
+    expect(ethereum.getTokenBySymbol('GTK')).toStrictEqual(GTK);
+    expect(ethereum.tokenListRevision).toBeGreaterThan(revision);
+    const overlay = JSON.parse(
+      await fs.readFile(ethereum.tokenOverlayFile, 'utf8')
+    );
+    expect(overlay.tokens).toStrictEqual([GTK]);
+  });
+
+  it('should hide a removed token until it is added again', async () => {
+    expect(ethereum.getTokenBySymbol('DAI')).toBeDefined();
+
+    await ethereum.removeToken('DAI');
+    expect(ethereum.getTokenBySymbol('DAI')).toBeUndefined();
+
+    patch(ethereum, 'getTokenMetadata', async () => DAI);
+    await ethereum.addToken(DAI.address);
+    expect(ethereum.getTokenBySymbol('DAI')?.address).toEqual(DAI.address);
+  });
+
+  it('should import the tokens of the chain from a token list', async () => {
+    const file = path.join(overlayDir, 'list.json');
+    await fs.writeFile(
+      file,
+      JSON.stringify({ tokens: [GTK, { ...GTK, chainId: 1 }] })
+    );
+
+    const tokens = await ethereum.importTokenList(file, 'FILE');
+
+    expect(tokens).toStrictEqual([GTK]);
+    expect(ethereum.getTokenBySymbol('GTK')).toStrictEqual(GTK);
+  });
+
+  it('should refuse a token list without tokens of the chain', async () => {
+    const file = path.join(overlayDir, 'list.json');
+    await fs.writeFile(file, JSON.stringify({ tokens: [] }));
+
+    await expect(ethereum.importTokenList(file, 'FILE')).rejects.toThrow(
+      'has no tokens for chain 5'
+    );
+  });
+});
+
+describe('verify connector token reload', () => {
+  it('should forget a removed token in the connectors', async () => {
+    const uniswap = await getConnector<Uniswap>(
+      'ethereum',
+      'goerli',
+      'uniswap'
+    );
+    const dai = uniswap.getTokenByAddress(DAI.address);
+    expect(dai).toBeDefined();
+
+    await ethereum.removeToken('DAI');
+    await getConnector('ethereum', 'goerli', 'uniswap');
+
+    expect(uniswap.getTokenByAddress(DAI.address)).toBeUndefined();
+    await expect(
+      uniswap.quoteRoutes(
+        CurrencyAmount.fromRawAmount(dai, '1'),
+        dai,
+        TradeType.EXACT_INPUT,
+        { path: [DAI.address] }
+      )
+    ).rejects.toThrow(`Token ${DAI.address} is not in the token list.`);
+  });
+});
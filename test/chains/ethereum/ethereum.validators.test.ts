@@ -4,6 +4,8 @@ import {
   invalidAddressError,
   validateSpender,
   invalidSpenderError,
+  validateTo,
+  invalidToError,
   validateNonce,
   invalidNonceError,
   invalidMaxFeePerGasError,
@@ -100,6 +102,24 @@ describe('validateSpender', () => {
   });
 });
 
+describe('validateTo', () => {
+  it('valid when req.to is a publicKey', () => {
+    expect(
+      validateTo({
+        to: '0xFaA12FD102FE8623C9299c72B03E45107F2772B5',
+      })
+    ).toEqual([]);
+  });
+
+  it('return error when req.to is not an address', () => {
+    expect(
+      validateTo({
+        to: 'uniswap',
+      })
+    ).toEqual([invalidToError]);
+  });
+});
+
 describe('validateNonce', () => {
   it('valid when req.nonce is a number', () => {
     expect(
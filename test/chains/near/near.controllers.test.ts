@@ -1,3 +1,4 @@
+import { InMemorySigner, KeyPair, keyStores } from 'near-api-js';
 import { Near } from '../../../src/chains/near/near';
 import { TokenInfo } from '../../../src/chains/near/near.base';
 import {
@@ -5,6 +6,8 @@ import {
   cancel,
   getTokenSymbolsToTokens,
   poll,
+  register,
+  storage,
 } from '../../../src/chains/near/near.controllers';
 import { PollResponse } from '../../../src/chains/near/near.requests';
 import { Nearish } from '../../../src/services/common-interfaces';
@@ -12,6 +15,8 @@ import {
   HttpException,
   LOAD_WALLET_ERROR_CODE,
   LOAD_WALLET_ERROR_MESSAGE,
+  TOKEN_NOT_SUPPORTED_ERROR_CODE,
+  TOKEN_NOT_SUPPORTED_ERROR_MESSAGE,
 } from '../../../src/services/error-handler';
 import { patch, unpatch } from '../../services/patch';
 import * as getTokenListData from './fixtures/getTokenList.json';
@@ -122,3 +127,209 @@ describe('getTokenSymbolsToTokens', () => {
     expect(getTokenSymbolsToTokens(near, ['ETH'])).toEqual({ ETH: eth });
   });
 });
+
+const usdc: TokenInfo = {
+  chainId: 0,
+  name: 'USD Coin',
+  symbol: 'USDC',
+  address: 'usdc.fakes.testnet',
+  decimals: 6,
+};
+const minimumDeposit = '1250000000000000000000'; // 0.00125 NEAR
+
+// a token contract on which only the registered accounts have storage
+const storageContract = (contractId: string, registered: string[]) => ({
+  contractId,
+  storage_balance_of: ({ account_id }: { account_id: string }) =>
+    registered.includes(account_id)
+      ? { total: minimumDeposit, available: '0' }
+      : null,
+  storage_balance_bounds: () => ({ min: minimumDeposit, max: null }),
+});
+
+describe('getStorageDeposits', () => {
+  it('deposits the minimum for each unregistered account', async () => {
+    const contract = storageContract(usdc.address, [publicKey]);
+    expect(
+      await near.getStorageDeposits(contract, [publicKey, 'ref.testnet'])
+    ).toEqual([{ accountId: 'ref.testnet', amount: minimumDeposit }]);
+  });
+
+  it('approves nothing when the accounts are registered', async () => {
+    const contract = {
+      ...storageContract(usdc.address, [publicKey, 'ref.testnet']),
+      account: { accountId: publicKey },
+    };
+    expect(
+      await near.approveFungibleToken(
+        contract,
+        {} as any,
+        'ref.testnet',
+        {} as any
+      )
+    ).toBeUndefined();
+  });
+});
+
+describe('storage and register', () => {
+  const tokens: Record<string, TokenInfo> = { ETH: eth, USDC: usdc };
+  let deposits: any[] = [];
+
+  beforeEach(() => {
+    deposits = [];
+    patch(near, 'getWallet', () => {
+      return { accountId: publicKey };
+    });
+    patch(near, 'getTokenBySymbol', (symbol: string) => tokens[symbol]);
+    patch(near, 'getContract', (address: string) =>
+      storageContract(address, address === eth.address ? [publicKey] : [])
+    );
+    patch(near, 'storageDeposit', (...args: any[]) => {
+      deposits.push(args.slice(1));
+      return {
+        transaction_outcome: { id: txHash },
+        transaction: { nonce: 7 },
+      };
+    });
+    patch(near.journal, 'record', () => undefined);
+  });
+
+  it('reports the storage of an unregistered account', async () => {
+    const result = await storage(near, {
+      chain: 'near',
+      network: 'testnet',
+      address: publicKey,
+      token: 'USDC',
+    });
+    expect(result.registered).toEqual(false);
+    expect(result.total).toEqual('0');
+    expect(result.minimum).toEqual('0.00125');
+    expect(result.txHash).toBeUndefined();
+    expect(deposits).toEqual([]);
+  });
+
+  it('deposits the amount of NEAR for the storage', async () => {
+    const result = await storage(near, {
+      chain: 'near',
+      network: 'testnet',
+      address: publicKey,
+      token: 'USDC',
+      amount: '0.00125',
+    });
+    expect(deposits).toEqual([
+      [usdc.address, { accountId: publicKey, amount: minimumDeposit }],
+    ]);
+    expect(result.txHash).toEqual(txHash);
+  });
+
+  it('fail for NEAR itself', async () => {
+    await expect(
+      storage(near, {
+        chain: 'near',
+        network: 'testnet',
+        address: publicKey,
+        token: 'NEAR',
+      })
+    ).rejects.toThrow(
+      new HttpException(
+        500,
+        TOKEN_NOT_SUPPORTED_ERROR_MESSAGE + 'NEAR',
+        TOKEN_NOT_SUPPORTED_ERROR_CODE
+      )
+    );
+  });
+
+  it('registers the account only where it is not registered', async () => {
+    const result = await register(near, {
+      chain: 'near',
+      network: 'testnet',
+      address: publicKey,
+      tokenSymbols: ['ETH', 'USDC'],
+    });
+    expect(result.registered).toEqual({ USDC: txHash });
+    expect(result.alreadyRegistered).toEqual(['ETH']);
+    expect(deposits).toEqual([
+      [usdc.address, { accountId: publicKey, amount: minimumDeposit }, true],
+    ]);
+  });
+});
+
+describe('storageDeposit', () => {
+  it('signs again with a new nonce when the nonce was used', async () => {
+    const keyStore = new keyStores.InMemoryKeyStore();
+    await keyStore.setKey('testnet', publicKey, KeyPair.fromRandom('ed25519'));
+    const nonces = [7, 8];
+    const sent: number[] = [];
+    const account: any = {
+      accountId: publicKey,
+      connection: {
+        networkId: 'testnet',
+        signer: new InMemorySigner(keyStore),
+        provider: {
+          query: () => ({ nonce: nonces.shift() }),
+          block: () => ({
+            header: { hash: '11111111111111111111111111111111' },
+          }),
+          sendTransaction: (signedTx: any) => {
+            sent.push(signedTx.transaction.nonce.toNumber());
+            if (sent.length === 1) throw { type: 'InvalidNonce' };
+            return { transaction_outcome: { id: txHash } };
+          },
+        },
+      },
+    };
+
+    const outcome = await near.storageDeposit(account, usdc.address, {
+      accountId: publicKey,
+      amount: minimumDeposit,
+    });
+    expect(outcome.transaction_outcome.id).toEqual(txHash);
+    expect(sent).toEqual([8, 9]);
+  });
+});
+
+describe('transfer', () => {
+  it('registers an unregistered receiver in the same transaction', async () => {
+    const keyStore = new keyStores.InMemoryKeyStore();
+    await keyStore.setKey('testnet', publicKey, KeyPair.fromRandom('ed25519'));
+    const sent: any[] = [];
+    const account: any = {
+      accountId: publicKey,
+      connection: {
+        networkId: 'testnet',
+        signer: new InMemorySigner(keyStore),
+        provider: {
+          query: () => ({ nonce: 7 }),
+          block: () => ({
+            header: { hash: '11111111111111111111111111111111' },
+          }),
+          sendTransaction: (signedTx: any) => {
+            sent.push(signedTx.transaction);
+            return { transaction_outcome: { id: txHash } };
+          },
+        },
+      },
+    };
+
+    const outcome = await near.transfer(
+      account,
+      'fresh.testnet',
+      '1000000',
+      storageContract(usdc.address, [publicKey])
+    );
+    expect(outcome.transaction_outcome.id).toEqual(txHash);
+    expect(sent.length).toEqual(1);
+    expect(sent[0].receiverId).toEqual(usdc.address);
+    const calls = sent[0].actions.map((action: any) => action.functionCall);
+    expect(calls.map((call: any) => call.methodName)).toEqual([
+      'storage_deposit',
+      'ft_transfer',
+    ]);
+    expect(calls[0].deposit.toString()).toEqual(minimumDeposit);
+    expect(JSON.parse(Buffer.from(calls[0].args).toString())).toEqual({
+      account_id: 'fresh.testnet',
+      registration_only: true,
+    });
+    expect(calls[1].deposit.toString()).toEqual('1');
+  });
+});
@@ -5,12 +5,14 @@ import {
   invalidNetworkError,
   invalidNonceError,
   invalidSpenderError,
+  invalidStorageAmountError,
   validateAddress,
   validateBalanceRequest,
   validateChain,
   validateNetwork,
   validateNonce,
   validateSpender,
+  validateStorageAmount,
 } from '../../../src/chains/near/near.validators';
 import { missingParameter } from '../../../src/services/validators';
 
@@ -157,3 +159,19 @@ describe('validateBalanceRequest', () => {
     ).toEqual(undefined);
   });
 });
+
+describe('validateStorageAmount', () => {
+  it('valid when req.amount does not exist', () => {
+    expect(validateStorageAmount({})).toEqual([]);
+  });
+
+  it('valid when req.amount is a positive number string', () => {
+    expect(validateStorageAmount({ amount: '0.00125' })).toEqual([]);
+  });
+
+  it('return error when req.amount is not positive', () => {
+    expect(validateStorageAmount({ amount: '0' })).toEqual([
+      invalidStorageAmountError,
+    ]);
+  });
+});
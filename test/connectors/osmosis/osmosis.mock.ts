@@ -0,0 +1,54 @@
+import { Writer } from 'protobufjs/minimal';
+
+// encodes the pools of a node for the Osmosis connector tests
+
+// an AllPoolsResponse of Any packed pools
+export const allPoolsResponse = (
+  pools: Array<[string, Uint8Array]>
+): Uint8Array => {
+  const writer = Writer.create();
+  for (const [typeUrl, pool] of pools) {
+    writer.uint32(10).fork();
+    writer.uint32(10).string(typeUrl);
+    writer.uint32(18).bytes(pool);
+    writer.ldelim();
+  }
+  return writer.finish();
+};
+
+export const balancerPool = (id: number, denoms: string[]): Uint8Array => {
+  const writer = Writer.create();
+  writer.uint32(10).string('osmo1pool');
+  writer.uint32(16).uint64(id);
+  for (const denom of denoms) {
+    writer.uint32(50).fork();
+    writer.uint32(10).fork().uint32(10).string(denom);
+    writer.uint32(18).string('1000000').ldelim();
+    writer.uint32(18).string('1073741824');
+    writer.ldelim();
+  }
+  return writer.finish();
+};
+
+export const concentratedPool = (
+  id: number,
+  token0: string,
+  token1: string
+): Uint8Array => {
+  const writer = Writer.create();
+  writer.uint32(10).string('osmo1pool');
+  writer.uint32(32).uint64(id);
+  writer.uint32(50).string(token0);
+  writer.uint32(58).string(token1);
+  return writer.finish();
+};
+
+export const stableswapPool = (id: number, denoms: string[]): Uint8Array => {
+  const writer = Writer.create();
+  writer.uint32(16).uint64(id);
+  for (const denom of denoms) {
+    writer.uint32(50).fork().uint32(10).string(denom);
+    writer.uint32(18).string('1000000').ldelim();
+  }
+  return writer.finish();
+};
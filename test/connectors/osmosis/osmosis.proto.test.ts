@@ -0,0 +1,80 @@
+import { Writer } from 'protobufjs/minimal';
+import {
+  decodeAllPoolsResponse,
+  decodeEstimateSwapResponse,
+  MsgSwapExactAmountInType,
+  MsgSwapExactAmountOutType,
+} from '../../../src/connectors/osmosis/osmosis.proto';
+import {
+  allPoolsResponse,
+  balancerPool,
+  concentratedPool,
+  stableswapPool,
+} from './osmosis.mock';
+
+const ATOM =
+  'ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2';
+const sender = 'osmo1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5helwsw';
+
+describe('swap messages', () => {
+  it('encodes and decodes MsgSwapExactAmountIn', () => {
+    const msg = {
+      sender,
+      routes: [{ poolId: 1, tokenOutDenom: ATOM }],
+      tokenIn: { denom: 'uosmo', amount: '1000000' },
+      tokenOutMinAmount: '118800',
+    };
+    const bytes = MsgSwapExactAmountInType.encode(msg).finish();
+    expect(MsgSwapExactAmountInType.decode(bytes)).toEqual(msg);
+  });
+
+  it('encodes and decodes MsgSwapExactAmountOut', () => {
+    const msg = {
+      sender,
+      routes: [{ poolId: 1135, tokenInDenom: 'uosmo' }],
+      tokenInMaxAmount: '909000',
+      tokenOut: { denom: ATOM, amount: '100000' },
+    };
+    const bytes = MsgSwapExactAmountOutType.encode(msg).finish();
+    expect(MsgSwapExactAmountOutType.decode(bytes)).toEqual(msg);
+  });
+});
+
+describe('decodeAllPoolsResponse', () => {
+  it('reads the id and denoms of each kind of pool', () => {
+    const pools = decodeAllPoolsResponse(
+      allPoolsResponse([
+        ['/osmosis.gamm.v1beta1.Pool', balancerPool(1, ['uosmo', ATOM])],
+        [
+          '/osmosis.gamm.poolmodels.stableswap.v1beta1.Pool',
+          stableswapPool(2, ['uion', 'uosmo']),
+        ],
+        [
+          '/osmosis.concentratedliquidity.v1beta1.Pool',
+          concentratedPool(1135, 'uosmo', ATOM),
+        ],
+      ])
+    );
+    expect(pools.map((pool) => [pool.id, pool.denoms])).toEqual([
+      [1, ['uosmo', ATOM]],
+      [2, ['uion', 'uosmo']],
+      [1135, ['uosmo', ATOM]],
+    ]);
+  });
+
+  it('leaves out CosmWasm pools', () => {
+    const pools = decodeAllPoolsResponse(
+      allPoolsResponse([
+        ['/osmosis.cosmwasmpool.v1beta1.CosmWasmPool', new Uint8Array()],
+      ])
+    );
+    expect(pools).toEqual([]);
+  });
+});
+
+describe('decodeEstimateSwapResponse', () => {
+  it('reads the amount of the estimate', () => {
+    const bytes = Writer.create().uint32(10).string('120000').finish();
+    expect(decodeEstimateSwapResponse(bytes)).toEqual('120000');
+  });
+});
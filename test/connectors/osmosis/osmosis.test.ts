@@ -0,0 +1,279 @@
+import { Reader, Writer } from 'protobufjs/minimal';
+import { Cosmos } from '../../../src/chains/cosmos/cosmos';
+import { getCosmosConfig } from '../../../src/chains/cosmos/cosmos.config';
+import { Token } from '../../../src/chains/cosmos/cosmos-base';
+import { OsmosisConnector } from '../../../src/connectors/osmosis/osmosis';
+import { trade } from '../../../src/connectors/osmosis/osmosis.controllers';
+import {
+  ALL_POOLS_QUERY,
+  MSG_SWAP_EXACT_AMOUNT_IN,
+  MSG_SWAP_EXACT_AMOUNT_OUT,
+} from '../../../src/connectors/osmosis/osmosis.proto';
+import { UniswapishPriceError } from '../../../src/services/error-handler';
+import { patch, unpatch } from '../../services/patch';
+import {
+  allPoolsResponse,
+  balancerPool,
+  concentratedPool,
+} from './osmosis.mock';
+
+let osmosis: Cosmos;
+let connector: OsmosisConnector;
+
+const OSMO: Token = {
+  base: 'uosmo',
+  address: 'uosmo',
+  name: 'Osmosis',
+  symbol: 'OSMO',
+  decimals: 6,
+};
+const ATOM: Token = {
+  base: 'ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2',
+  address:
+    'ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2',
+  name: 'Cosmos Hub Atom',
+  symbol: 'ATOM',
+  decimals: 6,
+};
+const ION: Token = {
+  base: 'uion',
+  address: 'uion',
+  name: 'Ion',
+  symbol: 'ION',
+  decimals: 6,
+};
+const sender = 'osmo1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5helwsw';
+
+beforeAll(async () => {
+  osmosis = Cosmos.getInstance('mainnet', 'osmosis');
+  patch(osmosis, 'getTokenList', () => [OSMO, ATOM, ION]);
+  await osmosis.init();
+
+  connector = OsmosisConnector.getInstance('osmosis', 'mainnet');
+  await connector.init();
+});
+
+afterEach(() => {
+  unpatch();
+});
+
+afterAll(async () => {
+  await osmosis.close();
+});
+
+let poolQueries = 0;
+
+// answers the pool query and the estimate of each pool, pools without an
+// estimate fail theirs. The pools the connector cached are queried again.
+const patchQueries = (estimates: Record<number, string>) => {
+  poolQueries = 0;
+  patch(connector, '_poolsExpiry', 0);
+  patch(osmosis, 'queryAbci', (path: string, request: Uint8Array) => {
+    if (path === ALL_POOLS_QUERY) {
+      poolQueries += 1;
+      return allPoolsResponse([
+        [
+          '/osmosis.gamm.v1beta1.Pool',
+          balancerPool(1, [OSMO.base, ATOM.base]),
+        ],
+        [
+          '/osmosis.concentratedliquidity.v1beta1.Pool',
+          concentratedPool(1135, OSMO.base, ATOM.base),
+        ],
+        [
+          '/osmosis.gamm.v1beta1.Pool',
+          balancerPool(2, [OSMO.base, ION.base]),
+        ],
+      ]);
+    }
+    // the pool id is the first field of both estimate requests
+    const reader = Reader.create(request);
+    reader.uint32();
+    const poolId = Number(reader.uint64().toString());
+    if (!(poolId in estimates)) {
+      throw new Error(`pool ${poolId} has not enough liquidity`);
+    }
+    return Writer.create().uint32(10).string(estimates[poolId]).finish();
+  });
+};
+
+describe('Cosmos', () => {
+  it('has the address prefix of the chain', () => {
+    expect(osmosis.chainName).toEqual('osmosis');
+    expect(osmosis.bech32Prefix).toEqual('osmo');
+  });
+
+  it('reads the config of the requested network', () => {
+    const config = getCosmosConfig('osmosis', 'testnet');
+    expect(config.network.name).toEqual('testnet');
+    expect(config.network.rpcURL).toEqual(
+      'https://rpc.osmotest5.osmosis.zone'
+    );
+  });
+});
+
+describe('estimateSellTrade', () => {
+  it('picks the pool that returns the most', async () => {
+    patchQueries({ 1: '100000', 1135: '120000' });
+    const trade = await connector.estimateSellTrade(OSMO, ATOM, '1');
+    expect(trade.poolId).toEqual(1135);
+    expect(trade.amountIn).toEqual('1000000');
+    expect(trade.amountOut).toEqual('120000');
+    expect(trade.exactIn).toEqual(true);
+  });
+
+  it('fail if no pool holds both tokens', async () => {
+    patchQueries({ 1: '100000' });
+    await expect(
+      connector.estimateSellTrade(ION, ATOM, '1')
+    ).rejects.toBeInstanceOf(UniswapishPriceError);
+  });
+});
+
+describe('estimateBuyTrade', () => {
+  it('picks the pool that costs the least', async () => {
+    patchQueries({ 1: '900000' });
+    const trade = await connector.estimateBuyTrade(OSMO, ATOM, '0.1');
+    expect(trade.poolId).toEqual(1);
+    expect(trade.tokenIn).toEqual(OSMO);
+    expect(trade.amountIn).toEqual('900000');
+    expect(trade.amountOut).toEqual('100000');
+    expect(trade.exactIn).toEqual(false);
+  });
+});
+
+describe('fetchPools', () => {
+  it('reuses the pools for the next estimates', async () => {
+    patchQueries({ 1: '900000', 1135: '120000' });
+    await connector.estimateSellTrade(OSMO, ATOM, '1');
+    await connector.estimateBuyTrade(OSMO, ATOM, '0.1');
+    expect(poolQueries).toEqual(1);
+  });
+});
+
+describe('swapMessage', () => {
+  it('receives at least the estimate less the slippage', () => {
+    const message = connector.swapMessage(
+      sender,
+      {
+        poolId: 1135,
+        tokenIn: OSMO,
+        tokenOut: ATOM,
+        amountIn: '1000000',
+        amountOut: '120000',
+        exactIn: true,
+      },
+      '1/100'
+    );
+    expect(message.typeUrl).toEqual(MSG_SWAP_EXACT_AMOUNT_IN);
+    expect(message.value.routes).toEqual([
+      { poolId: 1135, tokenOutDenom: ATOM.base },
+    ]);
+    expect(message.value.tokenOutMinAmount).toEqual('118800');
+  });
+
+  it('pays at most the estimate plus the slippage', () => {
+    const message = connector.swapMessage(
+      sender,
+      {
+        poolId: 1,
+        tokenIn: OSMO,
+        tokenOut: ATOM,
+        amountIn: '900000',
+        amountOut: '100000',
+        exactIn: false,
+      },
+      '1/100'
+    );
+    expect(message.typeUrl).toEqual(MSG_SWAP_EXACT_AMOUNT_OUT);
+    expect(message.value.tokenInMaxAmount).toEqual('909000');
+    expect(message.value.tokenOut).toEqual({
+      denom: ATOM.base,
+      amount: '100000',
+    });
+  });
+});
+
+describe('trade', () => {
+  const txHash =
+    'D7A3C5E1B2F40968A1C3E5F7092B4D6F8A0C2E4F6B8D0A2C4E6F8B0D2A4C6E8F'; // noqa: mock
+
+  beforeEach(() => {
+    patchQueries({ 1: '100000', 1135: '120000' });
+    patch(osmosis, 'getWallet', () => {
+      return {};
+    });
+    patch(osmosis, 'estimateFee', () => {
+      return {
+        amount: [{ denom: 'uosmo', amount: '2500' }],
+        gas: '100000',
+      };
+    });
+  });
+
+  it('journals the swap with its amounts and status', async () => {
+    patch(osmosis, 'signAndBroadcast', () => {
+      return {
+        transactionHash: txHash,
+        height: 12345,
+        code: 0,
+        rawLog: '[]',
+        gasUsed: 80000,
+        gasWanted: 100000,
+      };
+    });
+    const result = await trade(osmosis, connector, {
+      chain: 'osmosis',
+      network: 'mainnet',
+      connector: 'osmosis',
+      address: sender,
+      base: 'OSMO',
+      quote: 'ATOM',
+      amount: '1',
+      side: 'SELL',
+    });
+    expect(result.txHash).toEqual(txHash);
+
+    expect(await osmosis.journal.getEntry(txHash)).toMatchObject({
+      chain: 'osmosis',
+      type: 'trade',
+      connector: 'osmosis',
+      wallet: sender,
+      tokens: ['OSMO', 'ATOM'],
+      amounts: ['1', '0.12'],
+      side: 'SELL',
+      status: 'CONFIRMED',
+    });
+    await osmosis.journal.deleteEntry(txHash);
+  });
+
+  it('journals a failed swap as failed', async () => {
+    patch(osmosis, 'signAndBroadcast', () => {
+      return {
+        transactionHash: txHash,
+        height: 12345,
+        code: 7,
+        rawLog: 'slippage exceeded',
+        gasUsed: 80000,
+        gasWanted: 100000,
+      };
+    });
+    await expect(
+      trade(osmosis, connector, {
+        chain: 'osmosis',
+        network: 'mainnet',
+        connector: 'osmosis',
+        address: sender,
+        base: 'OSMO',
+        quote: 'ATOM',
+        amount: '1',
+        side: 'SELL',
+      })
+    ).rejects.toThrow();
+
+    expect(await osmosis.journal.getEntry(txHash)).toMatchObject({
+      status: 'FAILED',
+    });
+    await osmosis.journal.deleteEntry(txHash);
+  });
+});
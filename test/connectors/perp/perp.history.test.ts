@@ -0,0 +1,80 @@
+import { Big } from 'big.js';
+import { PerpConfig } from '../../../src/connectors/perp/perp.config';
+import { PerpPriceHistory } from '../../../src/connectors/perp/perp.history';
+
+let history: PerpPriceHistory;
+let markPrice: number;
+
+const source = {
+  availablePairs: () => ['AAVEUSD', 'WETHUSD'],
+  prices: async () => ({
+    markPrice: new Big(markPrice),
+    indexPrice: new Big('100'),
+  }),
+};
+
+beforeEach(() => {
+  // the settings are read from the config on every access
+  jest.spyOn(PerpConfig.config, 'priceHistorySize', 'get').mockReturnValue(2);
+  jest
+    .spyOn(PerpConfig.config, 'priceHistoryInterval', 'get')
+    .mockReturnValue(60);
+  history = new PerpPriceHistory();
+  // blocks are fed by hand
+  history.start(source, {
+    onNewBlock: () => undefined,
+    offNewBlock: () => undefined,
+  });
+  markPrice = 101;
+});
+
+afterEach(() => {
+  jest.restoreAllMocks();
+});
+
+describe('PerpPriceHistory', () => {
+  it('samples every market on a new block', async () => {
+    await history.onNewBlock(1, 1000);
+
+    expect(history.samples('AAVEUSD')).toStrictEqual([
+      { blockNumber: 1, timestamp: 1000, markPrice: '101', indexPrice: '100' },
+    ]);
+    expect(history.samples('WETHUSD').length).toEqual(1);
+  });
+
+  it('samples at most once per interval', async () => {
+    await history.onNewBlock(1, 1000);
+    await history.onNewBlock(2, 30000);
+    await history.onNewBlock(3, 61000);
+
+    const blocks = history.samples('AAVEUSD').map((s) => s.blockNumber);
+    expect(blocks).toStrictEqual([1, 3]);
+  });
+
+  it('keeps the latest samples only', async () => {
+    for (let block = 1; block <= 3; block++) {
+      markPrice = 100 + block;
+      await history.onNewBlock(block, block * 60000);
+    }
+
+    const prices = history.samples('AAVEUSD').map((s) => s.markPrice);
+    expect(prices).toStrictEqual(['102', '103']);
+    expect(history.samples('AAVEUSD', 180000).length).toEqual(1);
+  });
+
+  it('starts listening to blocks once', () => {
+    const handlers: any[] = [];
+    const blocks = {
+      onNewBlock: (func: any) => handlers.push(func),
+      offNewBlock: (func: any) => handlers.splice(handlers.indexOf(func), 1),
+    };
+    const started = new PerpPriceHistory();
+    started.start(source, blocks);
+    started.start(source, blocks);
+    expect(handlers.length).toEqual(1);
+
+    started.stop();
+    expect(handlers.length).toEqual(0);
+    expect(started.started).toEqual(false);
+  });
+});
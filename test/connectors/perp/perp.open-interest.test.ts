@@ -0,0 +1,163 @@
+import { providers, utils } from 'ethers';
+import { PerpConfig } from '../../../src/connectors/perp/perp.config';
+import {
+  clearingHouseEvents,
+  PerpOpenInterest,
+} from '../../../src/connectors/perp/perp.open-interest';
+
+const clearingHouse = '0x82ac2ce43e33683c58be4cdc40975e73aa50f459';
+const vAAVE = '0x34235c8489b06482a99bb7fcab6d7c467b92d248';
+const vETH = '0x8c835dfaa34e2ae61775e80ee29e2c724c6ae2bb';
+const alice = '0xfaa12fd102fe8623c9299c72b03e45107f2772b5';
+const bob = '0x5bdca4d4a02cf9d4e6e26f62b16f0b15f6de4bc2';
+
+const positionChanged = (
+  trader: string,
+  baseToken: string,
+  size: string
+): providers.Log => {
+  const { data, topics } = clearingHouseEvents.encodeEventLog(
+    clearingHouseEvents.getEvent('PositionChanged'),
+    [trader, baseToken, utils.parseEther(size), 0, 0, 0, 0, 0]
+  );
+  return <providers.Log>{ address: clearingHouse, data, topics };
+};
+
+const positionClosed = (trader: string, baseToken: string): providers.Log => {
+  const { data, topics } = clearingHouseEvents.encodeEventLog(
+    clearingHouseEvents.getEvent('PositionClosed'),
+    [trader, baseToken, 0, 0, 0, 0, 0]
+  );
+  return <providers.Log>{ address: clearingHouse, data, topics };
+};
+
+// a chain whose logs are listed by block, and that fails wide log queries
+const chainOf = (
+  latest: number,
+  logsByBlock: Record<number, providers.Log[]>,
+  maxRange: number = Infinity
+) => {
+  const queries: Array<[number, number]> = [];
+  const provider = <providers.Provider>(<unknown>{
+    getBlockNumber: async () => latest,
+    getLogs: async ({ fromBlock, toBlock }: providers.Filter) => {
+      const [from, to] = [Number(fromBlock), Number(toBlock)];
+      if (to - from + 1 > maxRange) throw new Error('block range too wide');
+      queries.push([from, to]);
+      const logs: providers.Log[] = [];
+      for (let block = from; block <= to; block++) {
+        logs.push(...(logsByBlock[block] || []));
+      }
+      return logs;
+    },
+  });
+  return { provider, queries };
+};
+
+// the open interest of a market once the first scan is done
+const scanned = async (
+  tracker: PerpOpenInterest,
+  provider: providers.Provider,
+  baseToken: string
+) => {
+  await tracker.update(provider, clearingHouse);
+  return tracker.openInterest(provider, clearingHouse, baseToken);
+};
+
+beforeEach(() => {
+  jest
+    .spyOn(PerpConfig.config, 'openInterestStartBlock', 'get')
+    .mockReturnValue(100);
+});
+
+afterEach(() => {
+  jest.restoreAllMocks();
+});
+
+describe('PerpOpenInterest', () => {
+  it('sums the long and the short taker positions of a market', async () => {
+    const { provider } = chainOf(200, {
+      110: [
+        positionChanged(alice, vAAVE, '3'),
+        positionChanged(bob, vAAVE, '-2'),
+      ],
+      120: [
+        positionChanged(alice, vAAVE, '-1'),
+        positionChanged(bob, vETH, '5'),
+      ],
+    });
+    const openInterest = await scanned(new PerpOpenInterest(), provider, vAAVE);
+    expect(openInterest?.long.toString()).toEqual('2');
+    expect(openInterest?.short.toString()).toEqual('2');
+  });
+
+  it('drops the positions settled in a closed market', async () => {
+    const { provider } = chainOf(200, {
+      110: [positionChanged(alice, vAAVE, '3')],
+      150: [positionClosed(alice, vAAVE)],
+    });
+    const openInterest = await scanned(new PerpOpenInterest(), provider, vAAVE);
+    expect(openInterest?.long.toString()).toEqual('0');
+  });
+
+  it('continues from the last block it scanned', async () => {
+    const logs: Record<number, providers.Log[]> = {
+      110: [positionChanged(alice, vAAVE, '3')],
+    };
+    const tracker = new PerpOpenInterest();
+    const first = chainOf(200, logs);
+    await scanned(tracker, first.provider, vAAVE);
+    expect(first.queries).toEqual([[100, 200]]);
+
+    logs[250] = [positionChanged(bob, vAAVE, '1.5')];
+    const second = chainOf(300, logs);
+    const openInterest = await tracker.openInterest(
+      second.provider,
+      clearingHouse,
+      vAAVE
+    );
+    expect(second.queries).toEqual([[201, 300]]);
+    expect(openInterest?.long.toString()).toEqual('4.5');
+  });
+
+  it('asks for fewer blocks when the node refuses the range', async () => {
+    const { provider, queries } = chainOf(
+      20099,
+      { 15000: [positionChanged(alice, vAAVE, '1')] },
+      5000
+    );
+    const openInterest = await scanned(new PerpOpenInterest(), provider, vAAVE);
+    expect(queries[0]).toEqual([100, 5099]);
+    expect(queries.length).toEqual(4);
+    expect(openInterest?.long.toString()).toEqual('1');
+  });
+
+  it('does not wait for the first scan of the logs', async () => {
+    const { provider } = chainOf(200, {
+      110: [positionChanged(alice, vAAVE, '3')],
+    });
+    // the scan is held until the chain head is released
+    let release: () => void = () => undefined;
+    const head = new Promise<void>((resolve) => (release = resolve));
+    const getBlockNumber = provider.getBlockNumber;
+    provider.getBlockNumber = async () => {
+      await head;
+      return getBlockNumber();
+    };
+    const tracker = new PerpOpenInterest();
+    expect(
+      await tracker.openInterest(provider, clearingHouse, vAAVE)
+    ).toBeUndefined();
+    expect(tracker.ready).toEqual(false);
+
+    release();
+    await tracker.update(provider, clearingHouse);
+    expect(tracker.ready).toEqual(true);
+    const openInterest = await tracker.openInterest(
+      provider,
+      clearingHouse,
+      vAAVE
+    );
+    expect(openInterest?.long.toString()).toEqual('3');
+  });
+});
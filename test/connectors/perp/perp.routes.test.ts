@@ -206,6 +206,113 @@ describe('POST /amm/perp/market-status', () => {
   });
 });
 
+describe('POST /amm/perp/funding and /amm/perp/market-info', () => {
+  it('funding should return the funding rates', async () => {
+    patch(perp, 'fundingRates', async (tickerSymbol: string) => {
+      return {
+        tickerSymbol,
+        fundingRate: '0.001',
+        predictedFundingRate: '0.002',
+        markTwapPrice: '100.1',
+        indexTwapPrice: '100',
+        twapInterval: 900,
+      };
+    });
+
+    await request(app)
+      .post(`/amm/perp/funding`)
+      .send({
+        chain: 'ethereum',
+        network: 'optimism',
+        connector: 'perp',
+        quote: 'USD',
+        base: 'AAVE',
+      })
+      .set('Accept', 'application/json')
+      .expect(200)
+      .then((res: any) => {
+        expect(res.body.tickerSymbol).toEqual('AAVEUSD');
+        expect(res.body.fundingRate).toEqual('0.001');
+        expect(res.body.predictedFundingRate).toEqual('0.002');
+      });
+  });
+
+  it('market-info should return the margin requirements', async () => {
+    patch(perp, 'marketInfo', async (tickerSymbol: string) => {
+      return {
+        tickerSymbol,
+        maxLeverage: '10.00',
+        initialMarginRatio: '0.1',
+        maintenanceMarginRatio: '0.0625',
+        tickSpacing: 60,
+        openInterestLong: '120.5',
+        openInterestShort: '80',
+        openInterest: '20050',
+      };
+    });
+
+    await request(app)
+      .post(`/amm/perp/market-info`)
+      .send({
+        chain: 'ethereum',
+        network: 'optimism',
+        connector: 'perp',
+        quote: 'USD',
+        base: 'AAVE',
+      })
+      .set('Accept', 'application/json')
+      .expect(200)
+      .then((res: any) => {
+        expect(res.body.maxLeverage).toEqual('10.00');
+        expect(res.body.tickSpacing).toEqual(60);
+        expect(res.body.openInterestLong).toEqual('120.5');
+        expect(res.body.openInterest).toEqual('20050');
+      });
+  });
+});
+
+describe('POST /amm/perp/price-history', () => {
+  it('should return the samples since from', async () => {
+    patch(perp, 'priceHistory', (tickerSymbol: string, from: number) => {
+      expect([tickerSymbol, from]).toEqual(['AAVEUSD', 1000]);
+      return [
+        { blockNumber: 2, timestamp: 1000, markPrice: '1', indexPrice: '2' },
+      ];
+    });
+
+    await request(app)
+      .post(`/amm/perp/price-history`)
+      .send({
+        chain: 'ethereum',
+        network: 'optimism',
+        connector: 'perp',
+        quote: 'USD',
+        base: 'AAVE',
+        from: 1000,
+      })
+      .set('Accept', 'application/json')
+      .expect(200)
+      .then((res: any) => {
+        expect(res.body.samples.length).toEqual(1);
+      });
+  });
+
+  it('should return 404 when from is not a timestamp', async () => {
+    await request(app)
+      .post(`/amm/perp/price-history`)
+      .send({
+        chain: 'ethereum',
+        network: 'optimism',
+        connector: 'perp',
+        quote: 'USD',
+        base: 'AAVE',
+        from: 'yesterday',
+      })
+      .set('Accept', 'application/json')
+      .expect(404);
+  });
+});
+
 describe('POST /amm/perp/pairs', () => {
   it('should return list of available pairs', async () => {
     patchMarket();
@@ -318,6 +425,191 @@ describe('POST /amm/perp/open and /amm/perp/close', () => {
   });
 });
 
+describe('POST /amm/perp/close with an amount', () => {
+  it('should reduce the position instead of closing it', async () => {
+    patchGasPrice();
+    patch(perp2, 'reducePosition', async (_symbol: string, amount: string) => {
+      expect(amount).toEqual('0.5');
+      return { hash: '0x0a', nonce: 116 };
+    });
+    patch(perp2, 'closePosition', async () => {
+      throw new Error('the position should not be closed');
+    });
+
+    await request(app)
+      .post(`/amm/perp/close`)
+      .send({
+        chain: 'ethereum',
+        network: 'optimism',
+        connector: 'perp',
+        quote: 'USD',
+        base: 'WETH',
+        amount: '0.5',
+        address: address,
+      })
+      .set('Accept', 'application/json')
+      .expect(200)
+      .then((res: any) => {
+        expect(res.body.txHash).toEqual('0x0a');
+        expect(res.body.amount).toEqual('0.5');
+      });
+  });
+});
+
+const orderId =
+  '0x6d1c1b4e2e2b1a6b4c4e7f7a0c6b1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b'; // noqa: mock
+
+describe('POST /amm/perp/order and /amm/perp/orders', () => {
+  it('order should return the order id', async () => {
+    patchGasPrice();
+    patch(perp2, 'createLimitOrder', async (...args: any[]) => {
+      expect(args).toEqual([true, 'WETHUSD', '1', '1500']);
+      return { orderId, transaction: { hash: '0x0b', nonce: 117 } };
+    });
+
+    await request(app)
+      .post(`/amm/perp/order`)
+      .send({
+        chain: 'ethereum',
+        network: 'optimism',
+        connector: 'perp',
+        quote: 'USD',
+        base: 'WETH',
+        amount: '1',
+        side: 'LONG',
+        price: '1500',
+        address: address,
+      })
+      .set('Accept', 'application/json')
+      .expect(200)
+      .then((res: any) => {
+        expect(res.body.orderId).toEqual(orderId);
+        expect(res.body.txHash).toEqual('0x0b');
+        expect(res.body.nonce).toEqual(117);
+      });
+  });
+
+  it('order should return 404 without a price', async () => {
+    await request(app)
+      .post(`/amm/perp/order`)
+      .send({
+        chain: 'ethereum',
+        network: 'optimism',
+        connector: 'perp',
+        quote: 'USD',
+        base: 'WETH',
+        amount: '1',
+        side: 'LONG',
+        address: address,
+      })
+      .set('Accept', 'application/json')
+      .expect(404);
+  });
+
+  it('orders should list the open orders', async () => {
+    patch(perp2, 'getLimitOrders', async () => [
+      {
+        orderId,
+        side: 'LONG',
+        lowerTick: 73080,
+        upperTick: 73140,
+        lowerPrice: '1491.1',
+        upperPrice: '1500.1',
+        liquidity: '1000',
+        baseAmount: '0',
+        quoteAmount: '1500',
+      },
+    ]);
+
+    await request(app)
+      .post(`/amm/perp/orders`)
+      .send({
+        chain: 'ethereum',
+        network: 'optimism',
+        connector: 'perp',
+        quote: 'USD',
+        base: 'WETH',
+        address: address,
+      })
+      .set('Accept', 'application/json')
+      .expect(200)
+      .then((res: any) => {
+        expect(res.body.orders.length).toEqual(1);
+        expect(res.body.orders[0].orderId).toEqual(orderId);
+      });
+  });
+
+  it('cancel should return with hash', async () => {
+    patchGasPrice();
+    patch(perp2, 'cancelLimitOrder', async (symbol: string, id: string) => {
+      expect([symbol, id]).toEqual(['WETHUSD', orderId]);
+      return { hash: '0x0c', nonce: 118 };
+    });
+
+    await request(app)
+      .post(`/amm/perp/order/cancel`)
+      .send({
+        chain: 'ethereum',
+        network: 'optimism',
+        connector: 'perp',
+        quote: 'USD',
+        base: 'WETH',
+        orderId,
+        address: address,
+      })
+      .set('Accept', 'application/json')
+      .expect(200)
+      .then((res: any) => {
+        expect(res.body.txHash).toEqual('0x0c');
+      });
+  });
+});
+
+describe('POST /amm/perp/margin/add and /amm/perp/margin/remove', () => {
+  it('add should deposit into the vault', async () => {
+    patchGasPrice();
+    patch(perp2, 'addMargin', async (amount: string) => {
+      expect(amount).toEqual('100');
+      return { hash: '0x0d', nonce: 119 };
+    });
+
+    await request(app)
+      .post(`/amm/perp/margin/add`)
+      .send({
+        chain: 'ethereum',
+        network: 'optimism',
+        connector: 'perp',
+        amount: '100',
+        address: address,
+      })
+      .set('Accept', 'application/json')
+      .expect(200)
+      .then((res: any) => {
+        expect(res.body.txHash).toEqual('0x0d');
+        expect(res.body.amount).toEqual('100');
+      });
+  });
+
+  it('remove should return 500 when the vault rejects it', async () => {
+    patchGasPrice();
+    patch(perp2, 'removeMargin', async () => {
+      throw new Error('V_NEFC');
+    });
+
+    await request(app)
+      .post(`/amm/perp/margin/remove`)
+      .send({
+        chain: 'ethereum',
+        network: 'optimism',
+        connector: 'perp',
+        amount: '100000',
+        address: address,
+      })
+      .set('Accept', 'application/json')
+      .expect(500);
+  });
+});
+
 describe('POST /amm/perp/estimateGas', () => {
   it('should return 200 with right parameter', async () => {
     patchGasPrice();
@@ -1,6 +1,16 @@
 jest.useFakeTimers();
-import { Perp } from '../../../src/connectors/perp/perp';
-import { MarketStatus } from '@perp/sdk-curie';
+import { BigNumber } from 'ethers';
+import {
+  fundingRate,
+  liquidationPrice,
+  Perp,
+  limitOrderRange,
+  priceToTick,
+  rangeAmounts,
+  sqrtPriceX96ToPrice,
+  tickToPrice,
+} from '../../../src/connectors/perp/perp';
+import { MarketStatus, PositionSide } from '@perp/sdk-curie';
 import { patch, unpatch } from '../../services/patch';
 import { Big } from 'big.js';
 import { Ethereum } from '../../../src/chains/ethereum/ethereum';
@@ -185,3 +195,128 @@ describe('getAllowedSlippage', () => {
     expect(allowedSlippage).toEqual(0.02);
   });
 });
+
+describe('verify perp reduce position', () => {
+  const patchLongPosition = () => {
+    patch(perp.perp, 'positions', () => {
+      return {
+        getTakerPositionByTickerSymbol() {
+          return { side: PositionSide.LONG, sizeAbs: new Big('2') };
+        },
+      };
+    });
+  };
+
+  it('reducePosition should trade the opposite side in base', async () => {
+    patchLongPosition();
+    let draft: any;
+    patch(perp.perp, 'clearingHouse', () => {
+      return {
+        createPositionDraft(args: any) {
+          draft = args;
+          return args;
+        },
+        async openPosition() {
+          return { transaction: { hash: '0x01' } };
+        },
+      };
+    });
+
+    const tx = await perp.reducePosition('AAVEUSD', '0.5', '1/10');
+    expect(tx.hash).toEqual('0x01');
+    expect(draft.side).toEqual(PositionSide.SHORT);
+    expect(draft.isAmountInputBase).toEqual(true);
+    expect(draft.amountInput.toString()).toEqual('0.5');
+  });
+
+  it('reducePosition should not flip the position', async () => {
+    patchLongPosition();
+    patchCH();
+
+    await expect(perp.reducePosition('AAVEUSD', '3', '1/10')).rejects.toThrow(
+      'Cannot reduce the 2 position on AAVEUSD by 3.'
+    );
+  });
+});
+
+describe('verify perp limit order math', () => {
+  it('priceToTick should round to the tick spacing', () => {
+    const tick = priceToTick(new Big('100'), 60);
+    expect(tick % 60).toEqual(0);
+    expect(Number(tickToPrice(tick))).toBeCloseTo(100, 0);
+  });
+
+  it('limitOrderRange should keep the range away from the mark price', () => {
+    // 99.5 is at tick 46003.9, between the ticks 45960 and 46020
+    const long = limitOrderRange(true, new Big('99.5'), new Big('100'), 60);
+    expect(long).toEqual({ lowerTick: 45900, upperTick: 45960 });
+
+    const short = limitOrderRange(false, new Big('99.5'), new Big('99'), 60);
+    expect(short).toEqual({ lowerTick: 46020, upperTick: 46080 });
+  });
+
+  it('limitOrderRange should fail for a range holding the mark price', () => {
+    expect(() =>
+      limitOrderRange(true, new Big('99.9'), new Big('99.5'), 60)
+    ).toThrow('holds the mark price');
+  });
+
+  it('rangeAmounts should hold quote below the price and base above', () => {
+    const liquidity = new Big('1e18');
+    const below = rangeAmounts(liquidity, 0, 60, new Big('2'));
+    expect(below.baseAmount.toString()).toEqual('0');
+    expect(below.quoteAmount.gt(0)).toEqual(true);
+
+    const above = rangeAmounts(liquidity, 0, 60, new Big('0.5'));
+    expect(above.baseAmount.gt(0)).toEqual(true);
+    expect(above.quoteAmount.toString()).toEqual('0');
+  });
+
+  it('liquidationPrice should leave the maintenance margin', () => {
+    // 10 of margin on a long of 1 at 100 with a 6.25% maintenance margin
+    const long = liquidationPrice(
+      true,
+      new Big('1'),
+      new Big('100'),
+      new Big('10'),
+      new Big('0.0625')
+    );
+    expect(long.toFixed(4)).toEqual('96.0000');
+
+    const short = liquidationPrice(
+      false,
+      new Big('1'),
+      new Big('100'),
+      new Big('10'),
+      new Big('0.0625')
+    );
+    expect(short.toFixed(4)).toEqual('103.5294');
+
+    // a long with more margin than its value can't be liquidated
+    expect(
+      liquidationPrice(
+        true,
+        new Big('1'),
+        new Big('100'),
+        new Big('200'),
+        new Big('0.0625')
+      ).toString()
+    ).toEqual('0');
+  });
+});
+
+describe('verify perp funding math', () => {
+  it('sqrtPriceX96ToPrice should square the fixed point price', () => {
+    const sqrtPriceX96 = BigNumber.from(2).pow(96).mul(10);
+    expect(sqrtPriceX96ToPrice(sqrtPriceX96).toString()).toEqual('100');
+  });
+
+  it('fundingRate should be the premium of the mark over the index', () => {
+    expect(fundingRate(new Big('101'), new Big('100')).toString()).toEqual(
+      '0.01'
+    );
+    expect(fundingRate(new Big('99'), new Big('100')).toString()).toEqual(
+      '-0.01'
+    );
+  });
+});
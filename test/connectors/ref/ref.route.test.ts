@@ -342,7 +342,10 @@ const patchEstimateSellTrade = () => {
 
 const patchExecuteTrade = () => {
   patch(ref, 'executeTrade', () => {
-    return { hash: '000000000000000' };
+    return {
+      hash: '000000000000000',
+      transaction_outcome: { id: '000000000000000' },
+    };
   });
 };
 
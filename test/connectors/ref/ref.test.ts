@@ -337,3 +337,31 @@ describe('verify Ref executeTrade', () => {
     }).rejects.toThrow();
   });
 });
+
+describe('verify Ref registration', () => {
+  const account: any = { accountId: 'test.near' };
+
+  it('registers the account on the token received first', async () => {
+    patch(near, 'getStorageDeposits', () => [
+      { accountId: 'test.near', amount: '1250000000000000000000' },
+    ]);
+    expect(await ref.registration(account, DAI)).toEqual([
+      {
+        receiverId: DAI.id,
+        functionCalls: [
+          {
+            methodName: 'storage_deposit',
+            args: { account_id: 'test.near', registration_only: true },
+            gas: '30000000000000',
+            amount: '0.00125',
+          },
+        ],
+      },
+    ]);
+  });
+
+  it('is empty if the account is registered', async () => {
+    patch(near, 'getStorageDeposits', () => []);
+    expect(await ref.registration(account, DAI)).toEqual([]);
+  });
+});
@@ -0,0 +1,86 @@
+import { BigNumber } from 'ethers';
+import {
+  depthAt,
+  initializedTicksOfWord,
+  liquidityRanges,
+  rangeAmounts,
+  tickWindow,
+  wordPosOfTick,
+} from '../../../src/connectors/uniswap/uniswap.lp.analytics';
+
+const L = BigNumber.from('1000000000000000000');
+
+describe('tick bitmap', () => {
+  it('finds the initialized ticks of a word', () => {
+    const bitmap = BigNumber.from(1).or(BigNumber.from(1).shl(3));
+    expect(initializedTicksOfWord(-1, bitmap, 10)).toStrictEqual([
+      -2560, -2530,
+    ]);
+    expect(initializedTicksOfWord(2, BigNumber.from(0), 10)).toStrictEqual([]);
+  });
+
+  it('rounds words down like the pool', () => {
+    expect(wordPosOfTick(-1, 10)).toEqual(-1);
+    expect(wordPosOfTick(0, 10)).toEqual(0);
+    expect(wordPosOfTick(2559, 10)).toEqual(0);
+    expect(wordPosOfTick(2560, 10)).toEqual(1);
+  });
+
+  it('covers a price range with ticks', () => {
+    expect(tickWindow(0, 10)).toStrictEqual({
+      lowerTick: -1054,
+      upperTick: 954,
+    });
+  });
+});
+
+describe('liquidityRanges', () => {
+  it('adds and removes the liquidity of the ticks crossed', () => {
+    const ranges = liquidityRanges(
+      0,
+      BigNumber.from(100),
+      [
+        { index: 60, liquidityNet: BigNumber.from(-30) },
+        { index: -60, liquidityNet: BigNumber.from(40) },
+        { index: 500, liquidityNet: BigNumber.from(10) },
+      ],
+      -120,
+      120
+    );
+    expect(
+      ranges.map((range) => [
+        range.tickLower,
+        range.tickUpper,
+        range.liquidity.toNumber(),
+      ])
+    ).toStrictEqual([
+      [-120, -60, 60],
+      [-60, 60, 100],
+      [60, 120, 70],
+    ]);
+  });
+});
+
+describe('pool amounts', () => {
+  const range = { tickLower: -2000, tickUpper: 2000, liquidity: L };
+
+  it('holds token0 above the price and token1 below it', () => {
+    const above = rangeAmounts({ ...range, tickLower: 100 }, 1);
+    expect(above.amount1).toEqual(0);
+    expect(above.amount0).toBeGreaterThan(0);
+    const below = rangeAmounts({ ...range, tickUpper: -100 }, 1);
+    expect(below.amount0).toEqual(0);
+    expect(below.amount1).toBeGreaterThan(0);
+  });
+
+  it('reports the depth of constant liquidity', () => {
+    const { token1In, token0In } = depthAt([range], 1, 2);
+    expect(token1In / 1e18).toBeCloseTo(Math.sqrt(1.02) - 1, 8);
+    expect(token0In / 1e18).toBeCloseTo(1 / Math.sqrt(0.98) - 1, 8);
+  });
+
+  it('only counts the liquidity there is', () => {
+    const narrow = { ...range, tickLower: -10, tickUpper: 10 };
+    expect(depthAt([narrow], 1, 5)).toStrictEqual(depthAt([narrow], 1, 10));
+  });
+});
@@ -306,6 +306,61 @@ describe('POST /liquidity/remove', () => {
   });
 });
 
+describe('POST /liquidity/rebalance', () => {
+  it('should return the new position', async () => {
+    patchGetWallet();
+    patchInit();
+    patchGasPrice();
+    patchPosition();
+    patch(uniswap, 'rebalancePosition', () => {
+      return {
+        transaction: { nonce: 21, hash: '000000000000000' },
+        tokenId: 2733,
+        amount0: '2',
+        amount1: '1.5',
+        swapRequired: true,
+      };
+    });
+
+    await request(app)
+      .post(`/amm/liquidity/rebalance`)
+      .send({
+        address: address,
+        tokenId: 2732,
+        lowerPrice: '2',
+        upperPrice: '6',
+        chain: 'ethereum',
+        network: 'goerli',
+        connector: 'uniswapLP',
+      })
+      .set('Accept', 'application/json')
+      .expect(200)
+      .then((res: any) => {
+        expect(res.body.tokenId).toEqual(2732);
+        expect(res.body.newTokenId).toEqual(2733);
+        expect(res.body.token0).toEqual('DAI');
+        expect(res.body.amount1).toEqual('1.5');
+        expect(res.body.swapRequired).toEqual(true);
+        expect(res.body.txHash).toEqual('000000000000000');
+      });
+  });
+
+  it('should return 404 without the new range', async () => {
+    await request(app)
+      .post(`/amm/liquidity/rebalance`)
+      .send({
+        address: address,
+        tokenId: 2732,
+        lowerPrice: '2',
+        chain: 'ethereum',
+        network: 'goerli',
+        connector: 'uniswapLP',
+      })
+      .set('Accept', 'application/json')
+      .expect(404);
+  });
+});
+
 describe('POST /liquidity/collect_fees', () => {
   const patchForBuy = () => {
     patchGetWallet();
@@ -389,6 +444,44 @@ describe('POST /liquidity/position', () => {
   });
 });
 
+describe('POST /liquidity/positions', () => {
+  it('should return the positions of the wallet', async () => {
+    patchInit();
+    patchStoredTokenList();
+    patch(uniswap, 'getPositions', async (owner: string) => {
+      expect(owner).toEqual(address);
+      return [{ tokenId: 2732, token0: 'DAI', token1: 'WETH', inRange: true }];
+    });
+
+    await request(app)
+      .post(`/amm/liquidity/positions`)
+      .send({
+        address: address,
+        chain: 'ethereum',
+        network: 'goerli',
+        connector: 'uniswapLP',
+      })
+      .set('Accept', 'application/json')
+      .expect(200)
+      .then((res: any) => {
+        expect(res.body.positions.length).toEqual(1);
+        expect(res.body.positions[0].tokenId).toEqual(2732);
+      });
+  });
+
+  it('should return 404 without an address', async () => {
+    await request(app)
+      .post(`/amm/liquidity/positions`)
+      .send({
+        chain: 'ethereum',
+        network: 'goerli',
+        connector: 'uniswapLP',
+      })
+      .set('Accept', 'application/json')
+      .expect(404);
+  });
+});
+
 describe('POST /liquidity/price', () => {
   const patchForBuy = () => {
     patchInit();
@@ -440,3 +533,115 @@ describe('POST /liquidity/price', () => {
       .expect(404);
   });
 });
+
+describe('POST /liquidity/pool', () => {
+  it('should return the pool analytics', async () => {
+    patchInit();
+    patchStoredTokenList();
+    patchGetTokenBySymbol();
+    patchGetTokenByAddress();
+    patch(
+      uniswap,
+      'getPoolInfo',
+      async (
+        _token0: any,
+        _token1: any,
+        _fee: number,
+        rangePercent: number,
+        depthPercents: number[],
+        twapWindow: number
+      ) => {
+        expect(rangePercent).toEqual(10);
+        expect(depthPercents).toStrictEqual([1, 2, 5]);
+        expect(twapWindow).toEqual(600);
+        return { tick: 100, ranges: [], depth: [] };
+      }
+    );
+
+    await request(app)
+      .post(`/amm/liquidity/pool`)
+      .send({
+        token0: 'DAI',
+        token1: 'WETH',
+        fee: 'LOW',
+        twapWindow: 600,
+        chain: 'ethereum',
+        network: 'goerli',
+        connector: 'uniswapLP',
+      })
+      .set('Accept', 'application/json')
+      .expect(200)
+      .then((res: any) => {
+        expect(res.body.tick).toEqual(100);
+        expect(res.body.network).toEqual('goerli');
+      });
+  });
+
+  it('should return 404 when a depth percent is out of range', async () => {
+    await request(app)
+      .post(`/amm/liquidity/pool`)
+      .send({
+        token0: 'DAI',
+        token1: 'WETH',
+        fee: 'LOW',
+        depthPercents: [1, 100],
+        chain: 'ethereum',
+        network: 'goerli',
+        connector: 'uniswapLP',
+      })
+      .set('Accept', 'application/json')
+      .expect(404);
+  });
+});
+
+describe('POST /liquidity/pool/observations', () => {
+  const POOL = '0x6c6Bc977E13Df9b0de53b251522280BB72383700';
+
+  it('should return 200 when all parameter are OK', async () => {
+    patchGetWallet();
+    patchInit();
+    patchStoredTokenList();
+    patchGetTokenBySymbol();
+    patchGetTokenByAddress();
+    patchGasPrice();
+    patch(uniswap, 'increaseObservationCardinality', () => {
+      return { nonce: 21, hash: '000000000000000', to: POOL };
+    });
+
+    await request(app)
+      .post(`/amm/liquidity/pool/observations`)
+      .send({
+        address: address,
+        token0: 'DAI',
+        token1: 'WETH',
+        fee: 'LOW',
+        cardinality: 100,
+        chain: 'ethereum',
+        network: 'goerli',
+        connector: 'uniswapLP',
+      })
+      .set('Accept', 'application/json')
+      .expect(200)
+      .then((res: any) => {
+        expect(res.body.pool).toEqual(POOL);
+        expect(res.body.cardinality).toEqual(100);
+        expect(res.body.txHash).toEqual('000000000000000');
+      });
+  });
+
+  it('should return 404 without a cardinality', async () => {
+    await request(app)
+      .post(`/amm/liquidity/pool/observations`)
+      .send({
+        address: address,
+        token0: 'DAI',
+        token1: 'WETH',
+        fee: 'LOW',
+        chain: 'ethereum',
+        network: 'goerli',
+        connector: 'uniswapLP',
+      })
+      .set('Accept', 'application/json')
+      .expect(404);
+  });
+});
@@ -1,9 +1,10 @@
 jest.useFakeTimers();
 import { Token } from '@uniswap/sdk-core';
 import * as uniV3 from '@uniswap/v3-sdk';
-import { BigNumber, Transaction, Wallet } from 'ethers';
+import { BigNumber, Transaction, utils, Wallet } from 'ethers';
 import { Ethereum } from '../../../src/chains/ethereum/ethereum';
 import { UniswapLP } from '../../../src/connectors/uniswap/uniswap.lp';
+import { findMintOutput } from '../../../src/connectors/uniswap/uniswap.lp.helper';
 import { patch, unpatch } from '../../services/patch';
 import { patchEVMNonceManager } from '../../evm.nonce.mock';
 let ethereum: Ethereum;
@@ -283,3 +284,317 @@ describe('verify UniswapLP Nft functions', () => {
     );
   });
 });
+
+describe('verify UniswapLP position discovery', () => {
+  it('getPosition reports the range and value of a position', async () => {
+    patchPoolState();
+    patch(uniswapLP, 'getTokenByAddress', (address: string) =>
+      address === DAI.address ? DAI : USDC
+    );
+    const tick = uniV3.nearestUsableTick(POOL_TICK_CURRENT, 10);
+    patch(uniswapLP, 'getContract', () => {
+      return {
+        positions() {
+          return {
+            token0: DAI.address,
+            token1: USDC.address,
+            fee: 500,
+            tickLower: tick - 100,
+            tickUpper: tick + 100,
+            liquidity: BigNumber.from('1000000000000000000'),
+          };
+        },
+        ownerOf() {
+          return wallet.address;
+        },
+        callStatic: {
+          collect() {
+            return { amount0: BigNumber.from(0), amount1: BigNumber.from(0) };
+          },
+        },
+      };
+    });
+
+    const position = await uniswapLP.getPosition(7);
+    expect(position.tokenId).toEqual(7);
+    expect(position.inRange).toEqual(true);
+    expect(Number(position.valueInToken0)).toBeGreaterThan(
+      Number(position.amount0)
+    );
+    expect(Number(position.valueInToken1)).toBeGreaterThan(
+      Number(position.amount1)
+    );
+  });
+
+  it('getPositions lists the NFTs of a wallet', async () => {
+    patch(uniswapLP, 'getContract', () => {
+      return {
+        balanceOf() {
+          return BigNumber.from(2);
+        },
+        tokenOfOwnerByIndex(_address: string, index: number) {
+          return BigNumber.from(10 + index);
+        },
+      };
+    });
+    patch(uniswapLP, 'getPosition', async (tokenId: number) => {
+      if (tokenId === 11) throw new Error('unknown token');
+      return { tokenId };
+    });
+
+    const positions = await uniswapLP.getPositions(wallet.address);
+    expect(positions).toStrictEqual([{ tokenId: 10 }]);
+  });
+});
+
+describe('verify UniswapLP rebalancing', () => {
+  const NFT = uniV3.NonfungiblePositionManager.INTERFACE;
+  const ROUTER = new utils.Interface([
+    'function multicall(bytes[] data) payable returns (bytes[] results)',
+    'function callPositionManager(bytes data) payable returns (bytes result)',
+  ]);
+  const coder = utils.defaultAbiCoder;
+  const MINTED = coder.encode(
+    ['uint256', 'uint128', 'uint256', 'uint256'],
+    [42, 1000, utils.parseUnits('1', 6), utils.parseUnits('2', 18)]
+  );
+  const REMOVED = [
+    coder.encode(['uint128', 'uint256', 'uint256'], [1000, 5, 6]),
+    coder.encode(['uint256', 'uint256'], [5, 6]),
+    '0x',
+  ];
+
+  const patchPosition = () => {
+    patchPoolState();
+    patch(uniswapLP, 'getTokenByAddress', (address: string) =>
+      address === DAI.address ? DAI : USDC
+    );
+    const tick = uniV3.nearestUsableTick(POOL_TICK_CURRENT, 10);
+    patch(uniswapLP, 'getContract', () => {
+      return {
+        positions() {
+          return {
+            nonce: 0,
+            token0: USDC.address,
+            token1: DAI.address,
+            fee: 500,
+            tickLower: tick - 100,
+            tickUpper: tick + 100,
+            liquidity: BigNumber.from('1000000000000000000'),
+          };
+        },
+        ownerOf() {
+          return wallet.address;
+        },
+        callStatic: {
+          collect() {
+            return { amount0: BigNumber.from(5), amount1: BigNumber.from(6) };
+          },
+        },
+      };
+    });
+  };
+
+  const fees = { amount0: BigNumber.from(5), amount1: BigNumber.from(6) };
+
+  it('mints in the position manager multicall without a swap', async () => {
+    patchPosition();
+    patchAlphaRouter();
+
+    const result = await uniswapLP.rebalancePositionHelper(
+      wallet,
+      7,
+      0.5,
+      2,
+      fees
+    );
+    expect(result.swapRequired).toEqual(false);
+    expect(result.token0).toEqual(USDC);
+    const [calls] = NFT.decodeFunctionData('multicall', result.calldata);
+    expect(calls.length).toEqual(2);
+    const [removal] = NFT.decodeFunctionData('multicall', calls[0]);
+    const names = removal.map(
+      (call: string) => NFT.parseTransaction({ data: call }).name
+    );
+    expect(names).toStrictEqual(['decreaseLiquidity', 'collect', 'burn']);
+    expect(NFT.parseTransaction({ data: calls[1] }).name).toEqual('mint');
+  });
+
+  it('goes through the router under a permit to swap', async () => {
+    patchPosition();
+    patch(uniswapLP.alphaRouter, 'routeToRatio', () => {
+      return {
+        status: 1,
+        result: { methodParameters: { calldata: '0x1234', value: '0x00' } },
+      };
+    });
+
+    const result = await uniswapLP.rebalancePositionHelper(
+      wallet,
+      7,
+      0.5,
+      2,
+      fees
+    );
+    expect(result.swapRequired).toEqual(true);
+    const [calls] = ROUTER.decodeFunctionData('multicall', result.calldata);
+    expect(calls[1]).toEqual('0x1234');
+    const [removalCall] = ROUTER.decodeFunctionData(
+      'callPositionManager',
+      calls[0]
+    );
+    const [removal] = NFT.decodeFunctionData('multicall', removalCall);
+    const permit = NFT.parseTransaction({ data: removal[0] });
+    expect(permit.name).toEqual('permit');
+    expect(permit.args.spender).toEqual(uniswapLP.router);
+  });
+
+  it('findMintOutput looks through nested multicall results', async () => {
+    const nft = coder.encode(['bytes[]'], [[...REMOVED, MINTED]]);
+    expect(findMintOutput(nft)?.[0].toNumber()).toEqual(42);
+
+    const router = coder.encode(
+      ['bytes[]'],
+      [
+        [
+          coder.encode(['bytes'], [coder.encode(['bytes[]'], [REMOVED])]),
+          coder.encode(
+            ['bytes[]'],
+            [
+              [
+                coder.encode(['uint256'], [7]),
+                '0x',
+                coder.encode(['bytes'], [MINTED]),
+              ],
+            ]
+          ),
+        ],
+      ]
+    );
+    expect(findMintOutput(router)?.[0].toNumber()).toEqual(42);
+    expect(
+      findMintOutput(coder.encode(['bytes[]'], [REMOVED]))
+    ).toBeUndefined();
+  });
+
+  it('rebalancePosition reports the minted position', async () => {
+    patchPosition();
+    patchAlphaRouter();
+    patchWallet();
+    patch(wallet, 'call', () =>
+      coder.encode(
+        ['bytes[]'],
+        [[coder.encode(['bytes[]'], [REMOVED]), MINTED]]
+      )
+    );
+
+    const result = await uniswapLP.rebalancePosition(wallet, 7, 0.5, 2, 1, 1);
+    expect(result.transaction.hash).toEqual(TX.hash);
+    expect(result.tokenId).toEqual(42);
+    expect(result.amount0).toEqual('1.0');
+    expect(result.amount1).toEqual('2.0');
+    expect(result.swapRequired).toEqual(false);
+  });
+});
+
+describe('verify UniswapLP pool analytics', () => {
+  // 1 USDC per DAI, USDC sorts first
+  const SQRT_PRICE = uniV3.encodeSqrtRatioX96('1000000000000000000', '1000000');
+  const TICK = uniV3.TickMath.getTickAtSqrtRatio(SQRT_PRICE);
+  const BASE = Math.floor(TICK / 10) * 10;
+  const LIQUIDITY = BigNumber.from('1000000000000000000');
+
+  // the pool holds observations going back oldest seconds
+  const patchPool = (oldest: number = 3600) => {
+    const nets: Record<number, BigNumber> = {
+      [BASE - 100]: LIQUIDITY,
+      [BASE + 100]: LIQUIDITY.mul(-1),
+    };
+    patch(uniswapLP, 'getPoolContract', () => {
+      return {
+        liquidity() {
+          return LIQUIDITY;
+        },
+        slot0() {
+          return [SQRT_PRICE.toString(), TICK, 0, 1, 1, 0, true];
+        },
+        ticks(index: number) {
+          return [0, nets[index] || BigNumber.from(0)];
+        },
+        tickBitmap(word: number) {
+          let bitmap = BigNumber.from(0);
+          for (const tick of Object.keys(nets).map(Number)) {
+            const compressed = tick / 10;
+            if (Math.floor(compressed / 256) === word) {
+              const bit = compressed - word * 256;
+              bitmap = bitmap.or(BigNumber.from(1).shl(bit));
+            }
+          }
+          return bitmap;
+        },
+        observe([window]: number[]) {
+          if (window > oldest) throw new Error('OLD');
+          return {
+            tickCumulatives: [
+              BigNumber.from(0),
+              BigNumber.from(TICK).mul(window),
+            ],
+          };
+        },
+      };
+    });
+  };
+
+  it('getPoolInfo reports the liquidity around the price', async () => {
+    patchPool();
+
+    const info = await uniswapLP.getPoolInfo(DAI, USDC, 500, 10, [1, 2, 5], 60);
+    expect(info.token0).toEqual('USDC');
+    expect(info.token1).toEqual('DAI');
+    expect(info.tickSpacing).toEqual(10);
+    expect(Number(info.price)).toBeCloseTo(1, 4);
+    expect(Number(info.twap)).toBeCloseTo(1, 3);
+    expect(info.ranges.map((range) => range.liquidity)).toStrictEqual([
+      '0',
+      LIQUIDITY.toString(),
+      '0',
+    ]);
+    expect(Number(info.ranges[1].amount0)).toBeGreaterThan(0);
+    expect(Number(info.ranges[1].amount1)).toBeGreaterThan(0);
+
+    // the liquidity ends about 1% from the price
+    const [, two, five] = info.depth;
+    expect(Number(two.token1In)).toBeGreaterThan(0);
+    expect(two.token1In).toEqual(five.token1In);
+    expect(two.token0In).toEqual(five.token0In);
+  });
+
+  it('getPoolInfo leaves the TWAP out past the observations', async () => {
+    patchPool(30);
+
+    const info = await uniswapLP.getPoolInfo(DAI, USDC, 500, 10, [1], 60);
+    expect(info.twap).toBeUndefined();
+  });
+
+  it('increaseObservationCardinality should work', async () => {
+    patch(uniswapLP, 'getPoolContract', () => {
+      return {
+        increaseObservationCardinalityNext(cardinality: number) {
+          expect(cardinality).toEqual(100);
+          return TX;
+        },
+      };
+    });
+
+    const tx = await uniswapLP.increaseObservationCardinality(
+      wallet,
+      DAI,
+      USDC,
+      500,
+      100,
+      50000,
+      10
+    );
+    expect(tx.hash).toEqual(TX.hash);
+  });
+});
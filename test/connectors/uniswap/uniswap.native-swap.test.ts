@@ -0,0 +1,186 @@
+import {
+  CurrencyAmount,
+  Percent,
+  Token,
+  TradeType,
+} from '@uniswap/sdk-core';
+import { Pair, Route } from '@uniswap/v2-sdk';
+import { SwapRouter, Trade } from '@uniswap/router-sdk';
+import {
+  routerNativeName,
+  toNativeSwap,
+  toNativeTrade,
+} from '../../../src/connectors/uniswap/uniswap.native-swap';
+import { nativeSwapFor } from '../../../src/connectors/uniswap/uniswap.controllers';
+import sushiswapRouter from '../../../src/connectors/sushiswap/sushiswap_router.json';
+import pangolinRouter from '../../../src/connectors/pangolin/IPangolinRouter.json';
+import uniswapV2Router from '../../../src/connectors/uniswap/uniswap_v2_router_abi.json';
+
+const v3RouterAbi =
+  require('@uniswap/v3-periphery/artifacts/contracts/SwapRouter.sol/SwapRouter.json').abi;
+
+const path = [
+  '0xB4FBF271143F4FBf7B91A5ded31805e42b2208d6',
+  '0xdc31Ee1784292379Fbb2964b3B9C4124D8F89C60',
+];
+const to = '0xFaA12FD102FE8623C9299c72B03E45107F2772B5';
+const deadline = '0x63b5d3a0';
+
+describe('routerNativeName', () => {
+  it('reads the native currency name from a V2 router', () => {
+    expect(routerNativeName(sushiswapRouter.abi)).toEqual('ETH');
+    expect(routerNativeName(pangolinRouter.abi)).toEqual('AVAX');
+    // Quickswap's router is a fork of Uniswap's V2 router
+    expect(routerNativeName(uniswapV2Router.abi)).toEqual('ETH');
+  });
+
+  it('is undefined for routers without native swaps', () => {
+    expect(routerNativeName(v3RouterAbi)).toBeUndefined();
+    expect(routerNativeName('')).toBeUndefined();
+  });
+});
+
+describe('toNativeSwap', () => {
+  const exactIn = {
+    methodName: 'swapExactTokensForTokens',
+    args: ['0x64', '0x5a', path, to, deadline],
+    value: '0x0',
+  };
+  const exactOut = {
+    methodName: 'swapTokensForExactTokens',
+    args: ['0x64', '0x6e', path, to, deadline],
+    value: '0x0',
+  };
+
+  it('leaves token to token swaps alone', () => {
+    expect(toNativeSwap(exactIn, sushiswapRouter.abi)).toEqual(exactIn);
+  });
+
+  it('pays the exact input in the native currency', () => {
+    expect(toNativeSwap(exactIn, sushiswapRouter.abi, 'in')).toEqual({
+      methodName: 'swapExactETHForTokens',
+      args: ['0x5a', path, to, deadline],
+      value: '0x64',
+    });
+  });
+
+  it('pays at most the maximum input in the native currency', () => {
+    expect(toNativeSwap(exactOut, pangolinRouter.abi, 'in')).toEqual({
+      methodName: 'swapAVAXForExactTokens',
+      args: ['0x64', path, to, deadline],
+      value: '0x6e',
+    });
+  });
+
+  it('receives the output in the native currency', () => {
+    expect(toNativeSwap(exactIn, sushiswapRouter.abi, 'out')).toEqual({
+      ...exactIn,
+      methodName: 'swapExactTokensForETH',
+    });
+    expect(toNativeSwap(exactOut, sushiswapRouter.abi, 'out')).toEqual({
+      ...exactOut,
+      methodName: 'swapTokensForExactETH',
+    });
+  });
+
+  it('throws for routers without native swaps', () => {
+    expect(() => toNativeSwap(exactIn, v3RouterAbi, 'in')).toThrow();
+  });
+});
+
+describe('toNativeTrade', () => {
+  const WETH = new Token(
+    5,
+    '0xB4FBF271143F4FBf7B91A5ded31805e42b2208d6',
+    18,
+    'WETH'
+  );
+  const DAI = new Token(
+    5,
+    '0xdc31Ee1784292379Fbb2964b3B9C4124D8F89C60',
+    18,
+    'DAI'
+  );
+  const trade = new Trade({
+    v2Routes: [
+      {
+        routev2: new Route(
+          [
+            new Pair(
+              CurrencyAmount.fromRawAmount(WETH, '2000000000000000000'),
+              CurrencyAmount.fromRawAmount(DAI, '1000000000000000000')
+            ),
+          ],
+          WETH,
+          DAI
+        ),
+        inputAmount: CurrencyAmount.fromRawAmount(WETH, '1000000000000000'),
+        outputAmount: CurrencyAmount.fromRawAmount(DAI, '490000000000000'),
+      },
+    ],
+    v3Routes: [],
+    tradeType: TradeType.EXACT_INPUT,
+  });
+  const options = {
+    deadlineOrPreviousBlockhash: 1672860000,
+    recipient: to,
+    slippageTolerance: new Percent(1, 100),
+  };
+
+  it('leaves token to token trades alone', () => {
+    expect(toNativeTrade(trade, 5)).toBe(trade);
+  });
+
+  it('pays the input in the native currency', () => {
+    const native = toNativeTrade(trade, 5, 'in');
+    expect(native.inputAmount.currency.isNative).toBe(true);
+    expect(native.outputAmount.currency).toEqual(DAI);
+    expect(SwapRouter.swapCallParameters(native, options).value).toEqual(
+      '0x038d7ea4c68000'
+    );
+    expect(SwapRouter.swapCallParameters(trade, options).value).toEqual(
+      '0x00'
+    );
+  });
+
+  it('throws on chains without a known wrapped native token', () => {
+    expect(() => toNativeTrade(trade, 99999, 'in')).toThrow();
+  });
+});
+
+describe('nativeSwapFor', () => {
+  const ethereumish: any = {
+    nativeTokenSymbol: 'ETH',
+    wrappedNativeAddress: path[0],
+    storedTokenList: [{ address: path[0], symbol: 'WETH' }],
+  };
+  const req: any = {
+    connector: 'uniswap',
+    base: 'ETH',
+    quote: 'DAI',
+    side: 'SELL',
+    autoWrap: true,
+  };
+
+  it('swaps the native currency on a connector that supports it', () => {
+    const uniswapish: any = {
+      routerAbi: v3RouterAbi,
+      supportsNativeSwap: true,
+    };
+    const result = nativeSwapFor(ethereumish, uniswapish, req);
+    expect(result.req.base).toEqual('WETH');
+    expect(result.nativeSwap).toEqual('in');
+  });
+
+  it('falls back on the router ABI of the V2 connectors', () => {
+    const result = nativeSwapFor(
+      ethereumish,
+      <any>{ routerAbi: sushiswapRouter.abi },
+      req
+    );
+    expect(result.nativeSwap).toEqual('in');
+    expect(() =>
+      nativeSwapFor(ethereumish, <any>{ routerAbi: v3RouterAbi }, req)
+    ).toThrow('cannot swap ETH');
+  });
+});
@@ -0,0 +1,152 @@
+import { Token } from '@uniswap/sdk-core';
+import { FeeAmount, Pool } from '@uniswap/v3-sdk';
+import {
+  candidateRoutes,
+  encodeRoutePath,
+  MAX_CANDIDATE_ROUTES,
+  routeViolation,
+  V3_FEE_TIERS,
+} from '../../../src/connectors/uniswap/uniswap.route';
+
+const WETH = new Token(
+  5,
+  '0xB4FBF271143F4FBf7B91A5ded31805e42b2208d6',
+  18,
+  'WETH'
+);
+const DAI = new Token(
+  5,
+  '0xdc31Ee1784292379Fbb2964b3B9C4124D8F89C60',
+  18,
+  'DAI'
+);
+const USDC = new Token(
+  5,
+  '0x07865c6E87B9F70255377e024ace6630C1Eaa37F',
+  6,
+  'USDC'
+);
+
+describe('verify candidateRoutes', () => {
+  it('should quote every fee tier of the direct pool', () => {
+    const routes = candidateRoutes([WETH, DAI], [], {});
+    expect(routes.map((route) => route.fees)).toStrictEqual(
+      V3_FEE_TIERS.map((fee) => [fee])
+    );
+  });
+
+  it('should only quote the fees given for a path', () => {
+    const routes = candidateRoutes([WETH, USDC, DAI], [], {
+      fees: [FeeAmount.LOW, FeeAmount.LOWEST],
+    });
+    expect(routes).toStrictEqual([
+      { tokens: [WETH, USDC, DAI], fees: [FeeAmount.LOW, FeeAmount.LOWEST] },
+    ]);
+  });
+
+  it('should add a route through each intermediate token', () => {
+    const routes = candidateRoutes([WETH, DAI], [USDC], {});
+    expect(routes.length).toEqual(4 + 16);
+    expect(routes[4].tokens).toStrictEqual([WETH, USDC, DAI]);
+  });
+
+  it('should leave out the routes through an excluded pool', () => {
+    const excluded = Pool.getAddress(WETH, DAI, FeeAmount.MEDIUM);
+    const routes = candidateRoutes([WETH, DAI], [], {
+      excludedPools: [excluded.toLowerCase()],
+    });
+    expect(routes.map((route) => route.fees)).toStrictEqual([
+      [FeeAmount.LOWEST],
+      [FeeAmount.LOW],
+      [FeeAmount.HIGH],
+    ]);
+  });
+
+  it('should reject fees that do not match the hops of the path', () => {
+    expect(() =>
+      candidateRoutes([WETH, USDC, DAI], [], { fees: [FeeAmount.LOW] })
+    ).toThrow('The path has 2 hops but 1 fee tiers were given.');
+  });
+
+  it('should reject a path with too many fee combinations', () => {
+    // 5 hops have 4^5 fee combinations
+    const path = [WETH, USDC, DAI, USDC, DAI, WETH];
+    expect(() => candidateRoutes(path, [], {})).toThrow(
+      `more than ${MAX_CANDIDATE_ROUTES}`
+    );
+    expect(
+      candidateRoutes(path, [], { fees: path.slice(1).map(() => 500) })
+    ).toHaveLength(1);
+  });
+});
+
+describe('verify encodeRoutePath', () => {
+  it('should encode exact output paths from the token out', () => {
+    const route = { tokens: [WETH, DAI], fees: [FeeAmount.LOW] };
+    const fee = '0001f4';
+    expect(encodeRoutePath(route, false)).toEqual(
+      WETH.address.toLowerCase() + fee + DAI.address.slice(2).toLowerCase()
+    );
+    expect(encodeRoutePath(route, true)).toEqual(
+      DAI.address.toLowerCase() + fee + WETH.address.slice(2).toLowerCase()
+    );
+  });
+});
+
+describe('verify routeViolation', () => {
+  const route = [
+    {
+      percent: 100,
+      hops: [
+        {
+          tokenIn: WETH.address,
+          tokenOut: USDC.address,
+          pool: '0x0000000000000000000000000000000000000001',
+          fee: FeeAmount.LOW,
+        },
+        {
+          tokenIn: USDC.address,
+          tokenOut: DAI.address,
+          pool: '0x0000000000000000000000000000000000000002',
+          fee: FeeAmount.LOWEST,
+        },
+      ],
+    },
+  ];
+
+  it('should accept a route that follows the path', () => {
+    expect(
+      routeViolation(route, {
+        path: [WETH.address, USDC.address, DAI.address],
+        fees: [FeeAmount.LOW, FeeAmount.LOWEST],
+      })
+    ).toBeUndefined();
+  });
+
+  it('should reject a route with other fee tiers', () => {
+    expect(
+      routeViolation(route, {
+        path: [WETH.address, USDC.address, DAI.address],
+        fees: [FeeAmount.LOW, FeeAmount.LOW],
+      })
+    ).toContain('is not the path asked for');
+  });
+
+  it('should reject a route through a token that is not allowed', () => {
+    expect(
+      routeViolation(route, { intermediateTokens: [WETH.address] })
+    ).toContain(USDC.address);
+  });
+
+  it('should reject a route through an excluded pool', () => {
+    expect(
+      routeViolation(route, {
+        excludedPools: ['0x0000000000000000000000000000000000000002'],
+      })
+    ).toContain('excluded pool');
+  });
+
+  it('should reject trades that do not report their route', () => {
+    expect(routeViolation(undefined, { excludedPools: [] })).toBeDefined();
+  });
+});
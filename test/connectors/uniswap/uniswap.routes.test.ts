@@ -1,6 +1,8 @@
 import express from 'express';
 import { Express } from 'express-serve-static-core';
 import request from 'supertest';
+import { Token } from '@uniswap/sdk-core';
+import { Pool } from '@uniswap/v3-sdk';
 import { Ethereum } from '../../../src/chains/ethereum/ethereum';
 import { Uniswap } from '../../../src/connectors/uniswap/uniswap';
 import { AmmRoutes } from '../../../src/amm/amm.routes';
@@ -562,6 +564,152 @@ describe('POST /amm/trade', () => {
       .expect(500);
   });
 
+  const WETH = new Token(
+    5,
+    '0xB4FBF271143F4FBf7B91A5ded31805e42b2208d6',
+    18,
+    'WETH'
+  );
+  const DAI = new Token(
+    5,
+    '0xdc31Ee1784292379Fbb2964b3B9C4124D8F89C60',
+    18,
+    'DAI'
+  );
+  const POOL = Pool.getAddress(WETH, DAI, 500);
+
+  // a SELL of WETH for DAI through the 0.05% pool
+  const patchRoutedSell = () => {
+    let routeOptions: any;
+    patch(uniswap, 'estimateSellTrade', (...args: any[]) => {
+      routeOptions = args[4];
+      return {
+        expectedAmount: {
+          toSignificant: () => 100,
+        },
+        trade: {
+          executionPrice: {
+            toSignificant: () => 100,
+            toFixed: () => '100',
+          },
+          inputAmount: { quotient: '10' },
+          swaps: [
+            {
+              inputAmount: { quotient: '10' },
+              route: {
+                pools: [{ token0: DAI, token1: WETH, fee: 500 }],
+                path: [WETH, DAI],
+              },
+            },
+          ],
+        },
+      };
+    });
+    return () => routeOptions;
+  };
+
+  it('should return 200 with the route of a SELL along a path', async () => {
+    patchForSell();
+    const routeOptions = patchRoutedSell();
+    await request(app)
+      .post(`/amm/trade`)
+      .send({
+        chain: 'ethereum',
+        network: 'goerli',
+        connector: 'uniswap',
+        quote: 'DAI',
+        base: 'WETH',
+        amount: '10000',
+        address,
+        side: 'SELL',
+        nonce: 21,
+        path: ['WETH', 'DAI'],
+        fees: ['LOW'],
+      })
+      .set('Accept', 'application/json')
+      .expect(200)
+      .then((res: any) => {
+        expect(routeOptions().path).toStrictEqual([
+          WETH.address,
+          DAI.address,
+        ]);
+        expect(routeOptions().fees).toStrictEqual([500]);
+        expect(res.body.route).toStrictEqual([
+          {
+            percent: 100,
+            hops: [
+              {
+                tokenIn: WETH.address,
+                tokenOut: DAI.address,
+                pool: POOL,
+                fee: 500,
+              },
+            ],
+          },
+        ]);
+      });
+  });
+
+  it('should return 500 when the route goes through an excluded pool', async () => {
+    patchForSell();
+    patchRoutedSell();
+    await request(app)
+      .post(`/amm/trade`)
+      .send({
+        chain: 'ethereum',
+        network: 'goerli',
+        connector: 'uniswap',
+        quote: 'DAI',
+        base: 'WETH',
+        amount: '10000',
+        address,
+        side: 'SELL',
+        nonce: 21,
+        excludedPools: [POOL],
+      })
+      .set('Accept', 'application/json')
+      .expect(500);
+  });
+
+  it('should return 500 for a path that does not end at the quote', async () => {
+    patchForSell();
+    await request(app)
+      .post(`/amm/trade`)
+      .send({
+        chain: 'ethereum',
+        network: 'goerli',
+        connector: 'uniswap',
+        quote: 'DAI',
+        base: 'WETH',
+        amount: '10000',
+        address,
+        side: 'SELL',
+        path: ['DAI', 'WETH'],
+      })
+      .set('Accept', 'application/json')
+      .expect(500);
+  });
+
+  it('should return 404 for an unknown fee tier', async () => {
+    patchInit();
+    await request(app)
+      .post(`/amm/trade`)
+      .send({
+        chain: 'ethereum',
+        network: 'goerli',
+        connector: 'uniswap',
+        quote: 'DAI',
+        base: 'WETH',
+        amount: '10000',
+        address,
+        side: 'SELL',
+        path: ['WETH', 'DAI'],
+        fees: ['CHEAP'],
+      })
+      .set('Accept', 'application/json')
+      .expect(404);
+  });
+
   it('should return 404 when parameters are incorrect', async () => {
     patchInit();
     await request(app)
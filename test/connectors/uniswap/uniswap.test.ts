@@ -154,3 +154,20 @@ describe('getAllowedSlippage', () => {
     expect(allowedSlippage).toEqual(new Percent('2', '100'));
   });
 });
+
+describe('verify Uniswap quoteRoutes', () => {
+  it('should reject a path of more hops than maximumHops', async () => {
+    const path = Array(uniswap.maximumHops + 2).fill(WETH.address);
+    await expect(
+      uniswap.quoteRoutes(
+        CurrencyAmount.fromRawAmount(WETH, '1'),
+        DAI,
+        TradeType.EXACT_INPUT,
+        { path }
+      )
+    ).rejects.toThrow(
+      `The path has ${uniswap.maximumHops + 1} hops, more than the ` +
+        `${uniswap.maximumHops} allowed.`
+    );
+  });
+});
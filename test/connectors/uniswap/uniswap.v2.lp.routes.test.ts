@@ -0,0 +1,261 @@
+import express from 'express';
+import { Express } from 'express-serve-static-core';
+import request from 'supertest';
+import { Ethereum } from '../../../src/chains/ethereum/ethereum';
+import { Sushiswap } from '../../../src/connectors/sushiswap/sushiswap';
+import { UniswapV2LP } from '../../../src/connectors/uniswap/uniswap.v2.lp';
+import { AmmLiquidityRoutes } from '../../../src/amm/amm.routes';
+import { patch, unpatch } from '../../services/patch';
+import { patchEVMNonceManager } from '../../evm.nonce.mock';
+
+let app: Express;
+let ethereum: Ethereum;
+let sushiswap: Sushiswap;
+let lp: UniswapV2LP;
+
+beforeAll(async () => {
+  app = express();
+  app.use(express.json());
+  ethereum = Ethereum.getInstance('goerli');
+  patchEVMNonceManager(ethereum.nonceManager);
+  await ethereum.init();
+
+  sushiswap = Sushiswap.getInstance('ethereum', 'goerli');
+  await sushiswap.init();
+  lp = UniswapV2LP.getInstance(ethereum, sushiswap, 'sushiswap');
+  app.use('/amm/liquidity', AmmLiquidityRoutes.router);
+});
+
+beforeEach(() => {
+  patchEVMNonceManager(ethereum.nonceManager);
+});
+
+afterEach(() => {
+  unpatch();
+});
+
+afterAll(async () => {
+  await ethereum.close();
+});
+
+const address: string = '0xFaA12FD102FE8623C9299c72B03E45107F2772B5';
+
+const patchGetWallet = () => {
+  patch(ethereum, 'getWallet', () => {
+    return {
+      address: '0xFaA12FD102FE8623C9299c72B03E45107F2772B5',
+    };
+  });
+};
+
+const patchInit = () => {
+  patch(sushiswap, 'init', async () => {
+    return;
+  });
+};
+
+const patchGetTokenBySymbol = () => {
+  patch(ethereum, 'getTokenBySymbol', (symbol: string) => {
+    if (symbol === 'WETH') {
+      return {
+        chainId: 5,
+        name: 'WETH',
+        symbol: 'WETH',
+        address: '0xB4FBF271143F4FBf7B91A5ded31805e42b2208d6',
+        decimals: 18,
+      };
+    } else if (symbol === 'DAI') {
+      return {
+        chainId: 5,
+        name: 'DAI',
+        symbol: 'DAI',
+        address: '0xdc31Ee1784292379Fbb2964b3B9C4124D8F89C60',
+        decimals: 18,
+      };
+    }
+    return undefined;
+  });
+};
+
+const patchGasPrice = () => {
+  patch(ethereum, 'gasPrice', () => 100);
+};
+
+describe('POST /liquidity/v2/add', () => {
+  it('should return 200 when all parameter are OK', async () => {
+    patchGetWallet();
+    patchInit();
+    patchGetTokenBySymbol();
+    patchGasPrice();
+    patch(lp, 'addLiquidity', () => {
+      return { nonce: 21, hash: '000000000000000' };
+    });
+
+    await request(app)
+      .post(`/amm/liquidity/v2/add`)
+      .send({
+        address: address,
+        token0: 'WETH',
+        token1: 'DAI',
+        amount0: '1',
+        amount1: '2000',
+        chain: 'ethereum',
+        network: 'goerli',
+        connector: 'sushiswap',
+      })
+      .set('Accept', 'application/json')
+      .expect(200)
+      .then((res: any) => {
+        expect(res.body.token0).toEqual(
+          '0xB4FBF271143F4FBf7B91A5ded31805e42b2208d6'
+        );
+        expect(res.body.amount1).toEqual('2000');
+        expect(res.body.nonce).toEqual(21);
+        expect(res.body.txHash).toEqual('000000000000000');
+      });
+  });
+
+  it('should return 500 for an unsupported token', async () => {
+    patchGetWallet();
+    patchInit();
+    patchGetTokenBySymbol();
+
+    await request(app)
+      .post(`/amm/liquidity/v2/add`)
+      .send({
+        address: address,
+        token0: 'WETH',
+        token1: 'DOGE',
+        amount0: '1',
+        amount1: '2000',
+        chain: 'ethereum',
+        network: 'goerli',
+        connector: 'sushiswap',
+      })
+      .set('Accept', 'application/json')
+      .expect(500);
+  });
+
+  it('should return 500 for a connector without V2 pairs', async () => {
+    await request(app)
+      .post(`/amm/liquidity/v2/add`)
+      .send({
+        address: address,
+        token0: 'WETH',
+        token1: 'DAI',
+        amount0: '1',
+        amount1: '2000',
+        chain: 'ethereum',
+        network: 'goerli',
+        connector: 'uniswapLP',
+      })
+      .set('Accept', 'application/json')
+      .expect(500);
+  });
+
+  it('should return 404 for a malformed slippage', async () => {
+    await request(app)
+      .post(`/amm/liquidity/v2/add`)
+      .send({
+        address: address,
+        token0: 'WETH',
+        token1: 'DAI',
+        amount0: '1',
+        amount1: '2000',
+        allowedSlippage: '1%',
+        chain: 'ethereum',
+        network: 'goerli',
+        connector: 'sushiswap',
+      })
+      .set('Accept', 'application/json')
+      .expect(404);
+  });
+});
+
+describe('POST /liquidity/v2/remove', () => {
+  it('should return 200 with the approval when one was needed', async () => {
+    patchGetWallet();
+    patchInit();
+    patchGetTokenBySymbol();
+    patchGasPrice();
+    patch(lp, 'removeLiquidity', () => {
+      return {
+        approval: { nonce: 21, hash: '000000000000001' },
+        transaction: { nonce: 22, hash: '000000000000002' },
+        liquidity: '5.0',
+      };
+    });
+
+    await request(app)
+      .post(`/amm/liquidity/v2/remove`)
+      .send({
+        address: address,
+        token0: 'WETH',
+        token1: 'DAI',
+        decreasePercent: 50,
+        chain: 'ethereum',
+        network: 'goerli',
+        connector: 'sushiswap',
+      })
+      .set('Accept', 'application/json')
+      .expect(200)
+      .then((res: any) => {
+        expect(res.body.liquidity).toEqual('5.0');
+        expect(res.body.approvalTxHash).toEqual('000000000000001');
+        expect(res.body.nonce).toEqual(22);
+        expect(res.body.txHash).toEqual('000000000000002');
+      });
+  });
+
+  it('should return 404 without the tokens', async () => {
+    await request(app)
+      .post(`/amm/liquidity/v2/remove`)
+      .send({
+        address: address,
+        chain: 'ethereum',
+        network: 'goerli',
+        connector: 'sushiswap',
+      })
+      .set('Accept', 'application/json')
+      .expect(404);
+  });
+});
+
+describe('POST /liquidity/v2/position', () => {
+  it('should return 200 with the position', async () => {
+    patchInit();
+    patchGetTokenBySymbol();
+    patch(lp, 'getPosition', () => {
+      return {
+        pair: '0x397FF1542f962076d0BFE58eA045FfA2d347ACa0',
+        tokenA: 'WETH',
+        tokenB: 'DAI',
+        reserveA: '1.0',
+        reserveB: '2000.0',
+        lpTokenBalance: '10.0',
+        lpTotalSupply: '100.0',
+        poolShare: '0.1',
+        amountA: '0.1',
+        amountB: '200.0',
+      };
+    });
+
+    await request(app)
+      .post(`/amm/liquidity/v2/position`)
+      .send({
+        address: address,
+        token0: 'WETH',
+        token1: 'DAI',
+        chain: 'ethereum',
+        network: 'goerli',
+        connector: 'sushiswap',
+      })
+      .set('Accept', 'application/json')
+      .expect(200)
+      .then((res: any) => {
+        expect(res.body.network).toEqual('goerli');
+        expect(res.body.poolShare).toEqual('0.1');
+        expect(res.body.amountB).toEqual('200.0');
+      });
+  });
+});
@@ -0,0 +1,297 @@
+jest.useFakeTimers();
+import { BigNumber, utils, Wallet } from 'ethers';
+import { Ethereum } from '../../../src/chains/ethereum/ethereum';
+import { Harmony } from '../../../src/chains/harmony/harmony';
+import { Cronos } from '../../../src/chains/cronos/cronos';
+import { Sushiswap } from '../../../src/connectors/sushiswap/sushiswap';
+import { Defikingdoms } from '../../../src/connectors/defikingdoms/defikingdoms';
+import { DefikingdomsConfig } from '../../../src/connectors/defikingdoms/defikingdoms.config';
+import { Defira } from '../../../src/connectors/defira/defira';
+import { DefiraConfig } from '../../../src/connectors/defira/defira.config';
+import { VVSConnector } from '../../../src/connectors/vvs/vvs';
+import { VVSConfig } from '../../../src/connectors/vvs/vvs.config';
+import { MadMeerkat } from '../../../src/connectors/mad_meerkat/mad_meerkat';
+import { MadMeerkatConfig } from '../../../src/connectors/mad_meerkat/mad_meerkat.config';
+import {
+  Ethereumish,
+  Uniswapish,
+} from '../../../src/services/common-interfaces';
+import { UniswapV2LP } from '../../../src/connectors/uniswap/uniswap.v2.lp';
+import { SimulationWallet } from '../../../src/evm/evm.simulation';
+import { patch, unpatch } from '../../services/patch';
+import { patchEVMNonceManager } from '../../evm.nonce.mock';
+
+let ethereum: Ethereum;
+let lp: UniswapV2LP;
+let wallet: Wallet;
+
+const WETH = {
+  chainId: 5,
+  address: '0xB4FBF271143F4FBf7B91A5ded31805e42b2208d6',
+  decimals: 18,
+  symbol: 'WETH',
+  name: 'Wrapped Ether',
+};
+
+const DAI = {
+  chainId: 5,
+  address: '0xdc31Ee1784292379Fbb2964b3B9C4124D8F89C60',
+  decimals: 18,
+  symbol: 'DAI',
+  name: 'Dai Stablecoin',
+};
+
+const PAIR = '0x397FF1542f962076d0BFE58eA045FfA2d347ACa0';
+
+const TX = {
+  nonce: 13,
+  hash: '0x75f98675a8f64dcf14927ccde9a1d59b67fa09b72cc2642ad055dae4074853d9', // noqa: mock
+};
+
+beforeAll(async () => {
+  ethereum = Ethereum.getInstance('goerli');
+  patchEVMNonceManager(ethereum.nonceManager);
+  await ethereum.init();
+
+  wallet = new Wallet(
+    '0000000000000000000000000000000000000000000000000000000000000002', // noqa: mock
+    ethereum.provider
+  );
+  const sushiswap = Sushiswap.getInstance('ethereum', 'goerli');
+  await sushiswap.init();
+  lp = UniswapV2LP.getInstance(ethereum, sushiswap, 'sushiswap');
+});
+
+beforeEach(() => {
+  patchEVMNonceManager(ethereum.nonceManager);
+});
+
+afterEach(() => {
+  unpatch();
+  jest.restoreAllMocks();
+});
+
+afterAll(async () => {
+  await ethereum.close();
+});
+
+// DAI sorts before WETH, 2000 DAI and 1 WETH for 100 LP tokens
+const patchPair = (balance: string, allowance: string = '0') => {
+  patch(lp, 'getPairAddress', async () => PAIR);
+  patch(lp, 'getPairContract', () => {
+    return {
+      token0: async () => DAI.address,
+      getReserves: async () => [
+        utils.parseUnits('2000', 18),
+        utils.parseUnits('1', 18),
+        0,
+      ],
+      totalSupply: async () => utils.parseUnits('100', 18),
+      balanceOf: async () => utils.parseUnits(balance, 18),
+      allowance: async () => utils.parseUnits(allowance, 18),
+    };
+  });
+};
+
+describe('verify UniswapV2LP position', () => {
+  it('should report the share and underlying reserves of a wallet', async () => {
+    patchPair('10');
+
+    const position = await lp.getPosition(wallet.address, WETH, DAI);
+
+    expect(position.pair).toEqual(PAIR);
+    expect(position.reserveA).toEqual('1.0');
+    expect(position.reserveB).toEqual('2000.0');
+    expect(position.lpTokenBalance).toEqual('10.0');
+    expect(position.poolShare).toEqual('0.1');
+    expect(position.amountA).toEqual('0.1');
+    expect(position.amountB).toEqual('200.0');
+  });
+});
+
+// the allowance of the router for every token
+const patchAllowance = (allowance: string) => {
+  patch(ethereum, 'getContract', () => {
+    return { allowance: async () => utils.parseUnits(allowance, 18) };
+  });
+};
+
+describe('verify UniswapV2LP liquidity', () => {
+  it('should add liquidity with minimums within the slippage', async () => {
+    patchAllowance('2000');
+    let args: any[] = [];
+    patch(lp, 'getRouterContract', () => {
+      return {
+        addLiquidity: async (...a: any[]) => {
+          args = a;
+          return TX;
+        },
+      };
+    });
+
+    const tx = await lp.addLiquidity(
+      wallet,
+      WETH,
+      DAI,
+      '1',
+      '2000',
+      300000,
+      20,
+      undefined,
+      undefined,
+      undefined,
+      '1/100'
+    );
+
+    expect(tx.hash).toEqual(TX.hash);
+    expect(args.slice(0, 2)).toStrictEqual([WETH.address, DAI.address]);
+    expect(args[4]).toEqual(utils.parseUnits('0.99', 18));
+    expect(args[5]).toEqual(utils.parseUnits('1980', 18));
+    expect(args[6]).toEqual(wallet.address);
+  });
+
+  it('should fail to add liquidity the router may not spend', async () => {
+    patchAllowance('1');
+    patch(lp, 'getRouterContract', () => {
+      return { addLiquidity: async () => TX };
+    });
+
+    await expect(
+      lp.addLiquidity(wallet, WETH, DAI, '1', '2000', 300000, 20)
+    ).rejects.toThrow('to spend 2000.0 DAI');
+  });
+
+  it('should approve the LP tokens before removing them', async () => {
+    patchPair('10');
+    let approved = BigNumber.from(0);
+    patch(ethereum, 'approveERC20', async (...a: any[]) => {
+      approved = a[3];
+      return { ...TX, nonce: 12 };
+    });
+    let args: any[] = [];
+    patch(lp, 'getRouterContract', () => {
+      return {
+        removeLiquidity: async (...a: any[]) => {
+          args = a;
+          return TX;
+        },
+      };
+    });
+
+    const removed = await lp.removeLiquidity(
+      wallet,
+      WETH,
+      DAI,
+      50,
+      300000,
+      20,
+      undefined,
+      undefined,
+      undefined,
+      '1/100'
+    );
+
+    expect(removed.approval?.nonce).toEqual(12);
+    expect(removed.transaction.hash).toEqual(TX.hash);
+    expect(removed.liquidity).toEqual('5.0');
+    expect(approved).toEqual(utils.parseUnits('5', 18));
+    expect(args[2]).toEqual(utils.parseUnits('5', 18));
+    expect(args[3]).toEqual(utils.parseUnits('0.0495', 18));
+    expect(args[4]).toEqual(utils.parseUnits('99', 18));
+  });
+
+  it('should not approve again when the allowance covers the removal', async () => {
+    patchPair('10', '10');
+    patch(lp, 'getRouterContract', () => {
+      return { removeLiquidity: async () => TX };
+    });
+
+    const removed = await lp.removeLiquidity(
+      wallet,
+      WETH,
+      DAI,
+      100,
+      300000,
+      20
+    );
+
+    expect(removed.approval).toBeUndefined();
+    expect(removed.liquidity).toEqual('10.0');
+  });
+
+  it('should fail to simulate a removal of LP tokens not approved', async () => {
+    patchPair('10');
+    const simulationWallet = new SimulationWallet(wallet, 5000, 13, false);
+
+    await expect(
+      lp.removeLiquidity(simulationWallet, WETH, DAI, 50, 300000, 20)
+    ).rejects.toThrow('to spend 5.0 WETH-DAI LP tokens');
+  });
+
+  it('should fail when the wallet has no liquidity', async () => {
+    patchPair('0');
+
+    await expect(
+      lp.removeLiquidity(wallet, WETH, DAI, 100, 300000, 20)
+    ).rejects.toThrow('holds no WETH-DAI liquidity');
+  });
+});
+
+describe('verify UniswapV2LP of the V2 forks', () => {
+  const FACTORY = '0x5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f';
+
+  it.each<[string, () => Ethereumish, () => Uniswapish, string]>([
+    [
+      'defikingdoms',
+      () => Harmony.getInstance('mainnet'),
+      () => Defikingdoms.getInstance('harmony', 'mainnet'),
+      DefikingdomsConfig.config.routerAddress('mainnet'),
+    ],
+    [
+      'defira',
+      () => Harmony.getInstance('mainnet'),
+      () => Defira.getInstance('harmony', 'mainnet'),
+      DefiraConfig.config.routerAddress('mainnet'),
+    ],
+    [
+      'vvs',
+      () => Cronos.getInstance('mainnet'),
+      () => VVSConnector.getInstance('cronos', 'mainnet'),
+      VVSConfig.config.routerAddress('mainnet'),
+    ],
+    [
+      'mad_meerkat',
+      () => Cronos.getInstance('mainnet'),
+      () => MadMeerkat.getInstance('cronos', 'mainnet'),
+      MadMeerkatConfig.config.routerAddress('mainnet'),
+    ],
+  ])(
+    'should look up the pairs of %s through its router and factory',
+    async (name, getChain, getConnector, router) => {
+      const chain = getChain();
+      const forkLP = UniswapV2LP.getInstance(chain, getConnector(), name);
+      // the router answers factory() and the factory getPair()
+      const called: string[] = [];
+      jest
+        .spyOn(chain.provider, 'call')
+        .mockImplementation(async (tx: any) => {
+          const to: string = await tx.to;
+          called.push(to.toLowerCase());
+          return utils.defaultAbiCoder.encode(
+            ['address'],
+            [to.toLowerCase() === router.toLowerCase() ? FACTORY : PAIR]
+          );
+        });
+
+      expect(forkLP.router).toEqual(router);
+      expect(forkLP.getRouterContract(chain.provider).address).toEqual(
+        router
+      );
+      expect(await forkLP.getPairAddress(WETH, DAI)).toEqual(PAIR);
+      expect(called).toStrictEqual([
+        router.toLowerCase(),
+        FACTORY.toLowerCase(),
+      ]);
+    }
+  );
+});
@@ -0,0 +1,247 @@
+import {
+  BigNumber,
+  constants,
+  Contract,
+  ContractFactory,
+  providers,
+  utils,
+  Wallet,
+} from 'ethers';
+import {
+  encodeSqrtRatioX96,
+  FACTORY_ADDRESS,
+  FeeAmount,
+  nearestUsableTick,
+  TICK_SPACINGS,
+  TickMath,
+} from '@uniswap/v3-sdk';
+import { TokenInfo } from '../../src/chains/ethereum/ethereum-base';
+
+// contracts are deployed from the build artifacts shipped with the packages,
+// so the fixtures need neither a compiler nor a network connection
+const ERC20 = require('@uniswap/v2-core/build/ERC20.json');
+const UniswapV2Factory = require('@uniswap/v2-core/build/UniswapV2Factory.json');
+const UniswapV2Router02 = require('@uniswap/v2-periphery/build/UniswapV2Router02.json');
+const UniswapV3Factory = require('@uniswap/v3-core/artifacts/contracts/UniswapV3Factory.sol/UniswapV3Factory.json');
+const NonfungiblePositionManager = require('@uniswap/v3-periphery/artifacts/contracts/NonfungiblePositionManager.sol/NonfungiblePositionManager.json');
+const SwapRouter02 = require('@uniswap/swap-router-contracts/artifacts/contracts/SwapRouter02.sol/SwapRouter02.json');
+
+// the first two accounts of the default hardhat mnemonic, funded with 10000
+// ETH at genesis
+export const DEPLOYER_PRIVATE_KEY =
+  '0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80'; // noqa: mock
+export const WALLET_PRIVATE_KEY =
+  '0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d'; // noqa: mock
+
+export const FIXTURE_FEE = FeeAmount.MEDIUM;
+
+// every fixture token has 18 decimals, the v2-core ERC20 fixture can't do
+// otherwise
+const TOKENS = [
+  { symbol: 'WETH', name: 'Wrapped Ether', walletBalance: '10' },
+  { symbol: 'DAI', name: 'Dai Stablecoin', walletBalance: '20000' },
+  { symbol: 'USDC', name: 'USD Coin', walletBalance: '20000' },
+];
+
+// full range V3 positions and V2 pairs, which set the price to the ratio of
+// the amounts
+const POOLS = [
+  { tokens: ['WETH', 'DAI'], amounts: ['100', '200000'] },
+  { tokens: ['DAI', 'USDC'], amounts: ['100000', '100000'] },
+];
+
+export interface ForkFixtures {
+  chainId: number;
+  deployer: string;
+  wallet: string;
+  factory: string;
+  nftManager: string;
+  router: string;
+  v2Factory: string;
+  v2Router: string;
+  tokens: TokenInfo[];
+}
+
+async function deploy(
+  deployer: Wallet,
+  artifact: any,
+  ...args: any[]
+): Promise<Contract> {
+  const bytecode = artifact.bytecode || artifact.evm.bytecode.object;
+  const contract = await new ContractFactory(
+    artifact.abi,
+    bytecode,
+    deployer
+  ).deploy(...args);
+  await contract.deployed();
+  return contract;
+}
+
+// The SDKs compute pool addresses from the canonical factory address, so the
+// factory's code is moved there. NoDelegateCall keeps the deployment address
+// in an immutable, which has to follow.
+async function deployCanonicalFactory(
+  provider: providers.JsonRpcProvider,
+  deployer: Wallet
+): Promise<Contract> {
+  const deployed = await deploy(deployer, UniswapV3Factory);
+  const code: string = await provider.getCode(deployed.address);
+  await provider.send('hardhat_setCode', [
+    FACTORY_ADDRESS,
+    code
+      .split(deployed.address.slice(2).toLowerCase())
+      .join(FACTORY_ADDRESS.slice(2).toLowerCase()),
+  ]);
+
+  // the constructor didn't run at the canonical address, so the owner is the
+  // zero address and no fee tiers are enabled
+  await provider.send('hardhat_impersonateAccount', [constants.AddressZero]);
+  await provider.send('hardhat_setBalance', [
+    constants.AddressZero,
+    utils.hexValue(utils.parseEther('1')),
+  ]);
+  const factory = new Contract(
+    FACTORY_ADDRESS,
+    UniswapV3Factory.abi,
+    provider.getSigner(constants.AddressZero)
+  );
+  await (await factory.setOwner(deployer.address)).wait();
+  await provider.send('hardhat_stopImpersonatingAccount', [
+    constants.AddressZero,
+  ]);
+
+  const owned = factory.connect(deployer);
+  for (const fee of [FeeAmount.LOW, FeeAmount.MEDIUM, FeeAmount.HIGH]) {
+    await (await owned.enableFeeAmount(fee, TICK_SPACINGS[fee])).wait();
+  }
+  return owned;
+}
+
+/**
+ * Deploys the Uniswap V3 factory, position manager and router, the Uniswap V2
+ * factory and router, the fixture tokens and their V3 pools and V2 pairs, and
+ * funds the test wallet with tokens.
+ */
+export async function deployFixtures(
+  provider: providers.JsonRpcProvider
+): Promise<ForkFixtures> {
+  const { chainId } = await provider.getNetwork();
+  const deployer = new Wallet(DEPLOYER_PRIVATE_KEY, provider);
+  const wallet = new Wallet(WALLET_PRIVATE_KEY, provider);
+
+  const tokens: Record<string, Contract> = {};
+  for (const token of TOKENS) {
+    tokens[token.symbol] = await deploy(
+      deployer,
+      ERC20,
+      utils.parseEther('1000000000')
+    );
+    await (
+      await tokens[token.symbol].transfer(
+        wallet.address,
+        utils.parseEther(token.walletBalance)
+      )
+    ).wait();
+  }
+
+  const factory = await deployCanonicalFactory(provider, deployer);
+  const nftManager = await deploy(
+    deployer,
+    NonfungiblePositionManager,
+    factory.address,
+    tokens.WETH.address,
+    constants.AddressZero // token descriptor, only used by tokenURI
+  );
+  // the V2 router computes pair addresses from the init code hash of the
+  // v2-core pair, which the factory deploys
+  const v2Factory = await deploy(deployer, UniswapV2Factory, deployer.address);
+  const v2Router = await deploy(
+    deployer,
+    UniswapV2Router02,
+    v2Factory.address,
+    tokens.WETH.address
+  );
+  const router = await deploy(
+    deployer,
+    SwapRouter02,
+    v2Factory.address,
+    factory.address,
+    nftManager.address,
+    tokens.WETH.address
+  );
+
+  for (const token of Object.values(tokens)) {
+    for (const spender of [nftManager, v2Router]) {
+      await (await token.approve(spender.address, constants.MaxUint256)).wait();
+    }
+  }
+  const tickSpacing = TICK_SPACINGS[FIXTURE_FEE];
+  for (const pool of POOLS) {
+    let [token0, token1] = pool.tokens.map((symbol) => tokens[symbol]);
+    let [amount0, amount1] = pool.amounts.map((amount) =>
+      utils.parseEther(amount)
+    );
+    if (BigNumber.from(token0.address).gt(token1.address)) {
+      [token0, token1] = [token1, token0];
+      [amount0, amount1] = [amount1, amount0];
+    }
+    await (
+      await nftManager.createAndInitializePoolIfNecessary(
+        token0.address,
+        token1.address,
+        FIXTURE_FEE,
+        encodeSqrtRatioX96(
+          amount1.toString(),
+          amount0.toString()
+        ).toString()
+      )
+    ).wait();
+    await (
+      await nftManager.mint({
+        token0: token0.address,
+        token1: token1.address,
+        fee: FIXTURE_FEE,
+        tickLower: nearestUsableTick(TickMath.MIN_TICK, tickSpacing),
+        tickUpper: nearestUsableTick(TickMath.MAX_TICK, tickSpacing),
+        amount0Desired: amount0,
+        amount1Desired: amount1,
+        amount0Min: 0,
+        amount1Min: 0,
+        recipient: deployer.address,
+        deadline: constants.MaxUint256,
+      })
+    ).wait();
+    await (
+      await v2Router.addLiquidity(
+        token0.address,
+        token1.address,
+        amount0,
+        amount1,
+        0,
+        0,
+        deployer.address,
+        constants.MaxUint256
+      )
+    ).wait();
+  }
+
+  return {
+    chainId,
+    deployer: deployer.address,
+    wallet: wallet.address,
+    factory: factory.address,
+    nftManager: nftManager.address,
+    router: router.address,
+    v2Factory: v2Factory.address,
+    v2Router: v2Router.address,
+    tokens: TOKENS.map((token) => {
+      return {
+        chainId,
+        address: tokens[token.symbol].address,
+        name: token.name,
+        symbol: token.symbol,
+        decimals: 18,
+      };
+    }),
+  };
+}
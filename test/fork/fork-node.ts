@@ -0,0 +1,150 @@
+import { ChildProcess, spawn } from 'child_process';
+import fs from 'fs';
+import os from 'os';
+import path from 'path';
+import { providers } from 'ethers';
+import { ConfigManagerV2 } from '../../src/services/config-manager-v2';
+import { deployFixtures, ForkFixtures } from './fixtures';
+
+export const FORK_NETWORK = 'local';
+
+const HARDHAT_CONFIG = path.join(__dirname, 'hardhat.config.js');
+const HARDHAT_BIN = path.join(
+  __dirname,
+  '..',
+  '..',
+  'node_modules',
+  '.bin',
+  'hardhat'
+);
+const DEFAULT_PORT = 18545;
+const STARTUP_TIMEOUT = 30000;
+
+/**
+ * A local hardhat node for the connector integration tests. It runs offline:
+ * the fixtures are deployed on the fresh node on every run, see
+ * test/fork/README.md.
+ */
+export class ForkNode {
+  readonly url: string;
+  readonly provider: providers.StaticJsonRpcProvider;
+  private _process: ChildProcess;
+
+  private constructor(url: string, nodeProcess: ChildProcess) {
+    this.url = url;
+    this._process = nodeProcess;
+    this.provider = new providers.StaticJsonRpcProvider(url);
+  }
+
+  public static async start(
+    port: number = Number(process.env.FORK_NODE_PORT || DEFAULT_PORT)
+  ): Promise<ForkNode> {
+    const nodeProcess = spawn(
+      HARDHAT_BIN,
+      [
+        'node',
+        '--config',
+        HARDHAT_CONFIG,
+        '--hostname',
+        '127.0.0.1',
+        '--port',
+        String(port),
+      ],
+      {
+        env: { ...process.env, HARDHAT_DISABLE_TELEMETRY_PROMPT: 'true' },
+        stdio: 'ignore',
+      }
+    );
+    const node = new ForkNode(`http://127.0.0.1:${port}`, nodeProcess);
+    await node.waitUntilReady();
+    return node;
+  }
+
+  private async waitUntilReady(): Promise<void> {
+    const deadline = Date.now() + STARTUP_TIMEOUT;
+    for (;;) {
+      if (this._process.exitCode !== null) {
+        throw new Error(
+          `The hardhat node exited with code ${this._process.exitCode}.`
+        );
+      }
+      try {
+        await this.provider.send('eth_chainId', []);
+        return;
+      } catch (e) {
+        if (Date.now() > deadline) {
+          this.stop();
+          throw new Error(`The hardhat node didn't start at ${this.url}.`);
+        }
+        await new Promise((resolve) => setTimeout(resolve, 250));
+      }
+    }
+  }
+
+  public stop(): void {
+    this._process.kill();
+  }
+
+  /**
+   * Deploys the fixtures on the node, which must be fresh.
+   */
+  public async deployFixtures(): Promise<ForkFixtures> {
+    return await deployFixtures(this.provider);
+  }
+
+  public async takeSnapshot(): Promise<string> {
+    return await this.provider.send('evm_snapshot', []);
+  }
+
+  public async revertToSnapshot(id: string): Promise<void> {
+    await this.provider.send('evm_revert', [id]);
+  }
+}
+
+/**
+ * Adds the node as the `local` ethereum network, with the fixture tokens, the
+ * Uniswap contracts and the V2 router as Sushiswap's. Returns a function that
+ * removes it from the configuration again.
+ */
+export function useForkNetwork(
+  node: ForkNode,
+  fixtures: ForkFixtures
+): () => void {
+  const config = ConfigManagerV2.getInstance();
+  const ethereumNetworks = config.get('ethereum.networks');
+  const uniswapAddresses = config.get('uniswap.contractAddresses');
+  const sushiswapAddresses = config.get(
+    'sushiswap.contractAddresses.ethereum'
+  );
+
+  const tokenListSource = path.join(
+    fs.mkdtempSync(path.join(os.tmpdir(), 'gateway-fork-')),
+    'tokens.json'
+  );
+  fs.writeFileSync(
+    tokenListSource,
+    JSON.stringify({ tokens: fixtures.tokens }, null, 2)
+  );
+
+  config.set(`ethereum.networks.${FORK_NETWORK}`, {
+    chainID: fixtures.chainId,
+    nodeURL: node.url,
+    tokenListType: 'FILE',
+    tokenListSource,
+    nativeCurrencySymbol: 'ETH',
+  });
+  config.set(`uniswap.contractAddresses.${FORK_NETWORK}`, {
+    uniswapV3SmartOrderRouterAddress: fixtures.router,
+    uniswapV3NftManagerAddress: fixtures.nftManager,
+  });
+  config.set(`sushiswap.contractAddresses.ethereum.${FORK_NETWORK}`, {
+    sushiswapRouterAddress: fixtures.v2Router,
+  });
+
+  return () => {
+    config.set('ethereum.networks', ethereumNetworks);
+    config.set('uniswap.contractAddresses', uniswapAddresses);
+    config.set('sushiswap.contractAddresses.ethereum', sushiswapAddresses);
+    fs.rmSync(path.dirname(tokenListSource), { recursive: true, force: true });
+  };
+}
@@ -0,0 +1,132 @@
+import { Contract, providers } from 'ethers';
+import { CurrencyAmount, Token, TradeType } from '@uniswap/sdk-core';
+import { Trade as RouterTrade } from '@uniswap/router-sdk';
+import {
+  nearestUsableTick,
+  Pool,
+  Route,
+  TICK_SPACINGS,
+  TickMath,
+  Trade,
+} from '@uniswap/v3-sdk';
+import { SwapToRatioStatus } from '@uniswap/smart-order-router';
+import {
+  CurrencyAmount as SushiswapCurrencyAmount,
+  Pair as SushiswapPair,
+  Token as SushiswapToken,
+} from '@sushiswap/sdk';
+import { FIXTURE_FEE } from './fixtures';
+
+const UniswapV3Pool = require('@uniswap/v3-core/artifacts/contracts/UniswapV3Pool.sol/UniswapV3Pool.json');
+const IUniswapV2Pair = require('@uniswap/v2-core/build/IUniswapV2Pair.json');
+
+const V2_FACTORY_ABI = [
+  'function getPair(address tokenA, address tokenB) view returns (address pair)',
+];
+
+// The AlphaRouter finds routes through the routing API and subgraphs, which
+// aren't reachable offline. These stand-ins route through the fixture pools
+// read from the node instead; the transactions are built and sent by the
+// connectors as usual. Sushiswap computes pair addresses from its own
+// factory and init code hash, so its pairs are looked up from the fixture V2
+// factory instead.
+
+async function loadPool(
+  provider: providers.Provider,
+  tokenA: Token,
+  tokenB: Token
+): Promise<Pool> {
+  const contract = new Contract(
+    Pool.getAddress(tokenA, tokenB, FIXTURE_FEE),
+    UniswapV3Pool.abi,
+    provider
+  );
+  const tickSpacing = TICK_SPACINGS[FIXTURE_FEE];
+  const minTick = nearestUsableTick(TickMath.MIN_TICK, tickSpacing);
+  const maxTick = nearestUsableTick(TickMath.MAX_TICK, tickSpacing);
+  const [slot0, liquidity, lower, upper] = await Promise.all([
+    contract.slot0(),
+    contract.liquidity(),
+    contract.ticks(minTick),
+    contract.ticks(maxTick),
+  ]);
+  // the fixture pools only hold full range liquidity
+  return new Pool(
+    tokenA,
+    tokenB,
+    FIXTURE_FEE,
+    slot0.sqrtPriceX96.toString(),
+    liquidity.toString(),
+    slot0.tick,
+    [
+      {
+        index: minTick,
+        liquidityNet: lower.liquidityNet.toString(),
+        liquidityGross: lower.liquidityGross.toString(),
+      },
+      {
+        index: maxTick,
+        liquidityNet: upper.liquidityNet.toString(),
+        liquidityGross: upper.liquidityGross.toString(),
+      },
+    ]
+  );
+}
+
+// stands in for AlphaRouter.route, with the same arguments
+export async function routeThroughFixturePool(
+  provider: providers.Provider,
+  amount: CurrencyAmount<Token>,
+  quoteToken: Token,
+  tradeType: TradeType
+): Promise<{ trade: RouterTrade<Token, Token, TradeType> }> {
+  const [tokenIn, tokenOut] =
+    tradeType === TradeType.EXACT_INPUT
+      ? [amount.currency, quoteToken]
+      : [quoteToken, amount.currency];
+  const pool = await loadPool(provider, tokenIn, tokenOut);
+  const route = new Route([pool], tokenIn, tokenOut);
+  const trade = await Trade.fromRoute(route, amount, tradeType);
+  return {
+    trade: new RouterTrade({
+      v2Routes: [],
+      v3Routes: [
+        {
+          routev3: route,
+          inputAmount: trade.inputAmount,
+          outputAmount: trade.outputAmount,
+        },
+      ],
+      tradeType,
+    }),
+  };
+}
+
+// stands in for AlphaRouter.routeToRatio, liquidity is added in the ratio
+// of the amounts given
+export async function noSwapToRatio(): Promise<{ status: SwapToRatioStatus }> {
+  return { status: SwapToRatioStatus.NO_SWAP_NEEDED };
+}
+
+// stands in for Sushiswap.fetchData, with the same arguments
+export async function fetchFixturePair(
+  provider: providers.Provider,
+  v2Factory: string,
+  baseToken: SushiswapToken,
+  quoteToken: SushiswapToken
+): Promise<SushiswapPair> {
+  const factory = new Contract(v2Factory, V2_FACTORY_ABI, provider);
+  const pair = new Contract(
+    await factory.getPair(baseToken.address, quoteToken.address),
+    IUniswapV2Pair.abi,
+    provider
+  );
+  const [reserves0, reserves1] = await pair.getReserves();
+  const [baseReserves, quoteReserves] = baseToken.sortsBefore(quoteToken)
+    ? [reserves0, reserves1]
+    : [reserves1, reserves0];
+  return new SushiswapPair(
+    SushiswapCurrencyAmount.fromRawAmount(baseToken, baseReserves.toString()),
+    SushiswapCurrencyAmount.fromRawAmount(quoteToken, quoteReserves.toString())
+  );
+}
@@ -0,0 +1,19 @@
+// Hardhat network used by the forked-chain tests, see test/fork/README.md.
+// The chain id is mainnet's so that the Uniswap SDKs, which only know the
+// networks Uniswap is deployed on, accept it.
+const os = require('os');
+const path = require('path');
+
+module.exports = {
+  networks: {
+    hardhat: {
+      chainId: 1,
+      initialBaseFeePerGas: 1000000000,
+      mining: { auto: true },
+      loggingEnabled: false,
+    },
+  },
+  paths: {
+    cache: path.join(os.tmpdir(), 'gateway-fork-node'),
+  },
+};
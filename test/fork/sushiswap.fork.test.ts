@@ -0,0 +1,167 @@
+import 'jest-extended';
+import { Wallet } from 'ethers';
+import { Ethereum } from '../../src/chains/ethereum/ethereum';
+import { Sushiswap } from '../../src/connectors/sushiswap/sushiswap';
+import { UniswapV2LP } from '../../src/connectors/uniswap/uniswap.v2.lp';
+import {
+  approve,
+  balances,
+  poll,
+} from '../../src/chains/ethereum/ethereum.controllers';
+import {
+  trade,
+  v2AddLiquidity,
+  v2Position,
+  v2RemoveLiquidity,
+} from '../../src/connectors/uniswap/uniswap.controllers';
+import { patch, unpatch } from '../services/patch';
+import { FORK_NETWORK, ForkNode, useForkNetwork } from './fork-node';
+import { ForkFixtures, WALLET_PRIVATE_KEY } from './fixtures';
+import { fetchFixturePair } from './fork-routing';
+
+jest.setTimeout(120000);
+
+let node: ForkNode;
+let fixtures: ForkFixtures;
+let removeForkNetwork: () => void;
+let eth: Ethereum;
+let sushiswap: Sushiswap;
+let sushiswapLP: UniswapV2LP;
+let wallet: Wallet;
+
+const network = { chain: 'ethereum', network: FORK_NETWORK };
+
+beforeAll(async () => {
+  node = await ForkNode.start();
+  fixtures = await node.deployFixtures();
+  removeForkNetwork = useForkNetwork(node, fixtures);
+
+  eth = Ethereum.getInstance(FORK_NETWORK);
+  await eth.init();
+  sushiswap = Sushiswap.getInstance('ethereum', FORK_NETWORK);
+  await sushiswap.init();
+  sushiswapLP = UniswapV2LP.getInstance(eth, sushiswap, 'sushiswap');
+
+  wallet = new Wallet(WALLET_PRIVATE_KEY, eth.provider);
+});
+
+beforeEach(() => {
+  // the wallet isn't in the gateway's encrypted wallet store
+  patch(eth, 'getWallet', () => wallet);
+  patch(sushiswap, 'fetchData', (...args: any[]) =>
+    fetchFixturePair(eth.provider, fixtures.v2Factory, args[0], args[1])
+  );
+});
+
+afterEach(() => {
+  unpatch();
+});
+
+afterAll(async () => {
+  await eth.close();
+  removeForkNetwork();
+  node.stop();
+});
+
+// waits for the transaction through the poll route, the node mines it right
+// away
+const confirmed = async (txHash: string) => {
+  const result = await poll(eth, { ...network, txHash });
+  expect(result.txStatus).toEqual(1);
+  expect(result.txBlock).toBeGreaterThan(0);
+  return result;
+};
+
+const approveRouter = async (tokens: string[]) => {
+  for (const token of tokens) {
+    const result = await approve(eth, {
+      ...network,
+      address: wallet.address,
+      spender: 'sushiswap',
+      token,
+    });
+    await confirmed(result.approval?.hash as string);
+  }
+};
+
+describe('a V2 style router on a local chain', () => {
+  it('sells WETH for DAI through the router', async () => {
+    await approveRouter(['WETH']);
+    const before: any = await balances(eth, {
+      ...network,
+      address: wallet.address,
+      tokenSymbols: ['WETH', 'DAI'],
+    });
+
+    const result = await trade(eth, sushiswap, {
+      ...network,
+      connector: 'sushiswap',
+      address: wallet.address,
+      base: 'WETH',
+      quote: 'DAI',
+      amount: '1',
+      side: 'SELL',
+    });
+    const receipt = await confirmed(result.txHash);
+    expect(receipt.txReceipt?.to).toEqual(fixtures.v2Router);
+
+    const after: any = await balances(eth, {
+      ...network,
+      address: wallet.address,
+      tokenSymbols: ['WETH', 'DAI'],
+    });
+    const sold = Number(before.balances.WETH) - Number(after.balances.WETH);
+    const bought = Number(after.balances.DAI) - Number(before.balances.DAI);
+    expect(sold).toEqual(1);
+    // 100 WETH against 200000 DAI, less the fee and the price impact
+    expect(bought).toBeWithin(1950, 2000);
+    expect(bought).toBeGreaterThanOrEqual(Number(result.expectedOut));
+  });
+
+  it('adds liquidity to the DAI-USDC pair and removes it', async () => {
+    await approveRouter(['DAI', 'USDC']);
+
+    const added = await v2AddLiquidity(eth, sushiswapLP, {
+      ...network,
+      connector: 'sushiswap',
+      address: wallet.address,
+      token0: 'DAI',
+      token1: 'USDC',
+      amount0: '100',
+      amount1: '100',
+    });
+    await confirmed(added.txHash as string);
+
+    const position = await v2Position(eth, sushiswapLP, {
+      ...network,
+      connector: 'sushiswap',
+      address: wallet.address,
+      token0: 'DAI',
+      token1: 'USDC',
+    });
+    // the fixtures put 100000 of each token in the pair
+    expect(Number(position.lpTokenBalance)).toBeGreaterThan(0);
+    expect(Number(position.amountA)).toBeWithin(99.9, 100.0001);
+    expect(Number(position.amountB)).toBeWithin(99.9, 100.0001);
+
+    const removed = await v2RemoveLiquidity(eth, sushiswapLP, {
+      ...network,
+      connector: 'sushiswap',
+      address: wallet.address,
+      token0: 'DAI',
+      token1: 'USDC',
+    });
+    if (removed.approvalTxHash) await confirmed(removed.approvalTxHash);
+    await confirmed(removed.txHash as string);
+    expect(removed.liquidity).toEqual(position.lpTokenBalance);
+
+    const after = await v2Position(eth, sushiswapLP, {
+      ...network,
+      connector: 'sushiswap',
+      address: wallet.address,
+      token0: 'DAI',
+      token1: 'USDC',
+    });
+    expect(Number(after.lpTokenBalance)).toEqual(0);
+  });
+});
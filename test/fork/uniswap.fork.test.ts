@@ -0,0 +1,185 @@
+import 'jest-extended';
+import { Contract, Wallet } from 'ethers';
+import { Ethereum } from '../../src/chains/ethereum/ethereum';
+import { Uniswap } from '../../src/connectors/uniswap/uniswap';
+import { UniswapLP } from '../../src/connectors/uniswap/uniswap.lp';
+import {
+  allowances,
+  approve,
+  balances,
+  poll,
+} from '../../src/chains/ethereum/ethereum.controllers';
+import {
+  addLiquidity,
+  price,
+  trade,
+} from '../../src/connectors/uniswap/uniswap.controllers';
+import { patch, unpatch } from '../services/patch';
+import { FORK_NETWORK, ForkNode, useForkNetwork } from './fork-node';
+import { ForkFixtures, WALLET_PRIVATE_KEY } from './fixtures';
+import { noSwapToRatio, routeThroughFixturePool } from './fork-routing';
+
+jest.setTimeout(120000);
+
+let node: ForkNode;
+let fixtures: ForkFixtures;
+let removeForkNetwork: () => void;
+let eth: Ethereum;
+let uniswap: Uniswap;
+let uniswapLP: UniswapLP;
+let wallet: Wallet;
+
+const network = { chain: 'ethereum', network: FORK_NETWORK };
+
+beforeAll(async () => {
+  node = await ForkNode.start();
+  fixtures = await node.deployFixtures();
+  removeForkNetwork = useForkNetwork(node, fixtures);
+
+  eth = Ethereum.getInstance(FORK_NETWORK);
+  await eth.init();
+  uniswap = Uniswap.getInstance('ethereum', FORK_NETWORK);
+  await uniswap.init();
+  uniswapLP = UniswapLP.getInstance('ethereum', FORK_NETWORK);
+  await uniswapLP.init();
+
+  wallet = new Wallet(WALLET_PRIVATE_KEY, eth.provider);
+});
+
+beforeEach(() => {
+  // the wallet isn't in the gateway's encrypted wallet store
+  patch(eth, 'getWallet', () => wallet);
+  patch(uniswap.alphaRouter, 'route', (...args: any[]) =>
+    routeThroughFixturePool(eth.provider, args[0], args[1], args[2])
+  );
+  patch(uniswapLP.alphaRouter, 'routeToRatio', noSwapToRatio);
+});
+
+afterEach(() => {
+  unpatch();
+});
+
+afterAll(async () => {
+  await eth.close();
+  removeForkNetwork();
+  node.stop();
+});
+
+// waits for the transaction through the poll route, the node mines it right
+// away
+const confirmed = async (txHash: string) => {
+  const result = await poll(eth, { ...network, txHash });
+  expect(result.txStatus).toEqual(1);
+  expect(result.txBlock).toBeGreaterThan(0);
+  return result;
+};
+
+describe('uniswap on a local chain', () => {
+  it('approves the router to spend WETH', async () => {
+    const result = await approve(eth, {
+      ...network,
+      address: wallet.address,
+      spender: 'uniswap',
+      token: 'WETH',
+    });
+    await confirmed(result.approval?.hash as string);
+
+    const approvals = await allowances(eth, {
+      ...network,
+      address: wallet.address,
+      spender: 'uniswap',
+      tokenSymbols: ['WETH'],
+    });
+    expect((approvals as any).spender).toEqual(fixtures.router);
+    expect(Number((approvals as any).approvals.WETH)).toBeGreaterThan(1e9);
+  });
+
+  it('prices WETH from the pool state', async () => {
+    // 100 WETH against 200000 DAI, less the fee and the price impact
+    const result = await price(eth, uniswap, {
+      ...network,
+      connector: 'uniswap',
+      base: 'WETH',
+      quote: 'DAI',
+      amount: '1',
+      side: 'SELL',
+    });
+    expect(Number(result.price)).toBeWithin(1950, 2000);
+
+    const buy = await price(eth, uniswap, {
+      ...network,
+      connector: 'uniswap',
+      base: 'WETH',
+      quote: 'DAI',
+      amount: '1',
+      side: 'BUY',
+    });
+    expect(Number(buy.price)).toBeWithin(2000, 2050);
+  });
+
+  it('sells WETH for DAI through the router', async () => {
+    const before: any = await balances(eth, {
+      ...network,
+      address: wallet.address,
+      tokenSymbols: ['WETH', 'DAI'],
+    });
+
+    const result = await trade(eth, uniswap, {
+      ...network,
+      connector: 'uniswap',
+      address: wallet.address,
+      base: 'WETH',
+      quote: 'DAI',
+      amount: '1',
+      side: 'SELL',
+    });
+    const receipt = await confirmed(result.txHash);
+    expect(receipt.txReceipt?.to).toEqual(fixtures.router);
+
+    const after: any = await balances(eth, {
+      ...network,
+      address: wallet.address,
+      tokenSymbols: ['WETH', 'DAI'],
+    });
+    const sold = Number(before.balances.WETH) - Number(after.balances.WETH);
+    const bought = Number(after.balances.DAI) - Number(before.balances.DAI);
+    expect(sold).toEqual(1);
+    expect(bought).toBeGreaterThanOrEqual(Number(result.expectedOut));
+  });
+
+  it('adds liquidity to the DAI-USDC pool', async () => {
+    for (const token of ['DAI', 'USDC']) {
+      const approval = await approve(eth, {
+        ...network,
+        address: wallet.address,
+        spender: 'uniswapLP',
+        token,
+      });
+      await confirmed(approval.approval?.hash as string);
+    }
+
+    const result = await addLiquidity(eth, uniswapLP, {
+      ...network,
+      connector: 'uniswapLP',
+      address: wallet.address,
+      token0: 'DAI',
+      token1: 'USDC',
+      amount0: '100',
+      amount1: '100',
+      fee: 'MEDIUM',
+      lowerPrice: '0.9',
+      upperPrice: '1.1',
+    });
+    await confirmed(result.txHash as string);
+
+    const nftManager = new Contract(
+      fixtures.nftManager,
+      uniswapLP.nftAbi,
+      eth.provider
+    );
+    // the fixtures minted the first two positions to the deployer
+    expect((await nftManager.balanceOf(wallet.address)).toNumber()).toEqual(1);
+    const position = await nftManager.positions(3);
+    expect(position.liquidity.isZero()).toBeFalse();
+  });
+});
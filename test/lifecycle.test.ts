@@ -0,0 +1,91 @@
+import express, { NextFunction, Request, Response } from 'express';
+import request from 'supertest';
+import { GatewayLifecycle } from '../src/lifecycle';
+import {
+  gatewayErrorMiddleware,
+  SERVER_STOPPING_ERROR_CODE,
+} from '../src/services/error-handler';
+
+let lifecycle: GatewayLifecycle;
+let finishSlowRequest: () => void;
+const app = express();
+
+beforeAll(() => {
+  lifecycle = GatewayLifecycle.getInstance();
+  app.use(lifecycle.trackRequests);
+  app.get('/slow', async (_req: Request, res: Response) => {
+    await new Promise<void>((resolve) => (finishSlowRequest = resolve));
+    res.status(200).json({ done: true });
+  });
+  app.get('/', (_req: Request, res: Response) => {
+    res.status(200).json({ status: 'ok' });
+  });
+  app.use((err: Error, _req: Request, res: Response, _next: NextFunction) => {
+    const response = gatewayErrorMiddleware(err);
+    res.status(response.httpErrorCode).json(response);
+  });
+});
+
+afterEach(async () => {
+  await lifecycle.start(async () => []);
+});
+
+const waitForInFlight = async (count: number) => {
+  while (lifecycle.inFlight !== count) {
+    await new Promise((resolve) => setImmediate(resolve));
+  }
+};
+
+describe('GatewayLifecycle', () => {
+  it('waits for running requests before it stops', async () => {
+    const slow = request(app).get('/slow').then((res) => res);
+    await waitForInFlight(1);
+
+    let stopped = false;
+    const stopping = lifecycle.stop(5000).then(() => (stopped = true));
+    expect(lifecycle.accepting).toEqual(false);
+
+    const refused = await request(app).get('/');
+    expect(refused.status).toEqual(503);
+    expect(refused.body.errorCode).toEqual(SERVER_STOPPING_ERROR_CODE);
+    expect(stopped).toEqual(false);
+
+    finishSlowRequest();
+    expect((await slow).body).toEqual({ done: true });
+    await stopping;
+    expect(lifecycle.inFlight).toEqual(0);
+  });
+
+  it('gives up on requests that outlast the timeout', async () => {
+    const slow = request(app).get('/slow').then((res) => res);
+    await waitForInFlight(1);
+
+    await lifecycle.stop(10);
+    expect(lifecycle.inFlight).toEqual(1);
+
+    finishSlowRequest();
+    await slow;
+  });
+
+  it('stops within the timeout when a server stays open', async () => {
+    const slow = request(app).get('/slow').then((res) => res);
+    await waitForInFlight(1);
+    // a server whose connections never close
+    await lifecycle.start(async () => [<any>{ close: () => undefined }]);
+
+    const started = Date.now();
+    await lifecycle.stop(300);
+    expect(Date.now() - started).toBeLessThan(550);
+
+    finishSlowRequest();
+    await slow;
+  });
+
+  it('accepts requests again once started', async () => {
+    await lifecycle.stop(10);
+    await lifecycle.start(async () => []);
+
+    const res = await request(app).get('/');
+    expect(res.status).toEqual(200);
+  });
+});
@@ -0,0 +1,270 @@
+import { BigNumber } from 'ethers';
+import { WebSocket } from 'ws';
+import { Ethereum } from '../../src/chains/ethereum/ethereum';
+import {
+  EventStreamManager,
+  unknownSubscriptionError,
+} from '../../src/network/network.events';
+import { patchEVMNonceManager } from '../evm.nonce.mock';
+import { patch, unpatch } from '../services/patch';
+
+let eth: Ethereum;
+let manager: EventStreamManager;
+let handlers: Array<(bn: number) => void>;
+let sent: any[];
+
+const socket: any = {
+  readyState: WebSocket.OPEN,
+  send: (data: string) => sent.push(JSON.parse(data)),
+};
+
+const txHash =
+  '0x2faeb1aa55f96c1db55f643a8cf19b0f76bf091d0b7d1b068d2e829414576362'; // noqa: mock
+
+beforeAll(async () => {
+  eth = Ethereum.getInstance('goerli');
+  patchEVMNonceManager(eth.nonceManager);
+  await eth.init();
+  manager = EventStreamManager.getInstance();
+});
+
+beforeEach(() => {
+  patchEVMNonceManager(eth.nonceManager);
+  handlers = [];
+  sent = [];
+  patch(eth, 'onNewBlock', (func: (bn: number) => void) =>
+    handlers.push(func)
+  );
+  patch(eth, 'offNewBlock', (func: (bn: number) => void) => {
+    handlers = handlers.filter((handler) => handler !== func);
+  });
+});
+
+afterEach(async () => {
+  await manager.close();
+  unpatch();
+});
+
+afterAll(async () => {
+  await eth.close();
+});
+
+describe('EventStreamManager', () => {
+  it('pushes new blocks to subscribers', async () => {
+    await manager.handleMessage(
+      socket,
+      JSON.stringify({
+        action: 'subscribe',
+        channel: 'blocks',
+        chain: 'ethereum',
+        network: 'goerli',
+      })
+    );
+    expect(sent[0].event).toEqual('subscribed');
+    expect(handlers.length).toEqual(1);
+
+    await manager.onNewBlock('ethereum/goerli', 100);
+    expect(sent[1]).toMatchObject({
+      event: 'block',
+      id: sent[0].id,
+      chain: 'ethereum',
+      network: 'goerli',
+      blockNumber: 100,
+    });
+  });
+
+  it('shares one block listener per network', async () => {
+    const request = JSON.stringify({
+      action: 'subscribe',
+      channel: 'blocks',
+      chain: 'ethereum',
+      network: 'goerli',
+    });
+    await manager.handleMessage(socket, request);
+    await manager.handleMessage(socket, request);
+    expect(handlers.length).toEqual(1);
+
+    await manager.removeSocket(socket);
+    expect(handlers.length).toEqual(0);
+    expect(Object.keys(manager.subscriptions)).toHaveLength(0);
+  });
+
+  it('shares one block listener between concurrent subscriptions', async () => {
+    const request = JSON.stringify({
+      action: 'subscribe',
+      channel: 'blocks',
+      chain: 'ethereum',
+      network: 'goerli',
+    });
+    await Promise.all([
+      manager.handleMessage(socket, request),
+      manager.handleMessage(socket, request),
+    ]);
+    expect(handlers.length).toEqual(1);
+    expect(Object.keys(manager.subscriptions)).toHaveLength(2);
+
+    await manager.removeSocket(socket);
+    expect(handlers.length).toEqual(0);
+  });
+
+  it('drops a subscription whose socket closed while it started', async () => {
+    const closing: any = {
+      readyState: WebSocket.OPEN,
+      send: (data: string) => sent.push(JSON.parse(data)),
+    };
+    patch(eth, 'onNewBlock', (func: (bn: number) => void) => {
+      handlers.push(func);
+      closing.readyState = WebSocket.CLOSED;
+    });
+
+    await manager.handleMessage(
+      closing,
+      JSON.stringify({
+        action: 'subscribe',
+        channel: 'blocks',
+        chain: 'ethereum',
+        network: 'goerli',
+      })
+    );
+    expect(Object.keys(manager.subscriptions)).toHaveLength(0);
+    expect(Object.keys(manager.watchers)).toHaveLength(0);
+    expect(handlers.length).toEqual(0);
+    expect(sent).toEqual([]);
+  });
+
+  it('pushes the receipt once a transaction is mined', async () => {
+    let receipt: any = null;
+    patch(eth, 'getTransactionReceipt', () => receipt);
+
+    await manager.handleMessage(
+      socket,
+      JSON.stringify({
+        action: 'subscribe',
+        channel: 'tx',
+        chain: 'ethereum',
+        network: 'goerli',
+        txHash,
+      })
+    );
+    await manager.onNewBlock('ethereum/goerli', 100);
+    expect(sent).toHaveLength(1);
+
+    receipt = {
+      transactionHash: txHash,
+      blockNumber: 101,
+      status: 1,
+      gasUsed: 21000,
+      cumulativeGasUsed: 21000,
+    };
+    await manager.onNewBlock('ethereum/goerli', 101);
+    expect(sent[1]).toMatchObject({
+      event: 'tx',
+      txHash,
+      txBlock: 101,
+      txStatus: 1,
+    });
+    expect(Object.keys(manager.subscriptions)).toHaveLength(0);
+  });
+
+  it('only pushes balances when they change', async () => {
+    let balance = '10';
+    patch(eth, 'getWallet', () => {
+      return { address: '0xFaA12FD102FE8623C9299c72B03E45107F2772B5' };
+    });
+    patch(eth, 'getNativeBalance', () => {
+      return { value: BigNumber.from(balance), decimals: 1 };
+    });
+    patch(eth, 'getERC20Balance', () => {
+      return { value: BigNumber.from('0'), decimals: 1 };
+    });
+
+    await manager.handleMessage(
+      socket,
+      JSON.stringify({
+        action: 'subscribe',
+        channel: 'balances',
+        chain: 'ethereum',
+        network: 'goerli',
+        address: '0xFaA12FD102FE8623C9299c72B03E45107F2772B5',
+        tokenSymbols: ['ETH'],
+      })
+    );
+    expect(sent[1]).toMatchObject({
+      event: 'balances',
+      balances: { ETH: '1.0' },
+    });
+
+    await manager.onNewBlock('ethereum/goerli', 100);
+    expect(sent).toHaveLength(2);
+
+    balance = '20';
+    await manager.onNewBlock('ethereum/goerli', 101);
+    expect(sent[2]).toMatchObject({
+      event: 'balances',
+      balances: { ETH: '2.0' },
+    });
+  });
+
+  it('returns an error for malformed subscriptions', async () => {
+    await manager.handleMessage(
+      socket,
+      JSON.stringify({
+        action: 'subscribe',
+        channel: 'tx',
+        chain: 'ethereum',
+        network: 'goerli',
+      })
+    );
+    expect(sent[0].event).toEqual('error');
+    expect(Object.keys(manager.subscriptions)).toHaveLength(0);
+  });
+
+  it('only lets a socket end its own subscriptions', async () => {
+    await manager.handleMessage(
+      socket,
+      JSON.stringify({
+        action: 'subscribe',
+        channel: 'blocks',
+        chain: 'ethereum',
+        network: 'goerli',
+      })
+    );
+    const id = sent[0].id;
+
+    const otherSent: any[] = [];
+    const other: any = {
+      readyState: WebSocket.OPEN,
+      send: (data: string) => otherSent.push(JSON.parse(data)),
+    };
+    await manager.handleMessage(
+      other,
+      JSON.stringify({ action: 'unsubscribe', id })
+    );
+    expect(otherSent[0].event).toEqual('error');
+    expect(otherSent[0].message).toEqual(unknownSubscriptionError(id));
+    expect(id in manager.subscriptions).toEqual(true);
+
+    await manager.handleMessage(
+      socket,
+      JSON.stringify({ action: 'unsubscribe', id })
+    );
+    expect(sent[1]).toMatchObject({ event: 'unsubscribed', id });
+    expect(id in manager.subscriptions).toEqual(false);
+  });
+
+  it('terminates the open sockets when it closes', async () => {
+    const terminated: string[] = [];
+    const client = (name: string) => ({
+      terminate: () => terminated.push(name),
+    });
+    let closed = false;
+    (manager as any)._server = {
+      clients: new Set([client('a'), client('b')]),
+      close: () => (closed = true),
+    };
+
+    await manager.close();
+    expect(terminated).toEqual(['a', 'b']);
+    expect(closed).toEqual(true);
+  });
+});
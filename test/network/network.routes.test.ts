@@ -105,7 +105,8 @@ describe('GET /network/status', () => {
       .expect((res) => expect(res.body.chain).toBe('goerli'))
       .expect((res) => expect(res.body.chainId).toBeDefined())
       .expect((res) => expect(res.body.rpcUrl).toBeDefined())
-      .expect((res) => expect(res.body.currentBlockNumber).toBeDefined());
+      .expect((res) => expect(res.body.currentBlockNumber).toBeDefined())
+      .expect((res) => expect(res.body.rpcEndpoints).toHaveLength(1));
   });
 
   it('should return 200 when asking for goerli network status', async () => {
@@ -345,3 +346,100 @@ describe('GET /network/tokens', () => {
       .expect(500);
   });
 });
+
+describe('POST /network/tokens', () => {
+  const DAI = {
+    chainId: 5,
+    address: '0xdc31Ee1784292379Fbb2964b3B9C4124D8F89C60',
+    name: 'Dai Stablecoin',
+    symbol: 'DAI',
+    decimals: 18,
+  };
+
+  it('should return 200 with the token added', async () => {
+    patch(goerli, 'addToken', async () => DAI);
+
+    await request(gatewayApp)
+      .post(`/network/tokens/add`)
+      .send({
+        chain: 'ethereum',
+        network: 'goerli',
+        address: DAI.address,
+      })
+      .expect('Content-Type', /json/)
+      .expect(200)
+      .then((res: any) => {
+        expect(res.body.network).toEqual('goerli');
+        expect(res.body.token).toStrictEqual(DAI);
+      });
+  });
+
+  it('should return 500 when the address is not a token contract', async () => {
+    patch(goerli, 'addToken', async () => {
+      throw new Error('not an ERC-20 token contract');
+    });
+
+    await request(gatewayApp)
+      .post(`/network/tokens/add`)
+      .send({
+        chain: 'ethereum',
+        network: 'goerli',
+        address: DAI.address,
+      })
+      .expect(500);
+  });
+
+  it('should return 404 for a malformed token address', async () => {
+    await request(gatewayApp)
+      .post(`/network/tokens/add`)
+      .send({
+        chain: 'ethereum',
+        network: 'goerli',
+        address: 'DAI',
+      })
+      .expect(404);
+  });
+
+  it('should return 200 with the token removed', async () => {
+    patch(goerli, 'removeToken', async () => DAI);
+
+    await request(gatewayApp)
+      .post(`/network/tokens/remove`)
+      .send({
+        chain: 'ethereum',
+        network: 'goerli',
+        token: 'DAI',
+      })
+      .expect('Content-Type', /json/)
+      .expect(200)
+      .then((res: any) => expect(res.body.token.symbol).toEqual('DAI'));
+  });
+
+  it('should return 200 with the number of tokens imported', async () => {
+    patch(goerli, 'importTokenList', async () => [DAI]);
+
+    await request(gatewayApp)
+      .post(`/network/tokens/import`)
+      .send({
+        chain: 'ethereum',
+        network: 'goerli',
+        source: 'https://example.com/tokens.json',
+        type: 'URL',
+      })
+      .expect('Content-Type', /json/)
+      .expect(200)
+      .then((res: any) => expect(res.body.tokens).toEqual(1));
+  });
+
+  it('should return 404 for an unknown token list type', async () => {
+    await request(gatewayApp)
+      .post(`/network/tokens/import`)
+      .send({
+        chain: 'ethereum',
+        network: 'goerli',
+        source: 'https://example.com/tokens.json',
+        type: 'IPFS',
+      })
+      .expect(404);
+  });
+});
@@ -0,0 +1,217 @@
+import request from 'supertest';
+import { gatewayApp } from '../../src/app';
+import {
+  API_KEY_HEADER,
+  API_SIGNATURE_HEADER,
+  API_TIMESTAMP_HEADER,
+  authenticator,
+  isIpAllowed,
+  requiredScope,
+  signRequest,
+} from '../../src/services/auth';
+import { ConfigManagerV2 } from '../../src/services/config-manager-v2';
+import {
+  FORBIDDEN_ERROR_CODE,
+  UNAUTHORIZED_ERROR_CODE,
+} from '../../src/services/error-handler';
+
+const SECRET = 'a-market-data-secret-of-32-chars'; // noqa: mock
+const TRADER_SECRET = 'a-trading-bot-secret-of-32-chars'; // noqa: mock
+
+const signedHeaders = (
+  method: string,
+  path: string,
+  body: string = '',
+  name: string = 'market-data',
+  secret: string = SECRET,
+  timestamp: number = Date.now()
+) => {
+  return {
+    [API_KEY_HEADER]: name,
+    [API_TIMESTAMP_HEADER]: String(timestamp),
+    [API_SIGNATURE_HEADER]: signRequest(
+      secret,
+      String(timestamp),
+      method,
+      path,
+      body
+    ),
+  };
+};
+
+let ipWhitelist: string[];
+
+beforeAll(() => {
+  ipWhitelist = ConfigManagerV2.getInstance().get('server.ipWhitelist');
+  ConfigManagerV2.getInstance().set('server.apiKeys', [
+    { name: 'market-data', secret: SECRET, scopes: ['read'] },
+    { name: 'trader', secret: TRADER_SECRET, scopes: ['read', 'trade'] },
+  ]);
+});
+
+afterAll(() => {
+  ConfigManagerV2.getInstance().set('server.apiKeys', []);
+  ConfigManagerV2.getInstance().set('server.ipWhitelist', ipWhitelist);
+});
+
+describe('requiredScope', () => {
+  it('classifies routes by what they change', () => {
+    expect(requiredScope('GET', '/network/status?chain=ethereum')).toEqual(
+      'read'
+    );
+    expect(requiredScope('POST', '/amm/price')).toEqual('read');
+    expect(requiredScope('POST', '/amm/trade')).toEqual('trade');
+    expect(requiredScope('POST', '/evm/approve')).toEqual('trade');
+    expect(requiredScope('POST', '/wallet/add')).toEqual('admin');
+    expect(requiredScope('DELETE', '/wallet/remove')).toEqual('admin');
+    expect(requiredScope('POST', '/restart')).toEqual('admin');
+    expect(requiredScope('POST', '/network/tokens/import')).toEqual('admin');
+    expect(requiredScope('POST', '/evm/transfer')).toEqual('admin');
+  });
+
+  it('matches routes regardless of case and repeated slashes', () => {
+    expect(requiredScope('POST', '/Config/Update')).toEqual('admin');
+    expect(requiredScope('POST', '/WALLET/add')).toEqual('admin');
+    expect(requiredScope('POST', '/Network/Tokens/Import')).toEqual('admin');
+    expect(requiredScope('POST', '/Restart')).toEqual('admin');
+    expect(requiredScope('POST', '//wallet//add/')).toEqual('admin');
+    expect(requiredScope('POST', '/EVM/nextnonce')).toEqual('read');
+  });
+});
+
+describe('isIpAllowed', () => {
+  it('allows every IP with an empty whitelist', () => {
+    expect(isIpAllowed('10.0.0.2', [])).toEqual(true);
+  });
+
+  it('always allows localhost', () => {
+    expect(isIpAllowed('::ffff:127.0.0.1', ['10.0.0.1'])).toEqual(true);
+    expect(isIpAllowed('::1', ['10.0.0.1'])).toEqual(true);
+  });
+
+  it('rejects IPs that are not listed', () => {
+    expect(isIpAllowed('::ffff:10.0.0.1', ['10.0.0.1'])).toEqual(true);
+    expect(isIpAllowed('10.0.0.2', ['10.0.0.1'])).toEqual(false);
+  });
+
+  it('is enforced before the API key', () => {
+    ConfigManagerV2.getInstance().set('server.ipWhitelist', ['10.0.0.1']);
+    try {
+      expect(() =>
+        authenticator.authenticate({
+          ip: '10.0.0.2',
+          method: 'GET',
+          path: '/',
+          headers: signedHeaders('GET', '/'),
+        })
+      ).toThrow('Forbidden: IP 10.0.0.2');
+    } finally {
+      ConfigManagerV2.getInstance().set('server.ipWhitelist', ipWhitelist);
+    }
+  });
+});
+
+describe('API key authentication', () => {
+  it('accepts a signed request', async () => {
+    await request(gatewayApp)
+      .get('/')
+      .set(signedHeaders('GET', '/'))
+      .expect(200);
+  });
+
+  it('rejects unsigned requests', async () => {
+    const res = await request(gatewayApp).get('/').expect(401);
+    expect(res.body.errorCode).toEqual(UNAUTHORIZED_ERROR_CODE);
+  });
+
+  it('rejects a bad signature', async () => {
+    const headers = signedHeaders('GET', '/', '', 'market-data', 'wrong');
+    await request(gatewayApp).get('/').set(headers).expect(401);
+  });
+
+  it('rejects an unknown key', async () => {
+    const headers = signedHeaders('GET', '/', '', 'someone', SECRET);
+    await request(gatewayApp).get('/').set(headers).expect(401);
+  });
+
+  it('rejects an expired timestamp', async () => {
+    const headers = signedHeaders(
+      'GET',
+      '/',
+      '',
+      'market-data',
+      SECRET,
+      Date.now() - 60000
+    );
+    await request(gatewayApp).get('/').set(headers).expect(401);
+  });
+
+  it('rejects a replayed request', async () => {
+    const headers = signedHeaders('GET', '/');
+    await request(gatewayApp).get('/').set(headers).expect(200);
+    await request(gatewayApp).get('/').set(headers).expect(401);
+  });
+
+  it('signs the body', async () => {
+    const body = JSON.stringify({ chain: 'ethereum', network: 'goerli' });
+    const headers = signedHeaders('POST', '/amm/price', '{}');
+    await request(gatewayApp)
+      .post('/amm/price')
+      .set(headers)
+      .set('Content-Type', 'application/json')
+      .send(body)
+      .expect(401);
+  });
+
+  it('lets a read key reach read routes only', async () => {
+    // the key is accepted, the request then fails validation
+    const res = await request(gatewayApp)
+      .post('/amm/price')
+      .set(signedHeaders('POST', '/amm/price', '{}'))
+      .set('Content-Type', 'application/json')
+      .send('{}');
+    expect([401, 403]).not.toContain(res.status);
+
+    const trade = await request(gatewayApp)
+      .post('/amm/trade')
+      .set(signedHeaders('POST', '/amm/trade', '{}'))
+      .set('Content-Type', 'application/json')
+      .send('{}')
+      .expect(403);
+    expect(trade.body.errorCode).toEqual(FORBIDDEN_ERROR_CODE);
+
+    await request(gatewayApp)
+      .post('/wallet/add')
+      .set(signedHeaders('POST', '/wallet/add', '{}'))
+      .set('Content-Type', 'application/json')
+      .send('{}')
+      .expect(403);
+  });
+
+  it('keeps the secrets of the API keys from a read key', async () => {
+    const res = await request(gatewayApp)
+      .get('/network/config')
+      .set(signedHeaders('GET', '/network/config'))
+      .expect(200);
+    expect(res.body.server.apiKeys.map((key: any) => key.name)).toEqual([
+      'market-data',
+      'trader',
+    ]);
+    expect(JSON.stringify(res.body)).not.toContain(SECRET);
+    expect(JSON.stringify(res.body)).not.toContain(TRADER_SECRET);
+  });
+
+  it('keeps wallet administration from a trade key', async () => {
+    const res = await request(gatewayApp)
+      .post('/amm/trade')
+      .set(signedHeaders('POST', '/amm/trade', '{}', 'trader', TRADER_SECRET))
+      .set('Content-Type', 'application/json')
+      .send('{}');
+    expect([401, 403]).not.toContain(res.status);
+
+    await request(gatewayApp)
+      .post('/restart')
+      .set(signedHeaders('POST', '/restart', '', 'trader', TRADER_SECRET))
+      .expect(403);
+  });
+});
@@ -133,6 +133,36 @@ describe('Configuration manager v2 tests', () => {
     done();
   });
 
+  it('notifying the listeners of a namespace on update', async () => {
+    const changes: string[] = [];
+    const listener = (namespaceId: string, configPath: string, value: any) => {
+      changes.push(`${namespaceId}.${configPath}=${value}`);
+      return ['ethereum.goerli'];
+    };
+    const namespace = configManager.getNamespace(
+      'ethereum'
+    ) as ConfigurationNamespace;
+    namespace.onChange(listener);
+    namespace.onChange(listener); // registered once
+
+    expect(
+      await configManager.update('ethereum.networks.goerli.chainID', 970)
+    ).toEqual(['ethereum.goerli']);
+    expect(changes).toEqual(['ethereum.networks.goerli.chainID=970']);
+    expect(configManager.get('ethereum.networks.goerli.chainID')).toEqual(970);
+
+    // set() doesn't notify, and other namespaces have their own listeners
+    configManager.set('ethereum.networks.goerli.chainID', 971);
+    expect(
+      await configManager.update('server.certificatePath', 'new-gateway.crt')
+    ).toEqual([]);
+    namespace.offChange(listener);
+    expect(
+      await configManager.update('ethereum.networks.goerli.chainID', 972)
+    ).toEqual([]);
+    expect(changes.length).toEqual(1);
+  });
+
   it('writing an invalid configuration', (done) => {
     expect(() => {
       configManager.set('server.nonKeyPath', 'noSuchFile.txt');
@@ -0,0 +1,75 @@
+import { Ethereum } from '../../src/chains/ethereum/ethereum';
+import { Uniswap } from '../../src/connectors/uniswap/uniswap';
+import { UniswapV2LP } from '../../src/connectors/uniswap/uniswap.v2.lp';
+import { reloadChain, reloadConnector } from '../../src/services/config-reload';
+import { patchEVMNonceManager } from '../evm.nonce.mock';
+
+let goerli: Ethereum;
+let uniswap: Uniswap;
+
+beforeEach(() => {
+  goerli = Ethereum.getInstance('goerli');
+  patchEVMNonceManager(goerli.nonceManager);
+  uniswap = Uniswap.getInstance('ethereum', 'goerli');
+});
+
+afterAll(async () => {
+  await Ethereum.getInstance('goerli').close();
+});
+
+describe('reloadChain', () => {
+  it('replaces the network that changed and its connectors', async () => {
+    const reloaded = await reloadChain(
+      'ethereum',
+      'networks.goerli.nodeURL',
+      'http://localhost:8545'
+    );
+
+    expect(reloaded).toEqual(['ethereum.goerli', 'uniswap.ethereumgoerli']);
+    expect(goerli.closed).toEqual(true);
+    expect(Ethereum.getInstance('goerli')).not.toBe(goerli);
+    expect(Uniswap.getInstance('ethereum', 'goerli')).not.toBe(uniswap);
+  });
+
+  it('replaces every network on a change of the whole chain', async () => {
+    const reloaded = await reloadChain('ethereum', 'manualGasPrice', 110);
+
+    expect(reloaded).toContain('ethereum.goerli');
+    expect(Ethereum.getInstance('goerli')).not.toBe(goerli);
+  });
+
+  it('keeps the networks of other chains', async () => {
+    const reloaded = await reloadChain(
+      'avalanche',
+      'networks.fuji.nodeURL',
+      'http://localhost:9650'
+    );
+
+    expect(reloaded).toEqual([]);
+    expect(goerli.closed).toEqual(false);
+    expect(Uniswap.getInstance('ethereum', 'goerli')).toBe(uniswap);
+  });
+});
+
+describe('reloadConnector', () => {
+  it('replaces the connector and keeps the chain', async () => {
+    const reloaded = await reloadConnector('uniswap', 'ttl', 300);
+
+    expect(reloaded).toEqual(['uniswap.ethereumgoerli']);
+    expect(Uniswap.getInstance('ethereum', 'goerli')).not.toBe(uniswap);
+    expect(Ethereum.getInstance('goerli')).toBe(goerli);
+  });
+
+  it.each(['defikingdoms', 'defira', 'mad_meerkat', 'vvs'])(
+    'replaces the V2 liquidity connector of %s',
+    async (name) => {
+      const chain: any = { chainName: 'harmony', chain: 'mainnet' };
+      const lp = UniswapV2LP.getInstance(chain, {} as any, name);
+
+      const reloaded = await reloadConnector(name, 'ttl', 300);
+
+      expect(reloaded).toContain(`${name}.harmonymainnet`);
+      expect(UniswapV2LP.getInstance(chain, {} as any, name)).not.toBe(lp);
+    }
+  );
+});
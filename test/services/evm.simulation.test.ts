@@ -0,0 +1,176 @@
+import 'jest-extended';
+import { BigNumber, Contract, providers, utils, Wallet } from 'ethers';
+import {
+  decodeOutputAmounts,
+  decodeRevertReason,
+  simulateTransaction,
+} from '../../src/evm/evm.simulation';
+
+const ROUTER = '0x68b3465833fb72A70ecDF485E0e4C7bD8665Fc45';
+const erc20 = new utils.Interface([
+  'function approve(address spender, uint256 amount) returns (bool)',
+]);
+
+const revertData = (reason: string) =>
+  utils.hexConcat([
+    '0x08c379a0',
+    utils.defaultAbiCoder.encode(['string'], [reason]),
+  ]);
+
+// a provider that answers the simulation calls, without a node behind it
+const newProvider = (
+  call: (tx: any, blockTag: string) => string,
+  calls: Array<[string, any[]]> = []
+) => {
+  const provider = new providers.StaticJsonRpcProvider(
+    'http://127.0.0.1:8545',
+    5
+  );
+  jest.spyOn(provider, 'getBlockNumber').mockResolvedValue(100);
+  jest.spyOn(provider, 'getTransactionCount').mockResolvedValue(7);
+  jest
+    .spyOn(provider, 'send')
+    .mockImplementation(async (method: string, params: any[]) => {
+      calls.push([method, params]);
+      if (method === 'eth_call') return call(params[0], params[1]);
+      if (method === 'eth_estimateGas') return '0xb411';
+      throw new Error(`unexpected ${method}`);
+    });
+  return provider;
+};
+
+afterEach(() => {
+  jest.restoreAllMocks();
+});
+
+describe('decodeRevertReason', () => {
+  it('decodes Error(string) nested in provider errors', () => {
+    const error = {
+      message: 'processing response error',
+      error: { code: 3, data: revertData('Too little received') },
+    };
+    expect(decodeRevertReason(error)).toEqual('Too little received');
+  });
+
+  it('decodes panics', () => {
+    const data = utils.hexConcat([
+      '0x4e487b71',
+      utils.defaultAbiCoder.encode(['uint256'], [0x11]),
+    ]);
+    expect(decodeRevertReason({ data })).toEqual('Panic(0x11)');
+  });
+
+  it('returns custom errors as is and falls back to the message', () => {
+    expect(decodeRevertReason({ data: '0x5bf6f916' })).toEqual('0x5bf6f916');
+    expect(decodeRevertReason(new Error('insufficient funds'))).toEqual(
+      'insufficient funds'
+    );
+  });
+});
+
+describe('decodeOutputAmounts', () => {
+  it('decodes the amounts of single swaps, V2 routers and multicalls', () => {
+    const amount = utils.defaultAbiCoder.encode(['uint256'], [123]);
+    expect(decodeOutputAmounts('0x414bf389', amount)).toStrictEqual(['123']);
+
+    const amounts = utils.defaultAbiCoder.encode(['uint256[]'], [[10, 20]]);
+    expect(decodeOutputAmounts('0x38ed1739', amounts)).toStrictEqual([
+      '10',
+      '20',
+    ]);
+
+    // the output of the refund call is empty and skipped
+    const results = utils.defaultAbiCoder.encode(['bytes[]'], [[amount, '0x']]);
+    expect(decodeOutputAmounts('0xac9650d8', results)).toStrictEqual(['123']);
+  });
+
+  it('leaves output it cannot decode alone', () => {
+    expect(decodeOutputAmounts('0x12345678', '0x01')).toBeUndefined();
+  });
+});
+
+describe('simulateTransaction', () => {
+  it('simulates what the connector would send', async () => {
+    const calls: Array<[string, any[]]> = [];
+    const provider = newProvider(
+      () => utils.defaultAbiCoder.encode(['bool'], [true]),
+      calls
+    );
+    const wallet = Wallet.createRandom().connect(provider);
+
+    let sentNonce: number | undefined;
+    const result = await simulateTransaction(
+      provider,
+      wallet,
+      (signer: Wallet, nonce: number) => {
+        sentNonce = nonce;
+        return new Contract(ROUTER, erc20, signer).approve(ROUTER, 5, {
+          gasLimit: 100000,
+          nonce,
+        });
+      }
+    );
+
+    expect(sentNonce).toEqual(7);
+    expect(result).toMatchObject({
+      success: true,
+      blockNumber: 100,
+      nonce: 7,
+      to: ROUTER.toLowerCase(),
+      value: '0',
+      gasUsed: '46097',
+    });
+    expect(result.data).toEqual(
+      erc20.encodeFunctionData('approve', [ROUTER, 5])
+    );
+    expect(calls.map(([method]) => method)).toStrictEqual([
+      'eth_call',
+      'eth_estimateGas',
+    ]);
+    // both run against the block the result reports
+    expect(calls.every(([, params]) => params[1] === '0x64')).toBeTrue();
+    expect(calls[1][1][0].gas).toBeUndefined();
+  });
+
+  it('reports the revert reason of a failing transaction', async () => {
+    const provider = newProvider(() => {
+      throw Object.assign(new Error('execution reverted'), {
+        data: revertData('STF'),
+      });
+    });
+    const wallet = Wallet.createRandom().connect(provider);
+
+    const result = await simulateTransaction(
+      provider,
+      wallet,
+      (signer: Wallet, nonce: number) =>
+        signer.sendTransaction({
+          to: ROUTER,
+          data: '0x414bf389',
+          value: BigNumber.from(1),
+          nonce,
+        }),
+      3,
+      90,
+      true
+    );
+
+    expect(result).toMatchObject({
+      success: false,
+      blockNumber: 90,
+      nonce: 3,
+      value: '1',
+      revertReason: 'STF',
+    });
+    expect(result.gasUsed).toBeUndefined();
+    expect(result.amounts).toBeUndefined();
+  });
+
+  it('fails when nothing was sent', async () => {
+    const provider = newProvider(() => '0x');
+    const wallet = Wallet.createRandom().connect(provider);
+    await expect(
+      simulateTransaction(provider, wallet, async () => undefined)
+    ).rejects.toThrow('nothing to simulate');
+  });
+});
@@ -118,4 +118,29 @@ describe('Test local-storage', () => {
     // the key has been deleted, expect an empty object
     expect(results4).toStrictEqual({});
   });
+
+  it('follows replacement links to the latest transaction', async () => {
+    const chain = 'goerli';
+    const chainId = 5;
+    const tx1 =
+      '0x75f98675a8f64dcf14927ccde9a1d59b67fa09b72cc2642ad055dae4074853d9'; // noqa: mock
+    const tx2 =
+      '0x2faeb1aa55f96c1db55f643a8cf19b0f76bf091d0b7d1b068d2e829414576362'; // noqa: mock
+    const tx3 =
+      '0x5a1ed682d0d7a58fbd7828bbf5994cd024feb8895d4da82c741ec4a191b9e849'; // noqa: mock
+
+    expect(await db.getReplacement(chain, chainId, tx1)).toBeUndefined();
+
+    await db.saveTx(chain, chainId, tx1, new Date(), 100);
+    await db.saveReplacement(chain, chainId, tx1, tx2);
+    await db.saveReplacement(chain, chainId, tx2, tx3);
+
+    expect(await db.getReplacement(chain, chainId, tx1)).toEqual(tx3);
+    expect(await db.getReplacement(chain, chainId, tx2)).toEqual(tx3);
+    expect(await db.getReplacement(chain, chainId, tx3)).toBeUndefined();
+
+    // replacement links are not returned as transactions
+    expect(Object.keys(await db.getTxs(chain, chainId))).toStrictEqual([tx1]);
+    await db.deleteTx(chain, chainId, tx1);
+  });
 });
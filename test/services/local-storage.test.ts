@@ -59,6 +59,20 @@ describe('Test local-storage', () => {
     expect(results2).toStrictEqual({});
   });
 
+  it('get the value of a single key', async () => {
+    const db: LocalStorage = LocalStorage.getInstance(dbPath, handle);
+
+    await db.save('lion', { kingdom: 'animalia', family: 'felidae' });
+
+    expect(await db.getValue('lion')).toStrictEqual({
+      kingdom: 'animalia',
+      family: 'felidae',
+    });
+    expect(await db.getValue('unicorn')).toBeUndefined();
+
+    await db.del('lion');
+  });
+
   it('Put and retrieve a objects', async () => {
     const db: LocalStorage = LocalStorage.getInstance(dbPath, handle);
 
@@ -0,0 +1,186 @@
+import 'jest-extended';
+import { ConfigManagerV2 } from '../../src/services/config-manager-v2';
+import {
+  RISK_LIMIT_EXCEEDED_ERROR_CODE,
+} from '../../src/services/error-handler';
+import { checkRisk, dailyVolume } from '../../src/services/risk-manager';
+import {
+  JournalEntry,
+  TransactionJournal,
+} from '../../src/services/transaction-journal';
+
+const WALLET = '0xFaA12FD102FE8623C9299c72B03E45107F2772B5';
+
+const entry = (
+  type: string,
+  tokens: string[],
+  amounts: string[],
+  status: string = 'CONFIRMED'
+): JournalEntry =>
+  <JournalEntry>{
+    txHash: '0x' + Math.random().toString(16).slice(2),
+    chain: 'ethereum',
+    network: 'goerli',
+    type,
+    connector: 'uniswap',
+    wallet: WALLET,
+    timestamp: Date.now(),
+    tokens,
+    amounts,
+    status,
+  };
+
+// a journal holding today's transactions of the wallet
+const journalOf = (entries: JournalEntry[]): TransactionJournal =>
+  <TransactionJournal>(<unknown>{ query: async () => entries });
+
+const risk = {
+  maxNotional: { WETH: 5 },
+  maxDailyVolume: { weth: 10 },
+  tokenAllowList: [],
+  tokenDenyList: ['SHIB'],
+  maxAllowedSlippage: '2/100',
+};
+
+const expectRejected = async (promise: Promise<void>, reason: string) => {
+  await expect(promise).rejects.toMatchObject({
+    status: 403,
+    errorCode: RISK_LIMIT_EXCEEDED_ERROR_CODE,
+    message: expect.stringContaining(reason),
+  });
+};
+
+let original: Record<string, any>;
+
+beforeAll(() => {
+  const config = ConfigManagerV2.getInstance();
+  original = {};
+  for (const [key, value] of Object.entries(risk)) {
+    original[key] = config.get(`risk.${key}`);
+    config.set(`risk.${key}`, value);
+  }
+});
+
+afterAll(() => {
+  const config = ConfigManagerV2.getInstance();
+  for (const [key, value] of Object.entries(original)) {
+    config.set(`risk.${key}`, value);
+  }
+});
+
+describe('checkRisk', () => {
+  it('lets a trade within the limits through', async () => {
+    await expect(
+      checkRisk({
+        wallet: WALLET,
+        connector: 'uniswap',
+        tokens: ['WETH', 'DAI'],
+        amounts: ['1', '2000'],
+        allowedSlippage: '1/100',
+        journal: journalOf([entry('trade', ['WETH', 'DAI'], ['2', '4000'])]),
+      })
+    ).resolves.toBeUndefined();
+  });
+
+  it('rejects denied tokens', async () => {
+    await expectRejected(
+      checkRisk({ wallet: WALLET, tokens: ['shib'] }),
+      'SHIB is on the token deny list'
+    );
+  });
+
+  it('rejects tokens missing from a non empty allow list', async () => {
+    const config = ConfigManagerV2.getInstance();
+    config.set('risk.tokenAllowList', ['WETH', 'DAI']);
+    try {
+      await expectRejected(
+        checkRisk({ wallet: WALLET, tokens: ['WETH', 'USDC'] }),
+        'USDC is not on the token allow list'
+      );
+    } finally {
+      config.set('risk.tokenAllowList', []);
+    }
+  });
+
+  it('caps allowedSlippage', async () => {
+    await expectRejected(
+      checkRisk({
+        wallet: WALLET,
+        tokens: ['WETH', 'DAI'],
+        allowedSlippage: '50/100',
+      }),
+      'allowedSlippage 50/100 exceeds 2/100'
+    );
+  });
+
+  it("caps the connector's allowedSlippage when the request has none", async () => {
+    const config = ConfigManagerV2.getInstance();
+    const uniswapSlippage = config.get('uniswap.allowedSlippage');
+    config.set('uniswap.allowedSlippage', '5/100');
+    try {
+      await expectRejected(
+        checkRisk({
+          wallet: WALLET,
+          connector: 'uniswapLP',
+          tokens: ['WETH', 'DAI'],
+        }),
+        'allowedSlippage 5/100 exceeds 2/100'
+      );
+      await expect(
+        checkRisk({
+          wallet: WALLET,
+          connector: 'uniswap',
+          tokens: ['WETH', 'DAI'],
+          allowedSlippage: '1/100',
+        })
+      ).resolves.toBeUndefined();
+    } finally {
+      config.set('uniswap.allowedSlippage', uniswapSlippage);
+    }
+  });
+
+  it('limits the amount per trade', async () => {
+    await expectRejected(
+      checkRisk({
+        wallet: WALLET,
+        tokens: ['WETH', 'DAI'],
+        amounts: ['6', '12000'],
+      }),
+      '6 WETH exceeds the maximum of 5 per trade'
+    );
+  });
+
+  it('limits the daily volume of the wallet', async () => {
+    const journal = journalOf([
+      entry('trade', ['WETH', 'DAI'], ['4', '8000']),
+      entry('trade', ['WETH', 'DAI'], ['4', '8000']),
+    ]);
+    await expectRejected(
+      checkRisk({
+        wallet: WALLET,
+        connector: 'uniswap',
+        tokens: ['WETH', 'DAI'],
+        amounts: ['3', '6000'],
+        journal,
+      }),
+      'would trade 11 WETH today through uniswap, the maximum is 10'
+    );
+  });
+});
+
+describe('dailyVolume', () => {
+  it('adds up the trades that went through', async () => {
+    const journal = journalOf([
+      entry('trade', ['WETH', 'DAI'], ['1', '2000']),
+      entry('perpOpen', ['WETH', 'USD'], ['2']),
+      entry('perpLimitOrder', ['WETH', 'USD'], ['0.5']),
+      entry('trade', ['WETH', 'DAI'], ['4', '8000'], 'FAILED'),
+      entry('trade', ['WETH', 'DAI'], ['8', '16000'], 'REPLACED'),
+      entry('approve', ['WETH'], []),
+    ]);
+    const volume = await dailyVolume(journal, WALLET, 'uniswap');
+    expect(volume.WETH.toString()).toEqual('3.5');
+    expect(volume.DAI.toString()).toEqual('2000');
+    expect(volume.USD).toBeUndefined();
+  });
+});
@@ -0,0 +1,225 @@
+import 'jest-extended';
+import { providers } from 'near-api-js';
+import {
+  isQuorumRead,
+  isRetryableEvmError,
+} from '../../src/evm/evm.provider-pool';
+import { NearProviderPool } from '../../src/chains/near/near.provider-pool';
+import { isRetryableCosmosError } from '../../src/chains/cosmos/cosmos-base';
+import {
+  NoQuorumError,
+  RpcEndpointPool,
+} from '../../src/services/rpc-endpoint-pool';
+
+// a client is just its url, the calls under test decide how each one answers
+const newPool = (quorum = 1) =>
+  new RpcEndpointPool<string>(
+    [
+      { url: 'http://a', weight: 3 },
+      { url: 'http://b', weight: 2 },
+      { url: 'http://c', weight: 1 },
+    ],
+    (url: string) => url,
+    {
+      quorum,
+      isRetryable: (error: any) => error.message !== 'execution reverted',
+    }
+  );
+
+afterEach(() => {
+  jest.restoreAllMocks();
+});
+
+describe('RpcEndpointPool', () => {
+  it('fails over and cools down the failed endpoint', async () => {
+    const pool = newPool();
+    jest.spyOn(Math, 'random').mockReturnValue(0); // draw the first endpoint
+
+    const called: string[] = [];
+    const result = await pool.execute(async (url: string) => {
+      called.push(url);
+      if (url === 'http://a') throw new Error('429 Too Many Requests');
+      return 'ok';
+    });
+    expect(result).toEqual('ok');
+    expect(called).toStrictEqual(['http://a', 'http://b']);
+
+    const [a, b] = pool.health();
+    expect(a).toMatchObject({ healthy: false, errors: 1 });
+    expect(a.lastError).toEqual('429 Too Many Requests');
+    expect(b).toMatchObject({ healthy: true, requests: 1, errors: 0 });
+
+    // the cooling endpoint is only tried once the healthy ones failed
+    expect(pool.ordered().map((endpoint) => endpoint.url)).toStrictEqual([
+      'http://b',
+      'http://c',
+      'http://a',
+    ]);
+  });
+
+  it('picks the first endpoint in proportion to its score', async () => {
+    const pool = newPool();
+    // a holds the first half of the total score, b the next third
+    jest.spyOn(Math, 'random').mockReturnValue(0.6);
+    expect(pool.ordered()[0].url).toEqual('http://b');
+    jest.spyOn(Math, 'random').mockReturnValue(0.9);
+    expect(pool.ordered()[0].url).toEqual('http://c');
+  });
+
+  it('passes errors that are not retryable through', async () => {
+    const pool = newPool();
+    const called: string[] = [];
+    await expect(
+      pool.execute(async (url: string) => {
+        called.push(url);
+        throw new Error('execution reverted');
+      })
+    ).rejects.toThrow('execution reverted');
+    expect(called).toHaveLength(1);
+    expect(pool.health().every((endpoint) => endpoint.healthy)).toBeTrue();
+  });
+
+  it('throws the last error when every endpoint fails', async () => {
+    const pool = newPool();
+    await expect(
+      pool.execute(async (url: string) => {
+        throw new Error(`${url} is down`);
+      })
+    ).rejects.toThrow('is down');
+    expect(pool.health().every((endpoint) => !endpoint.healthy)).toBeTrue();
+  });
+
+  it('returns a quorum read once enough endpoints agree', async () => {
+    const pool = newPool(2);
+    jest.spyOn(Math, 'random').mockReturnValue(0);
+
+    const called: string[] = [];
+    const balance = await pool.executeQuorum(async (url: string) => {
+      called.push(url);
+      return url === 'http://b' ? '99' : '100';
+    });
+    expect(balance).toEqual('100');
+    // a and b disagreed, so c was asked to break the tie
+    expect(called).toStrictEqual(['http://a', 'http://b', 'http://c']);
+  });
+
+  it('fails a quorum read when the endpoints disagree', async () => {
+    const pool = newPool(2);
+    await expect(
+      pool.executeQuorum(async (url: string) => url)
+    ).rejects.toBeInstanceOf(NoQuorumError);
+  });
+
+  it('reads a result that lagging endpoints lack as pending', async () => {
+    const pool = newPool(2);
+    const receipt = await pool.executeQuorum(async (url: string) =>
+      url === 'http://a' ? { status: 1 } : null
+    );
+    expect(receipt).toBeNull();
+  });
+
+  it('still fails a quorum read when two results disagree', async () => {
+    const pool = newPool(3);
+    await expect(
+      pool.executeQuorum(async (url: string) =>
+        url === 'http://c' ? null : { blockHash: url }
+      )
+    ).rejects.toBeInstanceOf(NoQuorumError);
+  });
+
+  it('sends a call that must not be repeated to one endpoint', async () => {
+    const pool = newPool();
+    const called: string[] = [];
+    await expect(
+      pool.executeOnce(async (url: string) => {
+        called.push(url);
+        throw new Error('timeout');
+      })
+    ).rejects.toThrow('timeout');
+    expect(called).toHaveLength(1);
+  });
+
+  it('caps the quorum at the number of endpoints', () => {
+    expect(newPool(5).quorum).toEqual(3);
+  });
+});
+
+describe('EvmProviderPool', () => {
+  it('reads balances and receipts with a quorum', () => {
+    expect(isQuorumRead('eth_getBalance', ['0xabc', 'latest'])).toBeTrue();
+    expect(isQuorumRead('eth_getTransactionReceipt', ['0x123'])).toBeTrue();
+    expect(
+      isQuorumRead('eth_call', [
+        {
+          to: '0xdAC17F958D2ee523a2206206994597C13D831ec7',
+          data: '0x70a08231000000000000000000000000faa12fd102fe8623c9299c72b03e45107f2772b5', // noqa: mock
+        },
+        'latest',
+      ])
+    ).toBeTrue();
+    expect(isQuorumRead('eth_call', [{ data: '0x18160ddd' }])).toBeFalse();
+    expect(isQuorumRead('eth_sendRawTransaction', ['0x02f8'])).toBeFalse();
+  });
+
+  it('only fails over on transport errors and rate limits', () => {
+    expect(
+      isRetryableEvmError({ code: 'SERVER_ERROR', status: 429 })
+    ).toBeTrue();
+    expect(isRetryableEvmError({ code: 'TIMEOUT' })).toBeTrue();
+    expect(
+      isRetryableEvmError({ code: -32005, message: 'limit exceeded' })
+    ).toBeTrue();
+    expect(
+      isRetryableEvmError({ code: -32000, message: 'nonce too low' })
+    ).toBeFalse();
+    expect(
+      isRetryableEvmError({ code: 3, message: 'execution reverted' })
+    ).toBeFalse();
+  });
+});
+
+describe('NearProviderPool', () => {
+  it('broadcasts a transaction through a single endpoint', async () => {
+    const provider = new NearProviderPool([
+      { url: 'http://a' },
+      { url: 'http://b' },
+    ]);
+    // the clients of the endpoints, not the pool itself, time out
+    const called: string[] = [];
+    jest
+      .spyOn(providers.JsonRpcProvider.prototype, 'sendJsonRpc')
+      .mockImplementation(async function (this: providers.JsonRpcProvider) {
+        called.push(this.connection.url);
+        throw Object.assign(new Error('timed out'), { type: 'TimeoutError' });
+      });
+
+    await expect(
+      provider.sendJsonRpc('broadcast_tx_commit', ['AAAA'])
+    ).rejects.toThrow('timed out');
+    expect(called).toHaveLength(1);
+
+    // reads still fail over
+    called.length = 0;
+    await expect(
+      provider.sendJsonRpc('block', { finality: 'final' })
+    ).rejects.toThrow('timed out');
+    expect(called).toHaveLength(2);
+  });
+});
+
+describe('CosmosBase pool', () => {
+  it('only fails over on transport errors and rate limits', () => {
+    expect(isRetryableCosmosError({ code: 'ECONNREFUSED' })).toBeTrue();
+    expect(
+      isRetryableCosmosError({ message: 'x', response: { status: 503 } })
+    ).toBeTrue();
+    expect(
+      isRetryableCosmosError({ message: 'x', response: { status: 429 } })
+    ).toBeTrue();
+    expect(
+      isRetryableCosmosError(
+        new Error('Query failed with (18): invalid pool id')
+      )
+    ).toBeFalse();
+  });
+});
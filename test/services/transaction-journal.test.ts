@@ -0,0 +1,141 @@
+import fs from 'fs';
+import fsp from 'fs/promises';
+import fse from 'fs-extra';
+import os from 'os';
+import path from 'path';
+import 'jest-extended';
+import { EvmTxStorage } from '../../src/evm/evm.tx-storage';
+import { ReferenceCountingCloseable } from '../../src/services/refcounting-closeable';
+import {
+  JOURNAL_TX_TYPES,
+  NewJournalEntry,
+  TransactionJournal,
+} from '../../src/services/transaction-journal';
+import { validateHistoryType } from '../../src/services/history/history.validators';
+
+describe('Test transaction-journal', () => {
+  let dbPath: string = '';
+  let journal: TransactionJournal;
+  let handle: string;
+
+  const tradeTx =
+    '0xadaef9c4540192e45c991ffe6f12cc86be9c07b80b43487e5778d95c964405c7'; // noqa: mock
+  const approveTx =
+    '0xadaef9c4540192e45c991ffe6f12cc86be9c07b80b43487edddddddddddddddd'; // noqa: mock
+  const nearTx = 'CYrdLRzTWqYYQmkhMKN8cHdnGGpx3GZYvAQZz3RcvaFs'; // noqa: mock
+  const address = '0xFaA12FD102FE8623C9299c72B03E45107F2772B5';
+
+  const trade: NewJournalEntry = {
+    txHash: tradeTx,
+    chain: 'ethereum',
+    network: 'goerli',
+    type: 'trade',
+    connector: 'uniswap',
+    wallet: address,
+    nonce: 10,
+    tokens: ['WETH', 'DAI'],
+    amounts: ['0.1', '180.5'],
+    side: 'SELL',
+    price: '1805',
+    gasPrice: 100,
+    gasLimit: 300000,
+  };
+
+  beforeAll(async () => {
+    dbPath = await fsp.mkdtemp(
+      path.join(os.tmpdir(), '/transaction-journal.test.level')
+    );
+  });
+
+  afterAll(async () => {
+    await fse.emptyDir(dbPath);
+    fs.rmSync(dbPath, { force: true, recursive: true });
+  });
+
+  beforeEach(() => {
+    handle = ReferenceCountingCloseable.createHandle();
+    journal = TransactionJournal.getInstance(dbPath, handle);
+  });
+
+  afterEach(async () => {
+    await journal.deleteEntry(tradeTx);
+    await journal.deleteEntry(approveTx);
+    await journal.deleteEntry(nearTx);
+    await journal.close(handle);
+  });
+
+  it('records a transaction as pending and updates its status', async () => {
+    await journal.record(trade);
+
+    const entry = await journal.getEntry(tradeTx);
+    expect(entry).toMatchObject({ ...trade, status: 'PENDING' });
+    expect(entry?.timestamp).toBeNumber();
+
+    await journal.updateStatus(tradeTx, 'CONFIRMED', { blockNumber: 100 });
+    expect(await journal.getEntry(tradeTx)).toMatchObject({
+      status: 'CONFIRMED',
+      receipt: { blockNumber: 100 },
+    });
+  });
+
+  it('ignores status updates for unknown transactions', async () => {
+    await journal.updateStatus(approveTx, 'FAILED');
+    expect(await journal.getEntry(approveTx)).toBeUndefined();
+  });
+
+  it('filters the history', async () => {
+    await journal.record(trade);
+    await journal.record({
+      ...trade,
+      txHash: approveTx,
+      type: 'approve',
+      connector: undefined,
+      tokens: ['WETH'],
+      amounts: ['1.0'],
+    });
+    await journal.record({
+      ...trade,
+      txHash: nearTx,
+      chain: 'near',
+      network: 'testnet',
+      connector: 'ref',
+      wallet: 'test.testnet',
+    });
+
+    expect(await journal.query({})).toHaveLength(3);
+    expect(
+      (await journal.query({ chain: 'ethereum' })).map((e) => e.txHash)
+    ).toIncludeSameMembers([tradeTx, approveTx]);
+    expect(
+      (await journal.query({ connector: 'uniswap' })).map((e) => e.txHash)
+    ).toStrictEqual([tradeTx]);
+    expect(
+      (await journal.query({ type: 'approve' })).map((e) => e.txHash)
+    ).toStrictEqual([approveTx]);
+
+    // wallet addresses are compared case insensitively
+    expect(
+      await journal.query({ wallet: address.toLowerCase() })
+    ).toHaveLength(2);
+
+    expect(await journal.query({ from: Date.now() + 1000 })).toHaveLength(0);
+    expect(await journal.query({ to: Date.now() + 1000 })).toHaveLength(3);
+  });
+
+  it('does not interfere with EvmTxStorage', async () => {
+    const txStorage: EvmTxStorage = EvmTxStorage.getInstance(dbPath, handle);
+    await journal.record(trade);
+
+    expect(await txStorage.getTxs('goerli', 5)).toStrictEqual({});
+    await txStorage.close(handle);
+  });
+});
+
+describe('validateHistoryType', () => {
+  it('accepts every type the journal records', () => {
+    for (const type of JOURNAL_TX_TYPES) {
+      expect(validateHistoryType({ type })).toEqual([]);
+    }
+    expect(validateHistoryType({ type: 'deposit' })).toHaveLength(1);
+  });
+});
@@ -7,10 +7,12 @@ import {
   isBase58,
   validateToken,
   validateAmount,
+  validateTransferAmount,
   validateTxHash,
   invalidTokenSymbolsError,
   invalidTokenError,
   invalidAmountError,
+  invalidTransferAmountError,
   invalidTxHashError,
 } from '../../src/services/validators';
 import 'jest-extended';
@@ -176,6 +178,27 @@ describe('validateAmount', () => {
   });
 });
 
+describe('validateTransferAmount', () => {
+  it('valid when req.amount is a string of a positive number', () => {
+    expect(validateTransferAmount({ amount: '1.5' })).toEqual([]);
+  });
+
+  it('return error when req.amount does not exist', () => {
+    expect(validateTransferAmount({ hello: 'world' })).toEqual([
+      missingParameter('amount'),
+    ]);
+  });
+
+  it('return error when req.amount is zero or not a number', () => {
+    expect(validateTransferAmount({ amount: '0' })).toEqual([
+      invalidTransferAmountError,
+    ]);
+    expect(validateTransferAmount({ amount: 1.5 })).toEqual([
+      invalidTransferAmountError,
+    ]);
+  });
+});
+
 describe('validateTxHash', () => {
   it('valid when req.txHash is a string', () => {
     expect(validateTxHash({ txHash })).toEqual([]);